@@ -0,0 +1,69 @@
+/*
+Copyright ⓒ 2016 Daniel Keep.
+
+Licensed under the MIT license (see LICENSE or <http://opensource.org
+/licenses/MIT>) or the Apache License, Version 2.0 (see LICENSE of
+<http://www.apache.org/licenses/LICENSE-2.0>), at your option. All
+files in the project carrying such notice may not be copied, modified,
+or distributed except according to those terms.
+*/
+/*!
+Benchmarks for common inner loops, particularly those affected by the `fast-scan` feature.
+
+Requires a nightly compiler.  Compare `cargo bench` against `cargo bench --features fast-scan`.
+*/
+#![feature(test)]
+#[macro_use] extern crate scan_rules;
+extern crate test;
+
+use scan_rules::scanner::{CowString, Line, NonSpace, QuotedString};
+use test::Bencher;
+
+fn words() -> String {
+    (0..10_000).map(|i| format!("word{}   ", i)).collect()
+}
+
+fn quoted(escapes: bool) -> String {
+    let esc = if escapes { "\\\"" } else { "" };
+    (0..10_000).map(|i| format!("\"field {}{} of the log line\" ", esc, i)).collect()
+}
+
+fn lines() -> String {
+    (0..10_000).map(|i| format!("this is line number {} of the input\n", i)).collect()
+}
+
+#[bench]
+fn bench_non_space(b: &mut Bencher) {
+    let inp = words();
+    b.iter(|| scan!(&inp; ([let ws: NonSpace<&str>]+) => ws.len()).unwrap());
+}
+
+#[bench]
+fn bench_line(b: &mut Bencher) {
+    let inp = lines();
+    b.iter(|| scan!(&inp; ([let ls: Line<&str>]{10_000}) => ls.len()).unwrap());
+}
+
+#[bench]
+fn bench_literals(b: &mut Bencher) {
+    let inp = words().replace("word", "x ");
+    b.iter(|| scan!(&inp; (["x", let _: u32]+) => ()).unwrap());
+}
+
+#[bench]
+fn bench_quoted_string(b: &mut Bencher) {
+    let inp = quoted(false);
+    b.iter(|| scan!(&inp; ([let qs: QuotedString]+) => qs.len()).unwrap());
+}
+
+#[bench]
+fn bench_quoted_string_escapes(b: &mut Bencher) {
+    let inp = quoted(true);
+    b.iter(|| scan!(&inp; ([let qs: QuotedString]+) => qs.len()).unwrap());
+}
+
+#[bench]
+fn bench_cow_string(b: &mut Bencher) {
+    let inp = quoted(false);
+    b.iter(|| scan!(&inp; ([let qs: CowString]+) => qs.len()).unwrap());
+}
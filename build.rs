@@ -20,7 +20,23 @@ fn main() {
         println!("cargo:rustc-cfg=str_into_output_extra_broken");
     }
 
+    if version_matches("1.17.0") {
+        println!("cargo:rustc-cfg=collections_bound");
+    }
+
     if version_matches("1.10.0") {
         println!("cargo:rustc-cfg=macro_inter_stmt_binding_visibility");
     }
+
+    if version_matches("1.20.0") {
+        println!("cargo:rustc-cfg=compile_error_macro");
+    }
+
+    if version_matches(">= 1.21.0") {
+        println!("cargo:rustc-cfg=rc_from_str");
+    }
+
+    if version_matches(">= 1.26.0") {
+        println!("cargo:rustc-cfg=i128_type");
+    }
 }
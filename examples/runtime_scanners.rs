@@ -19,8 +19,8 @@ fn main() {
     // Adapted example from <http://en.cppreference.com/w/cpp/io/c/fscanf>.
     let inp = "25 54.32E-1 Thompson 56789 0123 56ß水";
 
-    // `let_scan!` avoids the need for indentation and braces, but only supports
-    // a single pattern, and panics if anything goes wrong.
+    // `let_scan!` avoids the need for indentation and braces, but panics if
+    // anything goes wrong.
     let_scan!(inp; (
         let i: i32, let x: f32, let str1 <| max_width_a::<NonSpace>(9),
     //               use runtime scanner ^~~~~~~~~~~~~~~~~~~~~~~~~~~~^
@@ -39,8 +39,8 @@ Licensed under the MIT license (see LICENSE or <http://opensource.org
 files in the project carrying such notice may not be copied, modified,
 or distributed except according to those terms.
 
-NOTE: The following code was generated by "scripts/unicode.py", do not edit
-directly.
+NOTE: The following code was generated by "scripts/unicode.py" from version
+%s.%s.%s of the Unicode Character Database, do not edit directly.
 */
 #![allow(non_upper_case_globals)]
 '''
@@ -59,13 +59,16 @@ expanded_categories = {
     'Cc': ['C'], 'Cf': ['C'], 'Cs': ['C'], 'Co': ['C'], 'Cn': ['C'],
 }
 
+# All tables must come from the same version of the UCD.
+UNICODE_VERSION = "8.0.0"
+
 def fetch(f):
     if not os.path.exists('local'):
         os.mkdir('local')
     out_path = os.path.join('local', f)
     if not os.path.exists(out_path):
         sys.stdout.write('Fetching %s...\n' % f)
-        res = urllib2.urlopen("http://www.unicode.org/Public/UNIDATA/%s" % f)
+        res = urllib2.urlopen("http://www.unicode.org/Public/%s/ucd/%s" % (UNICODE_VERSION, f))
         out = open(out_path, 'w')
         shutil.copyfileobj(res, out)
     return out_path
@@ -264,13 +267,14 @@ if __name__ == "__main__":
     if os.path.exists(r):
         os.remove(r)
     with open(r, "w") as rf:
-        # write the file's preamble
-        rf.write(preamble)
-
         # download and parse all the data
         with open(fetch("ReadMe.txt")) as readme:
             pattern = "for Version (\d+)\.(\d+)\.(\d+) of the Unicode"
             unicode_version = re.search(pattern, readme.read()).groups()
+
+        # write the file's preamble
+        rf.write(preamble % unicode_version)
+
         gencats = load_unicode_data("UnicodeData.txt")
         want_derived = ["XID_Start", "XID_Continue", "Alphabetic"]
         other_derived = []
@@ -283,7 +287,7 @@ if __name__ == "__main__":
         # all of these categories will also be available as \p{} in libregex
         allcats = []
         for (name, cat, filter) in [
-            ("general_category", gencats, lambda n: n == "Nd"),
+            ("general_category", gencats, lambda n: n in ("Nd", "P")),
             ("derived_property", derived, lambda n: n in ("XID_Start", "XID_Continue")),
             ("property", props, lambda n: n == "White_Space"),
         ]:
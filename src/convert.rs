@@ -0,0 +1,52 @@
+/*
+Copyright ⓒ 2016 Daniel Keep.
+
+Licensed under the MIT license (see LICENSE or <http://opensource.org
+/licenses/MIT>) or the Apache License, Version 2.0 (see LICENSE of
+<http://www.apache.org/licenses/LICENSE-2.0>), at your option. All
+files in the project carrying such notice may not be copied, modified,
+or distributed except according to those terms.
+*/
+/*!
+Defines the `FromScan` conversion trait.
+*/
+
+/**
+Conversion from the values bound by a pattern.
+
+This is used by rules of the form `(pattern) => @into Type`, which pass a tuple of every value bound by *pattern*, in order, to `Type::from_scan`.  A pattern with a single binding produces a one-element tuple, and a pattern without bindings produces `()`.
+
+Every type can be converted from itself, so `@into Pair` (where `type Pair = (A, B)`) simply returns the bound values as a tuple.
+
+## Examples
+
+```rust
+# #[macro_use] extern crate scan_rules;
+# use scan_rules::FromScan;
+# fn main() {
+#[derive(Debug, PartialEq)]
+struct Point { x: i32, y: i32 }
+
+impl FromScan<(i32, i32)> for Point {
+    fn from_scan((x, y): (i32, i32)) -> Self {
+        Point { x: x, y: y }
+    }
+}
+
+let p = scan!("(1, 2)"; ("(", let x: i32, ",", let y: i32, ")") => @into Point);
+assert_eq!(p.unwrap(), Point { x: 1, y: 2 });
+# }
+```
+*/
+pub trait FromScan<Captures>: Sized {
+    /**
+    Construct a value from the captures of a pattern.
+    */
+    fn from_scan(captures: Captures) -> Self;
+}
+
+impl<T> FromScan<T> for T {
+    fn from_scan(captures: T) -> Self {
+        captures
+    }
+}
@@ -32,6 +32,16 @@ pub struct ScanError {
     */
     pub kind: ScanErrorKind,
 
+    /**
+    Notes describing what was being scanned when the error occurred, innermost first.
+    */
+    context: Vec<&'static str>,
+
+    /**
+    Whether this error should abort scanning outright, rather than allowing later rules to be tried.
+    */
+    cut: bool,
+
     /**
     Dummy private field to prevent exhaustive deconstruction.
     */
@@ -44,8 +54,10 @@ impl ScanError {
     */
     pub fn new(at: usize, kind: ScanErrorKind) -> Self {
         ScanError {
-            at: ScanErrorAt { bytes: at },
+            at: ScanErrorAt { bytes: at, snippet: None },
             kind: kind,
+            context: vec![],
+            cut: false,
             _priv: (),
         }
     }
@@ -78,6 +90,13 @@ impl ScanError {
         Self::new(0, ScanErrorKind::Io(err))
     }
 
+    /**
+    Shorthand for constructing a `LimitExceeded` error.
+    */
+    pub fn limit_exceeded() -> Self {
+        Self::new(0, ScanErrorKind::LimitExceeded)
+    }
+
     /**
     Shorthand for constructing a `LiteralMismatch` error.
     */
@@ -85,6 +104,20 @@ impl ScanError {
         Self::new(0, ScanErrorKind::LiteralMismatch)
     }
 
+    /**
+    Shorthand for constructing a `Missing` error at the given offset, which should be the position at which the missing input was expected.
+    */
+    pub fn missing(at: usize) -> Self {
+        Self::new(at, ScanErrorKind::Missing)
+    }
+
+    /**
+    Shorthand for constructing an `Overflow` error.
+    */
+    pub fn overflow(type_name: &'static str) -> Self {
+        Self::new(0, ScanErrorKind::Overflow(type_name))
+    }
+
     /**
     Shorthand for constructing a `Syntax` error.
     */
@@ -121,9 +154,110 @@ impl ScanError {
     Adds the given number of `bytes` to the error's position.
 
     This is used where an error has been generated by trying to scan a subslice of the original input, and the position needs to be corrected.
+
+    Any snippet captured by `with_input` is discarded when the position moves, since it no longer describes the text at the new position; call `with_input` again once the position is final.
     */
     pub fn add_offset(self, bytes: usize) -> Self {
-        ScanError::new(self.at.offset() + bytes, self.kind)
+        let ScanError { at, kind, context, cut, _priv } = self;
+        let snippet = if bytes == 0 { at.snippet } else { None };
+        ScanError {
+            at: ScanErrorAt { bytes: at.bytes + bytes, snippet: snippet },
+            kind: kind,
+            context: context,
+            cut: cut,
+            _priv: _priv,
+        }
+    }
+
+    /**
+    Replaces the error's position with the result of applying `f` to it.
+
+    This is used where the input scanned was a transformed copy of the original input, and the position needs to be translated back into the original.  Unlike `add_offset`, any snippet is kept, since the translated position still refers to the same text.
+    */
+    pub fn map_offset<F>(mut self, f: F) -> Self
+    where F: FnOnce(usize) -> usize {
+        self.at.bytes = f(self.at.bytes);
+        self
+    }
+
+    /**
+    Captures a snippet of `input` at the error's position, so that it can be shown to the user after the input itself is gone.
+
+    `input` should be the complete input which was scanned.  The snippet runs from the error's position to the end of that line, and is truncated to a few dozen bytes.  If the error's position does not lie within `input`, no snippet is captured.
+
+    See: [`ScanErrorAt::snippet`](struct.ScanErrorAt.html#method.snippet).
+    */
+    pub fn with_input(mut self, input: &str) -> Self {
+        const MAX_SNIPPET: usize = 32;
+
+        let off = self.at.bytes;
+        if off > input.len() || !input.is_char_boundary(off) {
+            return self;
+        }
+
+        let rest = &input[off..];
+        let mut end = ::fast::find_line_break(rest.as_bytes()).unwrap_or(rest.len());
+        if end > MAX_SNIPPET {
+            end = MAX_SNIPPET;
+            while !rest.is_char_boundary(end) {
+                end -= 1;
+            }
+        }
+        self.at.snippet = Some(rest[..end].to_owned());
+        self
+    }
+
+    /**
+    Annotates the error with a note describing what was being scanned when it occurred, such as `"while scanning map entry"`.
+
+    Notes accumulate as the error propagates outward; the underlying kind and position are left untouched.
+    */
+    pub fn context(mut self, msg: &'static str) -> Self {
+        self.context.push(msg);
+        self
+    }
+
+    /**
+    Returns the notes attached to this error using `context`, innermost first.
+    */
+    pub fn context_chain(&self) -> &[&'static str] {
+        &self.context
+    }
+
+    /**
+    Marks the error as a hard failure.
+
+    When a hard failure reaches the rules of a `scan!` invocation, no further rules are tried; scanning stops immediately with this error.  Likewise, a hard failure inside a repetition fails the whole repetition, rather than ending it.
+
+    See: [`cut`](scanner/runtime/fn.cut.html).
+    */
+    pub fn cut(mut self) -> Self {
+        self.cut = true;
+        self
+    }
+
+    /**
+    Marks the error as a soft failure, undoing any previous `cut`.
+
+    Errors of kind `LimitExceeded` always abort scanning, and cannot be made soft.
+
+    See: [`soft`](scanner/runtime/fn.soft.html).
+    */
+    pub fn soft(mut self) -> Self {
+        self.cut = false;
+        self
+    }
+
+    /**
+    Returns `true` if this error should abort scanning outright, rather than allowing other rules or alternatives to be tried.
+
+    This is the case for errors marked with `cut`, and for all `LimitExceeded` errors.
+    */
+    pub fn is_fatal(&self) -> bool {
+        match self.kind {
+            ScanErrorKind::LimitExceeded => true,
+            _ => self.cut,
+        }
     }
 }
 
@@ -133,6 +267,13 @@ impl<'a> fmt::Display for ScanError {
         try!(self.kind.fmt(fmt));
         try!(", at offset: ".fmt(fmt));
         try!(self.at.offset().fmt(fmt));
+        if let Some(snippet) = self.at.snippet() {
+            try!(write!(fmt, ", near {:?}", snippet));
+        }
+        for msg in &self.context {
+            try!("; ".fmt(fmt));
+            try!(msg.fmt(fmt));
+        }
         Ok(())
     }
 }
@@ -151,14 +292,15 @@ impl Error for ScanError {
 Represents the position at which an error occurred.
 */
 /*
-This exists because I'm still considering including the input which generated the error, for the sake of nice error messages.
-
-I'm not using `StrCursor`, because I don't want errors tied to a specific input wrapper.
+The input is kept as an owned snippet, rather than a borrow or `StrCursor`, because I don't want errors tied to the lifetime of the input, or to a specific input wrapper.
 */
 #[derive(Debug)]
 pub struct ScanErrorAt {
     /// Offset in bytes.
     bytes: usize,
+
+    /// Input at the offset, if captured.
+    snippet: Option<String>,
 }
 
 impl ScanErrorAt {
@@ -168,10 +310,23 @@ impl ScanErrorAt {
     pub fn offset(&self) -> usize {
         self.bytes
     }
+
+    /**
+    Return the input at which the error occurred, if it was captured.
+
+    Snippets are only captured when requested with `ScanError::with_input`; the `readln!` and `try_readln!` macros do this automatically, since the line they read is not otherwise available once they return.
+    */
+    pub fn snippet(&self) -> Option<&str> {
+        self.snippet.as_ref().map(|s| &**s)
+    }
 }
 
 /**
 Indicates the kind of error that occurred during scanning.
+
+Custom scanners should report errors using the kind that best fits, so that callers can handle errors consistently regardless of which scanner produced them.  Each kind has a corresponding constructor on `ScanError`, such as `ScanError::missing`.
+
+All of the variants below may be matched on, and will not be removed or changed.  However, new kinds may be added in the future, so matches must always include a catch-all arm.
 */
 #[derive(Debug)]
 pub enum ScanErrorKind {
@@ -191,15 +346,36 @@ pub enum ScanErrorKind {
     /// Expected end-of-input.
     ExpectedEnd,
 
+    /**
+    Something required was not present at all, such as a repetition which matched fewer times than its minimum, or a mandatory field which never appeared.
+
+    This is distinct from `Syntax`, which indicates that something *was* present, but malformed.
+    */
+    Missing,
+
     /// Floating point parsing failed.
     Float(ParseFloatError),
 
     /// Integer parsing failed.
     Int(ParseIntError),
 
+    /**
+    A well-formed number was scanned, but it does not fit in the target type, which is named by the payload.
+
+    This is distinct from `Int`, so that "not a number" and "number too large" can be told apart.
+    */
+    Overflow(&'static str),
+
     /// An IO error occurred.
     Io(io::Error),
 
+    /**
+    A limit set on the input cursor was exceeded.
+
+    See: [`Limited`](input/struct.Limited.html).
+    */
+    LimitExceeded,
+
     /// Some other error occurred.
     Other(Box<Error>),
 
@@ -229,9 +405,12 @@ impl fmt::Display for ScanErrorKind {
             },
             SyntaxNoMessage => "unknown syntax error".fmt(fmt),
             ExpectedEnd => "expected end of input".fmt(fmt),
+            Missing => "required input is missing".fmt(fmt),
             Float(ref err) => err.fmt(fmt),
             Int(ref err) => err.fmt(fmt),
+            Overflow(ty) => write!(fmt, "number out of range for `{}`", ty),
             Io(ref err) => err.fmt(fmt),
+            LimitExceeded => "scanning limit exceeded".fmt(fmt),
             Other(ref err) => err.fmt(fmt),
             __DoNotMatch => panic!("do not use ScanErrorKind::__DoNotMatch!"),
         }
@@ -246,6 +425,9 @@ impl Error for ScanErrorKind {
             | Syntax(_)
             | SyntaxNoMessage
             | ExpectedEnd
+            | Missing
+            | Overflow(_)
+            | LimitExceeded
             => None,
             Float(ref err) => err.cause(),
             Int(ref err) => err.cause(),
@@ -262,11 +444,76 @@ impl Error for ScanErrorKind {
             Syntax(_) => "syntax error",
             SyntaxNoMessage => "unknown syntax error",
             ExpectedEnd => "expected end of input",
+            Missing => "required input is missing",
             Float(ref err) => err.description(),
             Int(ref err) => err.description(),
+            Overflow(_) => "number out of range",
             Io(ref err) => err.description(),
+            LimitExceeded => "scanning limit exceeded",
             Other(ref err) => err.description(),
             __DoNotMatch => panic!("do not use ScanErrorKind::__DoNotMatch!"),
         }
     }
 }
+
+#[cfg(test)]
+#[test]
+fn test_context() {
+    let err = ScanError::syntax("expected digit")
+        .context("while scanning entry value")
+        .context("while scanning map")
+        .add_offset(3);
+
+    assert_eq!(err.at.offset(), 3);
+    assert_eq!(err.context_chain(), &["while scanning entry value", "while scanning map"]);
+    assert_eq!(err.to_string(), "scan error: syntax error: expected digit, at offset: 3; while scanning entry value; while scanning map");
+
+    let err = ScanError::literal_mismatch();
+    assert!(err.context_chain().is_empty());
+    assert_eq!(err.to_string(), "scan error: did not match literal, at offset: 0");
+}
+
+#[cfg(test)]
+#[test]
+fn test_missing() {
+    let err = ScanError::missing(4).add_offset(2);
+    assert!(match err.kind { ScanErrorKind::Missing => true, _ => false });
+    assert_eq!(err.at.offset(), 6);
+    assert_eq!(err.to_string(), "scan error: required input is missing, at offset: 6");
+}
+
+#[cfg(test)]
+#[test]
+fn test_with_input() {
+    let input = "x = 1\ny = two\n";
+    let err = ScanError::syntax("expected integer").add_offset(10).with_input(input);
+    assert_eq!(err.at.offset(), 10);
+    assert_eq!(err.at.snippet(), Some("two"));
+    assert_eq!(err.to_string(), "scan error: syntax error: expected integer, at offset: 10, near \"two\"");
+
+    let err = ScanError::literal_mismatch().add_offset(4).with_input("abc");
+    assert_eq!(err.at.snippet(), None);
+
+    let err = ScanError::literal_mismatch().add_offset(1).with_input("xééééééééééééééééééééé");
+    assert_eq!(err.at.snippet(), Some("éééééééééééééééé"));
+    assert_eq!(err.at.offset(), 1);
+
+    // Moving the error invalidates the snippet.
+    let err = ScanError::literal_mismatch().with_input(input).add_offset(6);
+    assert_eq!(err.at.offset(), 6);
+    assert_eq!(err.at.snippet(), None);
+    let err = ScanError::literal_mismatch().with_input(input).add_offset(0);
+    assert_eq!(err.at.snippet(), Some("x = 1"));
+}
+
+#[cfg(test)]
+#[test]
+fn test_cut() {
+    let err = ScanError::syntax("expected integer");
+    assert!(!err.is_fatal());
+    let err = err.cut().add_offset(3).context("while scanning entry");
+    assert!(err.is_fatal());
+    assert_eq!(err.at.offset(), 3);
+    assert!(!err.soft().is_fatal());
+    assert!(ScanError::new(0, ScanErrorKind::LimitExceeded).soft().is_fatal());
+}
@@ -0,0 +1,221 @@
+/*
+Copyright ⓒ 2016 Daniel Keep.
+
+Licensed under the MIT license (see LICENSE or <http://opensource.org
+/licenses/MIT>) or the Apache License, Version 2.0 (see LICENSE of
+<http://www.apache.org/licenses/LICENSE-2.0>), at your option. All
+files in the project carrying such notice may not be copied, modified,
+or distributed except according to those terms.
+*/
+/*!
+Fast paths for common inner loops over mostly-ASCII input.
+
+Each function here handles only a leading ASCII prefix of its input, and tells the caller how far it got; the caller is responsible for continuing with the general (Unicode-aware) logic from there.  Without the `fast-scan` feature, these all do nothing, so the general logic handles everything.
+
+The SWAR ("SIMD within a register") tricks work on eight bytes at a time; see <https://graphics.stanford.edu/~seander/bithacks.html#ZeroInWord>.
+*/
+
+/**
+Returns the length of the leading run of ASCII whitespace.
+*/
+#[cfg(feature="fast-scan")]
+pub fn ascii_space_len(s: &str) -> usize {
+    let bs = s.as_bytes();
+    let mut i = 0;
+
+    // Skip whole words where every byte is whitespace.
+    while i + 8 <= bs.len() {
+        let w = load_u64(bs, i);
+        let space = eq_bytes(w, b' ') | eq_bytes(w, b'\t') | eq_bytes(w, b'\n')
+            | eq_bytes(w, b'\x0b') | eq_bytes(w, b'\x0c') | eq_bytes(w, b'\r');
+        if space != HI {
+            break;
+        }
+        i += 8;
+    }
+
+    while i < bs.len() && is_ascii_space(bs[i]) {
+        i += 1;
+    }
+
+    i
+}
+
+#[cfg(not(feature="fast-scan"))]
+#[inline]
+pub fn ascii_space_len(_: &str) -> usize {
+    0
+}
+
+/**
+Returns the length of the leading run of ASCII whitespace, excluding line breaks.
+*/
+#[cfg(feature="fast-scan")]
+pub fn ascii_space_non_line_len(s: &str) -> usize {
+    let bs = s.as_bytes();
+    let mut i = 0;
+
+    // Skip whole words where every byte is non-line whitespace.
+    while i + 8 <= bs.len() {
+        let w = load_u64(bs, i);
+        let space = eq_bytes(w, b' ') | eq_bytes(w, b'\t') | eq_bytes(w, b'\x0b') | eq_bytes(w, b'\x0c');
+        if space != HI {
+            break;
+        }
+        i += 8;
+    }
+
+    while i < bs.len() && is_ascii_space(bs[i]) && bs[i] != b'\r' && bs[i] != b'\n' {
+        i += 1;
+    }
+
+    i
+}
+
+#[cfg(not(feature="fast-scan"))]
+#[inline]
+pub fn ascii_space_non_line_len(_: &str) -> usize {
+    0
+}
+
+/**
+Returns the length of the leading run of ASCII non-whitespace.
+*/
+#[cfg(feature="fast-scan")]
+pub fn ascii_non_space_len(s: &str) -> usize {
+    let bs = s.as_bytes();
+    let mut i = 0;
+
+    // Skip whole words where every byte is in `0x21...0x7f`.
+    while i + 8 <= bs.len() {
+        let w = load_u64(bs, i);
+        if w & HI != 0 || has_less(w, 0x21) {
+            break;
+        }
+        i += 8;
+    }
+
+    while i < bs.len() && bs[i] < 0x80 && !is_ascii_space(bs[i]) {
+        i += 1;
+    }
+
+    i
+}
+
+#[cfg(not(feature="fast-scan"))]
+#[inline]
+pub fn ascii_non_space_len(_: &str) -> usize {
+    0
+}
+
+/**
+Returns the position of the first `\r` or `\n`, if any.
+*/
+#[cfg(feature="fast-scan")]
+pub fn find_line_break(bs: &[u8]) -> Option<usize> {
+    let mut i = 0;
+
+    while i + 8 <= bs.len() {
+        let w = load_u64(bs, i);
+        if has_zero(w ^ (LO * b'\r' as u64)) || has_zero(w ^ (LO * b'\n' as u64)) {
+            break;
+        }
+        i += 8;
+    }
+
+    bs[i..].iter()
+        .position(|&b| b == b'\r' || b == b'\n')
+        .map(|off| i + off)
+}
+
+#[cfg(not(feature="fast-scan"))]
+pub fn find_line_break(bs: &[u8]) -> Option<usize> {
+    bs.iter().position(|&b| b == b'\r' || b == b'\n')
+}
+
+#[cfg(feature="fast-scan")]
+const LO: u64 = 0x0101_0101_0101_0101;
+
+#[cfg(feature="fast-scan")]
+const HI: u64 = 0x8080_8080_8080_8080;
+
+#[cfg(feature="fast-scan")]
+fn is_ascii_space(b: u8) -> bool {
+    match b {
+        b' ' | b'\t' | b'\n' | b'\x0b' | b'\x0c' | b'\r' => true,
+        _ => false
+    }
+}
+
+/**
+Does any byte in `w` equal zero?
+*/
+#[cfg(feature="fast-scan")]
+fn has_zero(w: u64) -> bool {
+    w.wrapping_sub(LO) & !w & HI != 0
+}
+
+/**
+Returns `w` with the high bit of each byte set if, and only if, that byte is equal to `b`, and all other bits clear.
+
+Unlike `has_zero`, this is exact for every byte, so it can be used to check that *all* bytes match.
+*/
+#[cfg(feature="fast-scan")]
+fn eq_bytes(w: u64, b: u8) -> u64 {
+    let x = w ^ (LO * b as u64);
+    // Adding `0x7f` to the low seven bits of a byte sets its high bit unless they are all zero; this cannot carry into the next byte.
+    !(((x & !HI) + !HI) | x | !HI)
+}
+
+/**
+Is any byte in `w` less than `n`?  Only valid for `n <= 0x80`.
+*/
+#[cfg(feature="fast-scan")]
+fn has_less(w: u64, n: u8) -> bool {
+    w.wrapping_sub(LO * n as u64) & !w & HI != 0
+}
+
+/**
+Load eight bytes starting at `i`.  The byte order doesn't matter, since we only care *whether* a byte matches.
+*/
+#[cfg(feature="fast-scan")]
+fn load_u64(bs: &[u8], i: usize) -> u64 {
+    let bs = &bs[i..i + 8];
+    (bs[0] as u64)
+        | (bs[1] as u64) << 8
+        | (bs[2] as u64) << 16
+        | (bs[3] as u64) << 24
+        | (bs[4] as u64) << 32
+        | (bs[5] as u64) << 40
+        | (bs[6] as u64) << 48
+        | (bs[7] as u64) << 56
+}
+
+#[cfg(feature="fast-scan")]
+#[cfg(test)]
+#[test]
+fn test_fast() {
+    assert_eq!(ascii_space_len(""), 0);
+    assert_eq!(ascii_space_len(" \t\r\n x"), 5);
+    assert_eq!(ascii_space_len(" \u{3000}"), 1);
+    assert_eq!(ascii_space_non_line_len(" \t\r\n x"), 2);
+    assert_eq!(ascii_space_len("        \t\t\r\n\x0b\x0c  x"), 16);
+    assert_eq!(ascii_space_len("   \x01    "), 3);
+    assert_eq!(ascii_space_len("       \u{a0}"), 7);
+    assert_eq!(ascii_space_len("                 "), 17);
+    assert_eq!(ascii_space_non_line_len("        \t\t  \n         "), 12);
+    assert_eq!(ascii_space_non_line_len(" \t \t \t \t \t \t x"), 13);
+
+    assert_eq!(ascii_non_space_len(""), 0);
+    assert_eq!(ascii_non_space_len("abc def"), 3);
+    assert_eq!(ascii_non_space_len("abcdefghijklmnopqrstuvwxyz def"), 26);
+    assert_eq!(ascii_non_space_len("abcdefghijklmn\x01pq"), 17);
+    assert_eq!(ascii_non_space_len("abcdefghijklmnopé"), 16);
+    assert_eq!(ascii_non_space_len("abcdefgh\u{3000}"), 8);
+
+    assert_eq!(find_line_break(b""), None);
+    assert_eq!(find_line_break(b"abc"), None);
+    assert_eq!(find_line_break(b"abcdefghijklmnopqrstuvwxyz"), None);
+    assert_eq!(find_line_break(b"abcdefghijklmnopq\r\n"), Some(17));
+    assert_eq!(find_line_break(b"abcdefgh\nijklmnopq\r\n"), Some(8));
+}
@@ -19,7 +19,11 @@ The short version is this:
 `IntoScanCursor` will be of interest if you are implementing a type which you want to be scannable.  `StrCursor` will be of interest if you want to construct a specialised cursor.  `ScanCursor` will be of interest if you are using a `^..cursor` pattern to capture a cursor.
 */
 use std::borrow::Cow;
+use std::cell::RefCell;
+#[cfg(feature="std")]
+use std::io::{self, BufRead, BufReader, Read};
 use std::marker::PhantomData;
+use std::str::Chars;
 use ::ScanError;
 
 /**
@@ -67,6 +71,16 @@ impl<'a> IntoScanCursor<'a> for &'a Cow<'a, str> {
     }
 }
 
+/**
+Scans whatever the iterator has not yet yielded.
+*/
+impl<'a> IntoScanCursor<'a> for Chars<'a> {
+    type Output = StrCursor<'a>;
+    fn into_scan_cursor(self) -> Self::Output {
+        StrCursor::new(self.as_str())
+    }
+}
+
 /**
 This trait defines the interface to input values that can be scanned.
 */
@@ -107,10 +121,39 @@ pub trait ScanCursor<'a>: 'a + Sized + Clone {
     */
     fn as_str(self) -> &'a str;
 
+    /**
+    Returns the remaining input as a string slice, without consuming the cursor.
+    */
+    fn remaining(&self) -> &'a str {
+        self.clone().as_str()
+    }
+
     /**
     Returns the number of bytes consumed by this cursor since its creation.
     */
     fn offset(&self) -> usize;
+
+    /**
+    Returns the maximum number of times a single repetition may match, if any.
+
+    The default implementation returns `None`.
+
+    See: [`Limited`](struct.Limited.html).
+    */
+    fn max_repeats(&self) -> Option<usize> {
+        None
+    }
+
+    /**
+    Returns the current line and column, if this cursor keeps track of them.
+
+    The default implementation returns `None`.
+
+    See: [`LineTrackingCursor`](struct.LineTrackingCursor.html).
+    */
+    fn line_column(&self) -> Option<(usize, usize)> {
+        None
+    }
 }
 
 /**
@@ -143,251 +186,2026 @@ pub trait ScanInput<'a>: 'a + Sized + Clone {
     Turn the input into an independent cursor, suitable for feeding back into a user-facing scanning macro.
     */
     fn to_cursor(&self) -> Self::ScanCursor;
+
+    /**
+    Match a literal against the start of this input using the string comparison `Cmp` in place of the input's own, returning the number of bytes matched.
+
+    Other settings (such as how spaces and words are handled) should be preserved.  The default implementation uses the default `StrCursor` settings.
+    */
+    fn match_literal_with<Cmp>(&self, lit: &str) -> Result<usize, ScanError>
+    where Cmp: StrCompare {
+        StrCursor::<Cmp>::new(self.as_str())
+            .try_match_literal(lit)
+            .map(|cur| cur.offset())
+            .map_err(|(err, _)| err)
+    }
+}
+
+/**
+Basic cursor implementation wrapping a string slice.
+
+The `Cmp` parameter can be used to control the string comparison logic used.
+*/
+#[derive(Debug)]
+pub struct StrCursor<'a, Cmp=ExactCompare, Space=IgnoreSpace, Word=Wordish>
+where
+    Cmp: StrCompare,
+    Space: SkipSpace,
+    Word: SliceWord,
+{
+    offset: usize,
+    slice: &'a str,
+    _marker: PhantomData<(Cmp, Space, Word)>,
+}
+
+/*
+These have to be spelled out to avoid erroneous constraints on the type parameters.
+*/
+impl<'a, Cmp, Space, Word>
+Copy for StrCursor<'a, Cmp, Space, Word>
+where
+    Cmp: StrCompare,
+    Space: SkipSpace,
+    Word: SliceWord,
+{}
+
+impl<'a, Cmp, Space, Word>
+Clone for StrCursor<'a, Cmp, Space, Word>
+where
+    Cmp: StrCompare,
+    Space: SkipSpace,
+    Word: SliceWord,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, Cmp, Space, Word>
+StrCursor<'a, Cmp, Space, Word>
+where
+    Cmp: StrCompare,
+    Space: SkipSpace,
+    Word: SliceWord,
+{
+    /**
+    Construct a new `StrCursor` with a specific `offset`.
+
+    The `offset` is logically the number of bytes which have already been consumed from the original input; these already-consumed bytes *must not* be included in `slice`.
+    */
+    pub fn new(slice: &'a str) -> Self {
+        StrCursor {
+            offset: 0,
+            slice: slice,
+            _marker: PhantomData,
+        }
+    }
+
+    /**
+    Advance the cursor by the given number of bytes.
+    */
+    fn advance_by(self, bytes: usize) -> Self {
+        StrCursor {
+            offset: self.offset + bytes,
+            slice: &self.slice[bytes..],
+            _marker: PhantomData,
+        }
+    }
+
+    /**
+    Returns the number of bytes of input that have been consumed by this `StrCursor`.
+    */
+    fn offset(self) -> usize {
+        self.offset
+    }
+}
+
+impl<'a> StrCursor<'a> {
+    /**
+    Start building a `StrCursor` over `slice`, using the default comparison, space and word policies.
+
+    This is an alternative to spelling out `StrCursor`'s type parameters, which is both easier to read and won't break if more policies are added later.
+
+    ```ignore
+    let cur = StrCursor::builder(inp).ignore_case().exact_space().build();
+    ```
+    */
+    pub fn builder(slice: &'a str) -> StrCursorBuilder<'a> {
+        StrCursorBuilder {
+            slice: slice,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/**
+Builder for configuring a `StrCursor`.
+
+Each method replaces one of the cursor's policies; see [`StrCursor::builder`](struct.StrCursor.html#method.builder).
+*/
+#[derive(Debug)]
+pub struct StrCursorBuilder<'a, Cmp=ExactCompare, Space=IgnoreSpace, Word=Wordish>
+where
+    Cmp: StrCompare,
+    Space: SkipSpace,
+    Word: SliceWord,
+{
+    slice: &'a str,
+    _marker: PhantomData<(Cmp, Space, Word)>,
+}
+
+impl<'a, Cmp, Space, Word>
+StrCursorBuilder<'a, Cmp, Space, Word>
+where
+    Cmp: StrCompare,
+    Space: SkipSpace,
+    Word: SliceWord,
+{
+    /**
+    Use `NewCmp` to compare literals against the input.
+    */
+    pub fn compare<NewCmp>(self) -> StrCursorBuilder<'a, NewCmp, Space, Word>
+    where NewCmp: StrCompare {
+        StrCursorBuilder { slice: self.slice, _marker: PhantomData }
+    }
+
+    /**
+    Use `NewSpace` to skip whitespace between literals.
+    */
+    pub fn space<NewSpace>(self) -> StrCursorBuilder<'a, Cmp, NewSpace, Word>
+    where NewSpace: SkipSpace {
+        StrCursorBuilder { slice: self.slice, _marker: PhantomData }
+    }
+
+    /**
+    Use `NewWord` to determine how much of the input to compare against a literal.
+    */
+    pub fn word<NewWord>(self) -> StrCursorBuilder<'a, Cmp, Space, NewWord>
+    where NewWord: SliceWord {
+        StrCursorBuilder { slice: self.slice, _marker: PhantomData }
+    }
+
+    /**
+    Compare literals exactly.  This is the default.
+    */
+    pub fn exact_compare(self) -> StrCursorBuilder<'a, ExactCompare, Space, Word> {
+        self.compare()
+    }
+
+    /**
+    Compare literals ignoring case.
+    */
+    pub fn ignore_case(self) -> StrCursorBuilder<'a, IgnoreCase, Space, Word> {
+        self.compare()
+    }
+
+    /**
+    Compare literals ignoring ASCII case.
+    */
+    pub fn ignore_ascii_case(self) -> StrCursorBuilder<'a, IgnoreAsciiCase, Space, Word> {
+        self.compare()
+    }
+
+    /**
+    Require whitespace in the input to exactly match whitespace in literals.
+    */
+    pub fn exact_space(self) -> StrCursorBuilder<'a, Cmp, ExactSpace, Word> {
+        self.space()
+    }
+
+    /**
+    Require whitespace where literals have it, but allow it to differ in amount and kind.
+    */
+    pub fn fuzzy_space(self) -> StrCursorBuilder<'a, Cmp, FuzzySpace, Word> {
+        self.space()
+    }
+
+    /**
+    Ignore whitespace other than line breaks.
+    */
+    pub fn ignore_non_line(self) -> StrCursorBuilder<'a, Cmp, IgnoreNonLine, Word> {
+        self.space()
+    }
+
+    /**
+    Ignore all whitespace.  This is the default.
+    */
+    pub fn ignore_space(self) -> StrCursorBuilder<'a, Cmp, IgnoreSpace, Word> {
+        self.space()
+    }
+
+    /**
+    Compare literals against "wordish" tokens.  This is the default.
+    */
+    pub fn wordish(self) -> StrCursorBuilder<'a, Cmp, Space, Wordish> {
+        self.word()
+    }
+
+    /**
+    Compare literals against runs of non-whitespace.
+    */
+    pub fn non_space(self) -> StrCursorBuilder<'a, Cmp, Space, NonSpace> {
+        self.word()
+    }
+
+    /**
+    Construct the configured `StrCursor`.
+    */
+    pub fn build(self) -> StrCursor<'a, Cmp, Space, Word> {
+        StrCursor::new(self.slice)
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_str_cursor_builder() {
+    let inp = "Hello,   World";
+
+    let cur: StrCursor<ExactCompare, IgnoreSpace, Wordish> = StrCursor::builder(inp).build();
+    assert!(matches!(scan!(cur; ("hello", ..rest) => rest), Err(_)));
+
+    let cur = StrCursor::builder(inp).ignore_case().build();
+    assert!(matches!(scan!(cur; ("hello", ",", "WORLD") => ()), Ok(())));
+
+    let cur = StrCursor::builder(inp).ignore_ascii_case().exact_space().build();
+    assert!(matches!(scan!(cur; ("hello", ",", "WORLD") => ()), Err(_)));
+    assert!(matches!(scan!(cur; ("hello,   WORLD") => ()), Ok(())));
+
+    let cur = StrCursor::builder(inp).non_space().build();
+    assert!(matches!(scan!(cur; ("Hello", ..rest) => rest), Err(_)));
+    assert!(matches!(scan!(cur; ("Hello,", "World") => ()), Ok(())));
+
+    let cur = StrCursor::builder(inp).ignore_case().fuzzy_space().ignore_space().exact_compare().wordish().build();
+    let _: StrCursor<ExactCompare, IgnoreSpace, Wordish> = cur;
+}
+
+impl<'a, Cmp, Space, Word>
+ScanCursor<'a> for StrCursor<'a, Cmp, Space, Word>
+where
+    Cmp: StrCompare,
+    Space: SkipSpace,
+    Word: SliceWord,
+{
+    type ScanInput = Self;
+
+    fn try_end(self) -> Result<(), (ScanError, Self)> {
+        if Space::skip_space(self.slice) == self.slice.len() {
+            Ok(())
+        } else {
+            Err((ScanError::expected_end().add_offset(self.offset()), self))
+        }
+    }
+
+    fn try_scan<F, Out>(self, f: F) -> Result<(Out, Self), (ScanError, Self)>
+    where F: FnOnce(Self::ScanInput) -> Result<(Out, usize), ScanError> {
+        let tmp_off = Space::skip_space(self.slice);
+        let tmp = self.advance_by(tmp_off);
+        match f(tmp) {
+            Ok((out, off)) => Ok((out, tmp.advance_by(off))),
+            Err(err) => Err((err.add_offset(tmp.offset()), self)),
+        }
+    }
+
+    fn try_scan_raw<F, Out>(self, f: F) -> Result<(Out, Self), (ScanError, Self)>
+    where F: FnOnce(Self::ScanInput) -> Result<(Out, usize), ScanError> {
+        match f(self) {
+            Ok((out, off)) => Ok((out, self.advance_by(off))),
+            Err(err) => Err((err.add_offset(self.offset()), self)),
+        }
+    }
+
+    fn try_match_literal(self, lit: &str) -> Result<Self, (ScanError, Self)> {
+        let mut tmp_off = Space::skip_space(self.slice);
+        let mut tmp = &self.slice[tmp_off..];
+        let mut lit = lit;
+
+        while lit.len() > 0 {
+            // Match leading spaces.
+            match Space::match_spaces(tmp, lit) {
+                Ok((a, b)) => {
+                    tmp = &tmp[a..];
+                    tmp_off += a;
+                    lit = &lit[b..];
+                },
+                Err(off) => {
+                    return Err((
+                        ScanError::literal_mismatch()
+                            .add_offset(self.offset() + tmp_off + off),
+                        self
+                    ));
+                },
+            }
+
+            if lit.len() == 0 { break; }
+
+            // Pull out the leading wordish things.
+            let lit_word = match Word::slice_word(lit) {
+                Some(0) | None => panic!("literal {:?} begins with a non-space, non-word", lit),
+                Some(b) => &lit[..b],
+            };
+            let tmp_word = match Word::slice_word(tmp) {
+                Some(b) => &tmp[..b],
+                None => return Err((
+                    ScanError::literal_mismatch()
+                        .add_offset(self.offset() + tmp_off),
+                    self
+                )),
+            };
+
+            if !Cmp::compare(tmp_word, lit_word) {
+                return Err((
+                    ScanError::literal_mismatch()
+                        .add_offset(self.offset() + tmp_off),
+                    self
+                ));
+            }
+
+            tmp = &tmp[tmp_word.len()..];
+            tmp_off += tmp_word.len();
+            lit = &lit[lit_word.len()..];
+        }
+
+        Ok(self.advance_by(tmp_off))
+    }
+
+    fn as_str(self) -> &'a str {
+        self.slice
+    }
+
+    fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl<'a, Cmp, Space, Word>
+ScanInput<'a> for StrCursor<'a, Cmp, Space, Word>
+where
+    Cmp: StrCompare,
+    Space: SkipSpace,
+    Word: SliceWord,
+{
+    type ScanCursor = Self;
+    type StrCompare = Cmp;
+
+    fn as_str(&self) -> &'a str {
+        self.slice
+    }
+
+    fn from_subslice(&self, subslice: &'a str) -> Self {
+        use ::util::StrUtil;
+        let offset = self.as_str().subslice_offset_stable(subslice)
+            .expect("called `StrCursor::from_subslice` with disjoint subslice");
+
+        StrCursor {
+            offset: self.offset + offset,
+            slice: subslice,
+            _marker: PhantomData,
+        }
+    }
+
+    fn to_cursor(&self) -> Self::ScanCursor {
+        /*
+        Note that we strip the offset information here, essentially making this a *new* cursor, not just a copy of the existing one.
+        */
+        StrCursor::new(self.slice)
+    }
+
+    fn match_literal_with<NewCmp>(&self, lit: &str) -> Result<usize, ScanError>
+    where NewCmp: StrCompare {
+        StrCursor::<NewCmp, Space, Word>::new(self.slice)
+            .try_match_literal(lit)
+            .map(|cur| cur.offset())
+            .map_err(|(err, _)| err)
+    }
+}
+
+/**
+This implementation is provided to allow scanners to be used manually with a minimum of fuss.
+
+It *only* supports direct, exact equality comparison.
+*/
+impl<'a> ScanInput<'a> for &'a str {
+    type ScanCursor = StrCursor<'a>;
+    type StrCompare = ExactCompare;
+
+    fn as_str(&self) -> &'a str {
+        *self
+    }
+
+    fn from_subslice(&self, subslice: &'a str) -> Self {
+        subslice
+    }
+
+    fn to_cursor(&self) -> Self::ScanCursor {
+        self.into_scan_cursor()
+    }
+}
+
+/**
+Wraps a cursor to limit how much work scanning may do, for use with untrusted input.
+
+There are two limits which can be set:
+
+* `max_repeats` limits the number of times any single repetition in a pattern may match.
+* `max_bytes` limits how far into the input scanning may progress; this includes tail captures.
+
+If either limit is exceeded, scanning fails with a `LimitExceeded` error.  Unlike other errors, this is not "recovered" from: it will not cause a repetition to simply stop, nor will later rules be tried.
+
+Note that these limits apply to the *pattern*; they do not extend into scanners which perform their own scanning internally (such as `Vec<T>`).  Use `max_bytes` to bound those.
+
+```ignore
+let cur = Limited::new(input).max_repeats(1_000).max_bytes(64 * 1024);
+let nums = scan!(cur; ([let ns: i32],*) => ns);
+```
+*/
+#[derive(Clone, Copy, Debug)]
+pub struct Limited<C> {
+    cur: C,
+    max_repeats: Option<usize>,
+    max_bytes: Option<usize>,
+}
+
+impl<'a, C> Limited<C> where C: ScanCursor<'a> {
+    /**
+    Construct a new `Limited` cursor from the given input, with no limits set.
+    */
+    pub fn new<I>(input: I) -> Self where I: IntoScanCursor<'a, Output=C> {
+        Limited {
+            cur: input.into_scan_cursor(),
+            max_repeats: None,
+            max_bytes: None,
+        }
+    }
+
+    /**
+    Limit the number of times a single repetition may match.
+    */
+    pub fn max_repeats(self, n: usize) -> Self {
+        Limited { max_repeats: Some(n), ..self }
+    }
+
+    /**
+    Limit the number of bytes of input which may be consumed.
+    */
+    pub fn max_bytes(self, n: usize) -> Self {
+        Limited { max_bytes: Some(n), ..self }
+    }
+
+    fn wrap(&self, cur: C) -> Self {
+        Limited {
+            cur: cur,
+            max_repeats: self.max_repeats,
+            max_bytes: self.max_bytes,
+        }
+    }
+
+    /**
+    Checks that the given cursor, derived from this one, hasn't gone past the byte limit.
+    */
+    fn check(self, cur: C) -> Result<Self, (ScanError, Self)> {
+        match self.max_bytes {
+            Some(max) if cur.offset() > max => Err((ScanError::limit_exceeded().add_offset(max), self)),
+            _ => Ok(self.wrap(cur)),
+        }
+    }
+}
+
+impl<'a, C> ScanCursor<'a> for Limited<C> where C: ScanCursor<'a> {
+    type ScanInput = C::ScanInput;
+
+    fn try_end(self) -> Result<(), (ScanError, Self)> {
+        let cur = self.cur.clone();
+        cur.try_end().map_err(|(err, cur)| (err, self.wrap(cur)))
+    }
+
+    fn try_scan<F, Out>(self, f: F) -> Result<(Out, Self), (ScanError, Self)>
+    where F: FnOnce(Self::ScanInput) -> Result<(Out, usize), ScanError> {
+        match self.cur.clone().try_scan(f) {
+            Ok((out, cur)) => self.check(cur).map(|cur| (out, cur)),
+            Err((err, cur)) => Err((err, self.wrap(cur))),
+        }
+    }
+
+    fn try_scan_raw<F, Out>(self, f: F) -> Result<(Out, Self), (ScanError, Self)>
+    where F: FnOnce(Self::ScanInput) -> Result<(Out, usize), ScanError> {
+        match self.cur.clone().try_scan_raw(f) {
+            Ok((out, cur)) => self.check(cur).map(|cur| (out, cur)),
+            Err((err, cur)) => Err((err, self.wrap(cur))),
+        }
+    }
+
+    fn try_match_literal(self, lit: &str) -> Result<Self, (ScanError, Self)> {
+        match self.cur.clone().try_match_literal(lit) {
+            Ok(cur) => self.check(cur),
+            Err((err, cur)) => Err((err, self.wrap(cur))),
+        }
+    }
+
+    fn as_str(self) -> &'a str {
+        self.cur.as_str()
+    }
+
+    fn offset(&self) -> usize {
+        self.cur.offset()
+    }
+
+    fn max_repeats(&self) -> Option<usize> {
+        self.max_repeats
+    }
+
+    fn line_column(&self) -> Option<(usize, usize)> {
+        self.cur.line_column()
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_limited() {
+    use ::ScanErrorKind as SEK;
+
+    let inp = "1 2 3 4";
+    let cur = Limited::new(inp).max_repeats(4);
+    assert_eq!(scan!(cur; ([let ns: i32]*) => ns).ok(), Some(vec![1, 2, 3, 4]));
+
+    let cur = Limited::new(inp).max_repeats(3);
+    match scan!(cur; ([let ns: i32]*) => ns) {
+        Err(err) => {
+            assert!(matches!(err.kind, SEK::LimitExceeded));
+            assert_eq!(err.at.offset(), 5);
+        },
+        other => panic!("unexpected: {:?}", other),
+    }
+
+    // Later rules must not be tried once a limit has been hit.
+    let r = scan!(cur; ([let ns: i32]*) => ns.len(), (..rest) => rest.len());
+    assert!(matches!(r, Err(ref err) if matches!(err.kind, SEK::LimitExceeded)));
+
+    let cur = Limited::new(inp).max_bytes(4);
+    assert!(matches!(scan!(cur; (let a: i32, let b: i32, ...) => (a, b)), Ok((1, 2))));
+    assert!(matches!(scan!(cur; (let _: i32, ..rest) => rest),
+        Err(ref err) if matches!(err.kind, SEK::LimitExceeded)));
+}
+
+/**
+Wraps a cursor so that leading whitespace is never stripped before scanning a value.
+
+Every value scanned through this cursor behaves as though its scanner's `wants_leading_junk_stripped` returned `false`, making whitespace significant everywhere.  Literal terms are still matched by the wrapped cursor as normal.
+
+This is what `scan!(@raw input; ...)` uses.
+
+```ignore
+// Fails: the space before `2` is not skipped.
+let r = scan!(Raw::new("1, 2"); (let a: i32, ",", let b: i32) => (a, b));
+```
+*/
+#[derive(Clone, Copy, Debug)]
+pub struct Raw<C>(C);
+
+impl<'a, C> Raw<C> where C: ScanCursor<'a> {
+    /**
+    Construct a new `Raw` cursor from the given input.
+    */
+    pub fn new<I>(input: I) -> Self where I: IntoScanCursor<'a, Output=C> {
+        Raw(input.into_scan_cursor())
+    }
+}
+
+impl<'a, C> ScanCursor<'a> for Raw<C> where C: ScanCursor<'a> {
+    type ScanInput = C::ScanInput;
+
+    fn try_end(self) -> Result<(), (ScanError, Self)> {
+        self.0.try_end().map_err(|(err, cur)| (err, Raw(cur)))
+    }
+
+    fn try_scan<F, Out>(self, f: F) -> Result<(Out, Self), (ScanError, Self)>
+    where F: FnOnce(Self::ScanInput) -> Result<(Out, usize), ScanError> {
+        self.try_scan_raw(f)
+    }
+
+    fn try_scan_raw<F, Out>(self, f: F) -> Result<(Out, Self), (ScanError, Self)>
+    where F: FnOnce(Self::ScanInput) -> Result<(Out, usize), ScanError> {
+        match self.0.try_scan_raw(f) {
+            Ok((out, cur)) => Ok((out, Raw(cur))),
+            Err((err, cur)) => Err((err, Raw(cur))),
+        }
+    }
+
+    fn try_match_literal(self, lit: &str) -> Result<Self, (ScanError, Self)> {
+        match self.0.try_match_literal(lit) {
+            Ok(cur) => Ok(Raw(cur)),
+            Err((err, cur)) => Err((err, Raw(cur))),
+        }
+    }
+
+    fn as_str(self) -> &'a str {
+        self.0.as_str()
+    }
+
+    fn offset(&self) -> usize {
+        self.0.offset()
+    }
+
+    fn max_repeats(&self) -> Option<usize> {
+        self.0.max_repeats()
+    }
+
+    fn line_column(&self) -> Option<(usize, usize)> {
+        self.0.line_column()
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_raw() {
+    use ::ScanErrorKind as SEK;
+    use ::scanner::Word;
+
+    assert!(matches!(scan!("1,2"; (let a: i32, ",", let b: i32) => (a, b)), Ok((1, 2))));
+    assert!(matches!(scan!(Raw::new("1,2"); (let a: i32, ",", let b: i32) => (a, b)), Ok((1, 2))));
+    assert!(matches!(scan!(Raw::new("1, 2"); (let a: i32, ",", let b: i32) => (a, b)),
+        Err(ref err) if matches!(err.kind, SEK::Syntax(_)) && err.at.offset() == 2));
+    assert!(matches!(scan!(@raw "1, 2"; (let a: i32, ",", " ", let b: i32) => (a, b)), Ok((1, 2))));
+    assert!(matches!(scan!(@raw " a"; (let w: Word) => w), Err(_)));
+    assert!(matches!(scan!(@raw "a b"; (let w: Word, ..rest) => (w, rest)), Ok(("a", " b"))));
+
+    let raw = "x";
+    assert!(matches!(scan!(raw; (let w: Word) => w), Ok("x")));
+    let raw = [" y"];
+    assert!(matches!(scan!(raw[0]; (let w: Word) => w), Ok("y")));
+}
+
+/**
+Wraps a cursor to keep track of the current line and column as scanning advances.
+
+Lines and columns are both numbered from 1, and columns are counted in code points.  A line ends with `\n`; a preceding `\r` counts as part of the line.
+
+Capturing a `LineTrackingCursor` with `^..name` gives a cursor that can report where it is in the input.  Use [`line_column_at`](#method.line_column_at) to turn the offset of a `ScanError` into a position.
+
+```ignore
+let cur = LineTrackingCursor::new(input);
+match scan!(cur; (let k: Word, "=", let v: i32, ^..rest) => (k, v, rest)) {
+    Ok((k, v, rest)) => println!("{} = {}; now at line {}", k, v, rest.line()),
+    Err(err) => println!("error at {:?}", cur.line_column_at(err.at.offset())),
+}
+```
+*/
+#[derive(Clone, Copy, Debug)]
+pub struct LineTrackingCursor<'a, C> {
+    cur: C,
+    base: &'a str,
+    base_offset: usize,
+    line: usize,
+    column: usize,
+}
+
+impl<'a, C> LineTrackingCursor<'a, C> where C: ScanCursor<'a> {
+    /**
+    Construct a new `LineTrackingCursor` from the given input, starting at line 1, column 1.
+    */
+    pub fn new<I>(input: I) -> Self where I: IntoScanCursor<'a, Output=C> {
+        let cur = input.into_scan_cursor();
+        LineTrackingCursor {
+            base: cur.remaining(),
+            base_offset: cur.offset(),
+            cur: cur,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    /**
+    Returns the current line number.
+    */
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /**
+    Returns the current column number.
+    */
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /**
+    Returns the line and column of the given offset, such as that of a `ScanError`.
+
+    Returns `None` if the offset is not within the input this cursor was created with.
+    */
+    pub fn line_column_at(&self, offset: usize) -> Option<(usize, usize)> {
+        if offset < self.base_offset || !self.base.is_char_boundary(offset - self.base_offset) {
+            return None;
+        }
+        Some(advance_line_column((1, 1), &self.base[..offset - self.base_offset]))
+    }
+
+    /**
+    Wraps a cursor derived from this one, counting the lines and columns skipped over.
+    */
+    fn advance(&self, cur: C) -> Self {
+        let consumed = &self.cur.remaining()[..cur.offset() - self.cur.offset()];
+        let (line, column) = advance_line_column((self.line, self.column), consumed);
+        LineTrackingCursor {
+            cur: cur,
+            base: self.base,
+            base_offset: self.base_offset,
+            line: line,
+            column: column,
+        }
+    }
+}
+
+fn advance_line_column((mut line, mut column): (usize, usize), s: &str) -> (usize, usize) {
+    for c in s.chars() {
+        if c == '\n' {
+            line += 1;
+            column = 1;
+        } else {
+            column += 1;
+        }
+    }
+    (line, column)
+}
+
+impl<'a, C> ScanCursor<'a> for LineTrackingCursor<'a, C> where C: ScanCursor<'a> {
+    type ScanInput = C::ScanInput;
+
+    fn try_end(self) -> Result<(), (ScanError, Self)> {
+        let cur = self.cur.clone();
+        cur.try_end().map_err(|(err, cur)| (err, self.advance(cur)))
+    }
+
+    fn try_scan<F, Out>(self, f: F) -> Result<(Out, Self), (ScanError, Self)>
+    where F: FnOnce(Self::ScanInput) -> Result<(Out, usize), ScanError> {
+        match self.cur.clone().try_scan(f) {
+            Ok((out, cur)) => Ok((out, self.advance(cur))),
+            Err((err, cur)) => Err((err, self.advance(cur))),
+        }
+    }
+
+    fn try_scan_raw<F, Out>(self, f: F) -> Result<(Out, Self), (ScanError, Self)>
+    where F: FnOnce(Self::ScanInput) -> Result<(Out, usize), ScanError> {
+        match self.cur.clone().try_scan_raw(f) {
+            Ok((out, cur)) => Ok((out, self.advance(cur))),
+            Err((err, cur)) => Err((err, self.advance(cur))),
+        }
+    }
+
+    fn try_match_literal(self, lit: &str) -> Result<Self, (ScanError, Self)> {
+        match self.cur.clone().try_match_literal(lit) {
+            Ok(cur) => Ok(self.advance(cur)),
+            Err((err, cur)) => Err((err, self.advance(cur))),
+        }
+    }
+
+    fn as_str(self) -> &'a str {
+        self.cur.as_str()
+    }
+
+    fn offset(&self) -> usize {
+        self.cur.offset()
+    }
+
+    fn max_repeats(&self) -> Option<usize> {
+        self.cur.max_repeats()
+    }
+
+    fn line_column(&self) -> Option<(usize, usize)> {
+        Some((self.line, self.column))
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_line_tracking_cursor() {
+    use ::scanner::Word;
+
+    let inp = "a = 1\nb = 2\r\n  ccc = 3\né x";
+    let cur = LineTrackingCursor::new(inp);
+    assert_eq!((cur.line(), cur.column()), (1, 1));
+
+    let cur = scan!(cur; (let _: Word, "=", let _: i32, ^..rest) => rest).unwrap();
+    assert_eq!((cur.line(), cur.column()), (1, 6));
+
+    let cur = scan!(cur; (let _: Word, "=", let _: i32, let _: Word, ^..rest) => rest).unwrap();
+    assert_eq!((cur.line(), cur.column()), (3, 6));
+
+    let cur = scan!(cur; ("=", let _: i32, let _: Word, ^..rest) => rest).unwrap();
+    assert_eq!((cur.line(), cur.column()), (4, 2));
+    assert_eq!(ScanCursor::line_column(&cur), Some((4, 2)));
+    assert_eq!(ScanCursor::line_column(&Limited::new(cur)), Some((4, 2)));
+    assert_eq!(ScanCursor::line_column(&StrCursor::<ExactCompare>::new(inp)), None);
+
+    let cur = LineTrackingCursor::new(inp);
+    let err = scan!(cur; ([let _: Word, "=", let _: i32]+) => ()).unwrap_err();
+    assert_eq!(cur.line_column_at(err.at.offset()), Some((3, 10)));
+    assert_eq!(cur.line_column_at(0), Some((1, 1)));
+    assert_eq!(cur.line_column_at(inp.len()), Some((4, 4)));
+    assert_eq!(cur.line_column_at(inp.len() + 1), None);
+    assert_eq!(cur.line_column_at(inp.len() - 3), None);
+}
+
+/**
+Splits text from a `char` iterator into lines, buffering only one line at a time.
+
+This is intended for input which is not stored contiguously, such as a rope, or text that is being produced incrementally.  Rather than collecting everything into one big `String`, each line is collected into an internal buffer, which can then be scanned as normal.  The buffer is reused between lines.
+
+For a rope which exposes its contents as string chunks, use something like `CharLines::new(rope.chunks().flat_map(str::chars))`.
+
+```ignore
+let mut lines = CharLines::new(source_chars);
+while let Some(line) = lines.next_line() {
+    let_scan!(line; (let k: Word, "=", let v: i32));
+}
+```
+*/
+pub struct CharLines<It> {
+    chars: It,
+    line: String,
+}
+
+impl<It> CharLines<It> where It: Iterator<Item=char> {
+    /**
+    Construct a new `CharLines` around the given iterator.
+    */
+    pub fn new(chars: It) -> Self {
+        CharLines {
+            chars: chars,
+            line: String::new(),
+        }
+    }
+
+    /**
+    Reads the next line from the iterator, returning `None` once it is exhausted.
+
+    The returned line does not include the line terminator (`"\n"` or `"\r\n"`).
+    */
+    pub fn next_line(&mut self) -> Option<&str> {
+        self.line.clear();
+        let mut any = false;
+        for c in self.chars.by_ref() {
+            any = true;
+            if c == '\n' {
+                break;
+            }
+            self.line.push(c);
+        }
+        if !any {
+            return None;
+        }
+        if self.line.ends_with('\r') {
+            let len = self.line.len() - 1;
+            self.line.truncate(len);
+        }
+        Some(&self.line)
+    }
+
+    /**
+    Returns the underlying iterator.  Any partially read line is lost.
+    */
+    pub fn into_inner(self) -> It {
+        self.chars
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_char_lines() {
+    let mut lines = CharLines::new("".chars());
+    assert_eq!(lines.next_line(), None);
+
+    let mut lines = CharLines::new(["a = 1\r\nb", " = 2\n", "\nc = 3"].iter().flat_map(|s| s.chars()));
+    assert_eq!(lines.next_line(), Some("a = 1"));
+    assert_eq!(lines.next_line(), Some("b = 2"));
+    assert_eq!(lines.next_line(), Some(""));
+    assert_eq!(lines.next_line(), Some("c = 3"));
+    assert_eq!(lines.next_line(), None);
+    assert_eq!(lines.next_line(), None);
+}
+
+#[cfg(test)]
+#[test]
+fn test_chars_cursor() {
+    let mut chars = "x 42".chars();
+    chars.next();
+    assert_eq!(scan!(chars; (let v: i32) => v).ok(), Some(42));
+}
+
+/**
+Text stored as a sequence of separate chunks, such as the pieces of a rope, which can be scanned without first joining them into one big `String`.
+
+Scanning with `&ChunkedText` produces a [`ChunkCursor`](struct.ChunkCursor.html).  Whitespace between terms is skipped chunk by chunk, without copying anything.  Scanners, literals and tail captures are given all of the remaining text.  Where that lies entirely within the last chunk, it is borrowed directly.  Otherwise, the chunks are joined into a single buffer owned by the `ChunkedText`; this happens at most once, and the buffer is kept until the `ChunkedText` is dropped.
+
+Offsets, such as those of errors, are into the text as though all the chunks had been joined.
+
+```rust
+# #[macro_use] extern crate scan_rules;
+# use scan_rules::input::ChunkedText;
+# use scan_rules::scanner::Word;
+# fn main() {
+let text = ChunkedText::new(vec!["width = 4", "2\nhei", "ght = 7\n"]);
+let dims = scan!(&text; ([let k: Word, "=", let v: u32]+) => (k, v));
+assert_eq!(dims.unwrap(), (vec!["width", "height"], vec![42, 7]));
+# }
+```
+*/
+#[derive(Debug)]
+pub struct ChunkedText<'a> {
+    /// The non-empty chunks.
+    chunks: Vec<&'a str>,
+    /// The offset at which each chunk starts.
+    starts: Vec<usize>,
+    len: usize,
+    /**
+    All of the chunks joined together, built the first time text spanning more than one chunk is needed.
+
+    Once built, this is never replaced or modified, so the text remains at a fixed address for as long as `self` exists.
+    */
+    joined: RefCell<Option<Box<str>>>,
+}
+
+impl<'a> ChunkedText<'a> {
+    /**
+    Construct a new `ChunkedText` from the given chunks.  Empty chunks are ignored.
+    */
+    pub fn new<I>(chunks: I) -> Self where I: IntoIterator<Item=&'a str> {
+        let mut text = ChunkedText {
+            chunks: vec![],
+            starts: vec![],
+            len: 0,
+            joined: RefCell::new(None),
+        };
+        for chunk in chunks.into_iter().filter(|c| c.len() > 0) {
+            text.chunks.push(chunk);
+            text.starts.push(text.len);
+            text.len += chunk.len();
+        }
+        text
+    }
+
+    /**
+    Returns the total length of the text, in bytes.
+    */
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /**
+    Returns the index of the chunk containing `offset`, and the remainder of that chunk.  `offset` must be less than the total length.
+    */
+    fn chunk_at(&self, offset: usize) -> (usize, &'a str) {
+        let i = match self.starts.binary_search(&offset) {
+            Ok(i) => i,
+            Err(i) => i - 1,
+        };
+        (i, &self.chunks[i][offset - self.starts[i]..])
+    }
+
+    /**
+    Returns the offset of the first non-whitespace character at or after `offset`.
+    */
+    fn skip_space_from(&self, mut offset: usize) -> usize {
+        while offset < self.len {
+            let (_, rest) = self.chunk_at(offset);
+            let n = skip_space_len(rest);
+            offset += n;
+            if n < rest.len() {
+                break;
+            }
+        }
+        offset
+    }
+
+    /**
+    Returns all of the text from `offset` onward.
+    */
+    fn text_at(&self, offset: usize) -> &str {
+        if offset >= self.len {
+            return "";
+        }
+
+        let (i, rest) = self.chunk_at(offset);
+        if i + 1 == self.chunks.len() {
+            return rest;
+        }
+
+        let mut joined = self.joined.borrow_mut();
+        if joined.is_none() {
+            let mut text = String::with_capacity(self.len);
+            for chunk in &self.chunks {
+                text.push_str(chunk);
+            }
+            *joined = Some(text.into_boxed_str());
+        }
+        let text: *const str = &joined.as_ref().unwrap()[offset..];
+
+        // This is safe because the joined text is never replaced or dropped while `self` exists.
+        unsafe { &*text }
+    }
+}
+
+impl<'b, 'a: 'b> IntoScanCursor<'b> for &'b ChunkedText<'a> {
+    type Output = ChunkCursor<'b>;
+    fn into_scan_cursor(self) -> Self::Output {
+        ChunkCursor {
+            text: self,
+            offset: 0,
+        }
+    }
+}
+
+/**
+Cursor over a [`ChunkedText`](struct.ChunkedText.html).
+
+Scanners are given the rest of the text as a `StrCursor`.
+*/
+#[derive(Clone, Copy, Debug)]
+pub struct ChunkCursor<'a> {
+    text: &'a ChunkedText<'a>,
+    offset: usize,
+}
+
+impl<'a> ChunkCursor<'a> {
+    /**
+    Scans the text starting at `at`, without skipping whitespace.
+    */
+    fn scan_text_at<F, Out>(self, at: usize, f: F) -> Result<(Out, Self), (ScanError, Self)>
+    where F: FnOnce(StrCursor<'a>) -> Result<(Out, usize), ScanError> {
+        match StrCursor::new(self.text.text_at(at)).try_scan_raw(f) {
+            Ok((out, cur)) => Ok((out, ChunkCursor { offset: at + cur.offset(), ..self })),
+            Err((err, _)) => Err((err.add_offset(at), self)),
+        }
+    }
+}
+
+impl<'a> ScanCursor<'a> for ChunkCursor<'a> {
+    type ScanInput = StrCursor<'a>;
+
+    fn try_end(self) -> Result<(), (ScanError, Self)> {
+        if self.text.skip_space_from(self.offset) == self.text.len {
+            Ok(())
+        } else {
+            Err((ScanError::expected_end().add_offset(self.offset), self))
+        }
+    }
+
+    fn try_scan<F, Out>(self, f: F) -> Result<(Out, Self), (ScanError, Self)>
+    where F: FnOnce(Self::ScanInput) -> Result<(Out, usize), ScanError> {
+        let at = self.text.skip_space_from(self.offset);
+        self.scan_text_at(at, f)
+    }
+
+    fn try_scan_raw<F, Out>(self, f: F) -> Result<(Out, Self), (ScanError, Self)>
+    where F: FnOnce(Self::ScanInput) -> Result<(Out, usize), ScanError> {
+        let at = self.offset;
+        self.scan_text_at(at, f)
+    }
+
+    fn try_match_literal(self, lit: &str) -> Result<Self, (ScanError, Self)> {
+        let at = self.text.skip_space_from(self.offset);
+        match StrCursor::<ExactCompare>::new(self.text.text_at(at)).try_match_literal(lit) {
+            Ok(cur) => Ok(ChunkCursor { offset: at + cur.offset(), ..self }),
+            Err((err, _)) => Err((err.add_offset(at), self)),
+        }
+    }
+
+    fn as_str(self) -> &'a str {
+        self.text.text_at(self.offset)
+    }
+
+    fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_chunked_text() {
+    use ::ScanErrorKind as SEK;
+    use ::scanner::{Everything, QuotedString, Word};
+
+    let text = ChunkedText::new(vec![]);
+    assert_eq!(text.len(), 0);
+    assert_eq!(scan!(&text; () => ()).ok(), Some(()));
+
+    let text = ChunkedText::new(vec!["  a", "", "bc 1", "23  \n\n", " ", "\"x y\" ", "tail"]);
+    assert_eq!(text.len(), 24);
+    assert_eq!(
+        scan!(&text; (let w: Word, let n: u32, let q: QuotedString, ..rest) => (w, n, q, rest)).ok(),
+        Some(("abc", 123, String::from("x y"), " tail")));
+    assert_eq!(scan!(&text; (let w: Word, ...) => w).ok(), Some("abc"));
+
+    assert_eq!(scan!(&text; ("abc", "123", ^..cur) => cur.offset()).ok(), Some(9));
+    let err = scan!(&text; ("abc", let _: QuotedString) => ()).unwrap_err();
+    assert!(matches!(err.kind, SEK::Syntax(_)));
+    assert_eq!(err.at.offset(), 6);
+    let err = scan!(&text; ("abc", "123") => ()).unwrap_err();
+    assert!(matches!(err.kind, SEK::ExpectedEnd));
+    assert_eq!(err.at.offset(), 9);
+    let err = scan!(&text; ("abc", "12") => ()).unwrap_err();
+    assert!(matches!(err.kind, SEK::LiteralMismatch));
+    assert_eq!(err.at.offset(), 6);
+
+    // Tail captures, literals and scanners all see past the end of the current line.
+    let text = ChunkedText::new(vec!["key = v", "al\nsec", "ond line\n"]);
+    assert_eq!(
+        scan!(&text; (let k: Word, "=", ..rest) => (k, rest)).ok(),
+        Some(("key", " val\nsecond line\n")));
+    assert_eq!(
+        scan!(&text; ("key = val\nsecond", ..rest) => rest).ok(),
+        Some(" line\n"));
+    assert_eq!(
+        scan!(&text; ("key", let e: Everything) => e).ok(),
+        Some("= val\nsecond line\n"));
+    assert_eq!(scan!(&text; ("key", "=", "val", ^..cur) => cur.as_str()).ok(), Some("\nsecond line\n"));
+}
+
+/**
+Buffers text read from an `io::Read` for token-based scanning with [`scan_tokens!`](../macro.scan_tokens!.html).
+
+Unlike `readln!`, which scans exactly one line at a time, this allows a single pattern to span any number of lines: input is read a line at a time, but only as much as is needed for a pattern to match.  Whatever the pattern does not consume is kept for the next scan.
+
+```ignore
+let mut tokens = TokenReader::new(io::stdin());
+let n = scan_tokens!(tokens; (let n: usize) => n).unwrap();
+for _ in 0..n {
+    let (a, b) = scan_tokens!(tokens; (let a: i32, let b: i32) => (a, b)).unwrap();
+}
+```
+*/
+#[cfg(feature="std")]
+pub struct TokenReader<R> {
+    inner: BufReader<R>,
+    buf: String,
+    eof: bool,
+}
+
+#[cfg(feature="std")]
+impl<R> TokenReader<R> where R: Read {
+    /**
+    Construct a new `TokenReader` around the given reader.
+    */
+    pub fn new(inner: R) -> Self {
+        TokenReader {
+            inner: BufReader::new(inner),
+            buf: String::new(),
+            eof: false,
+        }
+    }
+
+    /**
+    Returns the buffered input which has not yet been consumed.
+    */
+    pub fn buffer(&self) -> &str {
+        &self.buf
+    }
+
+    /**
+    Discards the given number of bytes from the start of the buffer.
+    */
+    pub fn consume(&mut self, bytes: usize) {
+        self.buf.drain(..bytes);
+    }
+
+    /**
+    Reads another line of input into the buffer.  Returns `false` if the end of the input has been reached.
+    */
+    pub fn read_more(&mut self) -> io::Result<bool> {
+        if self.eof {
+            return Ok(false);
+        }
+        let n = try!(self.inner.read_line(&mut self.buf));
+        self.eof = n == 0;
+        Ok(!self.eof)
+    }
+
+    /**
+    Returns `true` if the end of the input has been reached.  There may still be unconsumed input in the buffer.
+    */
+    pub fn is_eof(&self) -> bool {
+        self.eof
+    }
+
+    /**
+    Returns the underlying reader.  Any buffered input is lost.
+    */
+    pub fn into_inner(self) -> R {
+        self.inner.into_inner()
+    }
+}
+
+#[cfg(feature="std")]
+#[cfg(test)]
+#[test]
+fn test_token_reader() {
+    let mut tokens = TokenReader::new("1 2\n\n3\nx".as_bytes());
+    assert_eq!(tokens.buffer(), "");
+    assert_eq!(tokens.read_more().ok(), Some(true));
+    assert_eq!(tokens.buffer(), "1 2\n");
+    tokens.consume(2);
+    assert_eq!(tokens.read_more().ok(), Some(true));
+    assert_eq!(tokens.read_more().ok(), Some(true));
+    assert_eq!(tokens.read_more().ok(), Some(true));
+    assert_eq!(tokens.buffer(), "2\n\n3\nx");
+    assert_eq!(tokens.is_eof(), false);
+    assert_eq!(tokens.read_more().ok(), Some(false));
+    assert_eq!(tokens.is_eof(), true);
+    assert_eq!(tokens.read_more().ok(), Some(false));
+}
+
+/**
+Scans an `io::BufRead` one record (line) at a time, keeping track of how much of the input has been consumed.
+
+This is intended for long-running scans over large inputs.  A progress callback can be registered with `on_progress`, and the counts of bytes and lines consumed so far can be saved so that a later session can pick up where this one left off using `resume_at`.
+
+```ignore
+let mut session = ScanSession::new(BufReader::new(file))
+    .on_progress(|bytes, _lines| eprint!("\r{} bytes", bytes));
+while let Some(rec) = try!(session.next_record(|line| scan!(line; (let k: Word<String>, "=", let v: i32) => (k, v)))) {
+    // ...
+}
+```
+*/
+#[cfg(feature="std")]
+pub struct ScanSession<R> {
+    inner: R,
+    line: String,
+    bytes: u64,
+    lines: u64,
+    progress: Option<Box<FnMut(u64, u64)>>,
+}
+
+#[cfg(feature="std")]
+impl<R> ScanSession<R> where R: BufRead {
+    /**
+    Construct a new `ScanSession` around the given reader.
+    */
+    pub fn new(inner: R) -> Self {
+        ScanSession::resume_at(inner, 0, 0)
+    }
+
+    /**
+    Construct a new `ScanSession` around a reader which has already been positioned part-way through its input.
+
+    `bytes` and `lines` are the counts reported by an earlier session at the point it stopped; they are used as the starting point for this session's counts.  Positioning the reader (for example, by seeking) is up to the caller.
+    */
+    pub fn resume_at(inner: R, bytes: u64, lines: u64) -> Self {
+        ScanSession {
+            inner: inner,
+            line: String::new(),
+            bytes: bytes,
+            lines: lines,
+            progress: None,
+        }
+    }
+
+    /**
+    Registers a callback which is invoked with the number of bytes and lines consumed so far, each time a record is read.
+    */
+    pub fn on_progress<F>(self, f: F) -> Self
+    where F: 'static + FnMut(u64, u64) {
+        ScanSession { progress: Some(Box::new(f)), ..self }
+    }
+
+    /**
+    Returns the number of bytes consumed so far, including line terminators.
+    */
+    pub fn bytes_consumed(&self) -> u64 {
+        self.bytes
+    }
+
+    /**
+    Returns the number of lines consumed so far.
+    */
+    pub fn lines_consumed(&self) -> u64 {
+        self.lines
+    }
+
+    /**
+    Reads the next line of input, and scans it (without its line terminator) using `f`, which will typically be a closure that invokes `scan!`.
+
+    Returns `Ok(None)` once the end of the input has been reached.  The line is counted as consumed even if scanning fails, so that a caller can report the error and continue with the next record.
+    */
+    pub fn next_record<F, T>(&mut self, f: F) -> Result<Option<T>, ScanError>
+    where F: FnOnce(&str) -> Result<T, ScanError> {
+        self.line.clear();
+        let n = try!(self.inner.read_line(&mut self.line).map_err(ScanError::io));
+        if n == 0 {
+            return Ok(None);
+        }
+
+        self.bytes += n as u64;
+        self.lines += 1;
+        if let Some(ref mut progress) = self.progress {
+            progress(self.bytes, self.lines);
+        }
+
+        let line = ::internal::strip_line_term(&self.line);
+        f(line)
+            .map(Some)
+            .map_err(|err| err.with_input(line))
+    }
+
+    /**
+    Returns the underlying reader.
+    */
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+#[cfg(feature="std")]
+#[cfg(test)]
+#[test]
+fn test_scan_session() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use ::ScanErrorKind as SEK;
+
+    let input = "a = 1\r\nb = x\nc = 3";
+    let calls = Rc::new(Cell::new(0));
+    let calls_cb = calls.clone();
+    let mut session = ScanSession::new(input.as_bytes())
+        .on_progress(move |_, _| calls_cb.set(calls_cb.get() + 1));
+
+    let rule = |line: &str| scan!(line; (let k: ::scanner::Word<String>, "=", let v: i32) => (k, v));
+    assert_eq!(session.next_record(&rule).ok(), Some(Some(("a".into(), 1))));
+    assert_eq!((session.bytes_consumed(), session.lines_consumed()), (7, 1));
+    assert!(match session.next_record(&rule) { Err(ScanError { kind: SEK::Syntax(_), .. }) => true, _ => false });
+    assert_eq!((session.bytes_consumed(), session.lines_consumed()), (13, 2));
+    assert_eq!(session.next_record(&rule).ok(), Some(Some(("c".into(), 3))));
+    assert_eq!((session.bytes_consumed(), session.lines_consumed()), (18, 3));
+    assert_eq!(session.next_record(&rule).ok(), Some(None));
+    assert_eq!(calls.get(), 3);
+
+    let mut session = ScanSession::resume_at(&input.as_bytes()[13..], 13, 2);
+    assert_eq!(session.next_record(&rule).ok(), Some(Some(("c".into(), 3))));
+    assert_eq!((session.bytes_consumed(), session.lines_consumed()), (18, 3));
+}
+
+/**
+A file mapped into memory, which can be scanned without first reading it into a buffer.
+
+The contents of the file are checked to be valid UTF-8 when it is opened.  The input can then be scanned directly using `&MappedInput`, or broken into records with [`lines`](#method.lines) and [`records`](#method.records).  Either way, everything scanned borrows from the `MappedInput`, so it cannot be unmapped whilst in use.
+
+```ignore
+let input = try!(unsafe { MappedInput::open("huge.log") });
+for (offset, line) in input.lines() {
+    match scan!(line; (let level: Word, ":", ..msg) => (level, msg)) {
+        Ok((level, msg)) => { /* ... */ },
+        Err(err) => println!("error at byte {}: {}", offset + err.at.offset(), err),
+    }
+}
+```
+
+**Note**: requires the `mmap` feature.
+*/
+#[cfg(feature="mmap")]
+pub struct MappedInput {
+    /*
+    Mapping an empty file is an error on some platforms, so empty files aren't mapped at all.
+    */
+    map: Option<::memmap::Mmap>,
+}
+
+#[cfg(feature="mmap")]
+impl MappedInput {
+    /**
+    Map the file at the given path into memory.
+
+    Fails with an `InvalidData` error if the file's contents are not valid UTF-8.
+
+    # Safety
+
+    The file must not be modified, by this or any other process, whilst it is mapped.  Doing so would allow the contents of a `&str` to change underneath it, possibly into something that is no longer valid UTF-8.
+    */
+    pub unsafe fn open<P: AsRef<::std::path::Path>>(path: P) -> io::Result<MappedInput> {
+        let file = try!(::std::fs::File::open(path));
+        if try!(file.metadata()).len() == 0 {
+            return Ok(MappedInput { map: None });
+        }
+
+        let map = try!(::memmap::Mmap::open(&file, ::memmap::Protection::Read));
+        if let Err(err) = ::std::str::from_utf8(map.as_slice()) {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, err));
+        }
+
+        Ok(MappedInput { map: Some(map) })
+    }
+
+    /**
+    Returns the contents of the file.
+    */
+    pub fn as_str(&self) -> &str {
+        match self.map {
+            // This was checked when the file was opened.
+            Some(ref map) => unsafe { ::std::str::from_utf8_unchecked(map.as_slice()) },
+            None => "",
+        }
+    }
+
+    /**
+    Returns an iterator over the lines of the file, along with the offset at which each line starts.
+
+    Lines are split as they are by [`ScanSession`](struct.ScanSession.html), and do not include their line terminators.
+    */
+    pub fn lines(&self) -> MappedRecords {
+        MappedRecords {
+            rest: self.as_str(),
+            offset: 0,
+            term: '\n',
+        }
+    }
+
+    /**
+    Returns an iterator over the records of the file, each ended by `term`, along with the offset at which each record starts.
+
+    Records do not include their terminators.  The final record need not be terminated.
+    */
+    pub fn records(&self, term: char) -> MappedRecords {
+        MappedRecords {
+            rest: self.as_str(),
+            offset: 0,
+            term: term,
+        }
+    }
+}
+
+#[cfg(feature="mmap")]
+impl<'a> IntoScanCursor<'a> for &'a MappedInput {
+    type Output = StrCursor<'a>;
+    fn into_scan_cursor(self) -> Self::Output {
+        StrCursor::new(self.as_str())
+    }
+}
+
+/**
+An iterator over the records in a `MappedInput`.
+
+See: [`MappedInput::records`](struct.MappedInput.html#method.records).
+
+**Note**: requires the `mmap` feature.
+*/
+#[cfg(feature="mmap")]
+pub struct MappedRecords<'a> {
+    rest: &'a str,
+    offset: usize,
+    term: char,
+}
+
+#[cfg(feature="mmap")]
+impl<'a> Iterator for MappedRecords<'a> {
+    type Item = (usize, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rest.len() == 0 {
+            return None;
+        }
+
+        let offset = self.offset;
+        let (rec, len) = match self.rest.find(self.term) {
+            Some(i) => (&self.rest[..i], i + self.term.len_utf8()),
+            None => (self.rest, self.rest.len()),
+        };
+        self.rest = &self.rest[len..];
+        self.offset += len;
+
+        let rec = if self.term == '\n' { ::internal::strip_line_term(rec) } else { rec };
+        Some((offset, rec))
+    }
+}
+
+#[cfg(feature="mmap")]
+#[cfg(test)]
+#[test]
+fn test_mapped_input() {
+    use std::fs::{self, File};
+    use std::io::Write;
+    use ::scanner::Word;
+
+    // Unique to this process and test, so concurrent test runs don't trample each other's files.
+    let path = ::std::env::temp_dir().join(format!("scan-rules-{}-test_mapped_input.txt", ::std::process::id()));
+    {
+        let mut file = File::create(&path).unwrap();
+        file.write_all(b"a = 1\r\nb = 2\nc = 3").unwrap();
+    }
+    let input = unsafe { MappedInput::open(&path) }.unwrap();
+
+    assert!(matches!(scan!(&input; (let k: Word, "=", let v: i32, ..rest) => (k, v, rest)), Ok(("a", 1, "\r\nb = 2\nc = 3"))));
+
+    let lines: Vec<_> = input.lines().collect();
+    assert_eq!(lines, vec![(0, "a = 1"), (7, "b = 2"), (13, "c = 3")]);
+
+    let recs: Vec<_> = input.records('=').map(|(_, r)| r).collect();
+    assert_eq!(recs, vec!["a ", " 1\r\nb ", " 2\nc ", " 3"]);
+
+    File::create(&path).unwrap();
+    let input = unsafe { MappedInput::open(&path) }.unwrap();
+    assert_eq!(input.as_str(), "");
+    assert_eq!(input.lines().count(), 0);
+
+    {
+        let mut file = File::create(&path).unwrap();
+        file.write_all(b"a = \xff").unwrap();
+    }
+    assert!(match unsafe { MappedInput::open(&path) } { Err(ref err) => err.kind() == io::ErrorKind::InvalidData, _ => false });
+
+    fs::remove_file(&path).unwrap();
+}
+
+/**
+Text converted from bytes which may not be valid UTF-8, with each invalid sequence replaced by U+FFFD REPLACEMENT CHARACTER.
+
+This is intended for input which is *mostly* text, such as log files containing the occasional bit of binary garbage.  Sequences are replaced in the same way as `String::from_utf8_lossy`.  Because a replacement character does not, in general, take up the same number of bytes as the sequence it replaces, the converted text keeps track of where each part of it came from.  Use [`original_offset`](#method.original_offset) or [`remap_error`](#method.remap_error) to translate a position in the converted text back into a position in the original bytes.
+
+`&LossyText` can be used directly as input to the scanning macros.
+
+See: [`LossyLines`](struct.LossyLines.html).
+*/
+#[derive(Clone, Debug)]
+pub struct LossyText {
+    text: String,
+
+    /*
+    One entry for the start of each replacement character, and one for the start of the valid text after it, as `(converted offset, original offset)`.  These are sorted, and replacement entries always have an even index.
+    */
+    fixups: Vec<(usize, usize)>,
+}
+
+impl LossyText {
+    /**
+    Converts the given bytes.
+    */
+    pub fn from_bytes(bytes: &[u8]) -> LossyText {
+        let mut text = LossyText {
+            text: String::with_capacity(bytes.len()),
+            fixups: vec![],
+        };
+        text.push_bytes(bytes, 0);
+        text
+    }
+
+    /**
+    Replaces the contents with the conversion of the given bytes, reusing the existing allocations.
+    */
+    #[cfg(feature="std")]
+    fn reset(&mut self, bytes: &[u8]) {
+        self.text.clear();
+        self.fixups.clear();
+        self.push_bytes(bytes, 0);
+    }
+
+    fn push_bytes(&mut self, mut bytes: &[u8], mut orig_off: usize) {
+        loop {
+            match ::std::str::from_utf8(bytes) {
+                Ok(s) => {
+                    self.text.push_str(s);
+                    return;
+                },
+                Err(err) => {
+                    let valid = err.valid_up_to();
+                    let invalid = invalid_utf8_len(&bytes[valid..]);
+                    self.text.push_str(unsafe { ::std::str::from_utf8_unchecked(&bytes[..valid]) });
+                    orig_off += valid;
+
+                    self.fixups.push((self.text.len(), orig_off));
+                    self.text.push('\u{fffd}');
+                    orig_off += invalid;
+                    self.fixups.push((self.text.len(), orig_off));
+
+                    bytes = &bytes[valid + invalid..];
+                },
+            }
+        }
+    }
+
+    /**
+    Returns the converted text.
+    */
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /**
+    Returns `true` if any invalid sequences were replaced.
+    */
+    pub fn is_lossy(&self) -> bool {
+        self.fixups.len() > 0
+    }
+
+    /**
+    Translates an offset into the converted text into an offset into the original bytes.
+
+    An offset within a replacement character is translated to the start of the sequence it replaced.
+    */
+    pub fn original_offset(&self, offset: usize) -> usize {
+        fixup_offset(&self.fixups, offset)
+    }
+
+    /**
+    Translates the position of an error from scanning the converted text into a position in the original bytes.
+
+    A snippet of the converted text is captured at the error's position, as by `ScanError::with_input`.
+    */
+    pub fn remap_error(&self, err: ScanError) -> ScanError {
+        err.with_input(&self.text)
+            .map_offset(|off| self.original_offset(off))
+    }
+}
+
+impl<'a> IntoScanCursor<'a> for &'a LossyText {
+    type Output = StrCursor<'a>;
+    fn into_scan_cursor(self) -> Self::Output {
+        StrCursor::new(self.as_str())
+    }
 }
 
 /**
-Basic cursor implementation wrapping a string slice.
+Translates an offset into converted text back into an offset into the original, given a list of fixups.
 
-The `Cmp` parameter can be used to control the string comparison logic used.
+The fixups come in pairs of `(converted offset, original offset)`: one for the start of each changed part of the text, and one for the start of the unchanged text after it.  An offset within a changed part is translated to the start of that part.
 */
-#[derive(Debug)]
-pub struct StrCursor<'a, Cmp=ExactCompare, Space=IgnoreSpace, Word=Wordish>
-where
-    Cmp: StrCompare,
-    Space: SkipSpace,
-    Word: SliceWord,
-{
-    offset: usize,
-    slice: &'a str,
-    _marker: PhantomData<(Cmp, Space, Word)>,
+fn fixup_offset(fixups: &[(usize, usize)], offset: usize) -> usize {
+    match fixups.binary_search_by(|&(conv, _)| conv.cmp(&offset)) {
+        Ok(i) => fixups[i].1,
+        Err(0) => offset,
+        Err(i) if i % 2 == 1 => fixups[i - 1].1,
+        Err(i) => {
+            let (conv, orig) = fixups[i - 1];
+            orig + (offset - conv)
+        }
+    }
 }
 
-/*
-These have to be spelled out to avoid erroneous constraints on the type parameters.
+/**
+Returns the length of the invalid sequence at the start of `bs`, following the "maximal subpart" rule used by `String::from_utf8_lossy`.
 */
-impl<'a, Cmp, Space, Word>
-Copy for StrCursor<'a, Cmp, Space, Word>
-where
-    Cmp: StrCompare,
-    Space: SkipSpace,
-    Word: SliceWord,
-{}
+fn invalid_utf8_len(bs: &[u8]) -> usize {
+    let (cont, lo, hi) = match bs[0] {
+        0xc2...0xdf => (1, 0x80, 0xbf),
+        0xe0 => (2, 0xa0, 0xbf),
+        0xe1...0xec | 0xee...0xef => (2, 0x80, 0xbf),
+        0xed => (2, 0x80, 0x9f),
+        0xf0 => (3, 0x90, 0xbf),
+        0xf1...0xf3 => (3, 0x80, 0xbf),
+        0xf4 => (3, 0x80, 0x8f),
+        _ => return 1
+    };
+
+    for i in 1..cont + 1 {
+        let (lo, hi) = if i == 1 { (lo, hi) } else { (0x80, 0xbf) };
+        match bs.get(i) {
+            Some(&b) if lo <= b && b <= hi => (),
+            _ => return i,
+        }
+    }
 
-impl<'a, Cmp, Space, Word>
-Clone for StrCursor<'a, Cmp, Space, Word>
-where
-    Cmp: StrCompare,
-    Space: SkipSpace,
-    Word: SliceWord,
-{
-    fn clone(&self) -> Self {
-        *self
+    // The sequence is complete, which means it wasn't actually invalid.
+    unreachable!()
+}
+
+#[cfg(test)]
+#[test]
+fn test_lossy_text() {
+    use ::ScanErrorKind as SEK;
+    use ::scanner::NonSpace;
+
+    let text = LossyText::from_bytes(b"plain");
+    assert_eq!(text.as_str(), "plain");
+    assert!(!text.is_lossy());
+    assert_eq!(text.original_offset(3), 3);
+
+    let cases: &[&[u8]] = &[
+        b"a\xffb",
+        b"a\xc3b",
+        b"a\xe2\x82b",
+        b"a\xf0\x9f\x98b",
+        b"a\xed\xa0\x80b",
+        b"a\xc0\x80b",
+        b"\xe2\x82\xac\xff\xfe\xe2",
+        b"caf\xc3\xa9",
+    ];
+    for &bs in cases {
+        assert_eq!(LossyText::from_bytes(bs).as_str(), String::from_utf8_lossy(bs));
     }
+
+    let bs = b"ab\xff\xfecd\xe2\x82xy";
+    let text = LossyText::from_bytes(bs);
+    assert_eq!(text.as_str(), "ab\u{fffd}\u{fffd}cd\u{fffd}xy");
+    assert!(text.is_lossy());
+    let offs: Vec<_> = (0..text.as_str().len() + 1).map(|i| text.original_offset(i)).collect();
+    assert_eq!(offs, [0, 1, 2, 2, 2, 3, 3, 3, 4, 5, 6, 6, 6, 8, 9, 10]);
+
+    let text = LossyText::from_bytes(b"count=\xff\xff12 size=x");
+    assert!(matches!(scan!(&text; ("count=", ..rest) => rest), Ok("\u{fffd}\u{fffd}12 size=x")));
+    let err = scan!(&text; ("count=", let _: NonSpace, "size=", let _: u32) => ()).unwrap_err();
+    assert!(matches!(err.kind, SEK::Syntax(_)));
+    assert_eq!(err.at.offset(), 20);
+    let err = text.remap_error(err);
+    assert_eq!(err.at.offset(), 16);
+    assert_eq!(err.at.snippet(), Some("x"));
 }
 
-impl<'a, Cmp, Space, Word>
-StrCursor<'a, Cmp, Space, Word>
-where
-    Cmp: StrCompare,
-    Space: SkipSpace,
-    Word: SliceWord,
-{
-    /**
-    Construct a new `StrCursor` with a specific `offset`.
+/**
+Reads lines from an `io::BufRead` which may not contain valid UTF-8, converting each one with [`LossyText`](struct.LossyText.html).
 
-    The `offset` is logically the number of bytes which have already been consumed from the original input; these already-consumed bytes *must not* be included in `slice`.
-    */
-    pub fn new(slice: &'a str) -> Self {
-        StrCursor {
-            offset: 0,
-            slice: slice,
-            _marker: PhantomData,
-        }
+Only one line is held in memory at a time.  The byte offset of each line in the original input is tracked, so that errors can be reported at their true position.
+
+```ignore
+let mut lines = LossyLines::new(BufReader::new(file));
+loop {
+    match lines.next_record(|line| scan!(line; (let level: Word, ":", ..msg) => (level.to_owned(), msg.to_owned()))) {
+        Ok(Some((level, msg))) => { /* ... */ },
+        Ok(None) => break,
+        Err(err) => println!("error at byte {}: {}", err.at.offset(), err),
     }
+}
+```
+*/
+#[cfg(feature="std")]
+pub struct LossyLines<R> {
+    inner: R,
+    buf: Vec<u8>,
+    line: LossyText,
+    offset: u64,
+}
 
+#[cfg(feature="std")]
+impl<R> LossyLines<R> where R: BufRead {
     /**
-    Advance the cursor by the given number of bytes.
+    Construct a new `LossyLines` around the given reader.
     */
-    fn advance_by(self, bytes: usize) -> Self {
-        StrCursor {
-            offset: self.offset + bytes,
-            slice: &self.slice[bytes..],
-            _marker: PhantomData,
+    pub fn new(inner: R) -> Self {
+        LossyLines {
+            inner: inner,
+            buf: vec![],
+            line: LossyText::from_bytes(b""),
+            offset: 0,
         }
     }
 
     /**
-    Returns the number of bytes of input that have been consumed by this `StrCursor`.
+    Returns the number of bytes of the original input consumed so far, including line terminators.
     */
-    fn offset(self) -> usize {
+    pub fn bytes_consumed(&self) -> u64 {
         self.offset
     }
-}
 
-impl<'a, Cmp, Space, Word>
-ScanCursor<'a> for StrCursor<'a, Cmp, Space, Word>
-where
-    Cmp: StrCompare,
-    Space: SkipSpace,
-    Word: SliceWord,
-{
-    type ScanInput = Self;
+    /**
+    Reads the next line of input, and scans it (without its line terminator) using `f`.
 
-    fn try_end(self) -> Result<(), (ScanError, Self)> {
-        if Space::skip_space(self.slice) == self.slice.len() {
-            Ok(())
-        } else {
-            Err((ScanError::expected_end().add_offset(self.offset()), self))
+    Returns `Ok(None)` once the end of the input has been reached.  If scanning fails, the error's position is translated into a byte offset from the start of the original input, and a snippet of the converted line is captured.  The line is counted as consumed either way.
+    */
+    pub fn next_record<F, T>(&mut self, f: F) -> Result<Option<T>, ScanError>
+    where F: FnOnce(&LossyText) -> Result<T, ScanError> {
+        self.buf.clear();
+        let n = try!(self.inner.read_until(b'\n', &mut self.buf).map_err(ScanError::io));
+        if n == 0 {
+            return Ok(None);
         }
-    }
 
-    fn try_scan<F, Out>(self, f: F) -> Result<(Out, Self), (ScanError, Self)>
-    where F: FnOnce(Self::ScanInput) -> Result<(Out, usize), ScanError> {
-        let tmp_off = Space::skip_space(self.slice);
-        let tmp = self.advance_by(tmp_off);
-        match f(tmp) {
-            Ok((out, off)) => Ok((out, tmp.advance_by(off))),
-            Err(err) => Err((err.add_offset(tmp.offset()), self)),
+        let start = self.offset;
+        self.offset += n as u64;
+
+        let mut end = self.buf.len();
+        if end > 0 && self.buf[end - 1] == b'\n' {
+            end -= 1;
+            if end > 0 && self.buf[end - 1] == b'\r' {
+                end -= 1;
+            }
         }
+        self.line.reset(&self.buf[..end]);
+
+        let line = &self.line;
+        f(line)
+            .map(Some)
+            .map_err(|err| line.remap_error(err).map_offset(|off| start as usize + off))
     }
 
-    fn try_scan_raw<F, Out>(self, f: F) -> Result<(Out, Self), (ScanError, Self)>
-    where F: FnOnce(Self::ScanInput) -> Result<(Out, usize), ScanError> {
-        match f(self) {
-            Ok((out, off)) => Ok((out, self.advance_by(off))),
-            Err(err) => Err((err.add_offset(self.offset()), self)),
-        }
+    /**
+    Returns the underlying reader.
+    */
+    pub fn into_inner(self) -> R {
+        self.inner
     }
+}
 
-    fn try_match_literal(self, lit: &str) -> Result<Self, (ScanError, Self)> {
-        let mut tmp_off = Space::skip_space(self.slice);
-        let mut tmp = &self.slice[tmp_off..];
-        let mut lit = lit;
+#[cfg(feature="std")]
+#[cfg(test)]
+#[test]
+fn test_lossy_lines() {
+    use ::scanner::NonSpace;
 
-        while lit.len() > 0 {
-            // Match leading spaces.
-            match Space::match_spaces(tmp, lit) {
-                Ok((a, b)) => {
-                    tmp = &tmp[a..];
-                    tmp_off += a;
-                    lit = &lit[b..];
-                },
-                Err(off) => {
-                    return Err((
-                        ScanError::literal_mismatch()
-                            .add_offset(self.offset() + tmp_off + off),
-                        self
-                    ));
-                },
-            }
+    let input: &[u8] = b"a = 1\r\nb\xff = 2\nc = \xfe\xfe\n";
+    let mut lines = LossyLines::new(input);
 
-            if lit.len() == 0 { break; }
+    let rule = |line: &LossyText| scan!(line; (let k: NonSpace<String>, "=", let v: i32) => (k, v));
+    assert_eq!(lines.next_record(&rule).ok(), Some(Some(("a".into(), 1))));
+    assert_eq!(lines.bytes_consumed(), 7);
+    assert_eq!(lines.next_record(&rule).ok(), Some(Some(("b\u{fffd}".into(), 2))));
+    assert_eq!(lines.bytes_consumed(), 14);
 
-            // Pull out the leading wordish things.
-            let lit_word = match Word::slice_word(lit) {
-                Some(0) | None => panic!("literal {:?} begins with a non-space, non-word", lit),
-                Some(b) => &lit[..b],
-            };
-            let tmp_word = match Word::slice_word(tmp) {
-                Some(b) => &tmp[..b],
-                None => return Err((
-                    ScanError::literal_mismatch()
-                        .add_offset(self.offset() + tmp_off),
-                    self
-                )),
-            };
+    let err = lines.next_record(&rule).unwrap_err();
+    assert_eq!(err.at.offset(), 18);
+    assert_eq!(err.at.snippet(), Some("\u{fffd}\u{fffd}"));
+    assert_eq!(lines.bytes_consumed(), 21);
 
-            if !Cmp::compare(tmp_word, lit_word) {
-                return Err((
-                    ScanError::literal_mismatch()
-                        .add_offset(self.offset() + tmp_off),
-                    self
-                ));
-            }
+    assert_eq!(lines.next_record(&rule).ok(), Some(None));
+}
 
-            tmp = &tmp[tmp_word.len()..];
-            tmp_off += tmp_word.len();
-            lit = &lit[lit_word.len()..];
-        }
+/**
+Text which has been converted to a Unicode normalization form, either NFC or NFD, in its entirety.
 
-        Ok(self.advance_by(tmp_off))
-    }
+Comparing with [`Normalized`](enum.Normalized.html) only normalizes the strings being compared, which does not help when slicing words or lines: a word in NFD text may end with combining marks that would not be there in NFC text, for example.  Normalizing the whole input up front means every scanner sees the same form.  Note that literals in scan patterns are *not* normalized; they should be written in the same form as the text, or be matched using a cursor which compares with `Normalized`.
 
-    fn as_str(self) -> &'a str {
-        self.slice
-    }
+As with [`LossyText`](struct.LossyText.html), the converted text keeps track of which parts of it were changed.  Use [`original_offset`](#method.original_offset) or [`remap_error`](#method.remap_error) to translate a position in the normalized text back into a position in the original.
 
-    fn offset(&self) -> usize {
-        self.offset
-    }
+`&NormalizedText` can be used directly as input to the scanning macros.
+
+**Note**: requires the `unicode-normalization` feature.
+
+```rust
+# #[macro_use] extern crate scan_rules;
+# use scan_rules::input::NormalizedText;
+# use scan_rules::scanner::Word;
+# fn main() {
+let text = NormalizedText::nfc("cafe\u{301} 3");
+assert_eq!(text.as_str(), "caf\u{e9} 3");
+assert_eq!(scan!(&text; (let w: Word, let n: u32) => (w, n)).unwrap(), ("caf\u{e9}", 3));
+# }
+```
+*/
+#[cfg(feature="unicode-normalization")]
+#[derive(Clone, Debug)]
+pub struct NormalizedText {
+    text: String,
+
+    /*
+    One entry for the start of each part of the text changed by normalization, and one for the start of the unchanged text after it, as `(normalized offset, original offset)`.  These are sorted, and entries for changed parts always have an even index.
+    */
+    fixups: Vec<(usize, usize)>,
 }
 
-impl<'a, Cmp, Space, Word>
-ScanInput<'a> for StrCursor<'a, Cmp, Space, Word>
-where
-    Cmp: StrCompare,
-    Space: SkipSpace,
-    Word: SliceWord,
-{
-    type ScanCursor = Self;
-    type StrCompare = Cmp;
+#[cfg(feature="unicode-normalization")]
+impl NormalizedText {
+    /**
+    Converts the given text to Normalization Form C (canonical composition).
+    */
+    pub fn nfc(s: &str) -> NormalizedText {
+        NormalizedText::new(s, true)
+    }
 
-    fn as_str(&self) -> &'a str {
-        self.slice
+    /**
+    Converts the given text to Normalization Form D (canonical decomposition).
+    */
+    pub fn nfd(s: &str) -> NormalizedText {
+        NormalizedText::new(s, false)
     }
 
-    fn from_subslice(&self, subslice: &'a str) -> Self {
-        use ::util::StrUtil;
-        let offset = self.as_str().subslice_offset_stable(subslice)
-            .expect("called `StrCursor::from_subslice` with disjoint subslice");
+    fn new(s: &str, compose: bool) -> NormalizedText {
+        use unicode_normalization::UnicodeNormalization;
 
-        StrCursor {
-            offset: self.offset + offset,
-            slice: subslice,
-            _marker: PhantomData,
-        }
-    }
+        let mut text = NormalizedText {
+            text: String::with_capacity(s.len()),
+            fixups: vec![],
+        };
 
-    fn to_cursor(&self) -> Self::ScanCursor {
         /*
-        Note that we strip the offset information here, essentially making this a *new* cursor, not just a copy of the existing one.
+        No ASCII character decomposes, is reordered, or composes with the character before it.  As such, the text can be normalized one piece at a time, with each piece running from one ASCII character up to the next.  This means the offsets of only the pieces which actually change need to be recorded, and runs of ASCII can be copied as-is.
         */
-        StrCursor::new(self.slice)
+        let bs = s.as_bytes();
+        let mut start = 0;
+        while start < bs.len() {
+            let ascii = bs[start..].iter().take_while(|&&b| b < 0x80).count();
+            let end = start + ascii;
+            if end == bs.len() {
+                text.text.push_str(&s[start..]);
+                break;
+            }
+
+            // The last ASCII character might combine with what follows.
+            let piece_start = if ascii > 0 { end - 1 } else { end };
+            text.text.push_str(&s[start..piece_start]);
+
+            let piece_end = end + bs[end..].iter().take_while(|&&b| b >= 0x80).count();
+            let piece = &s[piece_start..piece_end];
+            let norm_start = text.text.len();
+            if compose {
+                text.text.extend(piece.nfc());
+            } else {
+                text.text.extend(piece.nfd());
+            }
+            if &text.text[norm_start..] != piece {
+                text.fixups.push((norm_start, piece_start));
+                text.fixups.push((text.text.len(), piece_end));
+            }
+
+            start = piece_end;
+        }
+
+        text
     }
-}
 
-/**
-This implementation is provided to allow scanners to be used manually with a minimum of fuss.
+    /**
+    Returns the normalized text.
+    */
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
 
-It *only* supports direct, exact equality comparison.
-*/
-impl<'a> ScanInput<'a> for &'a str {
-    type ScanCursor = StrCursor<'a>;
-    type StrCompare = ExactCompare;
+    /**
+    Returns `true` if normalization changed the text.
+    */
+    pub fn is_changed(&self) -> bool {
+        self.fixups.len() > 0
+    }
 
-    fn as_str(&self) -> &'a str {
-        *self
+    /**
+    Translates an offset into the normalized text into an offset into the original text.
+
+    An offset within a part of the text changed by normalization is translated to the start of that part.
+    */
+    pub fn original_offset(&self, offset: usize) -> usize {
+        fixup_offset(&self.fixups, offset)
     }
 
-    fn from_subslice(&self, subslice: &'a str) -> Self {
-        subslice
+    /**
+    Translates the position of an error from scanning the normalized text into a position in the original text.
+
+    A snippet of the normalized text is captured at the error's position, as by `ScanError::with_input`.
+    */
+    pub fn remap_error(&self, err: ScanError) -> ScanError {
+        err.with_input(&self.text)
+            .map_offset(|off| self.original_offset(off))
     }
+}
 
-    fn to_cursor(&self) -> Self::ScanCursor {
-        self.into_scan_cursor()
+#[cfg(feature="unicode-normalization")]
+impl<'a> IntoScanCursor<'a> for &'a NormalizedText {
+    type Output = StrCursor<'a>;
+    fn into_scan_cursor(self) -> Self::Output {
+        StrCursor::new(self.as_str())
+    }
+}
+
+#[cfg(feature="unicode-normalization")]
+#[cfg(test)]
+#[test]
+fn test_normalized_text() {
+    use unicode_normalization::UnicodeNormalization;
+    use ::scanner::Word;
+
+    let text = NormalizedText::nfc("plain");
+    assert_eq!(text.as_str(), "plain");
+    assert!(!text.is_changed());
+    assert_eq!(text.original_offset(3), 3);
+
+    let cases = &[
+        "",
+        "e\u{301}",
+        "\u{301}abc",
+        "caf\u{e9} cafe\u{301}",
+        "a\u{323}\u{302}b\u{302}\u{323}",
+        "\u{1100}\u{1161}\u{11a8}x\u{ac00}\u{11a8}",
+        "\u{212b}ngstr\u{f6}m",
+        "x\u{344}\u{f900}\u{1d15e}y",
+    ];
+    for &s in cases {
+        assert_eq!(NormalizedText::nfc(s).as_str(), s.nfc().collect::<String>());
+        assert_eq!(NormalizedText::nfd(s).as_str(), s.nfd().collect::<String>());
     }
+
+    let text = NormalizedText::nfc("ab cde\u{301}f e\u{301}\u{301} x");
+    assert_eq!(text.as_str(), "ab cd\u{e9}f \u{e9}\u{301} x");
+    assert!(text.is_changed());
+    let offs: Vec<_> = (0..text.as_str().len() + 1).map(|i| text.original_offset(i)).collect();
+    assert_eq!(offs, [0, 1, 2, 3, 4, 5, 5, 8, 9, 10, 10, 10, 10, 15, 16, 17]);
+
+    let text = NormalizedText::nfd("caf\u{e9} na\u{ef}ve");
+    assert_eq!(text.as_str(), "cafe\u{301} nai\u{308}ve");
+    assert!(matches!(scan!(&text; (let a: Word, let b: Word) => (a, b)), Ok(("cafe\u{301}", "nai\u{308}ve"))));
+    let err = scan!(&text; (let _: Word, let _: u32) => ()).unwrap_err();
+    assert_eq!(err.at.offset(), 7);
+    let err = text.remap_error(err);
+    assert_eq!(err.at.offset(), 6);
+    assert_eq!(err.at.snippet(), Some("nai\u{308}ve"));
 }
 
 /**
 Skip all leading whitespace in a string, and return both the resulting slice and the number of bytes skipped.
 */
 fn skip_space(s: &str) -> (&str, usize) {
-    let off = s.char_indices()
+    let off = skip_space_len(s);
+    (&s[off..], off)
+}
+
+fn skip_space_len(s: &str) -> usize {
+    let fast = ::fast::ascii_space_len(s);
+    fast + s[fast..].char_indices()
         .take_while(|&(_, c)| c.is_whitespace())
         .map(|(i, c)| i + c.len_utf8())
         .last()
-        .unwrap_or(0);
-    (&s[off..], off)
+        .unwrap_or(0)
 }
 
 /**
@@ -508,7 +2326,8 @@ impl SkipSpace for IgnoreNonLine {
 }
 
 fn skip_space_non_line(s: &str) -> usize {
-    s.char_indices()
+    let fast = ::fast::ascii_space_non_line_len(s);
+    fast + s[fast..].char_indices()
         .take_while(|&(_, c)| c.is_whitespace()
             && c != '\r' && c != '\n')
         .last()
@@ -530,11 +2349,7 @@ impl SkipSpace for IgnoreSpace {
     }
 
     fn skip_space(s: &str) -> usize {
-        s.char_indices()
-            .take_while(|&(_, c)| c.is_whitespace())
-            .map(|(i, c)| i + c.len_utf8())
-            .last()
-            .unwrap_or(0)
+        skip_space_len(s)
     }
 }
 
@@ -733,10 +2548,14 @@ fn slice_non_space(s: &str) -> Option<usize> {
     use ::util::TableUtil;
     use ::unicode::property::White_Space_table as WS;
 
-    s.char_indices()
+    let fast = ::fast::ascii_non_space_len(s);
+    let len = fast + s[fast..].char_indices()
         .take_while(|&(_, c)| !WS.span_table_contains(&c))
         .map(|(i, c)| i + c.len_utf8())
         .last()
+        .unwrap_or(0);
+
+    if len == 0 { None } else { Some(len) }
 }
 
 fn slice_wordish(s: &str) -> Option<usize> {
@@ -35,6 +35,187 @@ pub fn subslice_offset(a: &str, b: &str) -> Option<usize> {
     a.subslice_offset_stable(b)
 }
 
+/**
+Determines whether an error should abort scanning outright, rather than allowing other alternatives to be tried.
+
+This is publicly exposed for the sake of macros and **is not** considered a stable part of the public API.
+*/
+pub fn is_fatal_error(err: &ScanError) -> bool {
+    err.is_fatal()
+}
+
+/**
+Combines the result of a rule with the best result so far, for `scan_longest!`.
+
+A successful result replaces the best so far only if it has consumed strictly *more* input, so the earliest of several equally long matches wins.  Fatal errors always win, as they would stop `scan!` from trying further rules.
+
+This is publicly exposed for the sake of macros and **is not** considered a stable part of the public API.
+*/
+pub fn longest_match<'a, T, C>(
+    best: Option<Result<(T, C), ScanError>>,
+    next: Result<(T, C), ScanError>,
+) -> Option<Result<(T, C), ScanError>>
+where C: ::input::ScanCursor<'a> {
+    match (best, next) {
+        (Some(Err(best)), _) if is_fatal_error(&best) => Some(Err(best)),
+        (_, Err(next)) if is_fatal_error(&next) => Some(Err(next)),
+        (None, next) => Some(next),
+        (Some(Ok(best)), Ok(next)) => if next.1.offset() > best.1.offset() {
+            Some(Ok(next))
+        } else {
+            Some(Ok(best))
+        },
+        (Some(Ok(best)), Err(_)) => Some(Ok(best)),
+        (Some(Err(_)), Ok(next)) => Some(Ok(next)),
+        (Some(Err(best)), Err(next)) => Some(Err(best.furthest_along(next))),
+    }
+}
+
+/**
+Estimate how many times a repetition with separator `sep` will match the start of `rest`, so that the collections can reserve space up front.
+
+This is just the number of separators plus one, capped at `max`.  So that the cost of each call is bounded (repetitions may be nested, or run over large inputs), only the current line is examined, and then only its first `HINT_WINDOW` bytes.  As such, this may underestimate; the collection will simply grow as usual.
+
+This is publicly exposed for the sake of macros and **is not** considered a stable part of the public API.
+*/
+pub fn repeat_hint(rest: &str, sep: &str, max: Option<usize>) -> usize {
+    const HINT_WINDOW: usize = 4 * 1024;
+
+    if sep.is_empty() {
+        return 0;
+    }
+
+    let mut end = match ::fast::find_line_break(rest.as_bytes()) {
+        Some(end) if !sep.contains(|c| c == '\r' || c == '\n') => end,
+        _ => rest.len(),
+    };
+    if end > HINT_WINDOW {
+        end = HINT_WINDOW;
+        while !rest.is_char_boundary(end) {
+            end -= 1;
+        }
+    }
+
+    let max = max.unwrap_or(!0);
+    let seps = rest[..end].matches(sep).take(max).count();
+    ::std::cmp::min(seps + 1, max)
+}
+
+/**
+Reserve space in a repetition's collection.
+
+Only collections with an inherent `reserve` method implement this.  Calling `(&mut col).reserve_hint(n)` with both this and [`NoReserveHint`](trait.NoReserveHint.html) in scope will use this implementation if there is one, and do nothing otherwise.
+
+This is publicly exposed for the sake of macros and **is not** considered a stable part of the public API.
+*/
+pub trait ReserveHint {
+    /**
+    Reserve space for at least `n` more elements.
+    */
+    fn reserve_hint(&mut self, n: usize);
+}
+
+impl<T> ReserveHint for Vec<T> {
+    fn reserve_hint(&mut self, n: usize) {
+        self.reserve(n)
+    }
+}
+
+impl<T> ReserveHint for ::std::collections::VecDeque<T> {
+    fn reserve_hint(&mut self, n: usize) {
+        self.reserve(n)
+    }
+}
+
+impl<T> ReserveHint for ::std::collections::BinaryHeap<T> where T: Ord {
+    fn reserve_hint(&mut self, n: usize) {
+        self.reserve(n)
+    }
+}
+
+impl<K, V, S> ReserveHint for ::std::collections::HashMap<K, V, S>
+where K: Eq + ::std::hash::Hash, S: ::std::hash::BuildHasher {
+    fn reserve_hint(&mut self, n: usize) {
+        self.reserve(n)
+    }
+}
+
+impl<T, S> ReserveHint for ::std::collections::HashSet<T, S>
+where T: Eq + ::std::hash::Hash, S: ::std::hash::BuildHasher {
+    fn reserve_hint(&mut self, n: usize) {
+        self.reserve(n)
+    }
+}
+
+/**
+Fallback for collections which don't implement [`ReserveHint`](trait.ReserveHint.html).
+
+This is publicly exposed for the sake of macros and **is not** considered a stable part of the public API.
+*/
+pub trait NoReserveHint {
+    /**
+    Does nothing.
+    */
+    fn reserve_hint(&mut self, _: usize) {}
+}
+
+impl<'a, T> NoReserveHint for &'a mut T {}
+
+#[cfg(feature="diagnostics")]
+thread_local! {
+    /**
+    The offset reached by the most recently matched pattern, for `trace_rule` to report.
+    */
+    static MATCHED_AT: ::std::cell::Cell<usize> = ::std::cell::Cell::new(0)
+}
+
+/**
+Record the offset a pattern reached on matching, passing the value of its body through.
+
+This is recorded *after* the body has been evaluated, so that any scanning done by the body itself doesn't clobber it.
+
+This is publicly exposed for the sake of macros and **is not** considered a stable part of the public API.
+*/
+#[cfg(feature="diagnostics")]
+pub fn trace_matched_at<T>(offset: usize, value: T) -> T {
+    MATCHED_AT.with(|cell| cell.set(offset));
+    value
+}
+
+#[cfg(not(feature="diagnostics"))]
+#[inline]
+pub fn trace_matched_at<T>(_: usize, value: T) -> T {
+    value
+}
+
+/**
+Log the outcome of trying a rule, if the `diagnostics` feature is enabled.
+
+This is publicly exposed for the sake of macros and **is not** considered a stable part of the public API.
+*/
+#[cfg(feature="diagnostics")]
+pub fn trace_rule<T>(rule: usize, result: &Result<T, ScanError>) {
+    match *result {
+        Ok(_) => trace!("rule {} matched up to offset {}", rule, MATCHED_AT.with(|cell| cell.get())),
+        Err(ref err) => trace!("rule {} failed at offset {}: {}", rule, err.at.offset(), err),
+    }
+}
+
+#[cfg(not(feature="diagnostics"))]
+#[inline]
+pub fn trace_rule<T>(_: usize, _: &Result<T, ScanError>) {}
+
+/**
+Scan the leading word of the input, for dispatching on keywords.
+
+This is publicly exposed for the sake of macros and **is not** considered a stable part of the public API.
+*/
+pub fn try_scan_keyword<'a, C>(cur: C) -> Result<(&'a str, C), ScanError>
+where C: ::input::ScanCursor<'a> {
+    try_scan_static::<C, ::scanner::Word<'a, &'a str>>(cur)
+        .map_err(|(err, _)| ScanError::literal_mismatch().add_offset(err.at.offset()))
+}
+
 /**
 Dispatch to a runtime scanner.
 
@@ -85,3 +266,350 @@ where
         cur.try_scan_raw(S::scan_self_from)
     }
 }
+
+#[cfg(feature="regex")]
+thread_local! {
+    /**
+    Anchored regular expressions compiled for `@re(..)` pattern terms, keyed on their source text.
+    */
+    static REGEX_CACHE: ::std::cell::RefCell<::std::collections::HashMap<String, ::regex::Regex>> = ::std::cell::RefCell::new(::std::collections::HashMap::new())
+}
+
+/**
+Match and discard input using a regular expression, which is anchored to the start of the input.
+
+Each expression is compiled once per thread, then reused.  An expression which fails to compile is reported as a syntax error.
+
+This is publicly exposed for the sake of macros and **is not** considered a stable part of the public API.
+*/
+#[cfg(feature="regex")]
+pub fn try_match_regex<'a, C>(cur: C, re: &str) -> Result<C, (ScanError, C)>
+where C: ::input::ScanCursor<'a> {
+    use ::input::ScanInput;
+    let re = REGEX_CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        if let Some(re) = cache.get(re) {
+            return Ok(re.clone());
+        }
+        match ::regex::Regex::new(&format!("^(?:{})", re)) {
+            Ok(compiled) => {
+                cache.insert(re.to_owned(), compiled.clone());
+                Ok(compiled)
+            },
+            Err(_) => Err(ScanError::syntax("invalid regular expression")),
+        }
+    });
+    let re = match re {
+        Ok(re) => re,
+        Err(err) => return Err((err.add_offset(cur.offset()), cur)),
+    };
+    cur.try_scan(|s| {
+        match re.find(s.as_str()) {
+            Some((_, end)) => Ok(((), end)),
+            None => Err(ScanError::syntax("no match for regular expression")),
+        }
+    }).map(|((), cur)| cur)
+}
+
+/**
+Match a literal byte-for-byte against the input, without skipping leading whitespace or involving the cursor's comparison rules.
+
+This is publicly exposed for the sake of macros and **is not** considered a stable part of the public API.
+*/
+pub fn try_match_raw_literal<'a, C>(cur: C, lit: &str) -> Result<C, (ScanError, C)>
+where C: ::input::ScanCursor<'a> {
+    use ::input::ScanInput;
+    cur.try_scan_raw(|s| {
+        if s.as_str().starts_with(lit) {
+            Ok(((), lit.len()))
+        } else {
+            Err(ScanError::literal_mismatch())
+        }
+    }).map(|((), cur)| cur)
+}
+
+/**
+Match the `Display` representation of an earlier binding against the input, as though it were a literal.
+
+This is publicly exposed for the sake of macros and **is not** considered a stable part of the public API.
+*/
+pub fn try_match_backref<'a, C, T: ?Sized>(cur: C, value: &T) -> Result<C, (ScanError, C)>
+where
+    C: ::input::ScanCursor<'a>,
+    T: ::std::fmt::Display,
+{
+    cur.try_match_literal(&value.to_string())
+}
+
+/**
+Check that the next term will start at the given offset, skipping leading whitespace as the cursor would for a scanned value.
+
+This is publicly exposed for the sake of macros and **is not** considered a stable part of the public API.
+*/
+pub fn try_at_offset<'a, C>(cur: C, offset: usize) -> Result<C, (ScanError, C)>
+where C: ::input::ScanCursor<'a> {
+    match cur.clone().try_scan(|_| Ok(((), 0))) {
+        Ok(((), new_cur)) => {
+            if new_cur.offset() == offset {
+                Ok(new_cur)
+            } else {
+                Err((ScanError::syntax("input is not at the expected offset").add_offset(new_cur.offset()), cur))
+            }
+        },
+        Err(err) => Err(err)
+    }
+}
+
+/**
+Check that the next term will start at the given column, skipping leading whitespace as the cursor would for a scanned value.
+
+If the cursor does not keep track of columns, this fails with a hard (cut) syntax error, since no other rule could do any better.
+
+This is publicly exposed for the sake of macros and **is not** considered a stable part of the public API.
+*/
+pub fn try_at_column<'a, C>(cur: C, column: usize) -> Result<C, (ScanError, C)>
+where C: ::input::ScanCursor<'a> {
+    match cur.clone().try_scan(|_| Ok(((), 0))) {
+        Ok(((), new_cur)) => {
+            match new_cur.line_column() {
+                Some((_, col)) if col == column => Ok(new_cur),
+                Some(_) => Err((ScanError::syntax("input is not at the expected column").add_offset(new_cur.offset()), cur)),
+                None => Err((ScanError::syntax("`@column` requires a cursor that tracks columns, such as `LineTrackingCursor`").add_offset(new_cur.offset()).cut(), cur)),
+            }
+        },
+        Err(err) => Err(err)
+    }
+}
+
+/**
+Capture the remaining input, optionally trimming whitespace from both ends, along with the offset at which the captured text starts.
+
+This is publicly exposed for the sake of macros and **is not** considered a stable part of the public API.
+*/
+pub fn scan_tail<'a, C>(cur: C, trim: bool) -> Result<((usize, &'a str), C), (ScanError, C)>
+where C: ::input::ScanCursor<'a> {
+    use ::input::ScanInput;
+    let at = cur.offset();
+    cur.try_scan_raw(|s| {
+        let s = s.as_str();
+        if trim {
+            let t = s.trim_left();
+            Ok(((at + s.len() - t.len(), t.trim_right()), s.len()))
+        } else {
+            Ok(((at, s), s.len()))
+        }
+    })
+}
+
+/**
+Scan `text`, which should be the `Debug` representation of `value`, back with `S`, and describe how the result differs from `value`, if it does.
+
+This is publicly exposed for the sake of macros and **is not** considered a stable part of the public API.
+*/
+pub fn check_roundtrip<'a, S, T>(value: &T, text: &'a str) -> Result<(), String>
+where
+    S: ::scanner::ScanFromStr<'a, Output=T>,
+    T: ::std::fmt::Debug + PartialEq,
+{
+    fn caret(s: &str, at: usize) -> String {
+        let pad: String = ::std::iter::repeat(' ').take(s[..at].chars().count()).collect();
+        format!("             {}^", pad)
+    }
+
+    match scan!(text; (let v: S) => v) {
+        Ok(ref v) if v == value => Ok(()),
+        Ok(v) => {
+            let back = format!("{:?}", v);
+            let at = text.char_indices().zip(back.chars())
+                .filter(|&((_, a), b)| a != b)
+                .map(|((i, _), _)| i)
+                .next()
+                .unwrap_or(::std::cmp::min(text.len(), back.len()));
+            Err(format!("scanned value differs from the original\n  formatted: {}\n    scanned: {}\n{}",
+                text, back, caret(text, at)))
+        },
+        Err(err) => {
+            let at = ::std::cmp::min(err.at.offset(), text.len());
+            Err(format!("could not scan the formatted value\n  formatted: {}\n{}\n      error: {}",
+                text, caret(text, at), err))
+        },
+    }
+}
+
+/**
+Like `check_roundtrip`, using `T` itself as the scanner.
+
+This is publicly exposed for the sake of macros and **is not** considered a stable part of the public API.
+*/
+pub fn check_roundtrip_self<'a, T>(value: &T, text: &'a str) -> Result<(), String>
+where T: ::scanner::ScanFromStr<'a, Output=T> + ::std::fmt::Debug + PartialEq {
+    check_roundtrip::<T, T>(value, text)
+}
+
+thread_local! {
+    /**
+    The limits currently placed on collection scanners by `collection_limits`, as `(max_elems, max_depth, depth)`.
+    */
+    static COLLECTION_LIMITS: ::std::cell::Cell<(Option<usize>, Option<usize>, usize)> = ::std::cell::Cell::new((None, None, 0))
+}
+
+/**
+Replace the collection limits for the current thread, returning the previous limits so they can be restored.
+
+This is publicly exposed for the sake of macros and **is not** considered a stable part of the public API.
+*/
+pub fn set_collection_limits(limits: (Option<usize>, Option<usize>, usize)) -> (Option<usize>, Option<usize>, usize) {
+    COLLECTION_LIMITS.with(|cell| {
+        let old = cell.get();
+        cell.set(limits);
+        old
+    })
+}
+
+/**
+Tracks how deeply collection scanners are nested, enforcing the limits set by `collection_limits`.
+
+This is publicly exposed for the sake of macros and **is not** considered a stable part of the public API.
+*/
+pub struct CollectionGuard {
+    max_elems: Option<usize>,
+    too_deep: bool,
+}
+
+impl CollectionGuard {
+    /**
+    Enter a collection scanner.
+
+    A collection one level deeper than allowed is still scanned, but may not contain any elements, and is rejected if it matches.  This way, failing to find a nested collection where one *could* have been is not an error, while actually finding one is.  Collections nested deeper than *that* are only tried once such a collection has already started matching, so they fail immediately.
+    */
+    pub fn enter() -> Result<CollectionGuard, ScanError> {
+        COLLECTION_LIMITS.with(|cell| {
+            let (max_elems, max_depth, depth) = cell.get();
+            match max_depth {
+                Some(max) if depth > max => Err(ScanError::limit_exceeded()),
+                _ => {
+                    cell.set((max_elems, max_depth, depth + 1));
+                    Ok(CollectionGuard {
+                        max_elems: max_elems,
+                        too_deep: max_depth.map(|max| depth == max).unwrap_or(false),
+                    })
+                }
+            }
+        })
+    }
+
+    /**
+    Apply the element limit, if any, to the cursor used to scan the collection.
+    */
+    pub fn limit<'a, C>(&self, cur: C) -> ::input::Limited<C>
+    where C: ::input::ScanCursor<'a> {
+        let cur = ::input::Limited::new(cur);
+        match (self.too_deep, self.max_elems) {
+            (true, _) => cur.max_repeats(0),
+            (false, Some(n)) => cur.max_repeats(n),
+            (false, None) => cur,
+        }
+    }
+
+    /**
+    Check the result of scanning the collection.
+    */
+    pub fn check<T>(&self, res: Result<T, ScanError>) -> Result<T, ScanError> {
+        match res {
+            Ok(_) if self.too_deep => Err(ScanError::limit_exceeded()),
+            res => res,
+        }
+    }
+}
+
+impl Drop for CollectionGuard {
+    fn drop(&mut self) {
+        COLLECTION_LIMITS.with(|cell| {
+            let (max_elems, max_depth, depth) = cell.get();
+            cell.set((max_elems, max_depth, depth - 1));
+        })
+    }
+}
+
+/**
+Repeatedly scan the buffered input of a `TokenReader`, reading more input whenever scanning stops at the end of the buffer.
+
+This is publicly exposed for the sake of macros and **is not** considered a stable part of the public API.
+*/
+#[cfg(feature="std")]
+pub fn scan_tokens<R, F, T>(reader: &mut ::input::TokenReader<R>, mut f: F) -> Result<T, ScanError>
+where
+    R: ::std::io::Read,
+    F: FnMut(&str) -> Result<(T, usize), ScanError>,
+{
+    loop {
+        let result = f(reader.buffer());
+        let wants_more = {
+            let buf = reader.buffer();
+            match result {
+                Ok((_, n)) => n == buf.len(),
+                Err(ref err) => err.at.offset() >= buf.trim_right().len(),
+            }
+        };
+        if wants_more && try!(reader.read_more().map_err(ScanError::io)) {
+            continue;
+        }
+        return result.map(|(v, n)| {
+            reader.consume(n);
+            v
+        });
+    }
+}
+
+#[cfg(feature="diagnostics")]
+#[cfg(test)]
+#[test]
+fn test_trace_matched_at() {
+    let matched_at = || MATCHED_AT.with(|cell| cell.get());
+
+    assert_eq!(scan!("a b  "; ("a", "b") => ()).ok(), Some(()));
+    assert_eq!(matched_at(), 3);
+    assert_eq!(scan!("a b c"; ("a", ^..cur) => ::input::ScanCursor::offset(&cur)).ok(), Some(1));
+    assert_eq!(matched_at(), 1);
+
+    // Scanning in the body doesn't affect the outer rule.
+    assert!(scan!("a b c"; ("a", "b", ...) => scan!("x"; ("x") => ())).is_ok());
+    assert_eq!(matched_at(), 3);
+}
+
+#[cfg(test)]
+#[test]
+fn test_repeat_hint() {
+    assert_eq!(repeat_hint("", ",", None), 1);
+    assert_eq!(repeat_hint("1, 2, 3", ",", None), 3);
+    assert_eq!(repeat_hint("1, 2, 3", ",", Some(2)), 2);
+    assert_eq!(repeat_hint("1, 2, 3\n4, 5", ",", None), 3);
+    assert_eq!(repeat_hint("1\n2\n3", "\n", None), 3);
+    assert_eq!(repeat_hint("1 2 3", "", None), 0);
+
+    let long: String = ::std::iter::repeat(',').take(10_000).collect();
+    assert_eq!(repeat_hint(&long, ",", None), 4 * 1024 + 1);
+    assert_eq!(repeat_hint(&long, ",", Some(10)), 10);
+    assert_eq!(repeat_hint(&format!("é{}", long), ",", None), 4 * 1024 - 1);
+
+    #[allow(unused_imports)]
+    use self::{ReserveHint, NoReserveHint};
+    let mut v: Vec<i32> = vec![];
+    (&mut v).reserve_hint(100);
+    assert!(v.capacity() >= 100);
+    let mut b: ::std::collections::BTreeSet<i32> = Default::default();
+    (&mut b).reserve_hint(100);
+}
+
+#[cfg(test)]
+#[test]
+fn test_check_roundtrip() {
+    assert_eq!(check_roundtrip_self(&42i32, "42"), Ok(()));
+    assert_eq!(check_roundtrip_self(&Some(1.5f64), "Some(1.5)"), Ok(()));
+    assert_eq!(
+        check_roundtrip_self(&1i32, "1 2"),
+        Err("could not scan the formatted value\n  formatted: 1 2\n              ^\n      error: scan error: expected end of input, at offset: 1".into()));
+    assert_eq!(
+        check_roundtrip::<::scanner::Hex<u32>, _>(&10, "10"),
+        Err("scanned value differs from the original\n  formatted: 10\n    scanned: 16\n              ^".into()));
+}
@@ -16,12 +16,19 @@ The macros of interest are:
 * [`readln!`](macro.readln!.html) - reads and scans a line from standard input.
 * [`try_readln!`](macro.try_readln!.html) - like `readln!`, except it returns a `Result` instead of panicking.
 * [`scan!`](macro.scan!.html) - scans the provided string.
+* [`scan_partial!`](macro.scan_partial!.html) - like `scan!`, except it need not consume all input, and returns a cursor to the remainder.
+* [`scan_longest!`](macro.scan_longest!.html) - like `scan_partial!`, except the rule which matches the most input wins, rather than the first.
+* [`scan_match!`](macro.scan_match!.html) - like `scan!`, except the last rule must be a catch-all, so it never fails.
+* [`scan_tokens!`](macro.scan_tokens!.html) - scans whitespace-separated tokens from a reader, reading more lines as needed.
+* [`scan_keyword!`](macro.scan_keyword!.html) - like `scan!`, except each rule starts with a keyword, and rules are selected by matching on the first word of the input.
 
 Plus two convenience macros:
 
-* [`let_scan!`](macro.let_scan!.html) - scans a string and binds captured values directly to local variables.  Only supports *one* pattern and panics if it doesn't match.
+* [`let_scan!`](macro.let_scan!.html) - scans a string and binds captured values directly to local variables.  Panics if none of its patterns match.
 * [`let_readln!`](macro.let_readln!.html) - reads and scans a line from standard input, binding captured values directly to local variables.  Only supports *one* pattern and panics if it doesn't match.
 
+Patterns which are needed in several places can be named and reused with [`define_scan_rule!`](macro.define_scan_rule!.html), long scanner types can be given short names with [`scanner_alias!`](macro.scanner_alias!.html), and structs can be scanned from their `Debug` output with [`scanner_for_debug_struct!`](macro.scanner_for_debug_struct!.html).  [`assert_roundtrip!`](macro.assert_roundtrip!.html) checks that a value can be scanned back from its `Debug` output.
+
 If you are interested in implementing support for your own types, see the [`ScanFromStr`](scanner/trait.ScanFromStr.html) and [`ScanStr`](scanner/trait.ScanStr.html) traits.
 
 The provided scanners can be found in the [`scanner`](scanner/index.html) module.
@@ -55,6 +62,8 @@ The provided scanners can be found in the [`scanner`](scanner/index.html) module
 
 * Due to a breaking change, `scan-rules` is not compatible with `regex` version 0.1.66 or higher.
 
+* `rustc` < 1.17 will not have scanning support for `std::collections::Bound`.
+
 * `rustc` < 1.10 will not have the `let_readln!` macro.
 
 * `rustc` < 1.7 will have only concrete implementations of `ScanFromStr` for the `Everything`, `Ident`, `Line`, `NonSpace`, `Number`, `Word`, and `Wordish` scanners for `&str` and `String` output types.  1.7 and higher will have generic implementations for all output types such that `&str: Into<Output>`.
@@ -67,13 +76,31 @@ The following [optional features](http://doc.crates.io/manifest.html#the-feature
 
 * `arrays-32`: implement scanning for arrays of up to 32 elements.  The default is up to 8 elements.
 
+* `diagnostics`: log every attempt to match a rule at the `trace` level, including the index of the rule, and either the offset it matched up to or where and why it failed.  Adds a dependency on the `log` crate.
+
 * `duration-iso8601-dates`: support scanning ISO 8601 durations with date components.
 
-* `regex`: include support for the `re`, `re_a`, and `re_str` regular expression-based runtime scanners.  Adds a dependency on the `regex` crate.
+* `english-numbers`: include support for the `EnglishNumber` scanner, which scans numbers written as English words.
+
+* `fast-scan`: use faster, ASCII-specialised loops for skipping whitespace and finding word and line boundaries.  This has no effect on behaviour, but considerably speeds up scanning large, mostly-ASCII inputs.
+
+* `http`: include the [`scanner::http`](scanner/http/index.html) module, which contains scanners for HTTP/1.x request lines, status lines, and header fields.
+
+* `logformats`: include the [`scanner::logformats`](scanner/logformats/index.html) module, which contains scanners for syslog message prefixes and Common Log Format lines.
+
+* `mmap`: include support for `MappedInput`, which allows files to be mapped into memory and scanned in place.  Adds a dependency on the `memmap` crate.
+
+* `python-literals`: include support for the `PyBool`, `PyOption`, and `PyString` scanners, which scan the `True`, `False`, and `None` literals and quoted strings as written by Python's `repr`.
+
+* `regex`: include support for the `re`, `re_a`, and `re_str` regular expression-based runtime scanners, and the `@re(..)` pattern term.  Adds a dependency on the `regex` crate.
+
+* `rustdev`: include the [`scanner::rustdev`](scanner/rustdev/index.html) module, which contains scanners for `rustc` version lines and test harness summary lines.
+
+* `std` (enabled by default): include the parts of the crate which read input or scan types from `std::io`, `std::collections::HashMap`/`HashSet`, and `std::net`.  This covers the `readln!`, `try_readln!`, `let_readln!`, and `scan_tokens!` macros; the `TokenReader`, `ScanSession`, and `LossyLines` input types; the `MapOf` and `SetOf` scanners; and the scanners for `HashMap`, `HashSet`, `io::ErrorKind`, and the `std::net` address types.  The crate itself still depends on `std`; disabling this feature only leaves these parts out.
 
 * `tuples-16`: implement scanning for tuples of up to 16 elements.  The default is up to 4 elements.
 
-* `unicode-normalization`: include support for `Normalized` and `IgnoreCaseNormalized` cursor types.  Adds a dependency on the `unicode-normalization` crate.
+* `unicode-normalization`: include support for `Normalized` and `IgnoreCaseNormalized` cursor types, and `NormalizedText` input.  Adds a dependency on the `unicode-normalization` crate.
 
 The following are only supported on nightly compilers, and may disappear/change at any time:
 
@@ -81,7 +108,7 @@ The following are only supported on nightly compilers, and may disappear/change
 
 ## Important Notes
 
-* There are no default scanners for `&str` or `String`; if you want a string, you should pick an appropriate abstract scanner from the [`scanner`](scanner/index.html) module.
+* There are no default scanners for `&str` or `String`; if you want a string, you should pick an appropriate abstract scanner from the [`scanner`](scanner/index.html) module.  The string scanners (such as `Word`, `Line`, and `Everything`) can output `&str`, `String`, or `Cow<str>`; *e.g.* `let w: Word<Cow<str>>`.
 
 * The macros in this crate are extremely complex.  Moderately complex usage can exhaust the standard macro recursion limit.  If this happens, you can raise the limit (from its default of 64) by adding the following attribute to your crate's root module:
 
@@ -198,8 +225,8 @@ fn main() {
     // Adapted example from <http://en.cppreference.com/w/cpp/io/c/fscanf>.
     let inp = "25 54.32E-1 Thompson 56789 0123 56ß水";
 
-    // `let_scan!` avoids the need for indentation and braces, but only supports
-    // a single pattern, and panics if anything goes wrong.
+    // `let_scan!` avoids the need for indentation and braces, but panics if
+    // anything goes wrong.
     let_scan!(inp; (
         let i: i32, let x: f32, let str1 <| max_width_a::<NonSpace>(9),
     //               use runtime scanner ^~~~~~~~~~~~~~~~~~~~~~~~~~~~^
@@ -241,10 +268,24 @@ scan! { input_expression;
 
 Note that the trailing comma on the last rule is optional.
 
-Rules are checked top-to-bottom, stopping at the first that matches.
+Rules are checked top-to-bottom, stopping at the first that matches.  If a rule fails with a *hard* failure, later rules are not tried, and scanning stops with that error; see the [`cut`](scanner/runtime/fn.cut.html) and [`soft`](scanner/runtime/fn.soft.html) scanners.
 
 Patterns (explained under ["Pattern Syntax"](#pattern-syntax)) must be enclosed in parentheses.  If a pattern matches the provided input, the corresponding body is evaluated.
 
+Instead of an expression, a body may be written as `@into` *type*.  In this case, the values bound by the pattern are collected into a tuple, in order, and converted to *type* using the [`FromScan`](trait.FromScan.html) trait.  This is useful for rules which do nothing but construct a value from their bindings.  *type* must be a plain, relative path such as `Point` or `geom::Point<f32>`; use a type alias for anything else.
+
+Where several rules share a common prefix, the remainder of each can be grouped into a *rule group*: a brace-enclosed list of rules, written as the final term of a pattern.  The prefix is matched once, and the rules in the group are then tried from that point, exactly as above.  A rule ending in a rule group has no body of its own; its value is that of whichever nested rule matched.  Groups may be nested.
+
+```ignore
+scan! { input_expression;
+    ( "set", {
+        ( "volume", let v: u8 ) => body,
+        ( "name", let n: Word ) => body,
+    }),
+    ( pattern ) => body,
+}
+```
+
 ### Pattern Syntax
 
 A scanning pattern is made up of one or more pattern terms, separated by commas.  The following terms are supported:
@@ -253,6 +294,18 @@ A scanning pattern is made up of one or more pattern terms, separated by commas.
 
   *E.g.* `"Two words"`, `"..."` (counts as three "words"), `&format!("{} {}", "Two", "words")`.
 
+* `raw` *string* - matches the string against the input exactly, byte-for-byte.  Unlike a normal literal, leading whitespace is *not* skipped, whitespace within the string is significant, and the input's comparison rules (such as case-insensitivity) are not used.
+
+  *E.g.* `raw "  "` (exactly two spaces), `raw "\t"`, `raw ":="`.
+
+* `=` *name* - matches the input against an earlier binding from the same pattern, as though its `Display` representation had been given as a literal.  This allows a later part of the input to be checked against an earlier part.  The binding must be in scope at that point; bindings made inside a repetition are only visible *within* that repetition.
+
+  *E.g.* `"<", let tag: Word, ">", let body: Word, "</", =tag, ">"` (closing tag must match the opening one).
+
+* `@re(` *string* `)` - matches input against a regular expression, and discards it.  The expression is anchored to the start of the remaining input, after leading whitespace has been skipped.  Each expression is compiled the first time it is used on a thread; one that fails to compile causes a syntax error.  **Note**: requires the `regex` feature.
+
+  *E.g.* `@re(r"\d{4}-\d{2}")`, `@re("[a-z]+:")`.
+
 * `let` *name* \[ `:` *type* ] - scans a value out of the input text, and binds it to *name*.  If *type* is omitted, it will be inferred.
 
   *E.g.* `let x`, `let n: i32`, `let words: Vec<_>`, `let _: &str` (scans and discards a value).
@@ -261,9 +314,41 @@ A scanning pattern is made up of one or more pattern terms, separated by commas.
 
   *E.g.* `let n <| scan_a::<i32>()` (same as above example for `n`), `let three_digits <| max_width_a::<u32>()` (scan a three-digit `u32`).
 
+* `let` *name* `= #consumed` - binds the number of bytes of input consumed by the rule so far, as a `usize`, to *name*.  This does not consume any input.  In a repetition, this still counts from the start of the rule; in a [rule group](#rule-syntax), it counts from the start of the group.
+
+  *E.g.* `let _: Line, let n = #consumed` (scan a line and record its length, including the line break).
+
+* `@` *rule* - scans a value using a rule defined with [`define_scan_rule!`](macro.define_scan_rule!.html), and discards it.  This is equivalent to `let _:` *rule*.
+
+  *E.g.* `@Date`, `@Date, "to", @Date`.
+
+* `@offset(` *n* `)` - succeeds only if the next term will start at byte offset *n* from the start of the input, after skipping leading whitespace as a scanned value would.  This consumes nothing but that whitespace.
+
+  `@column(` *n* `)` works the same way, except that it checks the column number, counted from 1.  This requires a cursor that keeps track of columns, such as [`LineTrackingCursor`](input/struct.LineTrackingCursor.html); with any other cursor, scanning stops with a syntax error, and no further rules are tried.
+
+  *E.g.* `let code: Word, @column(10), let name: Line` (the name must start in column 10).
+
+* `do |` *cursor* `|` *expression* - runs arbitrary code against the input.  *cursor* is bound to a [`ScanCursor`](input/trait.ScanCursor.html) positioned at the current point in the input, and *expression* must evaluate to a `Result` containing either a cursor positioned after whatever input it consumed, or a `ScanError`.  Leading whitespace is *not* skipped.
+
+  `let` *name* `= do |` *cursor* `|` *expression* works the same way, except that *expression* must evaluate to a `Result` containing a tuple of a value and a cursor; the value is bound to *name*.
+
+  This is an escape hatch for integrating existing, hand-written parsers into a pattern without having to write a scanner type.
+
+  *E.g.* `do |cur| cur.try_match_literal("x").map_err(|(e, _)| e)`, `let n = do |cur| cur.try_scan_raw(|s| my_parser(s.as_str())).map_err(|(e, _)| e)`.
+
 * `..` *name* - binds the remaining, unscanned input as a string to *name*.  This can *only* appear as the final term in a top-level pattern.
 
-* `[` *pattern* `]` \[ *(nothing)* | `,` | `(` *seperator pattern* `)` ] ( `?` | `*` | `+` | `{` *range* `}` ) \[ ":" *collection type* ] - scans *pattern* repeatedly.
+  This may be followed by `trimmed` to remove leading and trailing whitespace from the bound string, and/or by `at` *pos* to also bind the offset at which the bound string starts within the input, as a `usize`, to *pos*.
+
+  *E.g.* `..rest`, `..rest trimmed`, `..rest trimmed at pos`, `..rest at pos`.
+
+* `...` - matches any remaining input, without binding it.  This permits a pattern to match a *prefix* of the input, and is equivalent to `.._`.  This can *only* appear as the final term in a top-level pattern.
+
+* `{` *rules* `}` - a rule group; see ["Rule Syntax"](#rule-syntax).  This can *only* appear as the final term in a top-level pattern.
+
+* `^..` *name* - binds a cursor positioned at the remaining, unscanned input to *name*, without requiring the input to be exhausted.  This can *only* appear as the final term in a top-level pattern.  The cursor may be used as the input to another scan, allowing input to be scanned incrementally; see also [`scan_partial!`](macro.scan_partial!.html).
+
+* `[` *pattern* `]` \[ *(nothing)* | `,` | `(` *seperator pattern* `)` ] ( `?` | `*` | `+` | `{` *range* `}` ) \[ `t` ] \[ `binding` *name* `=` *tuple* ] \[ `as` *count* ] \[ ":" *collection type* ] - scans *pattern* repeatedly.
 
   The first (mandatory) part of the term specifies the *pattern* that should be repeatedly scanned.
 
@@ -279,27 +364,41 @@ A scanning pattern is made up of one or more pattern terms, separated by commas.
   * `{,b}` - match at most *b* times.
   * `{a, b}` - match at least *a* times, and at most *b* times.
 
-  The fourth (optional) part of the term specifies what type of collection scanned values should be added to.  Note that the type specified here applies to *all* values captured by this repetition.  As such, you typically want to use a partially inferred type such as `BTreeSet<_>`.  If omitted, it defaults to `Vec<_>`.
+  If *pattern* matches fewer times than the minimum, scanning fails with the error from the repeat that failed.  If that was because the input ran out, the error is instead `Missing`, at the position where the next repeat was expected.
+
+  If the repeats are separated, the count may be followed by `t` to make the separator act as a *terminator*: a separator following the final repeat is consumed and accepted, rather than causing the repetition to fail.  For example, `[ let ws: Word ](";")*t` matches both `a;b;c` and `a;b;c;`.  A repetition without a separator also accepts `t`, but it has no effect.
+
+  The last (optional) part of the term specifies what type of collection scanned values should be added to.  Note that the type specified here applies to *all* values captured by this repetition.  As such, you typically want to use a partially inferred type such as `BTreeSet<_>`.  If omitted, it defaults to `Vec<_>`.
+
+  Normally, each binding in *pattern* is collected separately.  If a `binding` clause is given, the values bound by each repeat are instead combined into *tuple* (which must be a single identifier or parenthesised expression), and collected into a single collection bound to *name*.  This is mostly useful for collecting into maps.  The bindings of *pattern* are *not* available outside the repetition in this case, though those of the separator pattern still are.
+
+  If an `as` clause is given, the number of times *pattern* matched is bound to *count* as a `usize`.  This works even when *pattern* has no bindings of its own, or when a `binding` clause is used.
 
-  *E.g.* `[ let nums: i32 ],+`, `[ "pretty" ]*, "please"`.
+  *E.g.* `[ let nums: i32 ],+`, `[ "pretty" ]*, "please"`, `[ let k: Word<String>, "=", let v: i32 ],* binding kvs = (k, v): HashMap<_, _>`, `[ let stmts: Word ](";")+t`, `[ "very" ]* as n, "good"`.
 
 */
 #![cfg_attr(feature="nightly-pattern", feature(pattern))]
 #![forbid(missing_docs)]
 #![recursion_limit="128"]
 #[macro_use] extern crate lazy_static;
+#[cfg(feature="diagnostics")] #[macro_use] extern crate log;
 extern crate itertools;
 extern crate strcursor;
+#[cfg(feature="mmap")] extern crate memmap;
 #[cfg(feature="regex")] extern crate regex;
 #[cfg(feature="unicode-normalization")] extern crate unicode_normalization;
 
 #[macro_use] mod macros;
 
+pub use convert::FromScan;
 pub use error::{ScanError, ScanErrorAt, ScanErrorKind};
 
+mod convert;
 mod error;
+mod fast;
 pub mod input;
 pub mod internal;
 pub mod scanner;
+pub mod table;
 mod unicode;
-mod util;
+pub mod util;
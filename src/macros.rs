@@ -26,6 +26,7 @@ See also: [Pattern Syntax](index.html#pattern-syntax), [`try_readln!`](macro.try
 
 Panics if an error is encountered while reading from standard input, or if all rules fail to match.
 */
+#[cfg(feature="std")]
 #[macro_export]
 macro_rules! readln {
     ($($rules:tt)*) => {
@@ -38,7 +39,7 @@ macro_rules! readln {
                     Ok(_) => {
                         let line = $crate::internal::strip_line_term(&line);
                         match scan!(line; $($rules)*) {
-                            Err(err) => panic!("{:?}", err),
+                            Err(err) => panic!("{:?}", $crate::ScanError::with_input(err, line)),
                             Ok(v) => v,
                         }
                     },
@@ -53,6 +54,7 @@ Reads a line of text from standard input, then scans it using the provided rules
 
 See also: [Pattern Syntax](index.html#pattern-syntax), [`readln!`](macro.readln!.html).
 */
+#[cfg(feature="std")]
 #[macro_export]
 macro_rules! try_readln {
     ($($rules:tt)*) => {
@@ -64,7 +66,10 @@ macro_rules! try_readln {
                     Err(err) => Err($crate::ScanError::io(err)),
                     Ok(_) => {
                         let line = $crate::internal::strip_line_term(&line);
-                        scan!(line; $($rules)*)
+                        ::std::result::Result::map_err(
+                            scan!(line; $($rules)*),
+                            |err| $crate::ScanError::with_input(err, line)
+                        )
                     },
                 }
             },
@@ -72,10 +77,54 @@ macro_rules! try_readln {
     };
 }
 
+/**
+Scans tokens from a [`TokenReader`](input/struct.TokenReader.html), using the provided rules.  The result is a `Result<T, ScanError>`, just as with `scan!`.
+
+Input is read from the underlying reader a line at a time, as needed: whenever all of the rules fail at (or a rule matches up to) the end of the buffered input, another line is read and the rules are tried again.  As a result, a pattern may span any number of lines, and line breaks are treated like any other whitespace.  Rules are *not* required to consume all of the input; anything left over remains buffered for the next `scan_tokens!`.
+
+Note that repetitions will not read more input to find further matches; they stop at the end of whatever input has been buffered.
+
+This macro *cannot* be used to capture slices of the input; all captured values must be owned.
+
+See also: [Pattern Syntax](index.html#pattern-syntax), [`scan_partial!`](macro.scan_partial!.html).
+
+## Examples
+
+```rust
+# #[macro_use] extern crate scan_rules;
+# use scan_rules::input::TokenReader;
+# fn main() {
+let input = "3\n1 2\n3 4 5\n6";
+let mut tokens = TokenReader::new(input.as_bytes());
+
+let n = scan_tokens!(tokens; (let n: usize) => n).unwrap();
+let mut pairs = vec![];
+for _ in 0..n {
+    pairs.push(scan_tokens!(tokens; (let a: i32, let b: i32) => (a, b)).unwrap());
+}
+assert_eq!(pairs, [(1, 2), (3, 4), (5, 6)]);
+# }
+```
+*/
+#[cfg(feature="std")]
+#[macro_export]
+macro_rules! scan_tokens {
+    ($reader:expr; $($rules:tt)*) => {
+        $crate::internal::scan_tokens(&mut $reader, |buf: &str| {
+            ::std::result::Result::map(
+                scan_partial!(buf; $($rules)*),
+                |(v, rest)| (v, $crate::input::ScanCursor::offset(&rest))
+            )
+        })
+    };
+}
+
 /**
 Scans the provided input, using the specified pattern.  All values are bound directly to local variables.
 
-Note that this macro only supports a *single* pattern.
+Several patterns may be given, separated by commas, for input which can come in more than one equivalent format.  They are tried in order, just like the rules of `scan!`.  The bindings are taken from the *first* pattern, and every other pattern must bind exactly the same names, with the same types.
+
+The input is borrowed, not consumed.  As a result, captured values may borrow from the input (*e.g.* `let w: Word` will bind a `&str`), provided the input outlives the bindings.  This works for string slices, `String`s, and anything else which can be sliced into a `&str`.
 
 See also: [Pattern Syntax](index.html#pattern-syntax), [`scan!`](macro.scan!.html).
 
@@ -88,18 +137,25 @@ See also: [Pattern Syntax](index.html#pattern-syntax), [`scan!`](macro.scan!.htm
 let input = "10¥, うまい棒";
 let_scan!(input; (let cost: u32, "¥,", let product: Word));
 println!("One {} costs {}¥.", product, cost);
+
+let input = "cost=25 product=ガリガリ君";
+let_scan!(input;
+    (let cost: u32, "¥,", let product: Word),
+    ("cost=", let cost: u32, "product=", let product: Word),
+);
+println!("One {} costs {}¥.", product, cost);
 # }
 ```
 
 ## Panics
 
-Panics if the pattern fails to match.
+Panics if none of the patterns match.
 */
 #[macro_export]
 macro_rules! let_scan {
-    ($input:expr; ($($pattern:tt)*)) => {
+    ($input:expr; ($($pattern:tt)*) $(, ($($alt_patterns:tt)*))* $(,)*) => {
         scan_rules_impl!(@with_bindings ($($pattern)*),
-            then: scan_rules_impl!(@let_bindings.panic $input, ($($pattern)*),);)
+            then: scan_rules_impl!(@let_bindings.panic $input, (($($pattern)*) $(($($alt_patterns)*))*),);)
     };
 }
 
@@ -125,7 +181,7 @@ println!("One {} costs {}¥.", product, cost);
 
 Panics if an error is encountered while reading from standard input, or if the pattern fails to match.
 */
-#[cfg(macro_inter_stmt_binding_visibility)]
+#[cfg(all(feature="std", macro_inter_stmt_binding_visibility))]
 #[macro_export]
 macro_rules! let_readln {
     ($($pattern:tt)*) => {
@@ -141,7 +197,7 @@ macro_rules! let_readln {
         };
         let line = line.unwrap();
         scan_rules_impl!(@with_bindings ($($pattern)*),
-            then: scan_rules_impl!(@let_bindings.panic line, ($($pattern)*),);)
+            then: scan_rules_impl!(@let_bindings.panic line, (($($pattern)*)),);)
     };
 }
 
@@ -150,10 +206,16 @@ Scans the provided input, using the specified rules.  The result is a `Result<T,
 
 The input may be any value which implements `IntoScanCursor`, which includes `&str`, `String`, and `Cow<str>`.
 
+If the input is preceded by `@raw`, leading whitespace is *not* stripped before scanning any value, regardless of what the scanner itself wants; see [`Raw`](input/struct.Raw.html).
+
 See also: [Pattern Syntax](index.html#pattern-syntax).
 */
 #[macro_export]
 macro_rules! scan {
+    (@raw $input:expr; $($rules:tt)+) => {
+        scan!($crate::input::Raw::new($input); $($rules)+)
+    };
+
     ($input:expr;
         $(($($patterns:tt)*) => $bodies:expr),+
     ) => {
@@ -166,15 +228,25 @@ macro_rules! scan {
     ) => {
         {
             let cur = $crate::input::IntoScanCursor::into_scan_cursor($input);
+            let _start = $crate::input::ScanCursor::offset(&cur);
 
-            let result = scan_rules_impl!(@scan (cur.clone()); ($($head_pattern)*,) => $head_body);
+            let result = scan_rules_impl!(@scan (cur.clone(), _start); ($($head_pattern)*,) => $head_body);
+            let _rule = 0usize;
+            $crate::internal::trace_rule(_rule, &result);
 
             $(
+                let _rule = _rule + 1;
                 let result = match result {
                     Ok(v) => Ok(v),
-                    Err(last_err) => match scan_rules_impl!(@scan (cur.clone()); ($($tail_patterns)*,) => $tail_bodies) {
-                        Ok(v) => Ok(v),
-                        Err(new_err) => Err(last_err.furthest_along(new_err))
+                    Err(last_err) => if $crate::internal::is_fatal_error(&last_err) {
+                        Err(last_err)
+                    } else {
+                        let new_result = scan_rules_impl!(@scan (cur.clone(), _start); ($($tail_patterns)*,) => $tail_bodies);
+                        $crate::internal::trace_rule(_rule, &new_result);
+                        match new_result {
+                            Ok(v) => Ok(v),
+                            Err(new_err) => Err(last_err.furthest_along(new_err))
+                        }
                     }
                 };
             )*
@@ -182,6 +254,511 @@ macro_rules! scan {
             result
         }
     };
+
+    ($input:expr; $($rules:tt)+) => {
+        scan_rules_impl!(@normalise_rules ($input), (); $($rules)+)
+    };
+
+    ($($_tail:tt)*) => {
+        scan_rules_impl!(@error "expected `input; (pattern) => body, ...`")
+    };
+}
+
+/**
+Scans a prefix of the provided input, using the specified rules.  Unlike `scan!`, the rules are not required to consume all of the input.  The result is a `Result<(T, C), ScanError>` where `T` is the type of the rule bodies, and `C` is a cursor positioned immediately after the matched input.
+
+The cursor can be passed back into `scan!` or `scan_partial!` to continue scanning from where the previous scan left off.  This is equivalent to ending each pattern with `^..rest` and returning `(body, rest)`.
+
+See also: [Pattern Syntax](index.html#pattern-syntax), [`scan!`](macro.scan!.html).
+
+## Examples
+
+```rust
+# #[macro_use] extern crate scan_rules;
+# use scan_rules::input::ScanCursor;
+# fn main() {
+let input = "3: 1 2 3";
+let (n, mut cur) = scan_partial!(input; (let n: usize, ":") => n).unwrap();
+
+let mut total = 0;
+for _ in 0..n {
+    let (v, next) = scan_partial!(cur; (let v: i32) => v).unwrap();
+    total += v;
+    cur = next;
+}
+
+assert_eq!(total, 6);
+assert_eq!(cur.remaining(), "");
+# }
+```
+*/
+#[macro_export]
+macro_rules! scan_partial {
+    ($input:expr;
+        $(($($patterns:tt)*) => $bodies:expr),+ $(,)*
+    ) => {
+        scan!($input; $(($($patterns)*, ^..rest) => ($bodies, rest),)+)
+    };
+}
+
+/**
+Scans a prefix of the provided input, using *whichever* of the specified rules matches the most input.  Like `scan_partial!`, the result is a `Result<(T, C), ScanError>`, where `C` is a cursor positioned immediately after the matched input.
+
+Where `scan!` and `scan_partial!` stop at the first rule which matches, `scan_longest!` tries *every* rule, and picks the one which consumed the most input; this is the "longest match" rule used by most lexers.  If several rules consume the same amount of input, the earliest of them wins.  If no rule matches, the error from the rule which got furthest is returned.
+
+Note that because every rule is tried, the body of every rule which matches is evaluated, not just the body of the winning rule.
+
+See also: [Pattern Syntax](index.html#pattern-syntax), [`scan_partial!`](macro.scan_partial!.html).
+
+## Examples
+
+```rust
+# #[macro_use] extern crate scan_rules;
+# fn main() {
+use scan_rules::scanner::Word;
+
+// With `scan_partial!`, the keyword would win, leaving `ifier` behind.
+let (token, _) = scan_longest!("identifier";
+    ("if") => "keyword",
+    (let _: Word) => "identifier",
+).unwrap();
+assert_eq!(token, "identifier");
+# }
+```
+*/
+#[macro_export]
+macro_rules! scan_longest {
+    ($input:expr;
+        $(($($patterns:tt)*) => $bodies:expr),+ $(,)*
+    ) => {
+        {
+            let cur = $crate::input::IntoScanCursor::into_scan_cursor($input);
+            let best = ::std::option::Option::None;
+            $(
+                let best = $crate::internal::longest_match(best,
+                    scan!(cur.clone(); ($($patterns)*, ^..rest) => ($bodies, rest)));
+            )+
+            ::std::option::Option::expect(best, "scan_longest! with no rules")
+        }
+    };
+}
+
+/**
+Scans the provided input, using the specified rules.  Unlike `scan!`, the final rule *must* be a catch-all of the form `(..name) => body` (or `(.._) => body`); omitting it is a compile error.
+
+Because the final rule cannot fail, the result of the `scan_match!` invocation is simply the type of the rule bodies, rather than a `Result`.  This allows you to statically guarantee that *all* input is handled, without having to deal with (or panic on) scanning errors.
+
+If none of the other rules match, the catch-all rule's name is bound to the entire input.
+
+See also: [Pattern Syntax](index.html#pattern-syntax), [`scan!`](macro.scan!.html).
+
+## Examples
+
+```rust
+# #[macro_use] extern crate scan_rules;
+# fn main() {
+let describe = |input: &str| scan_match! { input;
+    ("add", let a: i32, let b: i32) => format!("{}", a + b),
+    ("neg", let a: i32) => format!("{}", -a),
+    (..other) => format!("unknown command: {:?}", other),
+};
+assert_eq!(describe("add 1 2"), "3");
+assert_eq!(describe("neg 3"), "-3");
+assert_eq!(describe("mul 2 3"), "unknown command: \"mul 2 3\"");
+# }
+```
+*/
+#[macro_export]
+macro_rules! scan_match {
+    ($input:expr; $($rules:tt)*) => {
+        scan_rules_impl!(@scan_match ($input), (); $($rules)*)
+    };
+}
+
+/**
+Scans the provided input by dispatching on its first word.  The result is a `Result<T, ScanError>`, just as with `scan!`.
+
+Each rule is of the form `"keyword" => (pattern) => body`.  Rather than trying every rule in turn, the first word of the input is scanned *once*, and compared against the keywords using a single `match`; only the pattern of the matching rule is then scanned against the rest of the input.  This is considerably faster than `scan!` for command-parser style inputs with many rules, each starting with a distinct literal.
+
+Keywords must be string literals consisting of a single word, as matched by [`Word`](scanner/struct.Word.html), and are compared exactly, irrespective of the case sensitivity of the input.  Each keyword may only appear once; use repetitions, optional terms, or a nested `scan!` if a keyword needs several alternative forms.
+
+See also: [Pattern Syntax](index.html#pattern-syntax), [`scan!`](macro.scan!.html).
+
+## Examples
+
+```rust
+# #[macro_use] extern crate scan_rules;
+# fn main() {
+let eval = |input: &str| scan_keyword! { input;
+    "add" => (let a: i32, let b: i32) => a + b,
+    "neg" => (let a: i32) => -a,
+    "zero" => () => 0,
+};
+assert_eq!(eval("add 1 2").unwrap(), 3);
+assert_eq!(eval("neg 3").unwrap(), -3);
+assert_eq!(eval("zero").unwrap(), 0);
+assert!(eval("mul 2 3").is_err());
+# }
+```
+*/
+#[macro_export]
+macro_rules! scan_keyword {
+    ($input:expr;
+        $($kws:tt => ($($patterns:tt)*) => $bodies:expr),+ $(,)*
+    ) => {
+        {
+            let cur = $crate::input::IntoScanCursor::into_scan_cursor($input);
+
+            match $crate::internal::try_scan_keyword(cur) {
+                Ok((kw, rest)) => match kw {
+                    $($kws => scan!(rest; ($($patterns)*) => $bodies),)+
+                    _ => Err($crate::ScanError::add_offset(
+                        $crate::ScanError::literal_mismatch(),
+                        $crate::input::ScanCursor::offset(&rest) - kw.len()
+                    )),
+                },
+                Err(err) => Err(err),
+            }
+        }
+    };
+
+    ($($_tail:tt)*) => {
+        scan_rules_impl!(@error "expected `input; \"keyword\" => (pattern) => body, ...`")
+    };
+}
+
+/**
+Defines a named scanning rule, which can then be reused inside other patterns.
+
+Each definition creates a new scanner type with the given name.  Its rules are written exactly as for [`scan!`](macro.scan!.html), except that they only need to match a *prefix* of the input; the output of the scanner is the value of whichever rule body matched.  Bindings inside a rule are local to that rule, and do not leak into the patterns which use it.
+
+A rule can be used like any other static scanner (*e.g.* `let d: Date`), or as a pattern term of the form `@Date`, which scans the rule and discards the result.
+
+If the output type needs to borrow from the input, name the input lifetime after the rule name (*e.g.* `Key<'a> -> &'a str`).  The scanner type itself does not take a lifetime parameter.
+
+See also: [Pattern Syntax](index.html#pattern-syntax).
+
+## Examples
+
+```rust
+# #[macro_use] extern crate scan_rules;
+define_scan_rule! {
+    /// An ISO 8601 calendar date.
+    Date -> (u16, u8, u8) {
+        (let y: u16, "-", let m: u8, "-", let d: u8) => (y, m, d),
+    }
+}
+
+# fn main() {
+let range = scan!("2016-01-01 to 2016-12-31";
+    (let from: Date, "to", let to: Date) => (from, to)
+);
+assert_eq!(range.unwrap(), ((2016, 1, 1), (2016, 12, 31)));
+
+assert!(scan!("2016-01-01 to 2016-12-31"; (@Date, "to", @Date) => ()).is_ok());
+# }
+```
+*/
+#[macro_export]
+macro_rules! define_scan_rule {
+    () => {};
+
+    (
+        $(#[$attrs:meta])*
+        pub $name:ident<$lt:tt> -> $out:ty { $($rules:tt)* }
+        $($tail:tt)*
+    ) => {
+        $(#[$attrs])* pub enum $name {}
+        define_scan_rule! { @impl $lt, $name, $out; $($rules)* }
+        define_scan_rule! { $($tail)* }
+    };
+
+    (
+        $(#[$attrs:meta])*
+        pub $name:ident -> $out:ty { $($rules:tt)* }
+        $($tail:tt)*
+    ) => {
+        $(#[$attrs])* pub enum $name {}
+        define_scan_rule! { @impl 'a, $name, $out; $($rules)* }
+        define_scan_rule! { $($tail)* }
+    };
+
+    (
+        $(#[$attrs:meta])*
+        $name:ident<$lt:tt> -> $out:ty { $($rules:tt)* }
+        $($tail:tt)*
+    ) => {
+        $(#[$attrs])* enum $name {}
+        define_scan_rule! { @impl $lt, $name, $out; $($rules)* }
+        define_scan_rule! { $($tail)* }
+    };
+
+    (
+        $(#[$attrs:meta])*
+        $name:ident -> $out:ty { $($rules:tt)* }
+        $($tail:tt)*
+    ) => {
+        $(#[$attrs])* enum $name {}
+        define_scan_rule! { @impl 'a, $name, $out; $($rules)* }
+        define_scan_rule! { $($tail)* }
+    };
+
+    (@impl $lt:tt, $name:ident, $out:ty; $($rules:tt)*) => {
+        impl<$lt> $crate::scanner::ScanFromStr<$lt> for $name {
+            type Output = $out;
+            fn scan_from<I: $crate::input::ScanInput<$lt>>(s: I) -> ::std::result::Result<(Self::Output, usize), $crate::ScanError> {
+                ::std::result::Result::map(
+                    scan_partial!(s.to_cursor(); $($rules)*),
+                    |(v, tail)| (v, $crate::input::ScanCursor::offset(&tail))
+                )
+            }
+        }
+    };
+}
+
+/**
+Defines short names for scanner types, for use in a binding's type position.
+
+Each definition of the form `Name = Type;` creates a type alias.  A definition of the form `Name = <| Type;` instead wraps a *runtime* scanner which implements `Default` in [`Runtime`](scanner/runtime/struct.Runtime.html), so that it, too, can be used in the type position rather than with `<|`.
+
+If the type needs to borrow from the input, give the alias a lifetime parameter (*e.g.* `Key<'a> = Word<'a, &'a str>;`).
+
+## Examples
+
+```rust
+# #[macro_use] extern crate scan_rules;
+use scan_rules::scanner::Hex;
+use scan_rules::scanner::runtime::ScanA;
+
+scanner_alias! {
+    /// A port number, written in hex.
+    Port = Hex<u16>;
+    Count = <| ScanA<u32>;
+}
+
+# fn main() {
+let v = scan!("port 1f90, 3 times"; ("port", let p: Port, ",", let n: Count, "times") => (p, n));
+assert_eq!(v.unwrap(), (8080, 3));
+# }
+```
+*/
+#[macro_export]
+macro_rules! scanner_alias {
+    () => {};
+
+    ($(#[$attrs:meta])* pub $name:ident<$lt:tt> = <| $ty:ty; $($tail:tt)*) => {
+        $(#[$attrs])* pub type $name<$lt> = $crate::scanner::Runtime<$ty>;
+        scanner_alias! { $($tail)* }
+    };
+
+    ($(#[$attrs:meta])* pub $name:ident = <| $ty:ty; $($tail:tt)*) => {
+        $(#[$attrs])* pub type $name = $crate::scanner::Runtime<$ty>;
+        scanner_alias! { $($tail)* }
+    };
+
+    ($(#[$attrs:meta])* pub $name:ident<$lt:tt> = $ty:ty; $($tail:tt)*) => {
+        $(#[$attrs])* pub type $name<$lt> = $ty;
+        scanner_alias! { $($tail)* }
+    };
+
+    ($(#[$attrs:meta])* pub $name:ident = $ty:ty; $($tail:tt)*) => {
+        $(#[$attrs])* pub type $name = $ty;
+        scanner_alias! { $($tail)* }
+    };
+
+    ($(#[$attrs:meta])* $name:ident<$lt:tt> = <| $ty:ty; $($tail:tt)*) => {
+        $(#[$attrs])* type $name<$lt> = $crate::scanner::Runtime<$ty>;
+        scanner_alias! { $($tail)* }
+    };
+
+    ($(#[$attrs:meta])* $name:ident = <| $ty:ty; $($tail:tt)*) => {
+        $(#[$attrs])* type $name = $crate::scanner::Runtime<$ty>;
+        scanner_alias! { $($tail)* }
+    };
+
+    ($(#[$attrs:meta])* $name:ident<$lt:tt> = $ty:ty; $($tail:tt)*) => {
+        $(#[$attrs])* type $name<$lt> = $ty;
+        scanner_alias! { $($tail)* }
+    };
+
+    ($(#[$attrs:meta])* $name:ident = $ty:ty; $($tail:tt)*) => {
+        $(#[$attrs])* type $name = $ty;
+        scanner_alias! { $($tail)* }
+    };
+}
+
+/**
+Implements `ScanFromStr` for structs with named fields, using the same syntax as their derived `Debug` implementation.
+
+Each definition has the form `Name { field: Type, ... }`, restating the struct's fields and their types.  The generated scanner matches input of the form `Name { field: value, ... }`, with an optional trailing comma, and outputs `Name`.  Each field is scanned using its type as a static scanner.
+
+Prefixing a definition with `any order` allows the fields to appear in any order; each field must still appear exactly once.
+
+The struct must have at least one field, and must not have any lifetime or type parameters.
+
+## Examples
+
+```rust
+# #[macro_use] extern crate scan_rules;
+#[derive(Debug, PartialEq)]
+struct Point { x: i32, y: i32 }
+
+#[derive(Debug, PartialEq)]
+struct Server { name: String, port: u16 }
+
+scanner_for_debug_struct! {
+    Point { x: i32, y: i32 }
+    any order Server { name: String, port: u16 }
+}
+
+# fn main() {
+let p = Point { x: 1, y: -2 };
+let text = format!("{:?}", p);
+assert_eq!(scan!(&text; (let p: Point) => p).unwrap(), p);
+
+let s = scan!(r#"Server { port: 80, name: "web", }"#; (let s: Server) => s);
+assert_eq!(s.unwrap(), Server { name: "web".into(), port: 80 });
+# }
+```
+*/
+#[macro_export]
+macro_rules! scanner_for_debug_struct {
+    () => {};
+
+    (any order $name:ident { $($fs:ident: $tys:ty),+ $(,)* } $($tail:tt)*) => {
+        impl<'a> $crate::scanner::ScanFromStr<'a> for $name {
+            type Output = Self;
+            fn scan_from<I: $crate::input::ScanInput<'a>>(s: I) -> ::std::result::Result<(Self::Output, usize), $crate::ScanError> {
+                use ::std::option::Option::{self, Some, None};
+                use ::std::result::Result::{Ok, Err};
+                use $crate::input::ScanCursor;
+
+                let mut cur = try!(scan_partial!(s.to_cursor(); (stringify!($name), "{") => ())).1;
+                $(let mut $fs: Option<$tys> = None;)+
+                let mut first = true;
+                let end;
+
+                loop {
+                    if !first {
+                        if let Ok(((), c)) = scan_partial!(cur.clone(); ("}") => ()) {
+                            end = c;
+                            break;
+                        }
+                        cur = try!(scan_partial!(cur; (",") => ())).1;
+                        if let Ok(((), c)) = scan_partial!(cur.clone(); ("}") => ()) {
+                            end = c;
+                            break;
+                        }
+                    }
+                    first = false;
+
+                    let at = cur.offset();
+                    let (dup, c) = try!(scan_partial!(cur;
+                        $((stringify!($fs), ":", let v: $tys) => {
+                            let dup = $fs.is_some();
+                            $fs = Some(v);
+                            dup
+                        },)+
+                    ));
+                    if dup {
+                        return Err($crate::ScanError::syntax("duplicate field").add_offset(at));
+                    }
+                    cur = c;
+                }
+
+                $(
+                    let $fs = match $fs {
+                        Some(v) => v,
+                        None => return Err($crate::ScanError::syntax(concat!("missing field `", stringify!($fs), "`")).add_offset(end.offset())),
+                    };
+                )+
+
+                Ok(($name { $($fs: $fs),+ }, end.offset()))
+            }
+        }
+
+        scanner_for_debug_struct! { $($tail)* }
+    };
+
+    ($name:ident { $($fs:ident: $tys:ty),+ $(,)* } $($tail:tt)*) => {
+        scanner_for_debug_struct! { @impl $name; $($fs: $tys),+ }
+        scanner_for_debug_struct! { $($tail)* }
+    };
+
+    (@impl $name:ident; $f0:ident: $ty0:ty $(, $fs:ident: $tys:ty)*) => {
+        impl<'a> $crate::scanner::ScanFromStr<'a> for $name {
+            type Output = Self;
+            fn scan_from<I: $crate::input::ScanInput<'a>>(s: I) -> ::std::result::Result<(Self::Output, usize), $crate::ScanError> {
+                ::std::result::Result::map(
+                    scan_partial!(s.to_cursor();
+                        (
+                            stringify!($name), "{",
+                            stringify!($f0), ":", let $f0: $ty0,
+                            $(",", stringify!($fs), ":", let $fs: $tys,)*
+                            [","]?, "}"
+                        ) => $name { $f0: $f0, $($fs: $fs),* }
+                    ),
+                    |(v, tail)| (v, $crate::input::ScanCursor::offset(&tail))
+                )
+            }
+        }
+    };
+}
+
+/**
+Asserts that a value survives being formatted with `{:?}` and scanned back.
+
+`assert_roundtrip!(value)` scans the `Debug` representation of `value` using its own type as a static scanner, which requires that the type implements `ScanFromStr` with `Output = Self`.  `assert_roundtrip!(value, Scanner)` uses `Scanner` instead, which must output the same type as `value`.
+
+The whole of the formatted text must be consumed.  If it cannot be scanned, or scans to a different value, this panics with both representations and a caret marking the offending position.
+
+This is intended for testing scanners written with [`scanner_for_debug_struct!`](macro.scanner_for_debug_struct!.html), or by hand.
+
+## Examples
+
+```rust
+# #[macro_use] extern crate scan_rules;
+# use scan_rules::scanner::QuotedString;
+#[derive(Debug, PartialEq)]
+struct Point { x: i32, y: i32 }
+
+scanner_for_debug_struct! {
+    Point { x: i32, y: i32 }
+}
+
+# fn main() {
+assert_roundtrip!(42);
+assert_roundtrip!(Some(-1.5));
+assert_roundtrip!(String::from("say \"hi\"\n"));
+assert_roundtrip!(Point { x: 1, y: -2 });
+assert_roundtrip!(vec![1, 2, 3]);
+assert_roundtrip!((255u8, String::from("x")), (u8, QuotedString));
+# }
+```
+*/
+#[macro_export]
+macro_rules! assert_roundtrip {
+    ($value:expr) => {
+        match &$value {
+            value => {
+                let text = format!("{:?}", value);
+                if let ::std::result::Result::Err(msg) = $crate::internal::check_roundtrip_self(value, &text) {
+                    panic!("assertion failed: `{}` does not round-trip\n{}", stringify!($value), msg);
+                }
+            }
+        }
+    };
+
+    ($value:expr, $scanner:ty) => {
+        match &$value {
+            value => {
+                let text = format!("{:?}", value);
+                if let ::std::result::Result::Err(msg) = $crate::internal::check_roundtrip::<$scanner, _>(value, &text) {
+                    panic!("assertion failed: `{}` does not round-trip through `{}`\n{}", stringify!($value), stringify!($scanner), msg);
+                }
+            }
+        }
+    };
 }
 
 #[doc(hidden)]
@@ -196,19 +773,28 @@ macro_rules! scan_rules_impl {
     /*
     ## Termination rule.
     */
-    (@scan ($cur:expr); () => $body:expr) => {
+    (@scan ($cur:expr, $start:tt); () => $body:expr) => {
         {
-            match $crate::input::ScanCursor::try_end($cur) {
-                Ok(()) => Ok($body),
+            let cur = $cur;
+            let end = $crate::input::ScanCursor::offset(&cur);
+            match $crate::input::ScanCursor::try_end(cur) {
+                Ok(()) => Ok($crate::internal::trace_matched_at(end, $body)),
                 Err((err, _)) => Err(err)
             }
         }
     };
 
+    /*
+    An empty pattern only matches empty input.
+    */
+    (@scan ($cur:expr, $start:tt); (,) => $body:expr) => {
+        scan_rules_impl!(@scan ($cur, $start); () => $body)
+    };
+
     /*
     ## Tail capture.
     */
-    (@scan ($cur:expr); (.._,) => $body:expr) => {
+    (@scan ($cur:expr, $start:tt); (.._,) => $body:expr) => {
         {
             match $crate::input::ScanCursor::try_scan_raw(
                 $cur,
@@ -217,13 +803,13 @@ macro_rules! scan_rules_impl {
                     Ok::<_, $crate::ScanError>((s, s.len()))
                 }
             ) {
-                Ok((_, new_cur)) => scan_rules_impl!(@scan (new_cur); () => $body),
+                Ok((_, new_cur)) => scan_rules_impl!(@scan (new_cur, $start); () => $body),
                 Err((err, _)) => Err(err)
             }
         }
     };
 
-    (@scan ($cur:expr); (..$name:ident,) => $body:expr) => {
+    (@scan ($cur:expr, $start:tt); (..$name:ident,) => $body:expr) => {
         {
             match $crate::input::ScanCursor::try_scan_raw(
                 $cur,
@@ -232,66 +818,194 @@ macro_rules! scan_rules_impl {
                     Ok::<_, $crate::ScanError>((s, s.len()))
                 }
             ) {
-                Ok(($name, new_cur)) => scan_rules_impl!(@scan (new_cur); () => $body),
+                Ok(($name, new_cur)) => scan_rules_impl!(@scan (new_cur, $start); () => $body),
                 Err((err, _)) => Err(err)
             }
         }
     };
 
+    (@scan ($cur:expr, $start:tt); (..$name:ident trimmed,) => $body:expr) => {
+        match $crate::internal::scan_tail($cur, true) {
+            Ok(((_, $name), new_cur)) => scan_rules_impl!(@scan (new_cur, $start); () => $body),
+            Err((err, _)) => Err(err)
+        }
+    };
+
+    (@scan ($cur:expr, $start:tt); (..$name:ident trimmed at $pos:ident,) => $body:expr) => {
+        match $crate::internal::scan_tail($cur, true) {
+            Ok((($pos, $name), new_cur)) => scan_rules_impl!(@scan (new_cur, $start); () => $body),
+            Err((err, _)) => Err(err)
+        }
+    };
+
+    (@scan ($cur:expr, $start:tt); (..$name:ident at $pos:ident,) => $body:expr) => {
+        match $crate::internal::scan_tail($cur, false) {
+            Ok((($pos, $name), new_cur)) => scan_rules_impl!(@scan (new_cur, $start); () => $body),
+            Err((err, _)) => Err(err)
+        }
+    };
+
+    (@scan ($cur:expr, $start:tt); (.._, $($tail:tt)+) => $body:expr) => {
+        scan_rules_impl!(@error "`.._` can only appear as the final term of a pattern")
+    };
+
+    (@scan ($cur:expr, $start:tt); (..$name:ident, $($tail:tt)+) => $body:expr) => {
+        scan_rules_impl!(@error "`..name` can only appear as the final term of a pattern")
+    };
+
+    (@scan ($cur:expr, $start:tt); (..$name:ident trimmed $(at $pos:ident)*, $($tail:tt)+) => $body:expr) => {
+        scan_rules_impl!(@error "`..name trimmed` can only appear as the final term of a pattern")
+    };
+
+    (@scan ($cur:expr, $start:tt); (..$name:ident at $pos:ident, $($tail:tt)+) => $body:expr) => {
+        scan_rules_impl!(@error "`..name at pos` can only appear as the final term of a pattern")
+    };
+
+    /*
+    ## Prefix match.
+    */
+    (@scan ($cur:expr, $start:tt); (...,) => $body:expr) => {
+        {
+            let end = $crate::input::ScanCursor::offset(&$cur);
+            Ok::<_, $crate::ScanError>($crate::internal::trace_matched_at(end, $body))
+        }
+    };
+
+    (@scan ($cur:expr, $start:tt); (..., $($tail:tt)+) => $body:expr) => {
+        scan_rules_impl!(@error "`...` can only appear as the final term of a pattern")
+    };
+
     /*
     ## Anchor capture.
     */
-    (@scan ($cur:expr); (^..$name:ident,) => $body:expr) => {
+    (@scan ($cur:expr, $start:tt); (^..$name:ident,) => $body:expr) => {
         {
             let $name = $cur;
-            Ok($body)
+            let end = $crate::input::ScanCursor::offset(&$name);
+            Ok($crate::internal::trace_matched_at(end, $body))
         }
     };
 
+    (@scan ($cur:expr, $start:tt); (^..$name:ident, $($tail:tt)+) => $body:expr) => {
+        scan_rules_impl!(@error "`^..name` can only appear as the final term of a pattern")
+    };
+
     /*
     ## Value capture.
     */
-    (@scan ($cur:expr); (let _: $t:ty, $($tail:tt)*) => $body:expr) => {
+    (@scan ($cur:expr, $start:tt); (let _: $t:ty, $($tail:tt)*) => $body:expr) => {
         {
             match $crate::internal::try_scan_static::<_, $t>($cur) {
-                Ok((_, new_cur)) => scan_rules_impl!(@scan (new_cur); ($($tail)*) => $body),
+                Ok((_, new_cur)) => scan_rules_impl!(@scan (new_cur, $start); ($($tail)*) => $body),
                 Err((err, _)) => Err(err)
             }
         }
     };
 
-    (@scan ($cur:expr); (let _ <| $s:expr, $($tail:tt)*) => $body:expr) => {
+    (@scan ($cur:expr, $start:tt); (let _ <| $s:expr, $($tail:tt)*) => $body:expr) => {
         {
             match $crate::internal::try_scan_runtime($cur, &mut $s) {
-                Ok((_, new_cur)) => scan_rules_impl!(@scan (new_cur); ($($tail)*) => $body),
+                Ok((_, new_cur)) => scan_rules_impl!(@scan (new_cur, $start); ($($tail)*) => $body),
                 Err((err, _)) => Err(err)
             }
         }
     };
 
-    (@scan ($cur:expr); (let $name:ident, $($tail:tt)*) => $body:expr) => {
+    (@scan ($cur:expr, $start:tt); (let $name:ident, $($tail:tt)*) => $body:expr) => {
         {
             match $crate::internal::try_scan_static_self($cur) {
-                Ok(($name, new_cur)) => scan_rules_impl!(@scan (new_cur); ($($tail)*) => $body),
+                Ok(($name, new_cur)) => scan_rules_impl!(@scan (new_cur, $start); ($($tail)*) => $body),
                 Err((err, _)) => Err(err)
             }
         }
     };
 
-    (@scan ($cur:expr); (let $name:ident: $t:ty, $($tail:tt)*) => $body:expr) => {
+    (@scan ($cur:expr, $start:tt); (let $name:ident: $t:ty, $($tail:tt)*) => $body:expr) => {
         {
             match $crate::internal::try_scan_static::<_, $t>($cur) {
-                Ok(($name, new_cur)) => scan_rules_impl!(@scan (new_cur); ($($tail)*) => $body),
+                Ok(($name, new_cur)) => scan_rules_impl!(@scan (new_cur, $start); ($($tail)*) => $body),
                 Err((err, _)) => Err(err)
             }
         }
     };
 
-    (@scan ($cur:expr); (let $name:ident <| $s:expr, $($tail:tt)*) => $body:expr) => {
+    (@scan ($cur:expr, $start:tt); (let $name:ident <| $s:expr, $($tail:tt)*) => $body:expr) => {
+        {
+            match $crate::internal::try_scan_runtime($cur, &mut $s) {
+                Ok(($name, new_cur)) => scan_rules_impl!(@scan (new_cur, $start); ($($tail)*) => $body),
+                Err((err, _)) => Err(err)
+            }
+        }
+    };
+
+    (@scan ($cur:expr, $start:tt); (let $name:ident = do |$c:ident| $e:expr, $($tail:tt)*) => $body:expr) => {
+        {
+            match { let $c = $cur; $e } {
+                Ok(($name, new_cur)) => scan_rules_impl!(@scan (new_cur, $start); ($($tail)*) => $body),
+                Err(err) => Err(err)
+            }
+        }
+    };
+
+    /*
+    `$start` is the offset of the cursor at the beginning of the rule.
+    */
+    (@scan ($cur:expr, $start:tt); (let $name:ident = #consumed, $($tail:tt)*) => $body:expr) => {
+        {
+            let cur = $cur;
+            let $name: usize = $crate::input::ScanCursor::offset(&cur) - $start;
+            scan_rules_impl!(@scan (cur, $start); ($($tail)*) => $body)
+        }
+    };
+
+    (@scan ($cur:expr, $start:tt); (let $($tail:tt)*) => $body:expr) => {
+        scan_rules_impl!(@error "expected `let name`, `let name: Type`, `let name <| scanner`, `let name = do |cursor| expr` or `let name = #consumed`")
+    };
+
+    /*
+    ## Position assertions.
+
+    These have to come before named rules, since `offset(n)` would otherwise be parsed as a type.
+    */
+    (@scan ($cur:expr, $start:tt); (@offset($n:expr), $($tail:tt)*) => $body:expr) => {
+        match $crate::internal::try_at_offset($cur, $n) {
+            Ok(new_cur) => scan_rules_impl!(@scan (new_cur, $start); ($($tail)*) => $body),
+            Err((err, _)) => Err(err)
+        }
+    };
+
+    (@scan ($cur:expr, $start:tt); (@column($n:expr), $($tail:tt)*) => $body:expr) => {
+        match $crate::internal::try_at_column($cur, $n) {
+            Ok(new_cur) => scan_rules_impl!(@scan (new_cur, $start); ($($tail)*) => $body),
+            Err((err, _)) => Err(err)
+        }
+    };
+
+    /*
+    ## Regex match.
+    */
+    (@scan ($cur:expr, $start:tt); (@re($re:expr), $($tail:tt)*) => $body:expr) => {
+        match $crate::internal::try_match_regex($cur, $re) {
+            Ok(new_cur) => scan_rules_impl!(@scan (new_cur, $start); ($($tail)*) => $body),
+            Err((err, _)) => Err(err)
+        }
+    };
+
+    /*
+    ## Named rule.
+    */
+    (@scan ($cur:expr, $start:tt); (@$rule:ty, $($tail:tt)*) => $body:expr) => {
+        scan_rules_impl!(@scan ($cur, $start); (let _: $rule, $($tail)*) => $body)
+    };
+
+    /*
+    ## Inline code.
+    */
+    (@scan ($cur:expr, $start:tt); (do |$c:ident| $e:expr, $($tail:tt)*) => $body:expr) => {
         {
-            match $crate::internal::try_scan_runtime($cur, &mut $s) {
-                Ok(($name, new_cur)) => scan_rules_impl!(@scan (new_cur); ($($tail)*) => $body),
-                Err((err, _)) => Err(err)
+            match { let $c = $cur; $e } {
+                Ok(new_cur) => scan_rules_impl!(@scan (new_cur, $start); ($($tail)*) => $body),
+                Err(err) => Err(err)
             }
         }
     };
@@ -308,125 +1022,207 @@ macro_rules! scan_rules_impl {
     /*
     ### No separator.
     */
-    (@scan ($cur:expr); ([$($pat:tt)*]? $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
-        scan_rules_impl!(@repeat ($cur), [$($pat)*], (), {0, Some(1)}, ($($col_ty)*); ($($tail)*) => $body)
+    (@scan ($cur:expr, $start:tt); ([$($pat:tt)*]? $(binding $zname:ident = $ze:tt)* $(as $cname:ident)* $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
+        scan_rules_impl!(@repeat ($cur, $start), [$($pat)*], (), {0, Some(1), false $(, $cname)*}, ($($col_ty)*), ($(zip $zname = $ze)*); ($($tail)*) => $body)
     };
 
-    (@scan ($cur:expr); ([$($pat:tt)*]* $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
-        scan_rules_impl!(@repeat ($cur), [$($pat)*], (), {0, None}, ($($col_ty)*); ($($tail)*) => $body)
+    (@scan ($cur:expr, $start:tt); ([$($pat:tt)*]* $(binding $zname:ident = $ze:tt)* $(as $cname:ident)* $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
+        scan_rules_impl!(@repeat ($cur, $start), [$($pat)*], (), {0, None, false $(, $cname)*}, ($($col_ty)*), ($(zip $zname = $ze)*); ($($tail)*) => $body)
     };
 
-    (@scan ($cur:expr); ([$($pat:tt)*]+ $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
-        scan_rules_impl!(@repeat ($cur), [$($pat)*], (), {1, None}, ($($col_ty)*); ($($tail)*) => $body)
+    (@scan ($cur:expr, $start:tt); ([$($pat:tt)*]+ $(binding $zname:ident = $ze:tt)* $(as $cname:ident)* $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
+        scan_rules_impl!(@repeat ($cur, $start), [$($pat)*], (), {1, None, false $(, $cname)*}, ($($col_ty)*), ($(zip $zname = $ze)*); ($($tail)*) => $body)
     };
 
-    (@scan ($cur:expr); ([$($pat:tt)*]{,$max:expr} $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
-        scan_rules_impl!(@repeat ($cur), [$($pat)*], (), {0, Some($max)}, ($($col_ty)*); ($($tail)*) => $body)
+    (@scan ($cur:expr, $start:tt); ([$($pat:tt)*]{,$max:expr} $(binding $zname:ident = $ze:tt)* $(as $cname:ident)* $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
+        scan_rules_impl!(@repeat ($cur, $start), [$($pat)*], (), {0, Some($max), false $(, $cname)*}, ($($col_ty)*), ($(zip $zname = $ze)*); ($($tail)*) => $body)
     };
 
-    (@scan ($cur:expr); ([$($pat:tt)*]{$n:expr} $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
-        scan_rules_impl!(@repeat ($cur), [$($pat)*], (), {$n, Some($n)}, ($($col_ty)*); ($($tail)*) => $body)
+    (@scan ($cur:expr, $start:tt); ([$($pat:tt)*]{$n:expr} $(binding $zname:ident = $ze:tt)* $(as $cname:ident)* $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
+        scan_rules_impl!(@repeat ($cur, $start), [$($pat)*], (), {$n, Some($n), false $(, $cname)*}, ($($col_ty)*), ($(zip $zname = $ze)*); ($($tail)*) => $body)
     };
 
-    (@scan ($cur:expr); ([$($pat:tt)*]{$min:expr,} $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
-        scan_rules_impl!(@repeat ($cur), [$($pat)*], (), {$min, None}, ($($col_ty)*); ($($tail)*) => $body)
+    (@scan ($cur:expr, $start:tt); ([$($pat:tt)*]{$min:expr,} $(binding $zname:ident = $ze:tt)* $(as $cname:ident)* $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
+        scan_rules_impl!(@repeat ($cur, $start), [$($pat)*], (), {$min, None, false $(, $cname)*}, ($($col_ty)*), ($(zip $zname = $ze)*); ($($tail)*) => $body)
     };
 
-    (@scan ($cur:expr); ([$($pat:tt)*]{$min:expr, $max:expr} $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
-        scan_rules_impl!(@repeat ($cur), [$($pat)*], (), {$min, Some($max)}, ($($col_ty)*); ($($tail)*) => $body)
+    (@scan ($cur:expr, $start:tt); ([$($pat:tt)*]{$min:expr, $max:expr} $(binding $zname:ident = $ze:tt)* $(as $cname:ident)* $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
+        scan_rules_impl!(@repeat ($cur, $start), [$($pat)*], (), {$min, Some($max), false $(, $cname)*}, ($($col_ty)*), ($(zip $zname = $ze)*); ($($tail)*) => $body)
     };
 
     /*
     ### Comma separator.
     */
-    (@scan ($cur:expr); ([$($pat:tt)*],? $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
-        scan_rules_impl!(@repeat ($cur), [$($pat)*], (","), {0, Some(1)}, ($($col_ty)*); ($($tail)*) => $body)
+    (@scan ($cur:expr, $start:tt); ([$($pat:tt)*],? $(binding $zname:ident = $ze:tt)* $(as $cname:ident)* $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
+        scan_rules_impl!(@repeat ($cur, $start), [$($pat)*], (","), {0, Some(1), false $(, $cname)*}, ($($col_ty)*), ($(zip $zname = $ze)*); ($($tail)*) => $body)
     };
 
-    (@scan ($cur:expr); ([$($pat:tt)*],* $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
-        scan_rules_impl!(@repeat ($cur), [$($pat)*], (","), {0, None}, ($($col_ty)*); ($($tail)*) => $body)
+    (@scan ($cur:expr, $start:tt); ([$($pat:tt)*],* $(binding $zname:ident = $ze:tt)* $(as $cname:ident)* $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
+        scan_rules_impl!(@repeat ($cur, $start), [$($pat)*], (","), {0, None, false $(, $cname)*}, ($($col_ty)*), ($(zip $zname = $ze)*); ($($tail)*) => $body)
     };
 
-    (@scan ($cur:expr); ([$($pat:tt)*],+ $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
-        scan_rules_impl!(@repeat ($cur), [$($pat)*], (","), {1, None}, ($($col_ty)*); ($($tail)*) => $body)
+    (@scan ($cur:expr, $start:tt); ([$($pat:tt)*],+ $(binding $zname:ident = $ze:tt)* $(as $cname:ident)* $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
+        scan_rules_impl!(@repeat ($cur, $start), [$($pat)*], (","), {1, None, false $(, $cname)*}, ($($col_ty)*), ($(zip $zname = $ze)*); ($($tail)*) => $body)
     };
 
-    (@scan ($cur:expr); ([$($pat:tt)*],{,$max:expr} $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
-        scan_rules_impl!(@repeat ($cur), [$($pat)*], (","), {0, Some($max)}, ($($col_ty)*); ($($tail)*) => $body)
+    (@scan ($cur:expr, $start:tt); ([$($pat:tt)*],{,$max:expr} $(binding $zname:ident = $ze:tt)* $(as $cname:ident)* $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
+        scan_rules_impl!(@repeat ($cur, $start), [$($pat)*], (","), {0, Some($max), false $(, $cname)*}, ($($col_ty)*), ($(zip $zname = $ze)*); ($($tail)*) => $body)
     };
 
-    (@scan ($cur:expr); ([$($pat:tt)*],{$n:expr} $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
-        scan_rules_impl!(@repeat ($cur), [$($pat)*], (","), {$n, Some($n)}, ($($col_ty)*); ($($tail)*) => $body)
+    (@scan ($cur:expr, $start:tt); ([$($pat:tt)*],{$n:expr} $(binding $zname:ident = $ze:tt)* $(as $cname:ident)* $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
+        scan_rules_impl!(@repeat ($cur, $start), [$($pat)*], (","), {$n, Some($n), false $(, $cname)*}, ($($col_ty)*), ($(zip $zname = $ze)*); ($($tail)*) => $body)
     };
 
-    (@scan ($cur:expr); ([$($pat:tt)*],{$min:expr,} $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
-        scan_rules_impl!(@repeat ($cur), [$($pat)*], (","), {$min, None}, ($($col_ty)*); ($($tail)*) => $body)
+    (@scan ($cur:expr, $start:tt); ([$($pat:tt)*],{$min:expr,} $(binding $zname:ident = $ze:tt)* $(as $cname:ident)* $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
+        scan_rules_impl!(@repeat ($cur, $start), [$($pat)*], (","), {$min, None, false $(, $cname)*}, ($($col_ty)*), ($(zip $zname = $ze)*); ($($tail)*) => $body)
     };
 
-    (@scan ($cur:expr); ([$($pat:tt)*],{$min:expr, $max:expr} $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
-        scan_rules_impl!(@repeat ($cur), [$($pat)*], (","), {$min, Some($max)}, ($($col_ty)*); ($($tail)*) => $body)
+    (@scan ($cur:expr, $start:tt); ([$($pat:tt)*],{$min:expr, $max:expr} $(binding $zname:ident = $ze:tt)* $(as $cname:ident)* $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
+        scan_rules_impl!(@repeat ($cur, $start), [$($pat)*], (","), {$min, Some($max), false $(, $cname)*}, ($($col_ty)*), ($(zip $zname = $ze)*); ($($tail)*) => $body)
     };
 
     /*
     ### Sub-pattern separator.
     */
-    (@scan ($cur:expr); ([$($pat:tt)*]($($sep:tt)*)? $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
-        scan_rules_impl!(@repeat ($cur), [$($pat)*], ($($sep)*), {0, Some(1)}, ($($col_ty)*); ($($tail)*) => $body)
+    (@scan ($cur:expr, $start:tt); ([$($pat:tt)*]($($sep:tt)*)? $(binding $zname:ident = $ze:tt)* $(as $cname:ident)* $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
+        scan_rules_impl!(@repeat ($cur, $start), [$($pat)*], ($($sep)*), {0, Some(1), false $(, $cname)*}, ($($col_ty)*), ($(zip $zname = $ze)*); ($($tail)*) => $body)
+    };
+
+    (@scan ($cur:expr, $start:tt); ([$($pat:tt)*]($($sep:tt)*)* $(binding $zname:ident = $ze:tt)* $(as $cname:ident)* $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
+        scan_rules_impl!(@repeat ($cur, $start), [$($pat)*], ($($sep)*), {0, None, false $(, $cname)*}, ($($col_ty)*), ($(zip $zname = $ze)*); ($($tail)*) => $body)
+    };
+
+    (@scan ($cur:expr, $start:tt); ([$($pat:tt)*]($($sep:tt)*)+ $(binding $zname:ident = $ze:tt)* $(as $cname:ident)* $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
+        scan_rules_impl!(@repeat ($cur, $start), [$($pat)*], ($($sep)*), {1, None, false $(, $cname)*}, ($($col_ty)*), ($(zip $zname = $ze)*); ($($tail)*) => $body)
+    };
+
+    (@scan ($cur:expr, $start:tt); ([$($pat:tt)*]($($sep:tt)*){,$max:expr} $(binding $zname:ident = $ze:tt)* $(as $cname:ident)* $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
+        scan_rules_impl!(@repeat ($cur, $start), [$($pat)*], ($($sep)*), {0, Some($max), false $(, $cname)*}, ($($col_ty)*), ($(zip $zname = $ze)*); ($($tail)*) => $body)
+    };
+
+    (@scan ($cur:expr, $start:tt); ([$($pat:tt)*]($($sep:tt)*){$n:expr} $(binding $zname:ident = $ze:tt)* $(as $cname:ident)* $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
+        scan_rules_impl!(@repeat ($cur, $start), [$($pat)*], ($($sep)*), {$n, Some($n), false $(, $cname)*}, ($($col_ty)*), ($(zip $zname = $ze)*); ($($tail)*) => $body)
+    };
+
+    (@scan ($cur:expr, $start:tt); ([$($pat:tt)*]($($sep:tt)*){$min:expr,} $(binding $zname:ident = $ze:tt)* $(as $cname:ident)* $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
+        scan_rules_impl!(@repeat ($cur, $start), [$($pat)*], ($($sep)*), {$min, None, false $(, $cname)*}, ($($col_ty)*), ($(zip $zname = $ze)*); ($($tail)*) => $body)
+    };
+
+    (@scan ($cur:expr, $start:tt); ([$($pat:tt)*]($($sep:tt)*){$min:expr, $max:expr} $(binding $zname:ident = $ze:tt)* $(as $cname:ident)* $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
+        scan_rules_impl!(@repeat ($cur, $start), [$($pat)*], ($($sep)*), {$min, Some($max), false $(, $cname)*}, ($($col_ty)*), ($(zip $zname = $ze)*); ($($tail)*) => $body)
+    };
+
+    /*
+    ### Terminating separator.
+
+    Adding `t` after the repetition count allows the separator to also appear after the final repeat, where it is consumed.  Rather than listing every count again, the count is captured as a single token tree and unpacked by `@repeat.term_count`.
+    */
+    (@scan ($cur:expr, $start:tt); ([$($pat:tt)*], $count:tt t $(binding $zname:ident = $ze:tt)* $(as $cname:ident)* $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
+        scan_rules_impl!(@repeat.term_count $count, ($cur, $start), [$($pat)*], (","), ($($cname)*), ($($col_ty)*), ($(zip $zname = $ze)*); ($($tail)*) => $body)
     };
 
-    (@scan ($cur:expr); ([$($pat:tt)*]($($sep:tt)*)* $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
-        scan_rules_impl!(@repeat ($cur), [$($pat)*], ($($sep)*), {0, None}, ($($col_ty)*); ($($tail)*) => $body)
+    (@scan ($cur:expr, $start:tt); ([$($pat:tt)*]($($sep:tt)*) $count:tt t $(binding $zname:ident = $ze:tt)* $(as $cname:ident)* $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
+        scan_rules_impl!(@repeat.term_count $count, ($cur, $start), [$($pat)*], ($($sep)*), ($($cname)*), ($($col_ty)*), ($(zip $zname = $ze)*); ($($tail)*) => $body)
+    };
+
+    /*
+    Without a separator, there is nothing to terminate the repeats with, so `t` has no effect.  It's accepted anyway, so that a separator can be added or removed without having to touch the rest of the term.
+    */
+    (@scan ($cur:expr, $start:tt); ([$($pat:tt)*] $count:tt t $(binding $zname:ident = $ze:tt)* $(as $cname:ident)* $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
+        scan_rules_impl!(@repeat.term_count $count, ($cur, $start), [$($pat)*], (), ($($cname)*), ($($col_ty)*), ($(zip $zname = $ze)*); ($($tail)*) => $body)
     };
 
-    (@scan ($cur:expr); ([$($pat:tt)*]($($sep:tt)*)+ $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
-        scan_rules_impl!(@repeat ($cur), [$($pat)*], ($($sep)*), {1, None}, ($($col_ty)*); ($($tail)*) => $body)
+    /*
+    ## Rule group.
+
+    The nested rules are tried from the current position, exactly as though they had been passed to `scan!`.  The rule containing the group has no body of its own (see `@normalise_rules`), so `$_body` is ignored.
+    */
+    (@scan ($cur:expr, $start:tt); ({$($rules:tt)*},) => $_body:expr) => {
+        scan!($cur; $($rules)*)
     };
 
-    (@scan ($cur:expr); ([$($pat:tt)*]($($sep:tt)*){,$max:expr} $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
-        scan_rules_impl!(@repeat ($cur), [$($pat)*], ($($sep)*), {0, Some($max)}, ($($col_ty)*); ($($tail)*) => $body)
+    (@scan ($cur:expr, $start:tt); ({$($rules:tt)*}, $($tail:tt)+) => $_body:expr) => {
+        scan_rules_impl!(@error "a rule group `{ ... }` can only appear as the final term of a pattern")
     };
 
-    (@scan ($cur:expr); ([$($pat:tt)*]($($sep:tt)*){$n:expr} $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
-        scan_rules_impl!(@repeat ($cur), [$($pat)*], ($($sep)*), {$n, Some($n)}, ($($col_ty)*); ($($tail)*) => $body)
+    /*
+    ### Malformed repetitions.
+
+    Anything else starting with `[...]` would otherwise be treated as an array literal, which gives a *spectacularly* unhelpful error.
+    */
+    (@scan ($cur:expr, $start:tt); ([$($pat:tt)*] $($tail:tt)*) => $body:expr) => {
+        scan_rules_impl!(@error "repetition must be followed by `?`, `*`, `+` or `{..}`, optionally preceded by a separator")
     };
 
-    (@scan ($cur:expr); ([$($pat:tt)*]($($sep:tt)*){$min:expr,} $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
-        scan_rules_impl!(@repeat ($cur), [$($pat)*], ($($sep)*), {$min, None}, ($($col_ty)*); ($($tail)*) => $body)
+    /*
+    ## Verbatim literal match.
+    */
+    (@scan ($cur:expr, $start:tt); (raw $lit:expr, $($tail:tt)*) => $body:expr) => {
+        match $crate::internal::try_match_raw_literal($cur, $lit) {
+            Ok(new_cur) => scan_rules_impl!(@scan (new_cur, $start); ($($tail)*) => $body),
+            Err((err, _)) => Err(err)
+        }
     };
 
-    (@scan ($cur:expr); ([$($pat:tt)*]($($sep:tt)*){$min:expr, $max:expr} $(: $col_ty:ty)*, $($tail:tt)*) => $body:expr) => {
-        scan_rules_impl!(@repeat ($cur), [$($pat)*], ($($sep)*), {$min, Some($max)}, ($($col_ty)*); ($($tail)*) => $body)
+    /*
+    ## Backreference match.
+    */
+    (@scan ($cur:expr, $start:tt); (= $name:ident, $($tail:tt)*) => $body:expr) => {
+        match $crate::internal::try_match_backref($cur, &$name) {
+            Ok(new_cur) => scan_rules_impl!(@scan (new_cur, $start); ($($tail)*) => $body),
+            Err((err, _)) => Err(err)
+        }
     };
 
     /*
     ## Literal match.
     */
-    (@scan ($cur:expr); ($lit:expr, $($tail:tt)*) => $body:expr) => {
+    (@scan ($cur:expr, $start:tt); ($lit:expr, $($tail:tt)*) => $body:expr) => {
         match $crate::input::ScanCursor::try_match_literal($cur, $lit) {
-            Ok(new_cur) => scan_rules_impl!(@scan (new_cur); ($($tail)*) => $body),
+            Ok(new_cur) => scan_rules_impl!(@scan (new_cur, $start); ($($tail)*) => $body),
             Err((err, _)) => Err(err)
         }
     };
 
+    /*
+    ## Malformed terms.
+    */
+    (@scan ($cur:expr, $start:tt); (, $($tail:tt)*) => $body:expr) => {
+        scan_rules_impl!(@error "empty term in pattern; check for a doubled or trailing comma")
+    };
+
+    (@scan ($cur:expr, $start:tt); ($($tail:tt)*) => $body:expr) => {
+        scan_rules_impl!(@error "expected a literal, `let` binding, repetition, `@rule`, `do |cursor| expr`, `..name`, `^..name` or `...`; terms must be separated by commas")
+    };
+
     /*
 
     # `@repeat` - Repetition expansion.
 
     The first step here is to handle a missing `$col_ty` by replacing it with `Vec<_>`.  We delegate to `.with_col_ty` to handle the rest.
 
+    The last group is the collection "mode": either empty, meaning each binding in the repeating pattern gets its own collection, or `zip name = tuple`, meaning the bindings from each repeat are combined into `tuple` and collected into a single collection called `name`.  More than one `binding` clause makes no sense, so we reject it here.
+
     */
-    (@repeat ($cur:expr),
-        [$($pat:tt)*], ($($sep:tt)*), {$min:expr, $max:expr}, ();
+    (@repeat ($cur:expr, $start:tt),
+        [$($pat:tt)*], ($($sep:tt)*), {$min:expr, $max:expr, $term:expr $(, $cname:ident)*}, (), $mode:tt;
+        $($tail:tt)*
+    ) => {
+        scan_rules_impl!(@repeat.with_col_ty ($cur, $start), [$($pat)*], ($($sep)*), {$min, $max, $term $(, $cname)*}, Vec<_>, $mode; $($tail)*)
+    };
+
+    (@repeat ($cur:expr, $start:tt),
+        [$($pat:tt)*], ($($sep:tt)*), {$min:expr, $max:expr, $term:expr $(, $cname:ident)*}, ($col_ty:ty), $mode:tt;
         $($tail:tt)*
     ) => {
-        scan_rules_impl!(@repeat.with_col_ty ($cur), [$($pat)*], ($($sep)*), {$min, $max}, Vec<_>; $($tail)*)
+        scan_rules_impl!(@repeat.with_col_ty ($cur, $start), [$($pat)*], ($($sep)*), {$min, $max, $term $(, $cname)*}, $col_ty, $mode; $($tail)*)
     };
 
-    (@repeat ($cur:expr),
-        [$($pat:tt)*], ($($sep:tt)*), {$min:expr, $max:expr}, ($col_ty:ty);
+    (@repeat.with_col_ty ($cur:expr, $start:tt),
+        [$($pat:tt)*], ($($sep:tt)*), {$min:expr, $max:expr, $term:expr $(, $cname:ident)*}, $col_ty:ty, (zip $_n0:ident = $_e0:tt zip $($_rest:tt)*);
         $($tail:tt)*
     ) => {
-        scan_rules_impl!(@repeat.with_col_ty ($cur), [$($pat)*], ($($sep)*), {$min, $max}, $col_ty; $($tail)*)
+        scan_rules_impl!(@error "a repetition can have at most one `binding` clause")
     };
 
     /*
@@ -436,8 +1232,8 @@ macro_rules! scan_rules_impl {
 
     This will go rather *poorly* if someone is silly enough to use the same name more than once... but then, that's a bad idea in general.
     */
-    (@repeat.with_col_ty ($cur:expr),
-        [$($pat:tt)*], ($($sep:tt)*), {$min:expr, $max:expr}, $col_ty:ty;
+    (@repeat.with_col_ty ($cur:expr, $start:tt),
+        [$($pat:tt)*], ($($sep:tt)*), {$min:expr, $max:expr, $term:expr $(, $cname:ident)*}, $col_ty:ty, $mode:tt;
         $($tail:tt)*
     ) => {
         {
@@ -445,9 +1241,18 @@ macro_rules! scan_rules_impl {
             let mut repeats: usize = 0;
             let min: usize = $min;
             let max: ::std::option::Option<usize> = $max;
-            scan_rules_impl!(@with_bindings ($($pat)*), then: scan_rules_impl!(@repeat.define_cols $col_ty,););
+            let terminated: bool = $term;
+            scan_rules_impl!(@repeat.mode_define_cols $mode, $col_ty, ($($pat)*));
             scan_rules_impl!(@with_bindings ($($sep)*), then: scan_rules_impl!(@repeat.define_cols $col_ty,););
 
+            // Give the collections a rough idea of how many elements are coming.
+            {
+                #[allow(unused_imports)]
+                use $crate::internal::{ReserveHint, NoReserveHint};
+                let _hint = scan_rules_impl!(@repeat.hint (&cur), ($($sep)*), max);
+                scan_rules_impl!(@repeat.mode_reserve $mode, _hint, ($($pat)*));
+            }
+
             match (min, max) {
                 (a, Some(b)) if a > b => panic!("assertion failed: `(min <= max)` (min: `{:?}`, max: `{:?}`)", a, b),
                 _ => ()
@@ -459,12 +1264,17 @@ macro_rules! scan_rules_impl {
             // Did we break due to a scanning error *after* having successfully scanned a separator?
             let mut break_after_sep: bool;
 
+            // Did we break due to an error which must not be recovered from, such as exceeding a limit?
+            let mut break_fatal = false;
+
+            let max_repeats = $crate::input::ScanCursor::max_repeats(&cur);
+
             loop {
                 // Doing this here prevents an "does not need to be mut" warning.
                 break_after_sep = false;
 
                 match max {
-                    ::std::option::Option::Some(max) if max == repeats => break,
+                    ::std::option::Option::Some(max) if max == repeats && !terminated => break,
                     _ => ()
                 }
 
@@ -473,7 +1283,7 @@ macro_rules! scan_rules_impl {
                     () // Do nothing.
                 } else {
                     if repeats > 0 {
-                        match scan_rules_impl!(@scan (cur.clone());
+                        match scan_rules_impl!(@scan (cur.clone(), $start);
                             ($($sep)*, ^..after,) => {
                                 cur = after;
                                 scan_rules_impl!(@with_bindings ($($sep)*), then: scan_rules_impl!(@repeat.tuple))
@@ -485,6 +1295,7 @@ macro_rules! scan_rules_impl {
                                 scan_rules_impl!(@with_bindings ($($sep)*), then: scan_rules_impl!(@repeat.push elems,););
                             },
                             ::std::result::Result::Err(err) => {
+                                break_fatal = $crate::internal::is_fatal_error(&err);
                                 break_err = Some(err);
                                 break;
                             }
@@ -492,40 +1303,101 @@ macro_rules! scan_rules_impl {
                     }
                 });
 
+                // When terminating, the separator after the final repeat has now been consumed.
+                match max {
+                    ::std::option::Option::Some(max) if max == repeats => break,
+                    _ => ()
+                }
+
                 // Scan the repeating pattern.
-                match scan_rules_impl!(@scan (cur.clone());
+                let item_at = $crate::input::ScanCursor::offset(&cur);
+                match scan_rules_impl!(@scan (cur.clone(), $start);
                     ($($pat)*, ^..after,) => {
                         cur = after;
-                        scan_rules_impl!(@with_bindings ($($pat)*), then: scan_rules_impl!(@repeat.tuple))
+                        scan_rules_impl!(@repeat.mode_tuple $mode, ($($pat)*))
                     }
                 ) {
                     ::std::result::Result::Ok(elems) => {
                         // Black-hole the first element to stop Rust from complaining when there are no captures.
                         let _ = elems.0;
-                        scan_rules_impl!(@with_bindings ($($pat)*), then: scan_rules_impl!(@repeat.push elems,););
+                        scan_rules_impl!(@repeat.mode_push $mode, elems, ($($pat)*));
                         repeats += 1;
+
+                        match max_repeats {
+                            ::std::option::Option::Some(limit) if repeats > limit => {
+                                break_err = Some($crate::ScanError::limit_exceeded().add_offset(item_at));
+                                break_fatal = true;
+                                break;
+                            },
+                            _ => ()
+                        }
                     },
                     ::std::result::Result::Err(err) => {
                         scan_rules_impl!(@if_empty.expr ($($sep)*) {
                             () // Do nothing
                         } else {
-                            break_after_sep = repeats > 0
+                            break_after_sep = repeats > 0 && !terminated
                         });
+                        break_fatal = $crate::internal::is_fatal_error(&err);
                         break_err = Some(err);
                         break;
                     }
                 }
             }
 
-            if repeats < min || break_after_sep {
-                // Evaluate to the last error because *either* we didn't get enough elements, *or* because we found a separator that wasn't followed by a match.
+            if break_after_sep || break_fatal {
+                // Evaluate to the last error because we found a separator that wasn't followed by a match, or hit an error we must not recover from.
+                Err(break_err.unwrap())
+            } else if repeats < min && $crate::input::ScanCursor::try_end(cur.clone()).is_ok() {
+                // We didn't get enough elements because the input ran out; report that the next one is missing.
+                Err($crate::ScanError::missing($crate::input::ScanCursor::offset(&cur)))
+            } else if repeats < min {
+                // We didn't get enough elements; evaluate to the error from the one that failed.
                 Err(break_err.unwrap())
             } else {
-                scan_rules_impl!(@scan (cur); $($tail)*)
+                $(let $cname: usize = repeats;)*
+                scan_rules_impl!(@scan (cur, $start); $($tail)*)
             }
         }
     };
 
+    /*
+    ## `.term_count`
+
+    Unpacks the repetition count of a terminating repetition.
+    */
+    (@repeat.term_count ?, ($cur:expr, $start:tt), [$($pat:tt)*], ($($sep:tt)*), ($($cname:ident)*), $col_ty:tt, $mode:tt; $($tail:tt)*) => {
+        scan_rules_impl!(@repeat ($cur, $start), [$($pat)*], ($($sep)*), {0, Some(1), true $(, $cname)*}, $col_ty, $mode; $($tail)*)
+    };
+
+    (@repeat.term_count *, ($cur:expr, $start:tt), [$($pat:tt)*], ($($sep:tt)*), ($($cname:ident)*), $col_ty:tt, $mode:tt; $($tail:tt)*) => {
+        scan_rules_impl!(@repeat ($cur, $start), [$($pat)*], ($($sep)*), {0, None, true $(, $cname)*}, $col_ty, $mode; $($tail)*)
+    };
+
+    (@repeat.term_count +, ($cur:expr, $start:tt), [$($pat:tt)*], ($($sep:tt)*), ($($cname:ident)*), $col_ty:tt, $mode:tt; $($tail:tt)*) => {
+        scan_rules_impl!(@repeat ($cur, $start), [$($pat)*], ($($sep)*), {1, None, true $(, $cname)*}, $col_ty, $mode; $($tail)*)
+    };
+
+    (@repeat.term_count {,$max:expr}, ($cur:expr, $start:tt), [$($pat:tt)*], ($($sep:tt)*), ($($cname:ident)*), $col_ty:tt, $mode:tt; $($tail:tt)*) => {
+        scan_rules_impl!(@repeat ($cur, $start), [$($pat)*], ($($sep)*), {0, Some($max), true $(, $cname)*}, $col_ty, $mode; $($tail)*)
+    };
+
+    (@repeat.term_count {$n:expr}, ($cur:expr, $start:tt), [$($pat:tt)*], ($($sep:tt)*), ($($cname:ident)*), $col_ty:tt, $mode:tt; $($tail:tt)*) => {
+        scan_rules_impl!(@repeat ($cur, $start), [$($pat)*], ($($sep)*), {$n, Some($n), true $(, $cname)*}, $col_ty, $mode; $($tail)*)
+    };
+
+    (@repeat.term_count {$min:expr,}, ($cur:expr, $start:tt), [$($pat:tt)*], ($($sep:tt)*), ($($cname:ident)*), $col_ty:tt, $mode:tt; $($tail:tt)*) => {
+        scan_rules_impl!(@repeat ($cur, $start), [$($pat)*], ($($sep)*), {$min, None, true $(, $cname)*}, $col_ty, $mode; $($tail)*)
+    };
+
+    (@repeat.term_count {$min:expr, $max:expr}, ($cur:expr, $start:tt), [$($pat:tt)*], ($($sep:tt)*), ($($cname:ident)*), $col_ty:tt, $mode:tt; $($tail:tt)*) => {
+        scan_rules_impl!(@repeat ($cur, $start), [$($pat)*], ($($sep)*), {$min, Some($max), true $(, $cname)*}, $col_ty, $mode; $($tail)*)
+    };
+
+    (@repeat.term_count $($_other:tt)*) => {
+        scan_rules_impl!(@error "a terminating repetition (`t`) must follow `?`, `*`, `+` or `{..}`")
+    };
+
     /*
     ## `.define_cols`
 
@@ -537,6 +1409,25 @@ macro_rules! scan_rules_impl {
         )*
     };
 
+    /*
+    ## `.hint` and `.reserve`
+
+    Work out how many elements to reserve space for.  This is only attempted when the separator is a single token, which should be a string literal (or a variable containing a string).  Anything else is too hard to predict, so we don't bother.
+    */
+    (@repeat.hint ($cur:expr), ($sep:tt), $max:expr) => {
+        $crate::internal::repeat_hint($crate::input::ScanCursor::remaining($cur), $sep, $max)
+    };
+
+    (@repeat.hint ($cur:expr), ($($_sep:tt)*), $_max:expr) => {
+        0usize
+    };
+
+    (@repeat.reserve $hint:ident, $(($names:ident, $_idxs:expr),)*) => {
+        $(
+            (&mut $names).reserve_hint($hint);
+        )*
+    };
+
     /*
     ## `.tuple`
 
@@ -562,6 +1453,153 @@ macro_rules! scan_rules_impl {
         )*
     };
 
+    /*
+    ## `.mode_*`
+
+    Dispatch the above on the collection mode.  In `zip` mode, there is only one collection, and the tuple for each repeat is built *inside* the pattern body, where the bindings are in scope.
+    */
+    (@repeat.mode_define_cols (), $col_ty:ty, ($($pat:tt)*)) => {
+        scan_rules_impl!(@with_bindings ($($pat)*), then: scan_rules_impl!(@repeat.define_cols $col_ty,););
+    };
+
+    (@repeat.mode_define_cols (zip $name:ident = $_e:tt), $col_ty:ty, ($($pat:tt)*)) => {
+        let mut $name: $col_ty = ::std::default::Default::default();
+    };
+
+    (@repeat.mode_reserve (), $hint:ident, ($($pat:tt)*)) => {
+        scan_rules_impl!(@with_bindings ($($pat)*), then: scan_rules_impl!(@repeat.reserve $hint,););
+    };
+
+    (@repeat.mode_reserve (zip $name:ident = $_e:tt), $hint:ident, ($($pat:tt)*)) => {
+        (&mut $name).reserve_hint($hint);
+    };
+
+    (@repeat.mode_tuple (), ($($pat:tt)*)) => {
+        scan_rules_impl!(@with_bindings ($($pat)*), then: scan_rules_impl!(@repeat.tuple))
+    };
+
+    (@repeat.mode_tuple (zip $_name:ident = $e:tt), ($($pat:tt)*)) => {
+        ((), $e)
+    };
+
+    (@repeat.mode_push (), $elems:expr, ($($pat:tt)*)) => {
+        scan_rules_impl!(@with_bindings ($($pat)*), then: scan_rules_impl!(@repeat.push $elems,););
+    };
+
+    (@repeat.mode_push (zip $name:ident = $_e:tt), $elems:expr, ($($pat:tt)*)) => {
+        ::std::iter::Extend::extend(&mut $name, ::std::iter::once($elems.1));
+    };
+
+    /*
+
+    # `@normalise_rules` - Fill in missing and `@into` rule bodies.
+
+    Rules with a `@into Type` body have it replaced with a call to `FromScan::from_scan`, passing a tuple of the pattern's bindings.
+
+    A rule ending in a rule group takes its value from the group, and so is written without a body.  We give such rules a body which is an error; the `@scan` arm for groups never expands it, so it only fires for a body-less rule which *doesn't* end in a group.
+
+    */
+    (@normalise_rules ($input:expr), ($($rules:tt)+);) => {
+        scan!($input; $($rules)+)
+    };
+
+    (@normalise_rules $input:tt, $rules:tt; ($($pat:tt)*) => @into $ty:ty) => {
+        scan_rules_impl!(@normalise_rules $input, $rules; ($($pat)*) => @into $ty,)
+    };
+
+    (@normalise_rules $input:tt, $rules:tt; ($($pat:tt)*) => @into $ty:ty, $($tail:tt)*) => {
+        scan_rules_impl!(@with_bindings ($($pat)*),
+            then: scan_rules_impl!(@normalise_rules.into $input, $rules, ($($pat)*), $ty, ($($tail)*),))
+    };
+
+    (@normalise_rules $input:tt, ($($rules:tt)*); ($($pat:tt)*) => $body:expr) => {
+        scan_rules_impl!(@normalise_rules $input, ($($rules)* ($($pat)*) => $body,);)
+    };
+
+    (@normalise_rules $input:tt, ($($rules:tt)*); ($($pat:tt)*) => $body:expr, $($tail:tt)*) => {
+        scan_rules_impl!(@normalise_rules $input, ($($rules)* ($($pat)*) => $body,); $($tail)*)
+    };
+
+    (@normalise_rules $input:tt, $rules:tt; ($($pat:tt)*)) => {
+        scan_rules_impl!(@normalise_rules $input, $rules; ($($pat)*),)
+    };
+
+    (@normalise_rules $input:tt, ($($rules:tt)*); ($($pat:tt)*), $($tail:tt)*) => {
+        scan_rules_impl!(
+            @normalise_rules $input,
+            ($($rules)* ($($pat)*) => scan_rules_impl!(@error "only a rule ending in a rule group `{ ... }` may omit its body"),);
+            $($tail)*
+        )
+    };
+
+    (@normalise_rules $input:tt, $_rules:tt; $($_tail:tt)*) => {
+        scan_rules_impl!(@error "expected `input; (pattern) => body, ...`")
+    };
+
+    /*
+    Callback from `@with_bindings` for `@into` bodies.
+    */
+    (@normalise_rules.into $input:tt, ($($rules:tt)*), $pat:tt, $ty:ty, ($($tail:tt)*), $(($ns:ident, $_is:tt),)*) => {
+        scan_rules_impl!(
+            @normalise_rules $input,
+            ($($rules)* $pat => <$ty as $crate::FromScan<_>>::from_scan(($($ns,)*)),);
+            $($tail)*
+        )
+    };
+
+    /*
+
+    # `@scan_match` - Split off the catch-all rule.
+
+    Rules are moved one at a time into the accumulator until the only thing left is the catch-all rule.  If we run out of rules without finding one, or the catch-all isn't last, it's an error.
+
+    */
+    (@scan_match ($input:expr), (); (.._) => $body:expr $(,)*) => {
+        {
+            let _ = $crate::input::IntoScanCursor::into_scan_cursor($input);
+            $body
+        }
+    };
+
+    (@scan_match ($input:expr), (); (..$rest:ident) => $body:expr $(,)*) => {
+        {
+            let cur = $crate::input::IntoScanCursor::into_scan_cursor($input);
+            let $rest = $crate::input::ScanCursor::as_str(cur);
+            $body
+        }
+    };
+
+    (@scan_match ($input:expr), ($($rules:tt)*); (.._) => $body:expr $(,)*) => {
+        {
+            let cur = $crate::input::IntoScanCursor::into_scan_cursor($input);
+            match scan!(cur.clone(); $($rules)*) {
+                Ok(v) => v,
+                Err(_) => $body,
+            }
+        }
+    };
+
+    (@scan_match ($input:expr), ($($rules:tt)*); (..$rest:ident) => $body:expr $(,)*) => {
+        {
+            let cur = $crate::input::IntoScanCursor::into_scan_cursor($input);
+            match scan!(cur.clone(); $($rules)*) {
+                Ok(v) => v,
+                Err(_) => {
+                    let $rest = $crate::input::ScanCursor::as_str(cur);
+                    $body
+                },
+            }
+        }
+    };
+
+    (@scan_match ($input:expr), ($($rules:tt)*); ($($pat:tt)*) => $body:expr, $($tail:tt)+) => {
+        scan_rules_impl!(@scan_match ($input), ($($rules)* ($($pat)*) => $body,); $($tail)+)
+    };
+
+    (@scan_match ($input:expr), $_rules:tt; $($_tail:tt)*) => {
+        scan_rules_impl!(@error "`scan_match!` requires the last rule to be a catch-all of the form `(..name) => body`")
+    };
+
     /*
 
     # `@let_bindings`
@@ -570,11 +1608,21 @@ macro_rules! scan_rules_impl {
 
     */
 
-    (@let_bindings.panic $input:expr, $pattern:tt, $(($ns:ident, $_is:tt),)*) => {
+    /*
+    `$patterns` is a parenthesised list of patterns, all of which bind the same names.  The names are first collected into a single tuple so that they can be repeated once per pattern.
+    */
+    (@let_bindings.panic $input:expr, $patterns:tt, $(($ns:ident, $_is:tt),)*) => {
+        scan_rules_impl!(@let_bindings.panic_rules $input, $patterns, ($($ns,)*))
+    };
+
+    /*
+    Note that `$input` is borrowed *in place*, rather than being moved into the `match`.  This means captured values can borrow from the input, provided it outlives them.
+    */
+    (@let_bindings.panic_rules $input:expr, ($($pattern:tt)*), $ns:tt) => {
         scan_rules_impl!(
             @as_stmt
-            let ($($ns,)*) = match $input {
-                input => match scan!(&input[..]; $pattern => ($($ns,)*)) {
+            let $ns = match &$input[..] {
+                input => match scan!(input; $($pattern => $ns,)*) {
                     Ok(vs) => vs,
                     Err(err) => panic!("error while scanning `{:?}`: {}", input, err)
                 }
@@ -644,58 +1692,149 @@ macro_rules! scan_rules_impl {
         scan_rules_impl!(@with_bindings.inc $i, ($($names)* ($name, $i),), $cb; $($tail)*)
     };
 
-    (@with_bindings.step $i:tt, $names:tt, $cb:tt; [$($pat:tt)*]? $(: $col_ty:ty)*, $($tail:tt)*) => {
-        scan_rules_impl!(@with_bindings.step $i, $names, $cb; $($pat)*, $($tail)*)
+    (@with_bindings.step $i:tt, ($($names:tt)*), $cb:tt; let $name:ident = do |$_c:ident| $_e:expr, $($tail:tt)*) => {
+        scan_rules_impl!(@with_bindings.inc $i, ($($names)* ($name, $i),), $cb; $($tail)*)
     };
 
-    (@with_bindings.step $i:tt, $names:tt, $cb:tt; [$($pat:tt)*]* $(: $col_ty:ty)*, $($tail:tt)*) => {
-        scan_rules_impl!(@with_bindings.step $i, $names, $cb; $($pat)*, $($tail)*)
+    (@with_bindings.step $i:tt, ($($names:tt)*), $cb:tt; let $name:ident = #consumed, $($tail:tt)*) => {
+        scan_rules_impl!(@with_bindings.inc $i, ($($names)* ($name, $i),), $cb; $($tail)*)
     };
 
-    (@with_bindings.step $i:tt, $names:tt, $cb:tt; [$($pat:tt)*]+ $(: $col_ty:ty)*, $($tail:tt)*) => {
-        scan_rules_impl!(@with_bindings.step $i, $names, $cb; $($pat)*, $($tail)*)
+    (@with_bindings.step $i:tt, $names:tt, $cb:tt; let $($tail:tt)*) => {
+        scan_rules_impl!(@error "expected `let name`, `let name: Type`, `let name <| scanner`, `let name = do |cursor| expr` or `let name = #consumed`")
     };
 
-    (@with_bindings.step $i:tt, $names:tt, $cb:tt; [$($pat:tt)*]{$($_bounds:tt)*} $(: $col_ty:ty)*, $($tail:tt)*) => {
-        scan_rules_impl!(@with_bindings.step $i, $names, $cb; $($pat)*, $($tail)*)
+    (@with_bindings.step $i:tt, $names:tt, $cb:tt; [$($pat:tt)*]? $(binding $zname:ident = $_ze:tt)* $(as $cname:ident)* $(: $col_ty:ty)*, $($tail:tt)*) => {
+        scan_rules_impl!(@with_bindings.repeat $i, $names, $cb; ($($zname)*), ($($pat)*), $(let $cname: usize,)* $($tail)*)
     };
 
-    (@with_bindings.step $i:tt, $names:tt, $cb:tt; [$($pat:tt)*],? $(: $col_ty:ty)*, $($tail:tt)*) => {
-        scan_rules_impl!(@with_bindings.step $i, $names, $cb; $($pat)*, $($tail)*)
+    (@with_bindings.step $i:tt, $names:tt, $cb:tt; [$($pat:tt)*]* $(binding $zname:ident = $_ze:tt)* $(as $cname:ident)* $(: $col_ty:ty)*, $($tail:tt)*) => {
+        scan_rules_impl!(@with_bindings.repeat $i, $names, $cb; ($($zname)*), ($($pat)*), $(let $cname: usize,)* $($tail)*)
     };
 
-    (@with_bindings.step $i:tt, $names:tt, $cb:tt; [$($pat:tt)*],* $(: $col_ty:ty)*, $($tail:tt)*) => {
-        scan_rules_impl!(@with_bindings.step $i, $names, $cb; $($pat)*, $($tail)*)
+    (@with_bindings.step $i:tt, $names:tt, $cb:tt; [$($pat:tt)*]+ $(binding $zname:ident = $_ze:tt)* $(as $cname:ident)* $(: $col_ty:ty)*, $($tail:tt)*) => {
+        scan_rules_impl!(@with_bindings.repeat $i, $names, $cb; ($($zname)*), ($($pat)*), $(let $cname: usize,)* $($tail)*)
     };
 
-    (@with_bindings.step $i:tt, $names:tt, $cb:tt; [$($pat:tt)*],+ $(: $col_ty:ty)*, $($tail:tt)*) => {
-        scan_rules_impl!(@with_bindings.step $i, $names, $cb; $($pat)*, $($tail)*)
+    (@with_bindings.step $i:tt, $names:tt, $cb:tt; [$($pat:tt)*]{$($_bounds:tt)*} $(binding $zname:ident = $_ze:tt)* $(as $cname:ident)* $(: $col_ty:ty)*, $($tail:tt)*) => {
+        scan_rules_impl!(@with_bindings.repeat $i, $names, $cb; ($($zname)*), ($($pat)*), $(let $cname: usize,)* $($tail)*)
+    };
+
+    (@with_bindings.step $i:tt, $names:tt, $cb:tt; [$($pat:tt)*],? $(binding $zname:ident = $_ze:tt)* $(as $cname:ident)* $(: $col_ty:ty)*, $($tail:tt)*) => {
+        scan_rules_impl!(@with_bindings.repeat $i, $names, $cb; ($($zname)*), ($($pat)*), $(let $cname: usize,)* $($tail)*)
+    };
+
+    (@with_bindings.step $i:tt, $names:tt, $cb:tt; [$($pat:tt)*],* $(binding $zname:ident = $_ze:tt)* $(as $cname:ident)* $(: $col_ty:ty)*, $($tail:tt)*) => {
+        scan_rules_impl!(@with_bindings.repeat $i, $names, $cb; ($($zname)*), ($($pat)*), $(let $cname: usize,)* $($tail)*)
+    };
+
+    (@with_bindings.step $i:tt, $names:tt, $cb:tt; [$($pat:tt)*],+ $(binding $zname:ident = $_ze:tt)* $(as $cname:ident)* $(: $col_ty:ty)*, $($tail:tt)*) => {
+        scan_rules_impl!(@with_bindings.repeat $i, $names, $cb; ($($zname)*), ($($pat)*), $(let $cname: usize,)* $($tail)*)
     };
 
-    (@with_bindings.step $i:tt, $names:tt, $cb:tt; [$($pat:tt)*],{$($_bounds:tt)*} $(: $col_ty:ty)*, $($tail:tt)*) => {
+    (@with_bindings.step $i:tt, $names:tt, $cb:tt; [$($pat:tt)*],{$($_bounds:tt)*} $(binding $zname:ident = $_ze:tt)* $(as $cname:ident)* $(: $col_ty:ty)*, $($tail:tt)*) => {
+        scan_rules_impl!(@with_bindings.repeat $i, $names, $cb; ($($zname)*), ($($pat)*), $(let $cname: usize,)* $($tail)*)
+    };
+
+    (@with_bindings.step $i:tt, $names:tt, $cb:tt; [$($pat:tt)*]($($sep:tt)*)? $(binding $zname:ident = $_ze:tt)* $(as $cname:ident)* $(: $col_ty:ty)*, $($tail:tt)*) => {
+        scan_rules_impl!(@with_bindings.repeat $i, $names, $cb; ($($zname)*), ($($pat)*), $($sep)*, $(let $cname: usize,)* $($tail)*)
+    };
+
+    (@with_bindings.step $i:tt, $names:tt, $cb:tt; [$($pat:tt)*]($($sep:tt)*)* $(binding $zname:ident = $_ze:tt)* $(as $cname:ident)* $(: $col_ty:ty)*, $($tail:tt)*) => {
+        scan_rules_impl!(@with_bindings.repeat $i, $names, $cb; ($($zname)*), ($($pat)*), $($sep)*, $(let $cname: usize,)* $($tail)*)
+    };
+
+    (@with_bindings.step $i:tt, $names:tt, $cb:tt; [$($pat:tt)*]($($sep:tt)*)+ $(binding $zname:ident = $_ze:tt)* $(as $cname:ident)* $(: $col_ty:ty)*, $($tail:tt)*) => {
+        scan_rules_impl!(@with_bindings.repeat $i, $names, $cb; ($($zname)*), ($($pat)*), $($sep)*, $(let $cname: usize,)* $($tail)*)
+    };
+
+    (@with_bindings.step $i:tt, $names:tt, $cb:tt; [$($pat:tt)*]($($sep:tt)*){$($_bounds:tt)*} $(binding $zname:ident = $_ze:tt)* $(as $cname:ident)* $(: $col_ty:ty)*, $($tail:tt)*) => {
+        scan_rules_impl!(@with_bindings.repeat $i, $names, $cb; ($($zname)*), ($($pat)*), $($sep)*, $(let $cname: usize,)* $($tail)*)
+    };
+
+    (@with_bindings.step $i:tt, $names:tt, $cb:tt; [$($pat:tt)*], $_count:tt t $(binding $zname:ident = $_ze:tt)* $(as $cname:ident)* $(: $col_ty:ty)*, $($tail:tt)*) => {
+        scan_rules_impl!(@with_bindings.repeat $i, $names, $cb; ($($zname)*), ($($pat)*), $(let $cname: usize,)* $($tail)*)
+    };
+
+    (@with_bindings.step $i:tt, $names:tt, $cb:tt; [$($pat:tt)*]($($sep:tt)*) $_count:tt t $(binding $zname:ident = $_ze:tt)* $(as $cname:ident)* $(: $col_ty:ty)*, $($tail:tt)*) => {
+        scan_rules_impl!(@with_bindings.repeat $i, $names, $cb; ($($zname)*), ($($pat)*), $($sep)*, $(let $cname: usize,)* $($tail)*)
+    };
+
+    (@with_bindings.step $i:tt, $names:tt, $cb:tt; [$($pat:tt)*] $_count:tt t $(binding $zname:ident = $_ze:tt)* $(as $cname:ident)* $(: $col_ty:ty)*, $($tail:tt)*) => {
+        scan_rules_impl!(@with_bindings.repeat $i, $names, $cb; ($($zname)*), ($($pat)*), $(let $cname: usize,)* $($tail)*)
+    };
+
+    (@with_bindings.step $i:tt, $names:tt, $cb:tt; [$($pat:tt)*] $($tail:tt)*) => {
+        scan_rules_impl!(@error "repetition must be followed by `?`, `*`, `+` or `{..}`, optionally preceded by a separator")
+    };
+
+    /*
+    A repetition exposes the bindings of its repeating pattern, unless it has a `binding` clause, in which case it exposes the clause's name instead.  Either way, the bindings of the separator pattern (if any) have been moved into the tail.
+    */
+    (@with_bindings.repeat $i:tt, $names:tt, $cb:tt; (), ($($pat:tt)*), $($tail:tt)*) => {
         scan_rules_impl!(@with_bindings.step $i, $names, $cb; $($pat)*, $($tail)*)
     };
 
-    (@with_bindings.step $i:tt, $names:tt, $cb:tt; [$($pat:tt)*]($($sep:tt)*)? $(: $col_ty:ty)*, $($tail:tt)*) => {
-        scan_rules_impl!(@with_bindings.step $i, $names, $cb; $($pat)*, $($sep)*, $($tail)*)
+    (@with_bindings.repeat $i:tt, ($($names:tt)*), $cb:tt; ($zname:ident), ($($pat:tt)*), $($tail:tt)*) => {
+        scan_rules_impl!(@with_bindings.inc $i, ($($names)* ($zname, $i),), $cb; $($tail)*)
+    };
+
+    (@with_bindings.repeat $i:tt, $names:tt, $cb:tt; ($($znames:ident)*), ($($pat:tt)*), $($tail:tt)*) => {
+        scan_rules_impl!(@error "a repetition can have at most one `binding` clause")
+    };
+
+    (@with_bindings.step $i:tt, $names:tt, $cb:tt; @offset($_n:expr), $($tail:tt)*) => {
+        scan_rules_impl!(@with_bindings.step $i, $names, $cb; $($tail)*)
+    };
+
+    (@with_bindings.step $i:tt, $names:tt, $cb:tt; @column($_n:expr), $($tail:tt)*) => {
+        scan_rules_impl!(@with_bindings.step $i, $names, $cb; $($tail)*)
+    };
+
+    (@with_bindings.step $i:tt, $names:tt, $cb:tt; @re($_re:expr), $($tail:tt)*) => {
+        scan_rules_impl!(@with_bindings.step $i, $names, $cb; $($tail)*)
+    };
+
+    (@with_bindings.step $i:tt, $names:tt, $cb:tt; @$_rule:ty, $($tail:tt)*) => {
+        scan_rules_impl!(@with_bindings.step $i, $names, $cb; $($tail)*)
+    };
+
+    (@with_bindings.step $i:tt, $names:tt, $cb:tt; do |$_c:ident| $_e:expr, $($tail:tt)*) => {
+        scan_rules_impl!(@with_bindings.step $i, $names, $cb; $($tail)*)
+    };
+
+    (@with_bindings.step $i:tt, $names:tt, $cb:tt; ..$_name:ident trimmed $(at $_pos:ident)*, $($tail:tt)*) => {
+        scan_rules_impl!(@with_bindings.step $i, $names, $cb; $($tail)*)
+    };
+
+    (@with_bindings.step $i:tt, $names:tt, $cb:tt; ..$_name:ident at $_pos:ident, $($tail:tt)*) => {
+        scan_rules_impl!(@with_bindings.step $i, $names, $cb; $($tail)*)
     };
 
-    (@with_bindings.step $i:tt, $names:tt, $cb:tt; [$($pat:tt)*]($($sep:tt)*)* $(: $col_ty:ty)*, $($tail:tt)*) => {
-        scan_rules_impl!(@with_bindings.step $i, $names, $cb; $($pat)*, $($sep)*, $($tail)*)
+    (@with_bindings.step $i:tt, $names:tt, $cb:tt; ..., $($tail:tt)*) => {
+        scan_rules_impl!(@with_bindings.step $i, $names, $cb; $($tail)*)
     };
 
-    (@with_bindings.step $i:tt, $names:tt, $cb:tt; [$($pat:tt)*]($($sep:tt)*)+ $(: $col_ty:ty)*, $($tail:tt)*) => {
-        scan_rules_impl!(@with_bindings.step $i, $names, $cb; $($pat)*, $($sep)*, $($tail)*)
+    (@with_bindings.step $i:tt, $names:tt, $cb:tt; raw $_lit:expr, $($tail:tt)*) => {
+        scan_rules_impl!(@with_bindings.step $i, $names, $cb; $($tail)*)
     };
 
-    (@with_bindings.step $i:tt, $names:tt, $cb:tt; [$($pat:tt)*]($($sep:tt)*){$($_bounds:tt)*} $(: $col_ty:ty)*, $($tail:tt)*) => {
-        scan_rules_impl!(@with_bindings.step $i, $names, $cb; $($pat)*, $($sep)*, $($tail)*)
+    (@with_bindings.step $i:tt, $names:tt, $cb:tt; = $_name:ident, $($tail:tt)*) => {
+        scan_rules_impl!(@with_bindings.step $i, $names, $cb; $($tail)*)
     };
 
     (@with_bindings.step $i:tt, $names:tt, $cb:tt; $_lit:expr, $($tail:tt)*) => {
         scan_rules_impl!(@with_bindings.step $i, $names, $cb; $($tail)*)
     };
 
+    (@with_bindings.step $i:tt, $names:tt, $cb:tt; , $($tail:tt)*) => {
+        scan_rules_impl!(@error "empty term in pattern; check for a doubled or trailing comma")
+    };
+
+    (@with_bindings.step $i:tt, $names:tt, $cb:tt; $($tail:tt)*) => {
+        scan_rules_impl!(@error "expected a literal, `let` binding, repetition, `@rule`, `do |cursor| expr`, `..name`, `^..name` or `...`; terms must be separated by commas")
+    };
+
     /*
     ## `.inc`
 
@@ -742,6 +1881,13 @@ macro_rules! scan_rules_impl {
     (@as_expr $e:expr) => {$e};
     (@as_stmt $s:stmt) => {$s;};
 
+    /*
+    Emit a compile error with the given message.
+    */
+    (@error $msg:expr) => {
+        scan_rules_error!($msg)
+    };
+
     (@if_empty.expr () {$($th:tt)*} else {$($_el:tt)*}) => {
         scan_rules_impl!(@as_expr $($th)*)
     };
@@ -750,3 +1896,20 @@ macro_rules! scan_rules_impl {
         scan_rules_impl!(@as_expr $($el)*)
     };
 }
+
+/*
+Used to report errors in patterns.  On compilers which support it, this just forwards to `compile_error!`; on older compilers, it relies on the message being rejected as input, which at least gets the message in front of the user.
+*/
+#[cfg(compile_error_macro)]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! scan_rules_error {
+    ($msg:expr) => { compile_error!($msg) };
+}
+
+#[cfg(not(compile_error_macro))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! scan_rules_error {
+    () => {};
+}
@@ -0,0 +1,38 @@
+/*
+Copyright ⓒ 2016 Daniel Keep.
+
+Licensed under the MIT license (see LICENSE or <http://opensource.org
+/licenses/MIT>) or the Apache License, Version 2.0 (see LICENSE of
+<http://www.apache.org/licenses/LICENSE-2.0>), at your option. All
+files in the project carrying such notice may not be copied, modified,
+or distributed except according to those terms.
+*/
+/*!
+Byte-level helpers shared by the line format scanners, such as those in `http`, `logformats`, and `rustdev`.
+*/
+use ::ScanError;
+
+/**
+Is this byte a space or tab?
+*/
+pub fn is_space(b: u8) -> bool {
+    b == b' ' || b == b'\t'
+}
+
+/**
+Skips any spaces or tabs starting at `i`, returning the position after them.
+*/
+pub fn skip_space(bs: &[u8], i: usize) -> usize {
+    i + bs[i..].iter().take_while(|&&b| is_space(b)).count()
+}
+
+/**
+Skips at least one space or tab starting at `i`, failing if there are none.
+*/
+#[cfg(any(feature="http", feature="logformats"))]
+pub fn expect_space(bs: &[u8], i: usize) -> Result<usize, ScanError> {
+    match skip_space(bs, i) {
+        j if j == i => Err(ScanError::syntax("expected space").add_offset(i)),
+        j => Ok(j),
+    }
+}
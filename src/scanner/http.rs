@@ -0,0 +1,284 @@
+/*
+Copyright ⓒ 2016 Daniel Keep.
+
+Licensed under the MIT license (see LICENSE or <http://opensource.org
+/licenses/MIT>) or the Apache License, Version 2.0 (see LICENSE of
+<http://www.apache.org/licenses/LICENSE-2.0>), at your option. All
+files in the project carrying such notice may not be copied, modified,
+or distributed except according to those terms.
+*/
+/*!
+Scanners for the start line and header fields of HTTP/1.x messages.
+
+These follow [RFC 7230](https://tools.ietf.org/html/rfc7230), but are somewhat more tolerant than it requires: any run of spaces or tabs is accepted wherever a single space is expected, and the minor version number may be omitted (*e.g.* `HTTP/2`).
+
+None of these scanners consume the line break at the end of the line.
+
+**Note**: requires the `http` feature.
+
+## Examples
+
+```rust
+# #[macro_use] extern crate scan_rules;
+# use scan_rules::scanner::http::{HeaderLine, RequestLine};
+# fn main() {
+let req = "GET /index.html HTTP/1.1\r\nHost: example.com\r\nAccept: text/html,\r\n  text/plain\r\n";
+let r = scan!(req;
+    (let line: RequestLine, [let headers: HeaderLine]*) => (line, headers)
+);
+let (line, headers) = r.unwrap();
+assert_eq!(line, ("GET", "/index.html", (1, 1)));
+assert_eq!(headers[0].0, "Host");
+assert_eq!(headers[1].1, "text/html, text/plain");
+# }
+```
+*/
+use std::borrow::Cow;
+use ::ScanError;
+use ::input::ScanInput;
+use ::scanner::bytes::{expect_space, is_space, skip_space};
+use ::scanner::ScanFromStr;
+
+/**
+Scans an HTTP request line, such as `GET /index.html HTTP/1.1`.
+
+The output is a tuple of the method, the request target, and the `(major, minor)` protocol version.  The method must be a token; the target is taken as-is, up to the next space.
+*/
+pub enum RequestLine {}
+
+impl<'a> ScanFromStr<'a> for RequestLine {
+    type Output = (&'a str, &'a str, (u32, u32));
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        let bs = s.as_bytes();
+
+        let method_end = token_len(bs);
+        if method_end == 0 {
+            return Err(ScanError::syntax("expected HTTP method"));
+        }
+        let method = &s[..method_end];
+
+        let i = try!(expect_space(bs, method_end));
+        let target_len = bs[i..].iter().take_while(|&&b| !is_space(b) && b != b'\r' && b != b'\n').count();
+        if target_len == 0 {
+            return Err(ScanError::syntax("expected HTTP request target").add_offset(i));
+        }
+        let target = &s[i..i + target_len];
+
+        let i = try!(expect_space(bs, i + target_len));
+        let (version, i) = try!(scan_version(bs, i));
+        let i = skip_space(bs, i);
+        match bs.get(i) {
+            None | Some(&b'\r') | Some(&b'\n') => (),
+            Some(_) => return Err(ScanError::syntax("expected end of HTTP request line").add_offset(i)),
+        }
+
+        Ok(((method, target, version), i))
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_request_line() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+
+    let scan = RequestLine::scan_from;
+
+    assert_match!(scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("GET"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("GET /"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("GET / HTTP/1.1"), Ok((("GET", "/", (1, 1)), 14)));
+    assert_match!(scan("GET / HTTP/1.1\r\nHost: x"), Ok((("GET", "/", (1, 1)), 14)));
+    assert_match!(scan("POST\t/a?b=c   HTTP/1.0 \n"), Ok((("POST", "/a?b=c", (1, 0)), 23)));
+    assert_match!(scan("OPTIONS * HTTP/2"), Ok((("OPTIONS", "*", (2, 0)), 16)));
+    assert_match!(scan("GET / HTTP/1.1 x"), Err(SE { kind: SEK::Syntax(_), ref at, .. }) if at.offset() == 15);
+    assert_match!(scan("GET / FTP/1.1"), Err(SE { kind: SEK::Syntax(_), ref at, .. }) if at.offset() == 6);
+    assert_match!(scan("G(T / HTTP/1.1"), Err(SE { kind: SEK::Syntax(_), .. }));
+}
+
+/**
+Scans an HTTP status line, such as `HTTP/1.1 404 Not Found`.
+
+The output is a tuple of the `(major, minor)` protocol version, the status code, and the reason phrase.  The reason phrase runs to the end of the line, with trailing whitespace removed; it may be empty.
+*/
+pub enum StatusLine {}
+
+impl<'a> ScanFromStr<'a> for StatusLine {
+    type Output = ((u32, u32), u16, &'a str);
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        let bs = s.as_bytes();
+
+        let (version, i) = try!(scan_version(bs, 0));
+        let i = try!(expect_space(bs, i));
+
+        let code_len = bs[i..].iter().take_while(|&&b| b'0' <= b && b <= b'9').count();
+        if code_len != 3 {
+            return Err(ScanError::syntax("expected three digit HTTP status code").add_offset(i));
+        }
+        let code = bs[i..i + 3].iter().fold(0, |n, &b| n * 10 + (b - b'0') as u16);
+        let i = i + 3;
+
+        let line_end = i + ::fast::find_line_break(&bs[i..]).unwrap_or(bs.len() - i);
+        if i < line_end && !is_space(bs[i]) {
+            return Err(ScanError::syntax("expected space after HTTP status code").add_offset(i));
+        }
+        let reason = s[i..line_end].trim_matches(|c| c == ' ' || c == '\t');
+
+        Ok(((version, code, reason), line_end))
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_status_line() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+
+    let scan = StatusLine::scan_from;
+
+    assert_match!(scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("HTTP/1.1"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("HTTP/1.1 200 OK"), Ok((((1, 1), 200, "OK"), 15)));
+    assert_match!(scan("HTTP/1.1 404 Not Found \r\nServer: x"), Ok((((1, 1), 404, "Not Found"), 23)));
+    assert_match!(scan("HTTP/2 204"), Ok((((2, 0), 204, ""), 10)));
+    assert_match!(scan("HTTP/1.0 204 \n"), Ok((((1, 0), 204, ""), 13)));
+    assert_match!(scan("HTTP/1.1 20 OK"), Err(SE { kind: SEK::Syntax(_), ref at, .. }) if at.offset() == 9);
+    assert_match!(scan("HTTP/1.1 2000 OK"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("HTTP/1.1 200OK"), Err(SE { kind: SEK::Syntax(_), ref at, .. }) if at.offset() == 12);
+}
+
+/**
+Scans an HTTP header field, such as `Content-Type: text/html`.
+
+The output is a tuple of the field name and value.  The name must be a token, and must be followed immediately by a colon.  Whitespace around the value is removed.
+
+A value may be continued onto following lines, provided each continuation line starts with a space or tab (what RFC 7230 calls "obsolete line folding").  Each line break and the whitespace surrounding it is replaced with a single space, in which case the value is an owned string; otherwise, it borrows from the input.
+*/
+pub enum HeaderLine {}
+
+impl<'a> ScanFromStr<'a> for HeaderLine {
+    type Output = (&'a str, Cow<'a, str>);
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        let bs = s.as_bytes();
+
+        let name_end = token_len(bs);
+        if name_end == 0 {
+            return Err(ScanError::syntax("expected HTTP header name"));
+        }
+        if bs.get(name_end) != Some(&b':') {
+            return Err(ScanError::syntax("expected `:` after HTTP header name").add_offset(name_end));
+        }
+        let name = &s[..name_end];
+
+        let (first, mut end) = value_line(s, name_end + 1);
+        let mut value = Cow::Borrowed(first);
+
+        loop {
+            let next = match &bs[end..] {
+                rest if rest.starts_with(b"\r\n") => end + 2,
+                rest if rest.starts_with(b"\r") || rest.starts_with(b"\n") => end + 1,
+                _ => break,
+            };
+            match bs.get(next) {
+                Some(&b) if is_space(b) => (),
+                _ => break,
+            }
+
+            let (more, more_end) = value_line(s, next);
+            end = more_end;
+            if more.is_empty() {
+                continue;
+            }
+            if value.is_empty() {
+                value = Cow::Borrowed(more);
+            } else {
+                let v = value.to_mut();
+                v.push(' ');
+                v.push_str(more);
+            }
+        }
+
+        Ok(((name, value), end))
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_header_line() {
+    use std::borrow::Cow;
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+
+    let scan = HeaderLine::scan_from;
+
+    assert_match!(scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("Host"), Err(SE { kind: SEK::Syntax(_), ref at, .. }) if at.offset() == 4);
+    assert_match!(scan("Host : x"), Err(SE { kind: SEK::Syntax(_), ref at, .. }) if at.offset() == 4);
+    assert_match!(scan("Host: example.com"), Ok((("Host", Cow::Borrowed("example.com")), 17)));
+    assert_match!(scan("Host:example.com  \r\nAccept: */*"), Ok((("Host", Cow::Borrowed("example.com")), 18)));
+    assert_match!(scan("X-Empty:\r\n"), Ok((("X-Empty", Cow::Borrowed("")), 8)));
+    assert_match!(scan("X-Empty: \t"), Ok((("X-Empty", Cow::Borrowed("")), 10)));
+
+    assert_match!(scan("Accept: text/html,\r\n  text/plain\r\nHost: x"),
+        Ok((("Accept", Cow::Owned(ref v)), 32)) if v == "text/html, text/plain");
+    assert_match!(scan("Accept: a,\n\tb,\n c\n"),
+        Ok((("Accept", Cow::Owned(ref v)), 17)) if v == "a, b, c");
+    assert_match!(scan("Accept:\r\n text/plain"),
+        Ok((("Accept", Cow::Borrowed("text/plain")), 20)));
+    assert_match!(scan("Accept: a\r\n \r\nHost: x"),
+        Ok((("Accept", Cow::Borrowed("a")), 12)));
+}
+
+/**
+Is this byte a `tchar`, as allowed in HTTP methods and header names?
+*/
+fn is_tchar(b: u8) -> bool {
+    match b {
+        b'a'...b'z' | b'A'...b'Z' | b'0'...b'9'
+        | b'!' | b'#' | b'$' | b'%' | b'&' | b'\'' | b'*' | b'+'
+        | b'-' | b'.' | b'^' | b'_' | b'`' | b'|' | b'~' => true,
+        _ => false,
+    }
+}
+
+fn token_len(bs: &[u8]) -> usize {
+    bs.iter().take_while(|&&b| is_tchar(b)).count()
+}
+
+/**
+Scans an HTTP version, such as `HTTP/1.1`, starting at `i`.
+*/
+fn scan_version(bs: &[u8], i: usize) -> Result<((u32, u32), usize), ScanError> {
+    fn digit(b: Option<&u8>) -> Option<u32> {
+        match b {
+            Some(&b) if b'0' <= b && b <= b'9' => Some((b - b'0') as u32),
+            _ => None,
+        }
+    }
+
+    if !bs[i..].starts_with(b"HTTP/") {
+        return Err(ScanError::syntax("expected HTTP version").add_offset(i));
+    }
+    let major = match digit(bs.get(i + 5)) {
+        Some(d) => d,
+        None => return Err(ScanError::syntax("expected HTTP major version").add_offset(i + 5)),
+    };
+    if bs.get(i + 6) != Some(&b'.') {
+        return Ok(((major, 0), i + 6));
+    }
+    match digit(bs.get(i + 7)) {
+        Some(minor) => Ok(((major, minor), i + 8)),
+        None => Err(ScanError::syntax("expected HTTP minor version").add_offset(i + 7)),
+    }
+}
+
+/**
+Returns the part of the line starting at `i` with surrounding whitespace removed, and the offset of the end of the line.
+*/
+fn value_line(s: &str, i: usize) -> (&str, usize) {
+    let end = i + ::fast::find_line_break(&s.as_bytes()[i..]).unwrap_or(s.len() - i);
+    (s[i..end].trim_matches(|c| c == ' ' || c == '\t'), end)
+}
@@ -10,11 +10,10 @@ or distributed except according to those terms.
 /*!
 Implementations of `ScanFromStr` for primitive language types.
 */
-use itertools::Itertools;
 use strcursor::StrCursor;
 use ::ScanError;
 use ::input::ScanInput;
-use super::ScanFromStr;
+use super::{ScanFromHex, ScanFromStr};
 use super::misc::Word;
 
 parse_scanner! { impl<'a> for bool, from Word, err desc "expected `true` or `false`" }
@@ -67,83 +66,83 @@ parse_scanner! { impl<'a> for f32, matcher match_float, matcher err "expected fl
 parse_scanner! { impl<'a> for f64, matcher match_float, matcher err "expected floating point number", err map ScanError::float }
 
 fn match_float(s: &str) -> Option<((usize, usize), usize)> {
-    use std::iter::Peekable;
+    match_float_with(s, false)
+}
 
-    // First, check for one of the named constants.
-    if s.starts_with("inf") {
-        if s[3..].chars().next().map(|c| !c.is_alphabetic()).unwrap_or(true) {
-            return Some(((0, 3), 3));
-        }
-    }
+/**
+Matches a floating point number, as accepted by `f64::from_str`.
 
-    if s.starts_with("-inf") {
-        if s[4..].chars().next().map(|c| !c.is_alphabetic()).unwrap_or(true) {
-            return Some(((0, 4), 4));
-        }
-    }
+If `underscores` is set, `_` is also permitted anywhere after the first digit of each run of digits, as in Rust literals.  The caller is responsible for removing them before parsing.
+*/
+pub fn match_float_with(s: &str, underscores: bool) -> Option<((usize, usize), usize)> {
+    match_float_point(s, underscores, '.')
+}
+
+/**
+Matches a floating point number as `match_float_with` does, except that `point` is used as the decimal point.
+
+A decimal point other than `.` must be followed by a digit, so that "1, 2" is not mistaken for "1." followed by "2".
+*/
+pub fn match_float_point(s: &str, underscores: bool, point: char) -> Option<((usize, usize), usize)> {
+    let bs = s.as_bytes();
+
+    let sign = match bs.first() {
+        Some(&b'-') | Some(&b'+') => 1,
+        _ => 0
+    };
 
-    if s.starts_with("NaN") {
-        if s[3..].chars().next().map(|c| !c.is_alphabetic()).unwrap_or(true) {
-            return Some(((0, 3), 3));
+    // First, check for one of the named constants.
+    for name in &["infinity", "inf", "NaN"] {
+        let end = sign + name.len();
+        if s[sign..].starts_with(name)
+            && s[end..].chars().next().map(|c| !c.is_alphabetic()).unwrap_or(true)
+        {
+            return Some(((0, end), end));
         }
     }
 
     // Ok, try scanning an actual number.
-    let mut ibs = s.bytes().enumerate().peekable();
+    let is_digit = |i: usize| i < bs.len() && matches!(bs[i], b'0'...b'9');
+    let skip_digits = |mut i: usize| {
+        while i < bs.len() && (matches!(bs[i], b'0'...b'9') || (underscores && bs[i] == b'_')) {
+            i += 1;
+        }
+        i
+    };
 
-    match ibs.peek().map(|&(_, b)| b) {
-        Some(b'-') | Some(b'+') => { ibs.next(); },
-        _ => ()
+    // Leading integer part; this can only be omitted if there's a fractional part.
+    let mut end = sign;
+    if is_digit(end) {
+        end = skip_digits(end);
     }
-
-    // Skip over leading integer part.
-    let int_end = ibs
-        .take_while_ref(|&(_, b)| matches!(b, b'0'...b'9'))
-        .last()
-        .map(|(i, _)| i + 1)
-        .map(|n| ((0, n), n));
-
-    if let None = int_end {
+    let has_int = end > sign;
+
+    if s[end..].starts_with(point) {
+        let frac = end + point.len_utf8();
+        if is_digit(frac) {
+            end = skip_digits(frac);
+        } else if has_int && point == '.' {
+            end = frac;
+        } else if !has_int {
+            return None;
+        }
+    } else if !has_int {
         return None;
     }
 
-    // At this point, we get a decimal point, an "e", or the end of input.
-    fn match_exp<I: Iterator<Item=(usize, u8)>>(mut ibs: Peekable<I>)
-    -> Option<((usize, usize), usize)> {
-
-        match ibs.peek().map(|&(_, b)| b) {
-            Some(b'-') | Some(b'+') => { ibs.next(); },
-            _ => ()
+    // Finally, there *might* be an exponent.
+    if end < bs.len() && (bs[end] == b'e' || bs[end] == b'E') {
+        let mut exp = end + 1;
+        if exp < bs.len() && (bs[exp] == b'-' || bs[exp] == b'+') {
+            exp += 1;
         }
-
-        ibs.take_while(|&(_, b)| matches!(b, b'0'...b'9'))
-            .last()
-            .map(|(i, _)| i + 1)
-            .map(|n| ((0, n), n))
+        if !is_digit(exp) {
+            return None;
+        }
+        end = skip_digits(exp);
     }
 
-    match ibs.next() {
-        Some((i, b'.')) => {
-            // There *might* be another sequence of digits.
-            let end = (&mut ibs)
-                .take_while_ref(|&(_, b)| matches!(b, b'0'...b'9'))
-                .map(|(i, _)| i + 1)
-                .last()
-                .unwrap_or(i + 1);
-            
-            // Finally, there *might* be an exponent
-            match ibs.next().map(|(_, b)| b) {
-                Some(b'e') | Some(b'E') => {
-                    match_exp(ibs)
-                },
-                _ => Some(((0, end), end))
-            }
-        },
-
-        Some((_, b'e')) | Some((_, b'E')) => match_exp(ibs),
-
-        _ => int_end
-    }
+    Some(((0, end), end))
 }
 
 #[cfg(test)]
@@ -175,9 +174,19 @@ fn test_scan_f64() {
     assert_match!(<f64>::scan_from("0x"), Ok((0.0, 1)));
     assert_match!(<f64>::scan_from("0."), Ok((0.0, 2)));
     assert_match!(<f64>::scan_from("0.x"), Ok((0.0, 2)));
+    assert_match!(<f64>::scan_from("3"), Ok((3.0, 1)));
+    assert_match!(<f64>::scan_from("+3"), Ok((3.0, 2)));
+    assert_match!(<f64>::scan_from(".5"), Ok((0.5, 2)));
+    assert_match!(<f64>::scan_from("-.5e1"), Ok((-5.0, 5)));
+    assert_match!(<f64>::scan_from("2e3"), Ok((2000.0, 3)));
+    assert_match!(<f64>::scan_from("."), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(<f64>::scan_from("1e"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(<f64>::scan_from("1_000.0"), Ok((1.0, 1)));
 
     assert_match!(<f64>::scan_from("inf"), Ok((f, 3)) if f == ::std::f64::INFINITY);
     assert_match!(<f64>::scan_from("-inf"), Ok((f, 4)) if f == ::std::f64::NEG_INFINITY);
+    assert_match!(<f64>::scan_from("+infinity"), Ok((f, 9)) if f == ::std::f64::INFINITY);
+    assert_match!(<f64>::scan_from("info"), Err(SE { kind: SEK::Syntax(_), .. }));
     assert_match!(<f64>::scan_from("NaN"), Ok((v, 3)) if v.is_nan());
 
     check_f64!(0.0);
@@ -225,29 +234,246 @@ fn test_scan_f64_debug_is_roundtrip_accurate() {
     check_f64!(4.9406564584124654e-324);
 }
 
-parse_scanner! { impl<'a> for i8, matcher match_sinteger, matcher err "expected integer", err map ScanError::int }
-parse_scanner! { impl<'a> for i16, matcher match_sinteger, matcher err "expected integer", err map ScanError::int }
-parse_scanner! { impl<'a> for i32, matcher match_sinteger, matcher err "expected integer", err map ScanError::int }
-parse_scanner! { impl<'a> for i64, matcher match_sinteger, matcher err "expected integer", err map ScanError::int }
-parse_scanner! { impl<'a> for isize, matcher match_sinteger, matcher err "expected integer", err map ScanError::int }
-
-parse_scanner! { impl<'a> ScanFromBinary::scan_from_binary for i8, matcher match_bin_int, matcher err "expected binary integer", map |s| i8::from_str_radix(s, 2), err map ScanError::int }
-parse_scanner! { impl<'a> ScanFromBinary::scan_from_binary for i16, matcher match_bin_int, matcher err "expected binary integer", map |s| i16::from_str_radix(s, 2), err map ScanError::int }
-parse_scanner! { impl<'a> ScanFromBinary::scan_from_binary for i32, matcher match_bin_int, matcher err "expected binary integer", map |s| i32::from_str_radix(s, 2), err map ScanError::int }
-parse_scanner! { impl<'a> ScanFromBinary::scan_from_binary for i64, matcher match_bin_int, matcher err "expected binary integer", map |s| i64::from_str_radix(s, 2), err map ScanError::int }
-parse_scanner! { impl<'a> ScanFromBinary::scan_from_binary for isize, matcher match_bin_int, matcher err "expected binary integer", map |s| isize::from_str_radix(s, 2), err map ScanError::int }
-
-parse_scanner! { impl<'a> ScanFromOctal::scan_from_octal for i8, matcher match_oct_int, matcher err "expected octal integer", map |s| i8::from_str_radix(s, 8), err map ScanError::int }
-parse_scanner! { impl<'a> ScanFromOctal::scan_from_octal for i16, matcher match_oct_int, matcher err "expected octal integer", map |s| i16::from_str_radix(s, 8), err map ScanError::int }
-parse_scanner! { impl<'a> ScanFromOctal::scan_from_octal for i32, matcher match_oct_int, matcher err "expected octal integer", map |s| i32::from_str_radix(s, 8), err map ScanError::int }
-parse_scanner! { impl<'a> ScanFromOctal::scan_from_octal for i64, matcher match_oct_int, matcher err "expected octal integer", map |s| i64::from_str_radix(s, 8), err map ScanError::int }
-parse_scanner! { impl<'a> ScanFromOctal::scan_from_octal for isize, matcher match_oct_int, matcher err "expected octal integer", map |s| isize::from_str_radix(s, 8), err map ScanError::int }
-
-parse_scanner! { impl<'a> ScanFromHex::scan_from_hex for i8, matcher match_hex_int, matcher err "expected hex integer", map |s| i8::from_str_radix(s, 16), err map ScanError::int }
-parse_scanner! { impl<'a> ScanFromHex::scan_from_hex for i16, matcher match_hex_int, matcher err "expected hex integer", map |s| i16::from_str_radix(s, 16), err map ScanError::int }
-parse_scanner! { impl<'a> ScanFromHex::scan_from_hex for i32, matcher match_hex_int, matcher err "expected hex integer", map |s| i32::from_str_radix(s, 16), err map ScanError::int }
-parse_scanner! { impl<'a> ScanFromHex::scan_from_hex for i64, matcher match_hex_int, matcher err "expected hex integer", map |s| i64::from_str_radix(s, 16), err map ScanError::int }
-parse_scanner! { impl<'a> ScanFromHex::scan_from_hex for isize, matcher match_hex_int, matcher err "expected hex integer", map |s| isize::from_str_radix(s, 16), err map ScanError::int }
+impl<'a> ScanFromHex<'a> for f32 {
+    fn scan_from_hex<I: ScanInput<'a>>(s: I) -> Result<(Self, usize), ScanError> {
+        // The result is rounded to `f32` precision, so the conversion is exact.
+        scan_hex_float(s.as_str(), 24, -126, 127).map(|(v, n)| (v as f32, n))
+    }
+}
+
+impl<'a> ScanFromHex<'a> for f64 {
+    fn scan_from_hex<I: ScanInput<'a>>(s: I) -> Result<(Self, usize), ScanError> {
+        scan_hex_float(s.as_str(), 53, -1022, 1023)
+    }
+}
+
+/**
+Scans a C99-style hexadecimal floating point number, such as "-0x1.8p+3", correctly rounded to a float with `prec` bits of precision and an exponent between `min_exp` and `max_exp`.
+
+The `0x` prefix and the binary exponent are both optional.  The named constants accepted by `match_float` are also accepted, as is "nan".
+*/
+fn scan_hex_float(s: &str, prec: u32, min_exp: i64, max_exp: i64) -> Result<(f64, usize), ScanError> {
+    let bs = s.as_bytes();
+
+    let (neg, mut end) = match bs.first() {
+        Some(&b'-') => (true, 1),
+        Some(&b'+') => (false, 1),
+        _ => (false, 0)
+    };
+    let sign = if neg { -1.0 } else { 1.0 };
+
+    for &(name, v) in &[("infinity", ::std::f64::INFINITY), ("inf", ::std::f64::INFINITY), ("NaN", ::std::f64::NAN), ("nan", ::std::f64::NAN)] {
+        let name_end = end + name.len();
+        if s[end..].starts_with(name)
+            && s[name_end..].chars().next().map(|c| !c.is_alphabetic()).unwrap_or(true)
+        {
+            return Ok((sign * v, name_end));
+        }
+    }
+
+    if s[end..].starts_with("0x") || s[end..].starts_with("0X") {
+        // Only take the prefix if there's a number after it.
+        if bs.get(end + 2).map(|&b| hex_digit(b).is_some() || b == b'.').unwrap_or(false) {
+            end += 2;
+        }
+    }
+
+    /*
+    Accumulate the significant digits into `mant`, which holds at least 61 significant bits; anything further only matters for rounding, so it is folded into `sticky`.  The value is then `mant * 2^exp`.
+    */
+    let mut mant: u64 = 0;
+    let mut exp: i64 = 0;
+    let mut sticky = false;
+    let mut digits = 0;
+    let mut in_frac = false;
+
+    loop {
+        match bs.get(end) {
+            Some(&b'.') if !in_frac => in_frac = true,
+            Some(&b) => match hex_digit(b) {
+                Some(d) => {
+                    if mant < (1 << 60) {
+                        mant = mant * 16 + d as u64;
+                        if in_frac { exp -= 4; }
+                    } else {
+                        sticky |= d != 0;
+                        if !in_frac { exp += 4; }
+                    }
+                    digits += 1;
+                },
+                None => break,
+            },
+            None => break,
+        }
+        end += 1;
+    }
+
+    if digits == 0 {
+        return Err(ScanError::syntax("expected hex floating point number"));
+    }
+
+    // A lone trailing `.` is not part of the number.
+    if bs[end - 1] == b'.' && (end < 2 || hex_digit(bs[end - 2]).is_none()) {
+        end -= 1;
+    }
+
+    // The binary exponent, written in decimal.  Saturate rather than overflow; the result is zero or infinity either way.
+    if end < bs.len() && (bs[end] == b'p' || bs[end] == b'P') {
+        let mut i = end + 1;
+        let exp_neg = match bs.get(i) {
+            Some(&b'-') => { i += 1; true },
+            Some(&b'+') => { i += 1; false },
+            _ => false
+        };
+        let start = i;
+        let mut p: i64 = 0;
+        while i < bs.len() && matches!(bs[i], b'0'...b'9') {
+            p = ::std::cmp::min(p * 10 + (bs[i] - b'0') as i64, 1 << 20);
+            i += 1;
+        }
+        if i > start {
+            exp += if exp_neg { -p } else { p };
+            end = i;
+        }
+    }
+
+    if mant == 0 {
+        return Ok((sign * 0.0, end));
+    }
+
+    // Work out how many bits we can keep, taking subnormals into account, and round to nearest, ties to even.
+    let bits = 64 - mant.leading_zeros() as i64;
+    let top_exp = bits - 1 + exp;
+    let keep = if top_exp >= min_exp { prec as i64 } else { prec as i64 - (min_exp - top_exp) };
+    let shift = bits - keep;
+
+    if shift > 0 {
+        let (kept, half, rest) = if shift > 64 {
+            (0, false, true)
+        } else if shift == 64 {
+            (0, mant >> 63 == 1, mant << 1 != 0 || sticky)
+        } else {
+            let dropped = mant & ((1 << shift) - 1);
+            let half_bit = 1u64 << (shift - 1);
+            (mant >> shift, dropped & half_bit != 0, dropped & (half_bit - 1) != 0 || sticky)
+        };
+        mant = if half && (rest || kept & 1 == 1) { kept + 1 } else { kept };
+        exp += shift;
+    }
+
+    if mant == 0 {
+        return Ok((sign * 0.0, end));
+    }
+
+    let top_exp = 64 - mant.leading_zeros() as i64 - 1 + exp;
+    if top_exp > max_exp {
+        return Ok((sign * ::std::f64::INFINITY, end));
+    }
+
+    // `mant` now fits exactly, so scaling by powers of two is exact.
+    let mut v = mant as f64;
+    while exp > 1000 {
+        v *= 2f64.powi(1000);
+        exp -= 1000;
+    }
+    while exp < -1000 {
+        v *= 2f64.powi(-1000);
+        exp += 1000;
+    }
+    v *= 2f64.powi(exp as i32);
+
+    Ok((sign * v, end))
+}
+
+fn hex_digit(b: u8) -> Option<u8> {
+    match b {
+        b'0'...b'9' => Some(b - b'0'),
+        b'a'...b'f' => Some(b - b'a' + 10),
+        b'A'...b'F' => Some(b - b'A' + 10),
+        _ => None
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_scan_hex_float() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+    use ::scanner::ScanFromHex;
+    let scan = <f64>::scan_from_hex;
+
+    assert_match!(scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("x"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("."), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("-"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("0"), Ok((0.0, 1)));
+    assert_match!(scan("0x"), Ok((0.0, 1)));
+    assert_match!(scan("0x0p+0"), Ok((0.0, 6)));
+    assert_match!(scan("-0x0p+0"), Ok((v, 7)) if v == 0.0 && v.is_sign_negative());
+    assert_match!(scan("0x1p+0"), Ok((1.0, 6)));
+    assert_match!(scan("0x1.8p+1"), Ok((3.0, 8)));
+    assert_match!(scan("-0x1.8p1"), Ok((-3.0, 8)));
+    assert_match!(scan("1.8"), Ok((1.5, 3)));
+    assert_match!(scan("a"), Ok((10.0, 1)));
+    assert_match!(scan(".8"), Ok((0.5, 2)));
+    assert_match!(scan("1."), Ok((1.0, 2)));
+    assert_match!(scan("1p"), Ok((1.0, 1)));
+    assert_match!(scan("1p-x"), Ok((1.0, 1)));
+    assert_match!(scan("0x1p-2 x"), Ok((0.25, 6)));
+    assert_match!(scan("0X1P4"), Ok((16.0, 5)));
+    assert_match!(scan("inf"), Ok((v, 3)) if v == ::std::f64::INFINITY);
+    assert_match!(scan("-infinity"), Ok((v, 9)) if v == ::std::f64::NEG_INFINITY);
+    assert_match!(scan("nan"), Ok((v, 3)) if v.is_nan());
+
+    // Extremes.
+    assert_match!(scan("0x1.fffffffffffffp+1023"), Ok((v, 23)) if v == ::std::f64::MAX);
+    assert_match!(scan("0x1p+1024"), Ok((v, 9)) if v == ::std::f64::INFINITY);
+    assert_match!(scan("0x1p-1022"), Ok((v, 9)) if v == ::std::f64::MIN_POSITIVE);
+    assert_match!(scan("0x1p-1074"), Ok((v, 9)) if v == 5e-324);
+    assert_match!(scan("0x0.0000000000001p-1022"), Ok((v, 23)) if v == 5e-324);
+    assert_match!(scan("0x1p-1075"), Ok((0.0, 9)));
+    assert_match!(scan("0x1.0000000000001p-1075"), Ok((v, 23)) if v == 5e-324);
+    assert_match!(scan("0x1p-99999999999"), Ok((0.0, 16)));
+    assert_match!(scan("0x1p+99999999999"), Ok((v, 16)) if v == ::std::f64::INFINITY);
+
+    // Rounding to nearest, ties to even.
+    assert_match!(scan("0x1.00000000000008p+0"), Ok((1.0, 21)));
+    assert_match!(scan("0x1.00000000000018p+0"), Ok((v, 21)) if v == 1.0 + 2.0 * ::std::f64::EPSILON);
+    assert_match!(scan("0x1.000000000000080001p+0"), Ok((v, 25)) if v == 1.0 + ::std::f64::EPSILON);
+    assert_match!(scan("0x1.fffffffffffff8p+0"), Ok((2.0, 21)));
+    assert_match!(scan("0x10000000000000000000000001p-100"), Ok((1.0, 33)));
+
+    assert_match!(<f32>::scan_from_hex("0x1.8p+1"), Ok((3.0, 8)));
+    assert_match!(<f32>::scan_from_hex("0x1.fffffep+127"), Ok((v, 15)) if v == ::std::f32::MAX);
+    assert_match!(<f32>::scan_from_hex("0x1p+128"), Ok((v, 8)) if v == ::std::f32::INFINITY);
+    assert_match!(<f32>::scan_from_hex("0x1p-149"), Ok((v, 8)) if v == 1e-45);
+    assert_match!(<f32>::scan_from_hex("0x1.000001p+0"), Ok((1.0, 13)));
+    assert_match!(<f32>::scan_from_hex("0x1.000003p+0"), Ok((v, 13)) if v == 1.0 + 2.0 * ::std::f32::EPSILON);
+}
+
+parse_scanner! { impl<'a> for i8, matcher match_sinteger, matcher err "expected integer", err map |_| ScanError::overflow("i8") }
+parse_scanner! { impl<'a> for i16, matcher match_sinteger, matcher err "expected integer", err map |_| ScanError::overflow("i16") }
+parse_scanner! { impl<'a> for i32, matcher match_sinteger, matcher err "expected integer", err map |_| ScanError::overflow("i32") }
+parse_scanner! { impl<'a> for i64, matcher match_sinteger, matcher err "expected integer", err map |_| ScanError::overflow("i64") }
+parse_scanner! { impl<'a> for isize, matcher match_sinteger, matcher err "expected integer", err map |_| ScanError::overflow("isize") }
+
+parse_scanner! { impl<'a> ScanFromBinary::scan_from_binary for i8, matcher match_bin_int, matcher err "expected binary integer", map |s| i8::from_str_radix(s, 2), err map |_| ScanError::overflow("i8") }
+parse_scanner! { impl<'a> ScanFromBinary::scan_from_binary for i16, matcher match_bin_int, matcher err "expected binary integer", map |s| i16::from_str_radix(s, 2), err map |_| ScanError::overflow("i16") }
+parse_scanner! { impl<'a> ScanFromBinary::scan_from_binary for i32, matcher match_bin_int, matcher err "expected binary integer", map |s| i32::from_str_radix(s, 2), err map |_| ScanError::overflow("i32") }
+parse_scanner! { impl<'a> ScanFromBinary::scan_from_binary for i64, matcher match_bin_int, matcher err "expected binary integer", map |s| i64::from_str_radix(s, 2), err map |_| ScanError::overflow("i64") }
+parse_scanner! { impl<'a> ScanFromBinary::scan_from_binary for isize, matcher match_bin_int, matcher err "expected binary integer", map |s| isize::from_str_radix(s, 2), err map |_| ScanError::overflow("isize") }
+
+parse_scanner! { impl<'a> ScanFromOctal::scan_from_octal for i8, matcher match_oct_int, matcher err "expected octal integer", map |s| i8::from_str_radix(s, 8), err map |_| ScanError::overflow("i8") }
+parse_scanner! { impl<'a> ScanFromOctal::scan_from_octal for i16, matcher match_oct_int, matcher err "expected octal integer", map |s| i16::from_str_radix(s, 8), err map |_| ScanError::overflow("i16") }
+parse_scanner! { impl<'a> ScanFromOctal::scan_from_octal for i32, matcher match_oct_int, matcher err "expected octal integer", map |s| i32::from_str_radix(s, 8), err map |_| ScanError::overflow("i32") }
+parse_scanner! { impl<'a> ScanFromOctal::scan_from_octal for i64, matcher match_oct_int, matcher err "expected octal integer", map |s| i64::from_str_radix(s, 8), err map |_| ScanError::overflow("i64") }
+parse_scanner! { impl<'a> ScanFromOctal::scan_from_octal for isize, matcher match_oct_int, matcher err "expected octal integer", map |s| isize::from_str_radix(s, 8), err map |_| ScanError::overflow("isize") }
+
+parse_scanner! { impl<'a> ScanFromHex::scan_from_hex for i8, matcher match_hex_int, matcher err "expected hex integer", map |s| i8::from_str_radix(s, 16), err map |_| ScanError::overflow("i8") }
+parse_scanner! { impl<'a> ScanFromHex::scan_from_hex for i16, matcher match_hex_int, matcher err "expected hex integer", map |s| i16::from_str_radix(s, 16), err map |_| ScanError::overflow("i16") }
+parse_scanner! { impl<'a> ScanFromHex::scan_from_hex for i32, matcher match_hex_int, matcher err "expected hex integer", map |s| i32::from_str_radix(s, 16), err map |_| ScanError::overflow("i32") }
+parse_scanner! { impl<'a> ScanFromHex::scan_from_hex for i64, matcher match_hex_int, matcher err "expected hex integer", map |s| i64::from_str_radix(s, 16), err map |_| ScanError::overflow("i64") }
+parse_scanner! { impl<'a> ScanFromHex::scan_from_hex for isize, matcher match_hex_int, matcher err "expected hex integer", map |s| isize::from_str_radix(s, 16), err map |_| ScanError::overflow("isize") }
 
 #[cfg(test)]
 #[test]
@@ -265,31 +491,33 @@ fn test_scan_i32() {
     assert_match!(<i32>::scan_from("42"), Ok((42, 2)));
     assert_match!(<i32>::scan_from("-312"), Ok((-312, 4)));
     assert_match!(<i32>::scan_from("1_234"), Ok((1, 1)));
+    assert_match!(<i32>::scan_from("-2147483649"), Err(SE { kind: SEK::Overflow("i32"), .. }));
+    assert_match!(<i8>::scan_from("128"), Err(SE { kind: SEK::Overflow("i8"), .. }));
 }
 
-parse_scanner! { impl<'a> for u8, matcher match_uinteger, matcher err "expected integer", err map ScanError::int }
-parse_scanner! { impl<'a> for u16, matcher match_uinteger, matcher err "expected integer", err map ScanError::int }
-parse_scanner! { impl<'a> for u32, matcher match_uinteger, matcher err "expected integer", err map ScanError::int }
-parse_scanner! { impl<'a> for u64, matcher match_uinteger, matcher err "expected integer", err map ScanError::int }
-parse_scanner! { impl<'a> for usize, matcher match_uinteger, matcher err "expected integer", err map ScanError::int }
-
-parse_scanner! { impl<'a> ScanFromBinary::scan_from_binary for u8, matcher match_bin_int, matcher err "expected binary integer", map |s| u8::from_str_radix(s, 2), err map ScanError::int }
-parse_scanner! { impl<'a> ScanFromBinary::scan_from_binary for u16, matcher match_bin_int, matcher err "expected binary integer", map |s| u16::from_str_radix(s, 2), err map ScanError::int }
-parse_scanner! { impl<'a> ScanFromBinary::scan_from_binary for u32, matcher match_bin_int, matcher err "expected binary integer", map |s| u32::from_str_radix(s, 2), err map ScanError::int }
-parse_scanner! { impl<'a> ScanFromBinary::scan_from_binary for u64, matcher match_bin_int, matcher err "expected binary integer", map |s| u64::from_str_radix(s, 2), err map ScanError::int }
-parse_scanner! { impl<'a> ScanFromBinary::scan_from_binary for usize, matcher match_bin_int, matcher err "expected binary integer", map |s| usize::from_str_radix(s, 2), err map ScanError::int }
-
-parse_scanner! { impl<'a> ScanFromOctal::scan_from_octal for u8, matcher match_oct_int, matcher err "expected octal integer", map |s| u8::from_str_radix(s, 8), err map ScanError::int }
-parse_scanner! { impl<'a> ScanFromOctal::scan_from_octal for u16, matcher match_oct_int, matcher err "expected octal integer", map |s| u16::from_str_radix(s, 8), err map ScanError::int }
-parse_scanner! { impl<'a> ScanFromOctal::scan_from_octal for u32, matcher match_oct_int, matcher err "expected octal integer", map |s| u32::from_str_radix(s, 8), err map ScanError::int }
-parse_scanner! { impl<'a> ScanFromOctal::scan_from_octal for u64, matcher match_oct_int, matcher err "expected octal integer", map |s| u64::from_str_radix(s, 8), err map ScanError::int }
-parse_scanner! { impl<'a> ScanFromOctal::scan_from_octal for usize, matcher match_oct_int, matcher err "expected octal integer", map |s| usize::from_str_radix(s, 8), err map ScanError::int }
-
-parse_scanner! { impl<'a> ScanFromHex::scan_from_hex for u8, matcher match_hex_int, matcher err "expected hex integer", map |s| u8::from_str_radix(s, 16), err map ScanError::int }
-parse_scanner! { impl<'a> ScanFromHex::scan_from_hex for u16, matcher match_hex_int, matcher err "expected hex integer", map |s| u16::from_str_radix(s, 16), err map ScanError::int }
-parse_scanner! { impl<'a> ScanFromHex::scan_from_hex for u32, matcher match_hex_int, matcher err "expected hex integer", map |s| u32::from_str_radix(s, 16), err map ScanError::int }
-parse_scanner! { impl<'a> ScanFromHex::scan_from_hex for u64, matcher match_hex_int, matcher err "expected hex integer", map |s| u64::from_str_radix(s, 16), err map ScanError::int }
-parse_scanner! { impl<'a> ScanFromHex::scan_from_hex for usize, matcher match_hex_int, matcher err "expected hex integer", map |s| usize::from_str_radix(s, 16), err map ScanError::int }
+parse_scanner! { impl<'a> for u8, matcher match_uinteger, matcher err "expected integer", err map |_| ScanError::overflow("u8") }
+parse_scanner! { impl<'a> for u16, matcher match_uinteger, matcher err "expected integer", err map |_| ScanError::overflow("u16") }
+parse_scanner! { impl<'a> for u32, matcher match_uinteger, matcher err "expected integer", err map |_| ScanError::overflow("u32") }
+parse_scanner! { impl<'a> for u64, matcher match_uinteger, matcher err "expected integer", err map |_| ScanError::overflow("u64") }
+parse_scanner! { impl<'a> for usize, matcher match_uinteger, matcher err "expected integer", err map |_| ScanError::overflow("usize") }
+
+parse_scanner! { impl<'a> ScanFromBinary::scan_from_binary for u8, matcher match_bin_int, matcher err "expected binary integer", map |s| u8::from_str_radix(s, 2), err map |_| ScanError::overflow("u8") }
+parse_scanner! { impl<'a> ScanFromBinary::scan_from_binary for u16, matcher match_bin_int, matcher err "expected binary integer", map |s| u16::from_str_radix(s, 2), err map |_| ScanError::overflow("u16") }
+parse_scanner! { impl<'a> ScanFromBinary::scan_from_binary for u32, matcher match_bin_int, matcher err "expected binary integer", map |s| u32::from_str_radix(s, 2), err map |_| ScanError::overflow("u32") }
+parse_scanner! { impl<'a> ScanFromBinary::scan_from_binary for u64, matcher match_bin_int, matcher err "expected binary integer", map |s| u64::from_str_radix(s, 2), err map |_| ScanError::overflow("u64") }
+parse_scanner! { impl<'a> ScanFromBinary::scan_from_binary for usize, matcher match_bin_int, matcher err "expected binary integer", map |s| usize::from_str_radix(s, 2), err map |_| ScanError::overflow("usize") }
+
+parse_scanner! { impl<'a> ScanFromOctal::scan_from_octal for u8, matcher match_oct_int, matcher err "expected octal integer", map |s| u8::from_str_radix(s, 8), err map |_| ScanError::overflow("u8") }
+parse_scanner! { impl<'a> ScanFromOctal::scan_from_octal for u16, matcher match_oct_int, matcher err "expected octal integer", map |s| u16::from_str_radix(s, 8), err map |_| ScanError::overflow("u16") }
+parse_scanner! { impl<'a> ScanFromOctal::scan_from_octal for u32, matcher match_oct_int, matcher err "expected octal integer", map |s| u32::from_str_radix(s, 8), err map |_| ScanError::overflow("u32") }
+parse_scanner! { impl<'a> ScanFromOctal::scan_from_octal for u64, matcher match_oct_int, matcher err "expected octal integer", map |s| u64::from_str_radix(s, 8), err map |_| ScanError::overflow("u64") }
+parse_scanner! { impl<'a> ScanFromOctal::scan_from_octal for usize, matcher match_oct_int, matcher err "expected octal integer", map |s| usize::from_str_radix(s, 8), err map |_| ScanError::overflow("usize") }
+
+parse_scanner! { impl<'a> ScanFromHex::scan_from_hex for u8, matcher match_hex_int, matcher err "expected hex integer", map |s| u8::from_str_radix(s, 16), err map |_| ScanError::overflow("u8") }
+parse_scanner! { impl<'a> ScanFromHex::scan_from_hex for u16, matcher match_hex_int, matcher err "expected hex integer", map |s| u16::from_str_radix(s, 16), err map |_| ScanError::overflow("u16") }
+parse_scanner! { impl<'a> ScanFromHex::scan_from_hex for u32, matcher match_hex_int, matcher err "expected hex integer", map |s| u32::from_str_radix(s, 16), err map |_| ScanError::overflow("u32") }
+parse_scanner! { impl<'a> ScanFromHex::scan_from_hex for u64, matcher match_hex_int, matcher err "expected hex integer", map |s| u64::from_str_radix(s, 16), err map |_| ScanError::overflow("u64") }
+parse_scanner! { impl<'a> ScanFromHex::scan_from_hex for usize, matcher match_hex_int, matcher err "expected hex integer", map |s| usize::from_str_radix(s, 16), err map |_| ScanError::overflow("usize") }
 
 #[cfg(test)]
 #[test]
@@ -307,6 +535,42 @@ fn test_scan_u32() {
     assert_match!(<u32>::scan_from("42"), Ok((42, 2)));
     assert_match!(<u32>::scan_from("-312"), Err(SE { kind: SEK::Syntax(_), .. }));
     assert_match!(<u32>::scan_from("1_234"), Ok((1, 1)));
+    assert_match!(<u32>::scan_from("4294967296"), Err(SE { kind: SEK::Overflow("u32"), .. }));
+    assert_match!(
+        scan!("1 256"; (let _: u8, let b: u8) => b),
+        Err(SE { kind: SEK::Overflow("u8"), ref at, .. }) if at.offset() == 2
+    );
+}
+
+/*
+128-bit integers are only available from Rust 1.26.
+*/
+#[cfg(i128_type)] parse_scanner! { impl<'a> for i128, matcher match_sinteger, matcher err "expected integer", err map |_| ScanError::overflow("i128") }
+#[cfg(i128_type)] parse_scanner! { impl<'a> ScanFromBinary::scan_from_binary for i128, matcher match_bin_int, matcher err "expected binary integer", map |s| i128::from_str_radix(s, 2), err map |_| ScanError::overflow("i128") }
+#[cfg(i128_type)] parse_scanner! { impl<'a> ScanFromOctal::scan_from_octal for i128, matcher match_oct_int, matcher err "expected octal integer", map |s| i128::from_str_radix(s, 8), err map |_| ScanError::overflow("i128") }
+#[cfg(i128_type)] parse_scanner! { impl<'a> ScanFromHex::scan_from_hex for i128, matcher match_hex_int, matcher err "expected hex integer", map |s| i128::from_str_radix(s, 16), err map |_| ScanError::overflow("i128") }
+
+#[cfg(i128_type)] parse_scanner! { impl<'a> for u128, matcher match_uinteger, matcher err "expected integer", err map |_| ScanError::overflow("u128") }
+#[cfg(i128_type)] parse_scanner! { impl<'a> ScanFromBinary::scan_from_binary for u128, matcher match_bin_int, matcher err "expected binary integer", map |s| u128::from_str_radix(s, 2), err map |_| ScanError::overflow("u128") }
+#[cfg(i128_type)] parse_scanner! { impl<'a> ScanFromOctal::scan_from_octal for u128, matcher match_oct_int, matcher err "expected octal integer", map |s| u128::from_str_radix(s, 8), err map |_| ScanError::overflow("u128") }
+#[cfg(i128_type)] parse_scanner! { impl<'a> ScanFromHex::scan_from_hex for u128, matcher match_hex_int, matcher err "expected hex integer", map |s| u128::from_str_radix(s, 16), err map |_| ScanError::overflow("u128") }
+
+#[cfg(i128_type)]
+#[cfg(test)]
+#[test]
+fn test_scan_i128() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+    use ::scanner::{ScanFromBinary, ScanFromOctal, ScanFromHex};
+
+    assert_match!(<i128>::scan_from("-170141183460469231731687303715884105728"), Ok((::std::i128::MIN, 40)));
+    assert_match!(<i128>::scan_from("170141183460469231731687303715884105728"), Err(SE { kind: SEK::Overflow("i128"), .. }));
+    assert_match!(<u128>::scan_from("340282366920938463463374607431768211455"), Ok((::std::u128::MAX, 39)));
+    assert_match!(<u128>::scan_from("340282366920938463463374607431768211456"), Err(SE { kind: SEK::Overflow("u128"), .. }));
+    assert_match!(<u128>::scan_from_hex("ffffffffffffffffffffffffffffffff"), Ok((::std::u128::MAX, 32)));
+    assert_match!(<u128>::scan_from_hex("1ffffffffffffffffffffffffffffffff"), Err(SE { kind: SEK::Overflow("u128"), .. }));
+    assert_match!(<i128>::scan_from_binary("101"), Ok((5, 3)));
+    assert_match!(<u128>::scan_from_octal("777"), Ok((511, 3)));
 }
 
 fn match_bin_int(s: &str) -> Option<((usize, usize), usize)> {
@@ -0,0 +1,559 @@
+/*
+Copyright ⓒ 2016 Daniel Keep.
+
+Licensed under the MIT license (see LICENSE or <http://opensource.org
+/licenses/MIT>) or the Apache License, Version 2.0 (see LICENSE of
+<http://www.apache.org/licenses/LICENSE-2.0>), at your option. All
+files in the project carrying such notice may not be copied, modified,
+or distributed except according to those terms.
+*/
+/*!
+Scanners for common log file formats.
+
+Each scanner is a "static self scanner": it scans into an instance of itself, with one public field per part of the line.  Fields which are written as `-` in the log are `None`.  Timestamps are tuples of `(year, month, day, hour, minute, second)`, exactly as written, as produced by [`datetime`](../runtime/fn.datetime.html); UTC offsets are in minutes.
+
+The syslog scanners only scan the *prefix* of a message, stopping just before the free-form message text itself, which can then be scanned however you like.
+
+**Note**: requires the `logformats` feature.
+
+## Examples
+
+```rust
+# #[macro_use] extern crate scan_rules;
+# use scan_rules::scanner::Line;
+# use scan_rules::scanner::logformats::{CommonLogLine, Rfc3164Prefix};
+# fn main() {
+let line = r#"127.0.0.1 - frank [10/Oct/2000:13:55:36 -0700] "GET /apache_pb.gif HTTP/1.0" 200 2326"#;
+let_scan!(line; (let l: CommonLogLine));
+assert_eq!(l.user, Some("frank"));
+assert_eq!(l.status, 200);
+
+let line = "Oct 11 22:14:15 mymachine su[230]: 'su root' failed for lonvick on /dev/pts/8";
+let_scan!(line; (let p: Rfc3164Prefix, let msg: Line));
+assert_eq!((p.tag, p.pid), ("su", Some(230)));
+assert_eq!(msg, "'su root' failed for lonvick on /dev/pts/8");
+# }
+```
+*/
+use ::ScanError;
+use ::input::ScanInput;
+use ::scanner::bytes::{expect_space, is_space, skip_space};
+use ::scanner::{ScanFromStr, ScanStr};
+use ::scanner::runtime::datetime;
+
+/**
+Scans the prefix of a traditional BSD syslog message, as described in [RFC 3164](https://tools.ietf.org/html/rfc3164).
+
+For example: `<34>Oct 11 22:14:15 mymachine su[230]:`.  The leading priority is optional, as it is typically left out of messages written to log files.  The process ID is also optional.  Scanning stops immediately after the colon following the tag.
+
+Note that the timestamp does not include a year, so the year is always zero.
+*/
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Rfc3164Prefix<'a> {
+    /// The priority, which encodes both the facility (`priority / 8`) and severity (`priority % 8`).
+    pub priority: Option<u8>,
+    /// When the message was logged.
+    pub timestamp: (i32, u8, u8, u8, u8, u8),
+    /// The host which logged the message.
+    pub hostname: &'a str,
+    /// The name of the program or process which logged the message.
+    pub tag: &'a str,
+    /// The ID of the process which logged the message.
+    pub pid: Option<u32>,
+}
+
+impl<'a> ScanFromStr<'a> for Rfc3164Prefix<'a> {
+    type Output = Self;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        let bs = s.as_bytes();
+
+        let (priority, i) = if bs.first() == Some(&b'<') {
+            let (pri, i) = try!(scan_priority(bs));
+            (Some(pri), i)
+        } else {
+            (None, 0)
+        };
+
+        let (timestamp, n) = try!(datetime("%b %e %T").scan(&s[i..]).map_err(|e| e.add_offset(i)));
+        let i = try!(expect_space(bs, i + n));
+        let (hostname, i) = try!(field(s, i, "expected host name"));
+        let i = try!(expect_space(bs, i));
+
+        let tag_len = bs[i..].iter()
+            .take_while(|&&b| !is_space(b) && b != b'[' && b != b':' && b != b'\r' && b != b'\n')
+            .count();
+        if tag_len == 0 {
+            return Err(ScanError::syntax("expected syslog tag").add_offset(i));
+        }
+        let tag = &s[i..i + tag_len];
+        let mut i = i + tag_len;
+
+        let mut pid = None;
+        if bs.get(i) == Some(&b'[') {
+            let (v, n) = try!(digits(bs, i + 1, 10, "expected process ID"));
+            if bs.get(i + 1 + n) != Some(&b']') {
+                return Err(ScanError::syntax("expected `]` after process ID").add_offset(i + 1 + n));
+            }
+            pid = Some(v as u32);
+            i += n + 2;
+        }
+
+        if bs.get(i) != Some(&b':') {
+            return Err(ScanError::syntax("expected `:` after syslog tag").add_offset(i));
+        }
+
+        let prefix = Rfc3164Prefix {
+            priority: priority,
+            timestamp: timestamp,
+            hostname: hostname,
+            tag: tag,
+            pid: pid,
+        };
+        Ok((prefix, i + 1))
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_rfc3164_prefix() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+
+    let scan = Rfc3164Prefix::scan_from;
+
+    assert_match!(scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(
+        scan("<34>Oct 11 22:14:15 mymachine su: 'su root' failed"),
+        Ok((Rfc3164Prefix {
+            priority: Some(34),
+            timestamp: (0, 10, 11, 22, 14, 15),
+            hostname: "mymachine",
+            tag: "su",
+            pid: None,
+        }, 33)));
+    assert_match!(
+        scan("Feb  5 07:00:01 web-1 CRON[1234]: (root) CMD (true)"),
+        Ok((Rfc3164Prefix {
+            priority: None,
+            timestamp: (0, 2, 5, 7, 0, 1),
+            hostname: "web-1",
+            tag: "CRON",
+            pid: Some(1234),
+        }, 33)));
+    assert_match!(scan("<192>Oct 11 22:14:15 host su: x"), Err(SE { kind: SEK::Syntax(_), ref at, .. }) if at.offset() == 1);
+    assert_match!(scan("<34>Oct 11 22:14 host su: x"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("Oct 11 22:14:15 host su x"), Err(SE { kind: SEK::Syntax(_), ref at, .. }) if at.offset() == 23);
+    assert_match!(scan("Oct 11 22:14:15 host su[x]: x"), Err(SE { kind: SEK::Syntax(_), ref at, .. }) if at.offset() == 24);
+}
+
+/**
+Scans the header and structured data of a syslog message, as described in [RFC 5424](https://tools.ietf.org/html/rfc5424).
+
+For example: `<165>1 2003-10-11T22:14:15.003Z mymachine.example.com evntslog - ID47 [exampleSDID@32473 iut="3"]`.  Scanning stops immediately after the structured data.
+
+Fractional seconds in the timestamp are skipped.
+*/
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Rfc5424Prefix<'a> {
+    /// The priority, which encodes both the facility (`priority / 8`) and severity (`priority % 8`).
+    pub priority: u8,
+    /// The version of the syslog protocol.
+    pub version: u32,
+    /// When the message was logged.
+    pub timestamp: Option<(i32, u8, u8, u8, u8, u8)>,
+    /// The timestamp's offset from UTC, in minutes.  This is present if and only if the timestamp is.
+    pub utc_offset: Option<i32>,
+    /// The host which logged the message.
+    pub hostname: Option<&'a str>,
+    /// The name of the application which logged the message.
+    pub app_name: Option<&'a str>,
+    /// The ID of the process which logged the message.
+    pub proc_id: Option<&'a str>,
+    /// The type of message.
+    pub msg_id: Option<&'a str>,
+    /// The structured data elements, including their enclosing brackets, exactly as written.
+    pub structured_data: Option<&'a str>,
+}
+
+impl<'a> ScanFromStr<'a> for Rfc5424Prefix<'a> {
+    type Output = Self;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        let bs = s.as_bytes();
+
+        let (priority, i) = try!(scan_priority(bs));
+        let (version, n) = try!(digits(bs, i, 3, "expected syslog version"));
+        let i = try!(expect_space(bs, i + n));
+
+        let (timestamp, utc_offset, i) = if bs.get(i) == Some(&b'-') {
+            (None, None, i + 1)
+        } else {
+            let (ts, n) = try!(datetime("%Y-%m-%dT%H:%M:%S").scan(&s[i..]).map_err(|e| e.add_offset(i)));
+            let mut i = i + n;
+            if bs.get(i) == Some(&b'.') {
+                let (_, n) = try!(digits(bs, i + 1, 6, "expected fractional seconds"));
+                i += n + 1;
+            }
+            let (off, i) = try!(scan_utc_offset(bs, i, true));
+            (Some(ts), Some(off), i)
+        };
+
+        let i = try!(expect_space(bs, i));
+        let (hostname, i) = try!(field(s, i, "expected host name"));
+        let i = try!(expect_space(bs, i));
+        let (app_name, i) = try!(field(s, i, "expected application name"));
+        let i = try!(expect_space(bs, i));
+        let (proc_id, i) = try!(field(s, i, "expected process ID"));
+        let i = try!(expect_space(bs, i));
+        let (msg_id, i) = try!(field(s, i, "expected message ID"));
+        let i = try!(expect_space(bs, i));
+
+        let sd_start = i;
+        let mut i = i;
+        if bs.get(i) == Some(&b'-') {
+            i += 1;
+        } else {
+            while bs.get(i) == Some(&b'[') {
+                i = try!(skip_sd_element(bs, i));
+            }
+            if i == sd_start {
+                return Err(ScanError::syntax("expected structured data").add_offset(i));
+            }
+        }
+        match bs.get(i) {
+            None | Some(&b' ') | Some(&b'\r') | Some(&b'\n') => (),
+            Some(_) => return Err(ScanError::syntax("expected space after structured data").add_offset(i)),
+        }
+
+        let prefix = Rfc5424Prefix {
+            priority: priority,
+            version: version as u32,
+            timestamp: timestamp,
+            utc_offset: utc_offset,
+            hostname: nil(hostname),
+            app_name: nil(app_name),
+            proc_id: nil(proc_id),
+            msg_id: nil(msg_id),
+            structured_data: nil(&s[sd_start..i]),
+        };
+        Ok((prefix, i))
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_rfc5424_prefix() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+
+    let scan = Rfc5424Prefix::scan_from;
+
+    assert_match!(scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(
+        scan("<34>1 2003-10-11T22:14:15.003Z mymachine.example.com su - ID47 - 'su root' failed"),
+        Ok((Rfc5424Prefix {
+            priority: 34,
+            version: 1,
+            timestamp: Some((2003, 10, 11, 22, 14, 15)),
+            utc_offset: Some(0),
+            hostname: Some("mymachine.example.com"),
+            app_name: Some("su"),
+            proc_id: None,
+            msg_id: Some("ID47"),
+            structured_data: None,
+        }, 64)));
+    assert_match!(
+        scan(r#"<165>1 2003-08-24T05:14:15-07:00 host app 8710 - [a@1 x="]\"y"][b@2] msg"#),
+        Ok((Rfc5424Prefix {
+            priority: 165,
+            version: 1,
+            timestamp: Some((2003, 8, 24, 5, 14, 15)),
+            utc_offset: Some(-420),
+            hostname: Some("host"),
+            app_name: Some("app"),
+            proc_id: Some("8710"),
+            msg_id: None,
+            structured_data: Some(r#"[a@1 x="]\"y"][b@2]"#),
+        }, 68)));
+    assert_match!(
+        scan("<0>1 - - - - - -"),
+        Ok((Rfc5424Prefix { timestamp: None, utc_offset: None, hostname: None, .. }, 16)));
+    assert_match!(scan("<0>1 2003-10-11T22:14:15 - - - - -"), Err(SE { kind: SEK::Syntax(_), ref at, .. }) if at.offset() == 24);
+    assert_match!(scan("<0>1 - - - - - [a"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("<0>1 - - - - - x"), Err(SE { kind: SEK::Syntax(_), ref at, .. }) if at.offset() == 15);
+    assert_match!(scan("<0>1 - - - - -x"), Err(SE { kind: SEK::Syntax(_), ref at, .. }) if at.offset() == 15);
+}
+
+/**
+Scans a line in the Common Log Format used by Apache, Nginx, and other web servers.
+
+For example: `127.0.0.1 - frank [10/Oct/2000:13:55:36 -0700] "GET /apache_pb.gif HTTP/1.0" 200 2326`.
+
+If the line is followed by a quoted referrer and user agent, as in the "combined" log format, these are scanned as well.  The request, referrer, and user agent are exactly as written, without escapes being processed.
+*/
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CommonLogLine<'a> {
+    /// The address of the client.
+    pub host: &'a str,
+    /// The client's identity, as reported by `identd`.
+    pub ident: Option<&'a str>,
+    /// The name of the authenticated user.
+    pub user: Option<&'a str>,
+    /// When the request was received.
+    pub timestamp: (i32, u8, u8, u8, u8, u8),
+    /// The timestamp's offset from UTC, in minutes.
+    pub utc_offset: i32,
+    /// The request line, such as `GET / HTTP/1.1`.
+    pub request: &'a str,
+    /// The status code of the response.
+    pub status: u16,
+    /// The size of the response body, in bytes.
+    pub size: Option<u64>,
+    /// The referrer, in the combined log format.
+    pub referrer: Option<&'a str>,
+    /// The user agent, in the combined log format.
+    pub user_agent: Option<&'a str>,
+}
+
+impl<'a> ScanFromStr<'a> for CommonLogLine<'a> {
+    type Output = Self;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        let bs = s.as_bytes();
+
+        let (host, i) = try!(field(s, 0, "expected host"));
+        let i = try!(expect_space(bs, i));
+        let (ident, i) = try!(field(s, i, "expected identity"));
+        let i = try!(expect_space(bs, i));
+        let (user, i) = try!(field(s, i, "expected user name"));
+        let i = try!(expect_space(bs, i));
+
+        if bs.get(i) != Some(&b'[') {
+            return Err(ScanError::syntax("expected `[` before timestamp").add_offset(i));
+        }
+        let i = i + 1;
+        let (timestamp, n) = try!(datetime("%d/%b/%Y:%H:%M:%S").scan(&s[i..]).map_err(|e| e.add_offset(i)));
+        let i = try!(expect_space(bs, i + n));
+        let (utc_offset, i) = try!(scan_utc_offset(bs, i, false));
+        if bs.get(i) != Some(&b']') {
+            return Err(ScanError::syntax("expected `]` after timestamp").add_offset(i));
+        }
+        let i = try!(expect_space(bs, i + 1));
+
+        let (request, i) = try!(quoted(s, i));
+        let i = try!(expect_space(bs, i));
+        let (status, n) = try!(digits(bs, i, 3, "expected status code"));
+        if n != 3 {
+            return Err(ScanError::syntax("expected three digit status code").add_offset(i));
+        }
+        let i = try!(expect_space(bs, i + n));
+        let (size, i) = if bs.get(i) == Some(&b'-') {
+            (None, i + 1)
+        } else {
+            let (v, n) = try!(digits(bs, i, 20, "expected response size"));
+            (Some(v), i + n)
+        };
+
+        let mut line = CommonLogLine {
+            host: host,
+            ident: nil(ident),
+            user: nil(user),
+            timestamp: timestamp,
+            utc_offset: utc_offset,
+            request: request,
+            status: status as u16,
+            size: size,
+            referrer: None,
+            user_agent: None,
+        };
+
+        // Look for the extra fields of the combined log format.
+        let j = skip_space(bs, i);
+        if j == i || bs.get(j) != Some(&b'"') {
+            return Ok((line, i));
+        }
+        let (referrer, j) = try!(quoted(s, j));
+        let j = try!(expect_space(bs, j));
+        let (user_agent, j) = try!(quoted(s, j));
+        line.referrer = nil(referrer);
+        line.user_agent = nil(user_agent);
+        Ok((line, j))
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_common_log_line() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+
+    let scan = CommonLogLine::scan_from;
+
+    assert_match!(scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(
+        scan(r#"127.0.0.1 - frank [10/Oct/2000:13:55:36 -0700] "GET /apache_pb.gif HTTP/1.0" 200 2326"#),
+        Ok((CommonLogLine {
+            host: "127.0.0.1",
+            ident: None,
+            user: Some("frank"),
+            timestamp: (2000, 10, 10, 13, 55, 36),
+            utc_offset: -420,
+            request: "GET /apache_pb.gif HTTP/1.0",
+            status: 200,
+            size: Some(2326),
+            referrer: None,
+            user_agent: None,
+        }, 85)));
+    assert_match!(
+        scan(r#"::1 - - [05/Jan/2016:04:01:02 +1030] "GET /a\"b HTTP/1.1" 304 - "-" "curl/7.47.0"
+x"#),
+        Ok((CommonLogLine {
+            host: "::1",
+            ident: None,
+            user: None,
+            timestamp: (2016, 1, 5, 4, 1, 2),
+            utc_offset: 630,
+            request: r#"GET /a\"b HTTP/1.1"#,
+            status: 304,
+            size: None,
+            referrer: None,
+            user_agent: Some("curl/7.47.0"),
+        }, 81)));
+    assert_match!(
+        scan(r#"::1 - - [05/Jan/2016:04:01:02 +0000] "GET / HTTP/1.1" 200 0 x"#),
+        Ok((CommonLogLine { size: Some(0), referrer: None, .. }, 59)));
+    assert_match!(
+        scan(r#"::1 - - [05/Jan/2016:04:01:02] "GET / HTTP/1.1" 200 0"#),
+        Err(SE { kind: SEK::Syntax(_), ref at, .. }) if at.offset() == 29);
+    assert_match!(
+        scan(r#"::1 - - [05/Jan/2016:04:01:02 +0000] "GET / HTTP/1.1 200 0"#),
+        Err(SE { kind: SEK::Syntax(_), ref at, .. }) if at.offset() == 37);
+    assert_match!(
+        scan(r#"::1 - - [05/Jan/2016:04:01:02 +0000] "GET / HTTP/1.1" 20 0"#),
+        Err(SE { kind: SEK::Syntax(_), ref at, .. }) if at.offset() == 54);
+    assert_match!(
+        scan(r#"::1 - - [05/Jan/2016:04:01:02 +0000] "GET / HTTP/1.1" 200 99999999999999999999"#),
+        Err(SE { kind: SEK::Overflow("u64"), ref at, .. }) if at.offset() == 58);
+}
+
+/**
+Scans a non-empty run of non-whitespace characters starting at `i`.
+*/
+fn field<'a>(s: &'a str, i: usize, msg: &'static str) -> Result<(&'a str, usize), ScanError> {
+    let len = s.as_bytes()[i..].iter()
+        .take_while(|&&b| !is_space(b) && b != b'\r' && b != b'\n')
+        .count();
+    if len == 0 {
+        return Err(ScanError::syntax(msg).add_offset(i));
+    }
+    Ok((&s[i..i + len], i + len))
+}
+
+/**
+Maps the "nil" value, `-`, to `None`.
+*/
+fn nil(s: &str) -> Option<&str> {
+    if s == "-" { None } else { Some(s) }
+}
+
+/**
+Scans between one and `max` decimal digits starting at `i`, returning the value and the number of digits.
+*/
+fn digits(bs: &[u8], i: usize, max: usize, msg: &'static str) -> Result<(u64, usize), ScanError> {
+    let n = bs[i..].iter().take(max).take_while(|&&b| b'0' <= b && b <= b'9').count();
+    if n == 0 {
+        return Err(ScanError::syntax(msg).add_offset(i));
+    }
+    let mut v: u64 = 0;
+    for &b in &bs[i..i + n] {
+        v = match v.checked_mul(10).and_then(|v| v.checked_add((b - b'0') as u64)) {
+            Some(v) => v,
+            None => return Err(ScanError::overflow("u64").add_offset(i)),
+        };
+    }
+    Ok((v, n))
+}
+
+/**
+Scans a syslog priority, such as `<34>`, at the start of the input.
+*/
+fn scan_priority(bs: &[u8]) -> Result<(u8, usize), ScanError> {
+    if bs.first() != Some(&b'<') {
+        return Err(ScanError::syntax("expected `<` before syslog priority"));
+    }
+    let (pri, n) = try!(digits(bs, 1, 3, "expected syslog priority"));
+    if pri > 191 {
+        return Err(ScanError::syntax("syslog priority out of range").add_offset(1));
+    }
+    if bs.get(1 + n) != Some(&b'>') {
+        return Err(ScanError::syntax("expected `>` after syslog priority").add_offset(1 + n));
+    }
+    Ok((pri as u8, n + 2))
+}
+
+/**
+Scans a UTC offset, such as `-0700`, starting at `i`.  If `colon` is true, the offset must instead be either `Z` or of the form `-07:00`.
+*/
+fn scan_utc_offset(bs: &[u8], i: usize, colon: bool) -> Result<(i32, usize), ScanError> {
+    if colon && bs.get(i) == Some(&b'Z') {
+        return Ok((0, i + 1));
+    }
+    let sign = match bs.get(i) {
+        Some(&b'+') => 1,
+        Some(&b'-') => -1,
+        _ => return Err(ScanError::syntax("expected UTC offset").add_offset(i)),
+    };
+    let (hours, n) = try!(digits(bs, i + 1, 2, "expected UTC offset hours"));
+    let mut j = i + 1 + n;
+    if colon {
+        if bs.get(j) != Some(&b':') {
+            return Err(ScanError::syntax("expected `:` in UTC offset").add_offset(j));
+        }
+        j += 1;
+    }
+    let (minutes, m) = try!(digits(bs, j, 2, "expected UTC offset minutes"));
+    if n != 2 || m != 2 || hours > 23 || minutes > 59 {
+        return Err(ScanError::syntax("invalid UTC offset").add_offset(i));
+    }
+    Ok((sign * (hours * 60 + minutes) as i32, j + m))
+}
+
+/**
+Scans a double-quoted string starting at `i`, returning its contents without unescaping them.
+*/
+fn quoted(s: &str, i: usize) -> Result<(&str, usize), ScanError> {
+    let bs = s.as_bytes();
+    if bs.get(i) != Some(&b'"') {
+        return Err(ScanError::syntax("expected `\"`").add_offset(i));
+    }
+    let mut j = i + 1;
+    while j < bs.len() {
+        match bs[j] {
+            b'"' => return Ok((&s[i + 1..j], j + 1)),
+            b'\\' => j += 2,
+            b'\r' | b'\n' => break,
+            _ => j += 1,
+        }
+    }
+    Err(ScanError::syntax("unterminated quoted string").add_offset(i))
+}
+
+/**
+Skips over a single structured data element, such as `[id key="value"]`, starting at `i`.
+*/
+fn skip_sd_element(bs: &[u8], i: usize) -> Result<usize, ScanError> {
+    let mut j = i + 1;
+    let mut in_quotes = false;
+    while j < bs.len() {
+        match bs[j] {
+            b'"' => in_quotes = !in_quotes,
+            b'\\' if in_quotes => j += 1,
+            b']' if !in_quotes => return Ok(j + 1),
+            b'\r' | b'\n' => break,
+            _ => (),
+        }
+        j += 1;
+    }
+    Err(ScanError::syntax("unterminated structured data element").add_offset(i))
+}
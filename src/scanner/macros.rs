@@ -134,11 +134,11 @@ macro_rules! parse_scanner {
                     use $crate::ScanError;
 
                     let s = s.as_str();
-                    let ($s, end) = try!(
+                    let ($s, start, end) = try!(
                         Option::ok_or(
                             Option::map(
                                 Regex::find(&$regex, s),
-                                |(a, b)| (&s[a..b], b)
+                                |(a, b)| (&s[a..b], a, b)
                             ),
                             ScanError::syntax($re_err)
                         )
@@ -149,7 +149,7 @@ macro_rules! parse_scanner {
                             $map,
                             |v| (v, end)
                         ),
-                        $err
+                        |e| ScanError::add_offset(($err)(e), start)
                     )
                 }
             }
@@ -188,11 +188,11 @@ macro_rules! parse_scanner {
                     use $crate::ScanError;
 
                     let s = s.as_str();
-                    let ($s, end) = try!(
+                    let ($s, start, end) = try!(
                         Option::ok_or(
                             Option::map(
                                 $matcher(s),
-                                |((a, b), c)| (&s[a..b], c)
+                                |((a, b), c)| (&s[a..b], a, c)
                             ),
                             ScanError::syntax($ma_err)
                         )
@@ -203,7 +203,7 @@ macro_rules! parse_scanner {
                             $map,
                             |v| (v, end)
                         ),
-                        $err
+                        |e| ScanError::add_offset(($err)(e), start)
                     )
                 }
             }
@@ -242,11 +242,11 @@ macro_rules! parse_scanner {
                     use $crate::ScanError;
 
                     let s_str = s.as_str();
-                    let (w, end) = try!(
+                    let (w, start, end) = try!(
                         Option::ok_or(
                             Option::map(
                                 Regex::find(&$regex, s_str),
-                                |(a, b)| (&s_str[a..b], b)
+                                |(a, b)| (&s_str[a..b], a, b)
                             ),
                             ScanError::syntax($re_err)
                         )
@@ -257,7 +257,7 @@ macro_rules! parse_scanner {
                             ($map)(w),
                             |v| (v, end)
                         ),
-                        $err
+                        |e| ScanError::add_offset(($err)(e), start)
                     )
                 }
             }
@@ -280,11 +280,11 @@ macro_rules! parse_scanner {
                     use $crate::ScanError;
 
                     let s_str = s.as_str();
-                    let (w, end) = try!(
+                    let (w, start, end) = try!(
                         Option::ok_or(
                             Option::map(
                                 $matcher(s_str),
-                                |((a, b), c)| (&s_str[a..b], c)
+                                |((a, b), c)| (&s_str[a..b], a, c)
                             ),
                             ScanError::syntax($ma_err)
                         )
@@ -295,7 +295,7 @@ macro_rules! parse_scanner {
                             ($map)(w),
                             |v| (v, end)
                         ),
-                        $err
+                        |e| ScanError::add_offset(($err)(e), start)
                     )
                 }
             }
@@ -307,7 +307,32 @@ macro_rules! parse_scanner {
 Define a scanner implementation using `scan!` rules.
 
 The result of the rules should be `(Output, &str)` where the `&str` is the unconsumed tail.
+
+* `impl<'a, T...> ScanFromStr for Ty => Dest { ... }`: each type parameter must be a static self scanner, and the output is `Dest<T::Output...>`.
+
+* `impl<'a, T...> ScanFromStr for Ty => Dest, collection where { ... } { ... }`: as above, except that the limits set by [`collection_limits`](scanner/runtime/fn.collection_limits.html) are enforced.
+
+* `impl<'a, ...> ScanFromStr for Ty, type Output = Out; where { ... } { ... }`: the output type is given explicitly, and no bounds are implied; any lifetime or type parameters may be used.  This allows the output to borrow from the input (*e.g.* `type Output = T::Output` where `T: ScanFromStr<'a>`).
+
+## Examples
+
+```rust
+# #[macro_use] extern crate scan_rules;
+use scan_rules::scanner::Word;
+
+/// Scans `name=value`, borrowing both halves from the input.
+struct Assign;
+
+scanner! { impl<'a> ScanFromStr for Assign, type Output = (&'a str, &'a str); {
+    (let k: Word, "=", let v: Word, ..tail) => ((k, v), tail)
+}}
+
+# fn main() {
+assert_eq!(scan!("x = y"; (let kv: Assign) => kv).unwrap(), ("x", "y"));
+# }
+```
 */
+#[macro_export]
 macro_rules! scanner {
     (@as_item $i:item) => {$i};
 
@@ -319,6 +344,20 @@ macro_rules! scanner {
 
     (
         impl<$lt:tt $(, $ty_params:ident)*> ScanFromStr for $ty:ty => $dest:ident, where {$($clauses:tt)*} { $($patterns:tt)* }
+    ) => {
+        scanner! {
+            impl<$lt $(, $ty_params)*> ScanFromStr for $ty,
+                type Output = $dest<$(<$ty_params as $crate::scanner::ScanFromStr<$lt>>::Output,)*>;
+                where {
+                    $($ty_params: $crate::scanner::ScanFromStr<$lt, Output=$ty_params>,)*
+                    $($clauses)*
+                }
+            { $($patterns)* }
+        }
+    };
+
+    (
+        impl<$lt:tt $(, $ty_params:ident)*> ScanFromStr for $ty:ty => $dest:ident, collection where {$($clauses:tt)*} { $($patterns:tt)* }
     ) => {
         scanner! {
             @as_item
@@ -329,18 +368,70 @@ macro_rules! scanner {
             {
                 type Output = $dest<$(<$ty_params as $crate::scanner::ScanFromStr<$lt>>::Output,)*>;
 
-                fn scan_from<I: $crate::input::ScanInput<$lt>>(s: I) -> Result<(Self::Output, usize), $crate::ScanError> {
-                    match scan! { s.to_cursor(); $($patterns)* } {
-                        Ok((v, tail)) => {
-                            let off = ::std::option::Option::expect($crate::internal::subslice_offset(s.as_str(), tail), "scanner returned tail that wasn't part of the original input");
-                            Ok((v, off))
+                fn scan_from<I: $crate::input::ScanInput<$lt>>(s: I) -> ::std::result::Result<(Self::Output, usize), $crate::ScanError> {
+                    let guard = try!($crate::internal::CollectionGuard::enter());
+                    match guard.check(scan! { guard.limit($crate::input::ScanInput::to_cursor(&s)); $($patterns)* }) {
+                        ::std::result::Result::Ok((v, tail)) => {
+                            let off = ::std::option::Option::expect($crate::internal::subslice_offset($crate::input::ScanInput::as_str(&s), tail), "scanner returned tail that wasn't part of the original input");
+                            ::std::result::Result::Ok((v, off))
                         },
-                        Err(err) => Err(err),
+                        ::std::result::Result::Err(err) => ::std::result::Result::Err(err),
                     }
                 }
             }
         }
     };
+
+    (
+        impl<$lt:tt $(, $params:tt)*> ScanFromStr for $ty:ty, type Output = $out:ty; { $($patterns:tt)* }
+    ) => {
+        scanner! { impl<$lt $(, $params)*> ScanFromStr for $ty, type Output = $out; where {} { $($patterns)* } }
+    };
+
+    (
+        impl<$lt:tt $(, $params:tt)*> ScanFromStr for $ty:ty, type Output = $out:ty; where {$($clauses:tt)*} { $($patterns:tt)* }
+    ) => {
+        scanner! {
+            @as_item
+            impl<$lt $(, $params)*> $crate::scanner::ScanFromStr<$lt> for $ty
+            where
+                $($clauses)*
+            {
+                type Output = $out;
+
+                fn scan_from<I: $crate::input::ScanInput<$lt>>(s: I) -> ::std::result::Result<(Self::Output, usize), $crate::ScanError> {
+                    match scan! { $crate::input::ScanInput::to_cursor(&s); $($patterns)* } {
+                        ::std::result::Result::Ok((v, tail)) => {
+                            let off = ::std::option::Option::expect($crate::internal::subslice_offset($crate::input::ScanInput::as_str(&s), tail), "scanner returned tail that wasn't part of the original input");
+                            ::std::result::Result::Ok((v, off))
+                        },
+                        ::std::result::Result::Err(err) => ::std::result::Result::Err(err),
+                    }
+                }
+            }
+        }
+    };
+}
+
+/**
+Define a scanner implementation for a fieldless enum, matching the names of its variants as they appear in `Debug` output.
+
+The type must be a plain identifier in scope; use an import alias if necessary.
+*/
+macro_rules! unit_enum_scanner {
+    (impl<$lt:tt> for $ty:ident { $($variants:ident),* $(,)* }, err desc $msg:expr) => {
+        impl<$lt> $crate::scanner::ScanFromStr<$lt> for $ty {
+            type Output = Self;
+            fn scan_from<I: $crate::input::ScanInput<$lt>>(s: I) -> ::std::result::Result<(Self::Output, usize), $crate::ScanError> {
+                use ::std::result::Result::{Ok, Err};
+                match scan! { s.to_cursor(); $((stringify!($variants), ..tail) => ($ty::$variants, tail),)* } {
+                    // The tail always runs to the end of the input.
+                    Ok((v, tail)) => Ok((v, s.as_str().len() - tail.len())),
+                    Err(_) => Err($crate::ScanError::syntax($msg)),
+                }
+            }
+        }
+    };
 }
 
 /**
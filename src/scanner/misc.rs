@@ -10,8 +10,13 @@ or distributed except according to those terms.
 /*!
 Miscellaneous, abstract scanners.
 */
+use std::borrow::Cow;
+#[cfg(feature="std")] use std::collections::{HashMap, HashSet};
+#[cfg(feature="std")] use std::hash::Hash;
 use std::marker::PhantomData;
-use strcursor::StrCursor;
+use std::num::{ParseFloatError, ParseIntError};
+use std::path::PathBuf;
+use std::str::FromStr;
 use ::ScanError;
 use ::input::ScanInput;
 use ::util::StrUtil;
@@ -19,6 +24,7 @@ use super::{
     ScanFromStr, ScanSelfFromStr,
     ScanFromBinary, ScanFromOctal, ScanFromHex,
 };
+use super::lang::match_float_with;
 
 /**
 Scans the given `Output` type from its binary representation.
@@ -42,6 +48,102 @@ fn test_binary() {
     assert_match!(Binary::<i32>::scan_from("110010101110000b"), Ok((0x6570, 15)));
 }
 
+/**
+Scans a string of binary digits, optionally grouped with underscores, into the given `Output` type.
+
+Underscores may only appear *between* digits, as in `1010_1100`.  Unlike `Binary`, this does not accept a sign.
+*/
+pub struct Bits<Output>(PhantomData<Output>);
+
+impl<'a, Output> ScanFromStr<'a> for Bits<Output>
+where Output: for<'b> ScanFromBinary<'b> {
+    type Output = Output;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        let bs = s.as_bytes();
+        let mut digits = String::new();
+        let mut end = 0;
+
+        for (i, &b) in bs.iter().enumerate() {
+            match b {
+                b'0' | b'1' => {
+                    digits.push(b as char);
+                    end = i + 1;
+                },
+                b'_' if end == i && end != 0 => (),
+                _ => break,
+            }
+        }
+
+        if digits.is_empty() {
+            return Err(ScanError::syntax("expected binary digits"));
+        }
+
+        Output::scan_from_binary(&*digits).map(|(v, _)| (v, end))
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_bits() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+    let scan = Bits::<u8>::scan_from;
+
+    assert_match!(scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("_1"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("2"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("0"), Ok((0, 1)));
+    assert_match!(scan("1010_1100"), Ok((0b1010_1100, 9)));
+    assert_match!(scan("1010__1100"), Ok((0b1010, 4)));
+    assert_match!(scan("11_ x"), Ok((0b11, 2)));
+    assert_match!(scan("101 1"), Ok((0b101, 3)));
+    assert_match!(scan("1_0000_0000"), Err(SE { kind: SEK::Overflow(_), .. }));
+    assert_match!(Bits::<u32>::scan_from("1_0000_0000"), Ok((0x100, 11)));
+}
+
+/**
+Scans a set of bit indices, such as `{0, 3, 7}`, into a `u64` mask.
+
+Each index must be less than 64.
+*/
+pub enum BitSet {}
+
+impl<'a> ScanFromStr<'a> for BitSet {
+    type Output = u64;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        use ::input::ScanCursor;
+        let (idxs, end): (Vec<u32>, usize) = try!(scan!(s.to_cursor();
+            ("{", [let idxs: u32],*, "}", ^..tail) => (idxs, tail.offset())
+        ));
+
+        let mut mask = 0u64;
+        for idx in idxs {
+            if idx >= 64 {
+                return Err(ScanError::syntax("bit index out of range"));
+            }
+            mask |= 1 << idx;
+        }
+        Ok((mask, end))
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_bit_set() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+    let scan = BitSet::scan_from;
+
+    assert_match!(scan(""), Err(SE { kind: SEK::LiteralMismatch, .. }));
+    assert_match!(scan("{}"), Ok((0, 2)));
+    assert_match!(scan("{0, 3, 7}"), Ok((0b1000_1001, 9)));
+    assert_match!(scan("{ 63 } x"), Ok((0x8000_0000_0000_0000, 6)));
+    assert_match!(scan("{1, 1}"), Ok((0b10, 6)));
+    assert_match!(scan("{64}"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("{1, 2"), Err(SE { kind: SEK::LiteralMismatch, .. }));
+}
+
 /**
 Scans all remaining input into a string.
 
@@ -67,6 +169,15 @@ impl<'a> ScanFromStr<'a> for Everything<'a, String> {
     }
 }
 
+#[cfg(str_into_output_extra_broken)]
+impl<'a> ScanFromStr<'a> for Everything<'a, Cow<'a, str>> {
+    type Output = Cow<'a, str>;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        Ok((s.into(), s.len()))
+    }
+}
+
 #[cfg(not(str_into_output_extra_broken))]
 impl<'a, Output> ScanFromStr<'a> for Everything<'a, Output>
 where &'a str: Into<Output> {
@@ -84,736 +195,2909 @@ fn test_everything() {
     assert_match!(Everything::<&str>::scan_from(""), Ok(("", 0)));
     assert_match!(Everything::<&str>::scan_from("で"), Ok(("で", 3)));
     assert_match!(Everything::<&str>::scan_from("うまいー　うまいー　ぼうぼうぼうぼう"), Ok(("うまいー　うまいー　ぼうぼうぼうぼう", 54)));
+    assert_match!(Everything::<Cow<str>>::scan_from("で"), Ok((Cow::Borrowed("で"), 3)));
+    assert_match!(
+        scan!("a b c"; (let a: Word, let rest: Everything<Cow<str>>) => (a, rest)),
+        Ok(("a", Cow::Borrowed("b c")))
+    );
 }
 
 /**
-Scans the given `Output` type from its hexadecimal representation.
+Scans a size in bytes, with an optional unit suffix, into a `u64` byte count.
+
+The size may be an integer or a decimal fraction (*e.g.* `512`, `1.5MiB`), optionally separated from the unit by spaces or tabs.  The following units are recognised, case-insensitively:
+
+* `B` - bytes.
+* `KB`, `MB`, `GB`, `TB`, `PB`, `EB` - decimal multiples (*i.e.* powers of 1000).
+* `KiB`, `MiB`, `GiB`, `TiB`, `PiB`, `EiB` - binary multiples (*i.e.* powers of 1024).
+* `K`, `M`, `G`, `T`, `P`, `E` - also binary multiples, as used by tools such as `ls`, `du`, and `df`.
+
+Fractional sizes are rounded to the nearest byte, and are not permitted without a multiple.  Sizes which do not fit in a `u64` are an error.
 */
-pub struct Hex<Output>(PhantomData<Output>);
+pub enum ByteSize {}
 
-impl<'a, Output> ScanFromStr<'a> for Hex<Output>
-where Output: ScanFromHex<'a> {
-    type Output = Output;
+impl<'a> ScanFromStr<'a> for ByteSize {
+    type Output = u64;
     fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
-        Output::scan_from_hex(s)
+        let s = s.as_str();
+        let digits = |s: &str| s.bytes().take_while(|&b| b'0' <= b && b <= b'9').count();
+
+        let int_len = digits(s);
+        if int_len == 0 {
+            return Err(ScanError::syntax("expected byte size"));
+        }
+        // Only digits have been taken, so this can only fail by overflowing.
+        let int: u64 = try!(s[..int_len].parse().map_err(|_| ScanError::overflow("u64")));
+
+        let frac_len = if s[int_len..].starts_with('.') { digits(&s[int_len + 1..]) } else { 0 };
+        let num_len = if frac_len > 0 { int_len + 1 + frac_len } else { int_len };
+
+        let rest = &s[num_len..];
+        let ws = rest.len() - rest.trim_left_matches(|c| c == ' ' || c == '\t').len();
+        let unit_len = rest[ws..].bytes()
+            .take_while(|&b| (b'a' <= b && b <= b'z') || (b'A' <= b && b <= b'Z'))
+            .count();
+
+        let (scale, end) = match byte_size_unit(&rest[ws..ws + unit_len].to_lowercase()) {
+            Some(scale) => (scale, num_len + ws + unit_len),
+            None => (1, num_len),
+        };
+
+        let too_large = || ScanError::overflow("u64");
+        let mut bytes = try!(int.checked_mul(scale).ok_or_else(&too_large));
+
+        if frac_len > 0 {
+            if scale == 1 {
+                return Err(ScanError::syntax("fractional byte size requires a multiple").add_offset(int_len));
+            }
+            let frac: f64 = try!(s[int_len..num_len].parse().map_err(ScanError::float));
+            let frac = (frac * scale as f64).round() as u64;
+            bytes = try!(bytes.checked_add(frac).ok_or_else(&too_large));
+        }
+
+        Ok((bytes, end))
     }
 }
 
+fn byte_size_unit(unit: &str) -> Option<u64> {
+    let (prefix, binary) = match unit.len() {
+        1 if unit == "b" => return Some(1),
+        1 => (unit, true),
+        2 if unit.ends_with('b') => (&unit[..1], false),
+        3 if unit.ends_with("ib") => (&unit[..1], true),
+        _ => return None
+    };
+
+    let power = match prefix {
+        "k" => 1,
+        "m" => 2,
+        "g" => 3,
+        "t" => 4,
+        "p" => 5,
+        "e" => 6,
+        _ => return None
+    };
+
+    let base: u64 = if binary { 1024 } else { 1000 };
+    Some((0..power).fold(1, |acc, _| acc * base))
+}
+
 #[cfg(test)]
 #[test]
-fn test_hex() {
-    assert_match!(Hex::<i32>::scan_from("0 1 2 x"), Ok((0x0, 1)));
-    assert_match!(Hex::<i32>::scan_from("012x"), Ok((0x12, 3)));
-    assert_match!(Hex::<i32>::scan_from("0x012x"), Ok((0x0, 1)));
-    assert_match!(Hex::<i32>::scan_from("BadCafé"), Ok((0xbadcaf, 6)));
+fn test_byte_size() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+    let scan = ByteSize::scan_from;
+
+    assert_match!(scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("K"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("512"), Ok((512, 3)));
+    assert_match!(scan("512 files"), Ok((512, 3)));
+    assert_match!(scan("512B"), Ok((512, 4)));
+    assert_match!(scan("4K"), Ok((4096, 2)));
+    assert_match!(scan("4k /tmp"), Ok((4096, 2)));
+    assert_match!(scan("2 GB"), Ok((2_000_000_000, 4)));
+    assert_match!(scan("2 GiB"), Ok((2_147_483_648, 5)));
+    assert_match!(scan("1.5MiB"), Ok((1_572_864, 6)));
+    assert_match!(scan("1.5 kB"), Ok((1500, 6)));
+    assert_match!(scan("1."), Ok((1, 1)));
+    assert_match!(scan("16E"), Err(SE { kind: SEK::Overflow("u64"), .. }));
+    assert_match!(scan("15E"), Ok((17_293_822_569_102_704_640, 3)));
+    assert_match!(scan("1.5"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("18446744073709551616"), Err(SE { kind: SEK::Overflow("u64"), .. }));
 }
 
 /**
-Scans a sequence of horizontal (non-newline) space characters into a string.
+Scans a colour into an `(r, g, b, a)` tuple.
 
-This *will not* match an empty sequence; there must be at least one space character for the scan to succeed.
-*/
-pub struct HorSpace<'a, Output=&'a str>(PhantomData<(&'a (), Output)>);
+The following syntaxes are supported:
 
-// FIXME: Error message omitted due to https://github.com/rust-lang/rust/issues/26448.
-#[cfg(str_into_output_extra_broken)]
-impl<'a> ScanFromStr<'a> for HorSpace<'a, &'a str> {
-    type Output = &'a str;
+* `#rgb`, `#rgba`, `#rrggbb`, `#rrggbbaa` - hexadecimal components.
+* `rgb(r, g, b)` - decimal components between 0 and 255.
+* `rgba(r, g, b, a)` - as above, with an alpha component between 0.0 and 1.0.
+* One of the basic CSS colour names (*e.g.* `red`, `navy`, `orange`), matched case-insensitively.
+
+Like colour names, the `rgb` and `rgba` function names are matched case-insensitively.  The alpha component is `None` unless it was explicitly given.
+*/
+pub enum Color {}
 
+impl<'a> ScanFromStr<'a> for Color {
+    type Output = (u8, u8, u8, Option<u8>);
     fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
         let s = s.as_str();
-        match match_hor_space(s) {
-            Some(b) => {
-                let word = &s[..b];
-                let tail = &s[b..];
-                Ok((word.into(), s.subslice_offset_stable(tail).unwrap()))
-            },
-            // None => Err(ScanError::syntax("expected a space")),
-            None => Err(ScanError::syntax_no_message()),
-        }
-    }
 
-    fn wants_leading_junk_stripped() -> bool { false }
-}
+        if s.starts_with('#') {
+            return match match_hex_color(&s[1..]) {
+                Some((c, n)) => Ok((c, n + 1)),
+                None => Err(ScanError::syntax("expected hexadecimal colour").add_offset(1)),
+            };
+        }
 
-// FIXME: Error message omitted due to https://github.com/rust-lang/rust/issues/26448.
-#[cfg(str_into_output_extra_broken)]
-impl<'a> ScanFromStr<'a> for HorSpace<'a, String> {
-    type Output = String;
+        let name_len = s.bytes()
+            .take_while(|&b| (b'a' <= b && b <= b'z') || (b'A' <= b && b <= b'Z'))
+            .count();
+        let name = s[..name_len].to_lowercase();
+
+        if name == "rgb" || name == "rgba" {
+            let ((r, g, b), rest) = try!(scan!(&s[name_len..];
+                ("(", let r: u8, ",", let g: u8, ",", let b: u8, ..rest) => ((r, g, b), rest),
+            ).map_err(|e| e.add_offset(name_len)));
+            let rest_at = s.subslice_offset_stable(rest).unwrap();
+
+            let (a, tail) = if name == "rgba" {
+                let (a, tail) = try!(scan!(rest;
+                    (",", let a: f64, ")", ..tail) => (a, tail),
+                ).map_err(|e| e.add_offset(rest_at)));
+                let after_comma = &rest[rest.find(',').unwrap() + 1..];
+                let a_at = s.subslice_offset_stable(after_comma.trim_left()).unwrap();
+                if !(0.0 <= a && a <= 1.0) {
+                    return Err(ScanError::syntax("alpha component must be between 0 and 1").add_offset(a_at));
+                }
+                (Some((a * 255.0).round() as u8), tail)
+            } else {
+                try!(scan!(rest; (")", ..tail) => (None, tail)).map_err(|e| e.add_offset(rest_at)))
+            };
+            let n = s.subslice_offset_stable(tail).unwrap();
+            return Ok(((r, g, b, a), n));
+        }
 
-    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
-        let s = s.as_str();
-        match match_hor_space(s) {
-            Some(b) => {
-                let word = &s[..b];
-                let tail = &s[b..];
-                Ok((word.into(), s.subslice_offset_stable(tail).unwrap()))
-            },
-            // None => Err(ScanError::syntax("expected a space")),
-            None => Err(ScanError::syntax_no_message()),
+        match lookup_color_name(&name) {
+            Some((r, g, b)) => Ok(((r, g, b, None), name_len)),
+            None => Err(ScanError::syntax("expected colour")),
         }
     }
-
-    fn wants_leading_junk_stripped() -> bool { false }
 }
 
-// FIXME: Error message omitted due to https://github.com/rust-lang/rust/issues/26448.
-#[cfg(not(str_into_output_extra_broken))]
-impl<'a, Output> ScanFromStr<'a> for HorSpace<'a, Output>
-where &'a str: Into<Output> {
-    type Output = Output;
-
-    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
-        let s = s.as_str();
-        match match_hor_space(s) {
-            Some(b) => {
-                let word = &s[..b];
-                let tail = &s[b..];
-                Ok((word.into(), s.subslice_offset_stable(tail).unwrap()))
-            },
-            // None => Err(ScanError::syntax("expected a space")),
-            None => Err(ScanError::syntax_no_message()),
+fn match_hex_color(s: &str) -> Option<((u8, u8, u8, Option<u8>), usize)> {
+    fn hex(b: u8) -> u8 {
+        match b {
+            b'0'...b'9' => b - b'0',
+            b'a'...b'f' => b - b'a' + 10,
+            _ => b - b'A' + 10,
         }
     }
 
-    fn wants_leading_junk_stripped() -> bool { false }
-}
+    let bs = s.as_bytes();
+    let n = bs.iter()
+        .take_while(|&&b| match b { b'0'...b'9' | b'a'...b'f' | b'A'...b'F' => true, _ => false })
+        .count();
 
-fn match_hor_space(s: &str) -> Option<usize> {
-    use ::util::TableUtil;
-    use ::unicode::property::White_Space_table as WS;
+    let short = |i: usize| hex(bs[i]) * 0x11;
+    let long = |i: usize| hex(bs[i]) * 0x10 + hex(bs[i + 1]);
 
-    s.char_indices()
-        .take_while(|&(_, c)| match c {
-            '\x0a'...'\x0d' | '\u{85}' | '\u{2028}' | '\u{2029}' => false,
-            c => WS.span_table_contains(&c)
-        })
-        .map(|(i, c)| i + c.len_utf8())
-        .last()
+    let c = match n {
+        3 => (short(0), short(1), short(2), None),
+        4 => (short(0), short(1), short(2), Some(short(3))),
+        6 => (long(0), long(2), long(4), None),
+        8 => (long(0), long(2), long(4), Some(long(6))),
+        _ => return None
+    };
+
+    Some((c, n))
+}
+
+fn lookup_color_name(name: &str) -> Option<(u8, u8, u8)> {
+    Some(match name {
+        "black" => (0x00, 0x00, 0x00),
+        "silver" => (0xc0, 0xc0, 0xc0),
+        "gray" | "grey" => (0x80, 0x80, 0x80),
+        "white" => (0xff, 0xff, 0xff),
+        "maroon" => (0x80, 0x00, 0x00),
+        "red" => (0xff, 0x00, 0x00),
+        "purple" => (0x80, 0x00, 0x80),
+        "fuchsia" | "magenta" => (0xff, 0x00, 0xff),
+        "green" => (0x00, 0x80, 0x00),
+        "lime" => (0x00, 0xff, 0x00),
+        "olive" => (0x80, 0x80, 0x00),
+        "yellow" => (0xff, 0xff, 0x00),
+        "navy" => (0x00, 0x00, 0x80),
+        "blue" => (0x00, 0x00, 0xff),
+        "teal" => (0x00, 0x80, 0x80),
+        "aqua" | "cyan" => (0x00, 0xff, 0xff),
+        "orange" => (0xff, 0xa5, 0x00),
+        _ => return None
+    })
 }
 
 #[cfg(test)]
 #[test]
-fn test_hor_space() {
+fn test_color() {
     use ::ScanError as SE;
     use ::ScanErrorKind as SEK;
-
-    assert_match!(HorSpace::<&str>::scan_from(""), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
-    assert_match!(HorSpace::<&str>::scan_from("a"), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
-    assert_match!(HorSpace::<&str>::scan_from("0"), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
-    assert_match!(HorSpace::<&str>::scan_from(" "), Ok((" ", 1)));
-    assert_match!(HorSpace::<&str>::scan_from("\t"), Ok(("\t", 1)));
-    assert_match!(HorSpace::<&str>::scan_from("\r"), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
-    assert_match!(HorSpace::<&str>::scan_from("\n"), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
-    assert_match!(HorSpace::<&str>::scan_from("\r\n"), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
-    assert_match!(HorSpace::<&str>::scan_from("  \t \n \t\t "), Ok(("  \t ", 4)));
+    let scan = Color::scan_from;
+
+    assert_match!(scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("#"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("#ff"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("#fffff"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("#fff"), Ok(((0xff, 0xff, 0xff, None), 4)));
+    assert_match!(scan("#f80c"), Ok(((0xff, 0x88, 0x00, Some(0xcc)), 5)));
+    assert_match!(scan("#FFaa00;"), Ok(((0xff, 0xaa, 0x00, None), 7)));
+    assert_match!(scan("#12345678"), Ok(((0x12, 0x34, 0x56, Some(0x78)), 9)));
+    assert_match!(scan("rgb(1,2,3)"), Ok(((1, 2, 3, None), 10)));
+    assert_match!(scan("rgb(1, 2, 3) x"), Ok(((1, 2, 3, None), 12)));
+    assert_match!(scan("rgba(1, 2, 3, 0.5)"), Ok(((1, 2, 3, Some(128)), 18)));
+    assert_match!(scan("rgba(1, 2, 3, 1.5)"), Err(SE { kind: SEK::Syntax(_), ref at, .. }) if at.offset() == 14);
+    assert_match!(scan("RGB(1, 2, 3)"), Ok(((1, 2, 3, None), 12)));
+    assert_match!(scan("Rgba(1,2,3,1)"), Ok(((1, 2, 3, Some(255)), 13)));
+    assert_match!(scan("rgb(1, 2, 300)"), Err(_));
+    assert_match!(scan("rgb(1, 2)"), Err(SE { ref at, .. }) if at.offset() == 8);
+    assert_match!(scan("rgba(1, 2, 3)"), Err(SE { ref at, .. }) if at.offset() == 12);
+    assert_match!(scan("red"), Ok(((0xff, 0x00, 0x00, None), 3)));
+    assert_match!(scan("Orange juice"), Ok(((0xff, 0xa5, 0x00, None), 6)));
+    assert_match!(scan("reddish"), Err(SE { kind: SEK::Syntax(_), .. }));
 }
 
 /**
-Scans a single identifier into a string.
+Scans the `Debug` representation of a tuple struct named by `Name`, with fields scanned as the tuple `T`.
 
-Specifically, this will match a single `XID_Start` character (or underscore) followed by zero or more `XID_Continue` characters.
+This matches the syntax `Name(a, b, ...)`, and outputs the fields as a tuple.  For newtypes, use a one-element tuple (*e.g.* `DebugTuple<Name, (u32,)>`), which will also accept `Name(a)`.
+
+The name is supplied by implementing the [`DebugName`](trait.DebugName.html) trait on a marker type.
+
+```ignore
+enum Point {}
+impl DebugName for Point {
+    fn debug_name() -> &'static str { "Point" }
+}
+
+let_scan!(input; (let p: DebugTuple<Point, (i32, i32)>));
+```
 */
-pub struct Ident<'a, Output=&'a str>(PhantomData<(&'a (), Output)>);
+pub struct DebugTuple<Name, T>(PhantomData<(Name, T)>);
 
-// FIXME: Error message omitted due to https://github.com/rust-lang/rust/issues/26448.
-#[cfg(str_into_output_extra_broken)]
-impl<'a> ScanFromStr<'a> for Ident<'a, &'a str> {
-    type Output = &'a str;
-    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
-        let s = s.as_str();
-        match match_ident(s) {
-            Some(b) => {
-                let word = &s[..b];
-                let tail = &s[b..];
-                Ok((word.into(), s.subslice_offset_stable(tail).unwrap()))
-            },
-            None => {
-                // Err(ScanError::syntax("expected identifier"))
-                Err(ScanError::syntax_no_message())
-            },
-        }
+scanner! { impl<'a, Name, T> ScanFromStr for DebugTuple<Name, T>, type Output = T::Output; where {Name: DebugName, T: ScanFromStr<'a>} {
+    (Name::debug_name(), let v: T, ..tail) => (v, tail)
+}}
+
+/**
+Provides the type name matched by the [`DebugTuple`](struct.DebugTuple.html) scanner.
+*/
+pub trait DebugName {
+    /**
+    The name of the type, as it appears in `Debug` output.
+    */
+    fn debug_name() -> &'static str;
+}
+
+#[cfg(test)]
+#[test]
+fn test_debug_tuple() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+
+    enum Point {}
+    impl DebugName for Point {
+        fn debug_name() -> &'static str { "Point" }
+    }
+
+    enum Meters {}
+    impl DebugName for Meters {
+        fn debug_name() -> &'static str { "Meters" }
     }
+
+    let scan = DebugTuple::<Point, (i32, i32)>::scan_from;
+    assert_match!(scan(""), Err(SE { kind: SEK::LiteralMismatch, .. }));
+    assert_match!(scan("Pt(1, 2)"), Err(SE { kind: SEK::LiteralMismatch, .. }));
+    assert_match!(scan("Point(1, 2)"), Ok(((1, 2), 11)));
+    assert_match!(scan("Point ( -1 , 2 ) x"), Ok(((-1, 2), 16)));
+    assert_match!(scan("Point(1)"), Err(_));
+
+    let scan = DebugTuple::<Meters, (f64,)>::scan_from;
+    assert_match!(scan("Meters(1.5)"), Ok(((1.5,), 11)));
+    assert_match!(scan("Meters(1.5,)"), Ok(((1.5,), 12)));
 }
 
-// FIXME: Error message omitted due to https://github.com/rust-lang/rust/issues/26448.
-#[cfg(str_into_output_extra_broken)]
-impl<'a> ScanFromStr<'a> for Ident<'a, String> {
-    type Output = String;
+/**
+Scans a single decimal digit character, outputting either its value (as a `u8`) or the character itself (as a `char`).
+
+Any character in the Unicode `Nd` (decimal number) category is accepted, not just `0` through `9`, as of Unicode 8.0.
+*/
+pub struct DecimalDigit<Output=u8>(PhantomData<Output>);
+
+impl<'a> ScanFromStr<'a> for DecimalDigit<u8> {
+    type Output = u8;
     fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
         let s = s.as_str();
-        match match_ident(s) {
-            Some(b) => {
-                let word = &s[..b];
-                let tail = &s[b..];
-                Ok((word.into(), s.subslice_offset_stable(tail).unwrap()))
-            },
-            None => {
-                // Err(ScanError::syntax("expected identifier"))
-                Err(ScanError::syntax_no_message())
-            },
+        match match_decimal_digit(s) {
+            Some((v, c)) => Ok((v, c.len_utf8())),
+            None => Err(ScanError::syntax("expected decimal digit")),
         }
     }
 }
 
-#[cfg(not(str_into_output_extra_broken))]
-// FIXME: Error message omitted due to https://github.com/rust-lang/rust/issues/26448.
-impl<'a, Output> ScanFromStr<'a> for Ident<'a, Output>
-where &'a str: Into<Output> {
-    type Output = Output;
+impl<'a> ScanFromStr<'a> for DecimalDigit<char> {
+    type Output = char;
     fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
         let s = s.as_str();
-        match match_ident(s) {
-            Some(b) => {
-                let word = &s[..b];
-                let tail = &s[b..];
-                Ok((word.into(), s.subslice_offset_stable(tail).unwrap()))
-            },
-            None => {
-                // Err(ScanError::syntax("expected identifier"))
-                Err(ScanError::syntax_no_message())
-            },
+        match match_decimal_digit(s) {
+            Some((_, c)) => Ok((c, c.len_utf8())),
+            None => Err(ScanError::syntax("expected decimal digit")),
         }
     }
 }
 
-fn match_ident(s: &str) -> Option<usize> {
-    use ::util::TableUtil;
-    use ::unicode::derived_property::{XID_Continue_table, XID_Start_table};
-
-    let mut ics = s.char_indices();
+/**
+Matches a leading decimal digit, returning its value and the character itself.
 
-    let first_len = match ics.next() {
-        Some((_, '_')) => 1,
-        Some((_, c)) if XID_Start_table.span_table_contains(&c) => c.len_utf8(),
-        _ => return None,
-    };
+Every range in the `Nd` table is made up of complete runs of ten digits, starting from zero, so the value is just the offset into the range, modulo ten.
+*/
+fn match_decimal_digit(s: &str) -> Option<(u8, char)> {
+    use ::unicode::general_category::Nd_table as Nd;
 
-    let len = ics
-        .take_while(|&(_, c)| XID_Continue_table.span_table_contains(&c))
-        .map(|(i, c)| i + c.len_utf8())
-        .last()
-        .unwrap_or(first_len);
+    use std::cmp::Ordering::{Equal, Greater, Less};
 
-    Some(len)
+    let c = try_opt!(s.chars().next());
+    Nd.binary_search_by(|&(lo, hi)| if hi < c { Less } else if c < lo { Greater } else { Equal })
+        .ok()
+        .map(|i| ((((c as u32) - (Nd[i].0 as u32)) % 10) as u8, c))
 }
 
 #[cfg(test)]
 #[test]
-fn test_ident() {
+fn test_decimal_digit() {
     use ::ScanError as SE;
     use ::ScanErrorKind as SEK;
 
-    assert_eq!(match_ident("a"), Some(1));
+    let scan = DecimalDigit::<u8>::scan_from;
+    assert_match!(scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("x"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("0"), Ok((0, 1)));
+    assert_match!(scan("42"), Ok((4, 1)));
+    assert_match!(scan("７"), Ok((7, 3)));
+    assert_match!(scan("𝟗"), Ok((9, 4)));
+    assert_match!(scan("\u{1d7ce}"), Ok((0, 4)));
+    assert_match!(scan("\u{a9f5}"), Ok((5, 3)));
+    assert_match!(scan("\u{d7ff}"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("Ⅳ"), Err(SE { kind: SEK::Syntax(_), .. }));
+
+    let scan = DecimalDigit::<char>::scan_from;
+    assert_match!(scan("9a"), Ok(('9', 1)));
+    assert_match!(scan("٣"), Ok(('٣', 2)));
+}
 
-    assert_match!(Ident::<&str>::scan_from(""), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
-    assert_match!(Ident::<&str>::scan_from("a"), Ok(("a", 1)));
-    assert_match!(Ident::<&str>::scan_from("two words "), Ok(("two", 3)));
-    assert_match!(Ident::<&str>::scan_from("two_words "), Ok(("two_words", 9)));
-    assert_match!(Ident::<&str>::scan_from("0123abc456 "), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
-    assert_match!(Ident::<&str>::scan_from("_0123abc456 "), Ok(("_0123abc456", 11)));
-    assert_match!(Ident::<&str>::scan_from("f(blah)"), Ok(("f", 1)));
+/**
+Runs the scanner `S` without consuming any input, and outputs the number of bytes it *would* have consumed.
+
+This is useful for lookahead: you can check whether, and how far, something matches before deciding what to do with it.  If `S` fails to scan, so does this.
+
+```ignore
+let_scan!(input; (let n: DryRun<Word>, let w: Word, ..tail));
+assert_eq!(n, w.len());
+```
+*/
+pub struct DryRun<S>(PhantomData<S>);
+
+impl<'a, S> ScanFromStr<'a> for DryRun<S> where S: ScanFromStr<'a> {
+    type Output = usize;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        S::peek_scan_from(s).map(|n| (n, 0))
+    }
+
+    fn wants_leading_junk_stripped() -> bool {
+        S::wants_leading_junk_stripped()
+    }
 }
 
-/**
-Explicitly infer the type of a scanner.
+#[cfg(test)]
+#[test]
+fn test_dry_run() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+    use ::scanner::Word;
 
-This is useful in cases where you want to only *partially* specify a scanner type, but the partial type cannot be inferred under normal circumstances.
+    let scan = DryRun::<Word<&str>>::scan_from;
+    assert_match!(scan(""), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
+    assert_match!(scan("?"), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
+    assert_match!(scan("abc def"), Ok((3, 0)));
 
-For example, tuples allow their element types to scan to be abstract scanners; *e.g.* `(Word<String>, Hex<i32>)` will scan to `(String, i32)`.  However, this interferes with inferring the scanner type when you *partially* specify a tuple type.  If you attempt to store the result of scanning `(_, _)` into a `(String, i32)`, Rust cannot determine whether the *scanner* type should be `(String, Hex<i32>)`, or `(Word<String>, i32)`, or something else entirely.
+    let scan = DryRun::<u32>::scan_from;
+    assert_match!(scan("1234 x"), Ok((4, 0)));
+    assert_match!(scan("x"), Err(SE { kind: SEK::Syntax(_), .. }));
+}
 
-This scanner, then, *requires* that the inner type scan to itself and *only* to itself.
+/**
+Scans a number written out as English words, such as "twenty-three" or "one hundred and five", into an integer.
+
+**Note**: requires the `english-numbers` feature.
+
+Words are matched case-insensitively, and may be separated by spaces; tens and units may also be joined with a hyphen.  Scales from "thousand" up to "quadrillion" are supported, and must appear in decreasing order.  "and" may follow "hundred" or a scale word.  Scanning stops at the first word which cannot continue the number.
 */
-pub struct Inferred<T>(PhantomData<T>);
+#[cfg(feature="english-numbers")]
+pub enum EnglishNumber {}
 
-impl<'a, T> ScanFromStr<'a> for Inferred<T>
-where T: ScanSelfFromStr<'a> {
-    type Output = T;
+#[cfg(feature="english-numbers")]
+impl<'a> ScanFromStr<'a> for EnglishNumber {
+    type Output = u64;
     fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
-        T::scan_from(s)
+        match match_english_number(s.as_str()) {
+            Some(res) => Ok(res),
+            None => Err(ScanError::syntax("expected number")),
+        }
+    }
+}
+
+#[cfg(feature="english-numbers")]
+fn match_english_number(s: &str) -> Option<(u64, usize)> {
+    #[derive(Copy, Clone)]
+    enum Tok { Zero, Unit(u64), Teen(u64), Tens(u64), Hundred, Scale(u64), And }
+
+    #[derive(Copy, Clone, PartialEq)]
+    enum Phase { Start, Zero, Unit, Teen, Tens, Hundred, Scale, And }
+
+    fn lookup(word: &str) -> Option<Tok> {
+        Some(match word {
+            "zero" => Tok::Zero,
+            "one" => Tok::Unit(1), "two" => Tok::Unit(2), "three" => Tok::Unit(3),
+            "four" => Tok::Unit(4), "five" => Tok::Unit(5), "six" => Tok::Unit(6),
+            "seven" => Tok::Unit(7), "eight" => Tok::Unit(8), "nine" => Tok::Unit(9),
+            "ten" => Tok::Teen(10), "eleven" => Tok::Teen(11), "twelve" => Tok::Teen(12),
+            "thirteen" => Tok::Teen(13), "fourteen" => Tok::Teen(14), "fifteen" => Tok::Teen(15),
+            "sixteen" => Tok::Teen(16), "seventeen" => Tok::Teen(17), "eighteen" => Tok::Teen(18),
+            "nineteen" => Tok::Teen(19),
+            "twenty" => Tok::Tens(20), "thirty" => Tok::Tens(30), "forty" => Tok::Tens(40),
+            "fifty" => Tok::Tens(50), "sixty" => Tok::Tens(60), "seventy" => Tok::Tens(70),
+            "eighty" => Tok::Tens(80), "ninety" => Tok::Tens(90),
+            "hundred" => Tok::Hundred,
+            "thousand" => Tok::Scale(1_000),
+            "million" => Tok::Scale(1_000_000),
+            "billion" => Tok::Scale(1_000_000_000),
+            "trillion" => Tok::Scale(1_000_000_000_000),
+            "quadrillion" => Tok::Scale(1_000_000_000_000_000),
+            "and" => Tok::And,
+            _ => return None
+        })
+    }
+
+    let mut total = 0;
+    let mut group = 0;
+    let mut had_hundred = false;
+    let mut last_scale = ::std::u64::MAX;
+    let mut phase = Phase::Start;
+    let mut pos = 0;
+    let mut end = None;
+
+    loop {
+        let (start, hyphen) = if phase == Phase::Start {
+            (pos, false)
+        } else {
+            let rest = &s[pos..];
+            if rest.starts_with('-') {
+                (pos + 1, true)
+            } else {
+                let ws = rest.len() - rest.trim_left_matches(|c| c == ' ' || c == '\t').len();
+                if ws == 0 { break; }
+                (pos + ws, false)
+            }
+        };
+
+        let len = s[start..].bytes()
+            .take_while(|&b| (b'a' <= b && b <= b'z') || (b'A' <= b && b <= b'Z'))
+            .count();
+        let tok = match lookup(&s[start..start + len].to_lowercase()) {
+            Some(tok) => tok,
+            None => break
+        };
+
+        phase = match (phase, tok) {
+            (Phase::Start, Tok::Zero) => Phase::Zero,
+            (Phase::Tens, Tok::Unit(n)) => { group += n; Phase::Unit },
+            (Phase::Start, Tok::Unit(n))
+            | (Phase::Hundred, Tok::Unit(n))
+            | (Phase::Scale, Tok::Unit(n))
+            | (Phase::And, Tok::Unit(n)) if !hyphen => { group += n; Phase::Unit },
+            (Phase::Start, Tok::Teen(n))
+            | (Phase::Hundred, Tok::Teen(n))
+            | (Phase::Scale, Tok::Teen(n))
+            | (Phase::And, Tok::Teen(n)) if !hyphen => { group += n; Phase::Teen },
+            (Phase::Start, Tok::Tens(n))
+            | (Phase::Hundred, Tok::Tens(n))
+            | (Phase::Scale, Tok::Tens(n))
+            | (Phase::And, Tok::Tens(n)) if !hyphen => { group += n; Phase::Tens },
+            (Phase::Unit, Tok::Hundred)
+            | (Phase::Teen, Tok::Hundred)
+            | (Phase::Tens, Tok::Hundred) if !hyphen && !had_hundred => {
+                group *= 100;
+                had_hundred = true;
+                Phase::Hundred
+            },
+            (Phase::Unit, Tok::Scale(k))
+            | (Phase::Teen, Tok::Scale(k))
+            | (Phase::Tens, Tok::Scale(k))
+            | (Phase::Hundred, Tok::Scale(k)) if !hyphen && k < last_scale => {
+                total += group * k;
+                group = 0;
+                had_hundred = false;
+                last_scale = k;
+                Phase::Scale
+            },
+            (Phase::Hundred, Tok::And)
+            | (Phase::Scale, Tok::And) if !hyphen => Phase::And,
+            _ => break
+        };
+
+        pos = start + len;
+        if phase != Phase::And {
+            end = Some(pos);
+        }
     }
+
+    end.map(|end| (total + group, end))
+}
+
+#[cfg(feature="english-numbers")]
+#[cfg(test)]
+#[test]
+fn test_english_number() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+    let scan = EnglishNumber::scan_from;
+
+    assert_match!(scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("x"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("and"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("tenant"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("zero"), Ok((0, 4)));
+    assert_match!(scan("zero one"), Ok((0, 4)));
+    assert_match!(scan("One"), Ok((1, 3)));
+    assert_match!(scan("twelve apples"), Ok((12, 6)));
+    assert_match!(scan("twenty-three"), Ok((23, 12)));
+    assert_match!(scan("twenty three"), Ok((23, 12)));
+    assert_match!(scan("twenty-"), Ok((20, 6)));
+    assert_match!(scan("one-two"), Ok((1, 3)));
+    assert_match!(scan("one hundred and five"), Ok((105, 20)));
+    assert_match!(scan("one hundred and"), Ok((100, 11)));
+    assert_match!(scan("nineteen hundred eighty-four"), Ok((1984, 28)));
+    assert_match!(scan("two thousand and sixteen"), Ok((2016, 24)));
+    assert_match!(scan("five million three hundred thousand"), Ok((5_300_000, 35)));
+    assert_match!(scan("one thousand one million"), Ok((1_001, 16)));
+    assert_match!(scan("one two"), Ok((1, 3)));
+    assert_match!(scan("twenty thirty"), Ok((20, 6)));
 }
 
 /**
-Scans everything up to the end of the current line, *or* the end of the input, whichever comes first.  The scanned result *does not* include the line terminator.
+Tries to scan a `T`, capturing any failure rather than propagating it.
 
-Note that this is effectively equivalent to the `Everything` matcher when used with `readln!`.
+This always succeeds: if `T` scans, the output is `Ok` with its value and the input is consumed as normal; if it does not, the output is `Err` with the reason and *no* input is consumed.  This allows a record with a malformed field to still be partially extracted, provided the rest of the pattern can carry on from the same position.
+
+```ignore
+let_scan!("1, x, 3"; ([let v: Fallible<i32>]*, ...));
+```
 */
-pub struct Line<'a, Output=&'a str>(PhantomData<(&'a (), Output)>);
+pub struct Fallible<T>(PhantomData<T>);
 
-#[cfg(str_into_output_extra_broken)]
-impl<'a> ScanFromStr<'a> for Line<'a, &'a str> {
-    type Output = &'a str;
+impl<'a, T> ScanFromStr<'a> for Fallible<T> where T: ScanFromStr<'a> {
+    type Output = Result<T::Output, ScanError>;
     fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
-        let s = s.as_str();
-        let (a, b) = match_line(s);
-        Ok((s[..a].into(), b))
+        match T::scan_from(s) {
+            Ok((v, n)) => Ok((Ok(v), n)),
+            Err(err) => Ok((Err(err), 0)),
+        }
+    }
+
+    fn wants_leading_junk_stripped() -> bool {
+        T::wants_leading_junk_stripped()
     }
 }
 
-#[cfg(str_into_output_extra_broken)]
-impl<'a> ScanFromStr<'a> for Line<'a, String> {
-    type Output = String;
+#[cfg(test)]
+#[test]
+fn test_fallible() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+
+    let scan = Fallible::<i32>::scan_from;
+    assert_match!(scan(""), Ok((Err(SE { kind: SEK::Syntax(_), .. }), 0)));
+    assert_match!(scan("x"), Ok((Err(SE { kind: SEK::Syntax(_), .. }), 0)));
+    assert_match!(scan("-12 x"), Ok((Ok(-12), 3)));
+    assert_match!(scan("99999999999"), Ok((Err(SE { kind: SEK::Overflow(_), .. }), 0)));
+}
+
+/**
+Scans a floating point number written using Rust literal syntax into `Output`.
+
+This accepts everything the `f32` and `f64` scanners do, as well as `_` separators between digits, such as in "1_000_000.5" or "6.022_140e23".  Type suffixes like `f32` are *not* accepted.
+*/
+pub struct FloatLiteral<Output=f64>(PhantomData<Output>);
+
+impl<'a, Output> ScanFromStr<'a> for FloatLiteral<Output>
+where Output: FromStr<Err=ParseFloatError> {
+    type Output = Output;
     fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
         let s = s.as_str();
-        let (a, b) = match_line(s);
-        Ok((s[..a].into(), b))
+        let n = match match_float_with(s, true) {
+            Some((_, n)) => n,
+            None => return Err(ScanError::syntax("expected floating point number")),
+        };
+
+        let lit = &s[..n];
+        let v = if lit.contains('_') {
+            lit.replace("_", "").parse()
+        } else {
+            lit.parse()
+        };
+
+        v.map(|v| (v, n)).map_err(ScanError::float)
     }
 }
 
-#[cfg(not(str_into_output_extra_broken))]
-impl<'a, Output> ScanFromStr<'a> for Line<'a, Output> where &'a str: Into<Output> {
+#[cfg(test)]
+#[test]
+fn test_float_literal() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+    let scan = <FloatLiteral>::scan_from;
+
+    assert_match!(scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("_1"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("42"), Ok((42.0, 2)));
+    assert_match!(scan("1_000_000.5"), Ok((1000000.5, 11)));
+    assert_match!(scan("-6.022_140e2_3"), Ok((-6.022140e23, 14)));
+    assert_match!(scan("1__0."), Ok((10.0, 5)));
+    assert_match!(scan("1_f32"), Ok((1.0, 2)));
+    assert_match!(FloatLiteral::<f32>::scan_from("2.5"), Ok((2.5, 3)));
+}
+
+/**
+Scans the given `Output` type from its hexadecimal representation.
+
+This also works for `f32` and `f64`, which are scanned from hexadecimal floating point notation, such as "0x1.8p+1".  See [`ScanFromHex`](trait.ScanFromHex.html).
+*/
+pub struct Hex<Output>(PhantomData<Output>);
+
+impl<'a, Output> ScanFromStr<'a> for Hex<Output>
+where Output: ScanFromHex<'a> {
     type Output = Output;
     fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
-        let s = s.as_str();
-        let (a, b) = match_line(s);
-        Ok((s[..a].into(), b))
+        Output::scan_from_hex(s)
     }
 }
 
-fn match_line(s: &str) -> (usize, usize) {
-    let mut ibs = s.bytes().enumerate();
+#[cfg(test)]
+#[test]
+fn test_hex() {
+    assert_match!(Hex::<i32>::scan_from("0 1 2 x"), Ok((0x0, 1)));
+    assert_match!(Hex::<i32>::scan_from("012x"), Ok((0x12, 3)));
+    assert_match!(Hex::<i32>::scan_from("0x012x"), Ok((0x0, 1)));
+    assert_match!(Hex::<i32>::scan_from("BadCafé"), Ok((0xbadcaf, 6)));
+    assert_match!(Hex::<f64>::scan_from("-0x1.4p+2,"), Ok((-5.0, 9)));
+}
+
+/**
+Scans a single line of a hex dump, as produced by `xxd` or `hexdump -C`, into the line's offset and the bytes it contains.
 
-    let line_end;
+Both of the following forms are understood:
 
-    loop {
-        match ibs.next() {
-            Some((i, b'\r')) => {
-                line_end = i;
+```text
+00000000: 4865 6c6c 6f2c 2077 6f72 6c64 210a       Hello, world!.
+00000000  48 65 6c 6c 6f 2c 20 77  6f 72 6c 64 21 0a        |Hello, world!.|
+```
+
+The offset is in hexadecimal, optionally followed by a colon.  Bytes are written as pairs of hex digits, which may be grouped together.  The ASCII column is skipped without being checked; it runs up to (but not including) the end of the line.  In the `xxd` form (*i.e.* with a colon after the offset), the byte columns end at the first run of two or more spaces.
+*/
+pub enum HexDumpLine {}
+
+impl<'a> ScanFromStr<'a> for HexDumpLine {
+    type Output = (u64, Vec<u8>);
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let bs = s.as_str().as_bytes();
+        let mut i = 0;
+
+        let mut offset: u64 = 0;
+        while let Some(d) = bs.get(i).and_then(|&b| hex_digit(b)) {
+            if i == 16 {
+                return Err(ScanError::syntax("hex dump offset too large"));
+            }
+            offset = (offset << 4) | d as u64;
+            i += 1;
+        }
+        if i == 0 {
+            return Err(ScanError::syntax("expected hex dump offset"));
+        }
+
+        let xxd = bs.get(i) == Some(&b':');
+        if xxd {
+            i += 1;
+        }
+
+        let mut bytes = vec![];
+        loop {
+            let spaces_start = i;
+            while bs.get(i) == Some(&b' ') {
+                i += 1;
+            }
+            let spaces = i - spaces_start;
+            if spaces == 0 || (xxd && spaces > 1 && bytes.len() > 0) {
                 break;
-            },
-            Some((i, b'\n')) => return (i, i+1),
-            Some(_) => (),
-            None => return (s.len(), s.len()),
+            }
+
+            let group_start = i;
+            while i + 1 < bs.len() {
+                match (hex_digit(bs[i]), hex_digit(bs[i + 1])) {
+                    (Some(hi), Some(lo)) => bytes.push((hi << 4) | lo),
+                    _ => break,
+                }
+                i += 2;
+            }
+            if i == group_start {
+                break;
+            }
+
+            match bs.get(i) {
+                None | Some(&b' ') | Some(&b'\r') | Some(&b'\n') => (),
+                Some(_) => return Err(ScanError::syntax("malformed byte in hex dump")),
+            }
         }
+
+        // Skip the ASCII column.
+        i += ::fast::find_line_break(&bs[i..]).unwrap_or(bs.len() - i);
+
+        Ok(((offset, bytes), i))
     }
+}
 
-    // If we get here, it's because we found an `\r` and need to look for an `\n`.
-    if let Some((_, b'\n')) = ibs.next() {
-        (line_end, line_end + 2)
-    } else {
-        (line_end, line_end + 1)
+fn hex_digit(b: u8) -> Option<u8> {
+    match b {
+        b'0'...b'9' => Some(b - b'0'),
+        b'a'...b'f' => Some(b - b'a' + 10),
+        b'A'...b'F' => Some(b - b'A' + 10),
+        _ => None,
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_line() {
-    assert_match!(Line::<&str>::scan_from(""), Ok(("", 0)));
-    assert_match!(Line::<&str>::scan_from("abc def"), Ok(("abc def", 7)));
-    assert_match!(Line::<&str>::scan_from("abc\ndef"), Ok(("abc", 4)));
-    assert_match!(Line::<&str>::scan_from("abc\r\ndef"), Ok(("abc", 5)));
-    assert_match!(Line::<&str>::scan_from("abc\rdef"), Ok(("abc", 4)));
+fn test_hex_dump_line() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+
+    let scan = HexDumpLine::scan_from;
+    const HELLO: &'static [u8] = b"Hello, world!\n";
+
+    assert_match!(scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("xyz"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("00000000: 4865 6c6c 6f2c 2077 6f72 6c64 210a       Hello, world!.\nx"),
+        Ok(((0, ref v), 65)) if *v == HELLO);
+    assert_match!(scan("00000010: 6361 6665 6361 6665 6361 6665 6361 6665  cafecafecafecafe"),
+        Ok(((0x10, ref v), 67)) if *v == b"cafecafecafecafe");
+    assert_match!(scan("00000000  48 65 6c 6c 6f 2c 20 77  6f 72 6c 64 21 0a        |Hello, world!.|\r\n"),
+        Ok(((0, ref v), 76)) if *v == HELLO);
+    assert_match!(scan("0000000e"), Ok(((0xe, ref v), 8)) if v.is_empty());
+    assert_match!(scan("00000000: 486"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("11112222333344445: 00"), Err(SE { kind: SEK::Syntax(_), .. }));
 }
 
 /**
-Scans a single newline into a string.
+Scans a git reference name, such as `refs/heads/master`, `origin/feature/x`, or `v1.0`, into a string.
 
-This *will not* match an empty sequence, and will not match more than one newline.
+The name runs up to the first character which cannot appear in a reference name (whitespace, control characters, or any of `~^:?*[\`), and must then be valid according to the rules of `git check-ref-format --allow-onelevel`: no component may be empty, begin with `.`, or end with `.lock`; the name cannot contain `..` or `@{`, end with `.`, or be exactly `@`.
 */
-pub struct Newline<'a, Output=&'a str>(PhantomData<(&'a (), Output)>);
+pub struct GitRef<'a, Output=&'a str>(PhantomData<(&'a (), Output)>);
 
 // FIXME: Error message omitted due to https://github.com/rust-lang/rust/issues/26448.
 #[cfg(str_into_output_extra_broken)]
-impl<'a> ScanFromStr<'a> for Newline<'a, &'a str> {
+impl<'a> ScanFromStr<'a> for GitRef<'a, &'a str> {
     type Output = &'a str;
     fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
         let s = s.as_str();
-        match match_newline(s) {
-            Some(b) => {
-                let word = &s[..b];
-                let tail = &s[b..];
-                Ok((word.into(), s.subslice_offset_stable(tail).unwrap()))
-            },
-            // None => Err(ScanError::syntax("expected at least one non-space character")),
-            None => Err(ScanError::syntax_no_message())
+        match match_git_ref(s) {
+            Some(b) => Ok((s[..b].into(), b)),
+            // None => Err(ScanError::syntax("expected git reference name")),
+            None => Err(ScanError::syntax_no_message()),
         }
     }
-
-    fn wants_leading_junk_stripped() -> bool { false }
 }
 
 // FIXME: Error message omitted due to https://github.com/rust-lang/rust/issues/26448.
 #[cfg(str_into_output_extra_broken)]
-impl<'a> ScanFromStr<'a> for Newline<'a, String> {
+impl<'a> ScanFromStr<'a> for GitRef<'a, String> {
     type Output = String;
     fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
         let s = s.as_str();
-        match match_newline(s) {
-            Some(b) => {
-                let word = &s[..b];
-                let tail = &s[b..];
-                Ok((word.into(), s.subslice_offset_stable(tail).unwrap()))
-            },
-            // None => Err(ScanError::syntax("expected at least one non-space character")),
-            None => Err(ScanError::syntax_no_message())
+        match match_git_ref(s) {
+            Some(b) => Ok((s[..b].into(), b)),
+            // None => Err(ScanError::syntax("expected git reference name")),
+            None => Err(ScanError::syntax_no_message()),
         }
     }
+}
 
-    fn wants_leading_junk_stripped() -> bool { false }
+// FIXME: Error message omitted due to https://github.com/rust-lang/rust/issues/26448.
+#[cfg(str_into_output_extra_broken)]
+impl<'a> ScanFromStr<'a> for GitRef<'a, Cow<'a, str>> {
+    type Output = Cow<'a, str>;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        match match_git_ref(s) {
+            Some(b) => Ok((s[..b].into(), b)),
+            // None => Err(ScanError::syntax("expected git reference name")),
+            None => Err(ScanError::syntax_no_message()),
+        }
+    }
 }
 
 // FIXME: Error message omitted due to https://github.com/rust-lang/rust/issues/26448.
 #[cfg(not(str_into_output_extra_broken))]
-impl<'a, Output> ScanFromStr<'a> for Newline<'a, Output>
+impl<'a, Output> ScanFromStr<'a> for GitRef<'a, Output>
 where &'a str: Into<Output> {
     type Output = Output;
     fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
         let s = s.as_str();
-        match match_newline(s) {
-            Some(b) => {
-                let word = &s[..b];
-                let tail = &s[b..];
-                Ok((word.into(), s.subslice_offset_stable(tail).unwrap()))
-            },
-            // None => Err(ScanError::syntax("expected at least one non-space character")),
-            None => Err(ScanError::syntax_no_message())
+        match match_git_ref(s) {
+            Some(b) => Ok((s[..b].into(), b)),
+            // None => Err(ScanError::syntax("expected git reference name")),
+            None => Err(ScanError::syntax_no_message()),
         }
     }
-
-    fn wants_leading_junk_stripped() -> bool { false }
 }
 
-fn match_newline(s: &str) -> Option<usize> {
-    // See: <http://www.unicode.org/reports/tr18/#RL1.6>.
-    println!("match_newline({:?})", s);
-    let mut cis = s.char_indices();
+fn match_git_ref(s: &str) -> Option<usize> {
+    let len = s.bytes()
+        .take_while(|&b| match b {
+            0...b' ' | 0x7f | b'~' | b'^' | b':' | b'?' | b'*' | b'[' | b'\\' => false,
+            _ => true,
+        })
+        .count();
+    let name = &s[..len];
 
-    let r = match cis.next() {
-        Some((_, '\x0a')) => Some(1),
-        Some((_, '\x0b')) => Some(1),
-        Some((_, '\x0c')) => Some(1),
-        Some((_, '\x0d')) => match cis.next() {
-            Some((_, '\x0a')) => Some(2),
-            _ => Some(1)
-        },
-        Some((_, c @ '\u{85}')) => Some(c.len_utf8()),
-        Some((_, c @ '\u{2028}')) => Some(c.len_utf8()),
-        Some((_, c @ '\u{2029}')) => Some(c.len_utf8()),
-        _ => None
-    };
+    if name.is_empty() || name == "@" || name.ends_with('.')
+        || name.contains("..") || name.contains("@{")
+    {
+        return None;
+    }
 
-    println!("-> {:?}", r);
-    r
+    for comp in name.split('/') {
+        if comp.is_empty() || comp.starts_with('.') || comp.ends_with(".lock") {
+            return None;
+        }
+    }
+
+    Some(len)
 }
 
 #[cfg(test)]
 #[test]
-fn test_newline() {
+fn test_git_ref() {
     use ::ScanError as SE;
     use ::ScanErrorKind as SEK;
-
-    assert_match!(Newline::<&str>::scan_from(""), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
-    assert_match!(Newline::<&str>::scan_from("x"), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
-    assert_match!(Newline::<&str>::scan_from("\rx"), Ok(("\r", 1)));
-    assert_match!(Newline::<&str>::scan_from("\nx"), Ok(("\n", 1)));
-    assert_match!(Newline::<&str>::scan_from("\r\nx"), Ok(("\r\n", 2)));
-    assert_match!(Newline::<&str>::scan_from("\n\rx"), Ok(("\n", 1)));
+    let scan = GitRef::<&str>::scan_from;
+
+    assert_match!(scan(""), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
+    assert_match!(scan("master"), Ok(("master", 6)));
+    assert_match!(scan("refs/heads/feature/x y"), Ok(("refs/heads/feature/x", 20)));
+    assert_match!(scan("origin/master\tabc"), Ok(("origin/master", 13)));
+    assert_match!(scan("refs/tags/v1.0^{}"), Ok(("refs/tags/v1.0", 14)));
+    assert_match!(scan("HEAD~2"), Ok(("HEAD", 4)));
+    assert_match!(scan("a@b/ü-x_y+z"), Ok(("a@b/ü-x_y+z", 12)));
+    assert_match!(scan("@"), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
+    assert_match!(scan("/master"), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
+    assert_match!(scan("refs/heads/"), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
+    assert_match!(scan("refs//heads"), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
+    assert_match!(scan("refs/.hidden"), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
+    assert_match!(scan("master.lock"), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
+    assert_match!(scan("master."), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
+    assert_match!(scan("a..b"), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
+    assert_match!(scan("master@{1}"), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
+    assert_match!(GitRef::<String>::scan_from("main"), Ok((ref v, 4)) if v == "main");
 }
 
 /**
-Scans a sequence of non-space characters into a string.
+Scans a git object name, such as `8f3c2a1` or `8f3c2a1d9e6b...`, into a string.
 
-This *will not* match an empty sequence; there must be at least one non-space character for the scan to succeed.
+This matches between 7 and 40 hexadecimal digits (*i.e.* an abbreviated or full SHA-1 hash), which must not be immediately followed by another letter, digit, or underscore.
 */
-pub struct NonSpace<'a, Output=&'a str>(PhantomData<(&'a (), Output)>);
+pub struct GitSha<'a, Output=&'a str>(PhantomData<(&'a (), Output)>);
 
 // FIXME: Error message omitted due to https://github.com/rust-lang/rust/issues/26448.
 #[cfg(str_into_output_extra_broken)]
-impl<'a> ScanFromStr<'a> for NonSpace<'a, &'a str> {
+impl<'a> ScanFromStr<'a> for GitSha<'a, &'a str> {
     type Output = &'a str;
     fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
         let s = s.as_str();
-        match match_non_space(s) {
-            Some(b) => {
-                let word = &s[..b];
-                let tail = &s[b..];
-                Ok((word.into(), s.subslice_offset_stable(tail).unwrap()))
-            },
-            // None => Err(ScanError::syntax("expected at least one non-space character")),
-            None => Err(ScanError::syntax_no_message())
+        match match_git_sha(s) {
+            Some(b) => Ok((s[..b].into(), b)),
+            // None => Err(ScanError::syntax("expected git object name")),
+            None => Err(ScanError::syntax_no_message()),
         }
     }
 }
 
 // FIXME: Error message omitted due to https://github.com/rust-lang/rust/issues/26448.
 #[cfg(str_into_output_extra_broken)]
-impl<'a> ScanFromStr<'a> for NonSpace<'a, String> {
+impl<'a> ScanFromStr<'a> for GitSha<'a, String> {
     type Output = String;
     fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
         let s = s.as_str();
-        match match_non_space(s) {
-            Some(b) => {
-                let word = &s[..b];
-                let tail = &s[b..];
-                Ok((word.into(), s.subslice_offset_stable(tail).unwrap()))
-            },
-            // None => Err(ScanError::syntax("expected at least one non-space character")),
-            None => Err(ScanError::syntax_no_message())
+        match match_git_sha(s) {
+            Some(b) => Ok((s[..b].into(), b)),
+            // None => Err(ScanError::syntax("expected git object name")),
+            None => Err(ScanError::syntax_no_message()),
+        }
+    }
+}
+
+// FIXME: Error message omitted due to https://github.com/rust-lang/rust/issues/26448.
+#[cfg(str_into_output_extra_broken)]
+impl<'a> ScanFromStr<'a> for GitSha<'a, Cow<'a, str>> {
+    type Output = Cow<'a, str>;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        match match_git_sha(s) {
+            Some(b) => Ok((s[..b].into(), b)),
+            // None => Err(ScanError::syntax("expected git object name")),
+            None => Err(ScanError::syntax_no_message()),
         }
     }
 }
 
 // FIXME: Error message omitted due to https://github.com/rust-lang/rust/issues/26448.
 #[cfg(not(str_into_output_extra_broken))]
-impl<'a, Output> ScanFromStr<'a> for NonSpace<'a, Output>
+impl<'a, Output> ScanFromStr<'a> for GitSha<'a, Output>
 where &'a str: Into<Output> {
     type Output = Output;
     fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
         let s = s.as_str();
-        match match_non_space(s) {
+        match match_git_sha(s) {
+            Some(b) => Ok((s[..b].into(), b)),
+            // None => Err(ScanError::syntax("expected git object name")),
+            None => Err(ScanError::syntax_no_message()),
+        }
+    }
+}
+
+fn match_git_sha(s: &str) -> Option<usize> {
+    let bs = s.as_bytes();
+    let len = bs.iter().take(40).take_while(|&&b| hex_digit(b).is_some()).count();
+    if len < 7 {
+        return None;
+    }
+    match bs.get(len) {
+        Some(&b) if b == b'_' || (b as char).is_alphanumeric() => None,
+        _ => Some(len),
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_git_sha() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+    let scan = GitSha::<&str>::scan_from;
+    const FULL: &'static str = "0123456789abcdef0123456789ABCDEF01234567";
+
+    assert_match!(scan(""), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
+    assert_match!(scan("abc123"), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
+    assert_match!(scan("abc1234"), Ok(("abc1234", 7)));
+    assert_match!(scan("abc1234 refs/heads/master"), Ok(("abc1234", 7)));
+    assert_match!(scan("abc1234..def5678"), Ok(("abc1234", 7)));
+    assert_match!(scan(FULL), Ok((sha, 40)) if sha == FULL);
+    assert_match!(scan("0123456789abcdef0123456789abcdef012345678"), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
+    assert_match!(scan("abc1234g"), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
+    assert_match!(scan("deadbeef_x"), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
+    assert_match!(GitSha::<String>::scan_from("deadbeef\t"), Ok((ref v, 8)) if v == "deadbeef");
+}
+
+/**
+Scans a sequence of horizontal (non-newline) space characters into a string.
+
+This *will not* match an empty sequence; there must be at least one space character for the scan to succeed.
+*/
+pub struct HorSpace<'a, Output=&'a str>(PhantomData<(&'a (), Output)>);
+
+// FIXME: Error message omitted due to https://github.com/rust-lang/rust/issues/26448.
+#[cfg(str_into_output_extra_broken)]
+impl<'a> ScanFromStr<'a> for HorSpace<'a, &'a str> {
+    type Output = &'a str;
+
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        match match_hor_space(s) {
             Some(b) => {
                 let word = &s[..b];
                 let tail = &s[b..];
                 Ok((word.into(), s.subslice_offset_stable(tail).unwrap()))
             },
-            // None => Err(ScanError::syntax("expected at least one non-space character")),
-            None => Err(ScanError::syntax_no_message())
+            // None => Err(ScanError::syntax("expected a space")),
+            None => Err(ScanError::syntax_no_message()),
         }
     }
+
+    fn wants_leading_junk_stripped() -> bool { false }
 }
 
-fn match_non_space(s: &str) -> Option<usize> {
+// FIXME: Error message omitted due to https://github.com/rust-lang/rust/issues/26448.
+#[cfg(str_into_output_extra_broken)]
+impl<'a> ScanFromStr<'a> for HorSpace<'a, String> {
+    type Output = String;
+
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        match match_hor_space(s) {
+            Some(b) => {
+                let word = &s[..b];
+                let tail = &s[b..];
+                Ok((word.into(), s.subslice_offset_stable(tail).unwrap()))
+            },
+            // None => Err(ScanError::syntax("expected a space")),
+            None => Err(ScanError::syntax_no_message()),
+        }
+    }
+
+    fn wants_leading_junk_stripped() -> bool { false }
+}
+
+// FIXME: Error message omitted due to https://github.com/rust-lang/rust/issues/26448.
+#[cfg(str_into_output_extra_broken)]
+impl<'a> ScanFromStr<'a> for HorSpace<'a, Cow<'a, str>> {
+    type Output = Cow<'a, str>;
+
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        match match_hor_space(s) {
+            Some(b) => {
+                let word = &s[..b];
+                let tail = &s[b..];
+                Ok((word.into(), s.subslice_offset_stable(tail).unwrap()))
+            },
+            // None => Err(ScanError::syntax("expected a space")),
+            None => Err(ScanError::syntax_no_message()),
+        }
+    }
+
+    fn wants_leading_junk_stripped() -> bool { false }
+}
+
+// FIXME: Error message omitted due to https://github.com/rust-lang/rust/issues/26448.
+#[cfg(not(str_into_output_extra_broken))]
+impl<'a, Output> ScanFromStr<'a> for HorSpace<'a, Output>
+where &'a str: Into<Output> {
+    type Output = Output;
+
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        match match_hor_space(s) {
+            Some(b) => {
+                let word = &s[..b];
+                let tail = &s[b..];
+                Ok((word.into(), s.subslice_offset_stable(tail).unwrap()))
+            },
+            // None => Err(ScanError::syntax("expected a space")),
+            None => Err(ScanError::syntax_no_message()),
+        }
+    }
+
+    fn wants_leading_junk_stripped() -> bool { false }
+}
+
+fn match_hor_space(s: &str) -> Option<usize> {
     use ::util::TableUtil;
     use ::unicode::property::White_Space_table as WS;
 
     s.char_indices()
-        .take_while(|&(_, c)| !WS.span_table_contains(&c))
+        .take_while(|&(_, c)| match c {
+            '\x0a'...'\x0d' | '\u{85}' | '\u{2028}' | '\u{2029}' => false,
+            c => WS.span_table_contains(&c)
+        })
         .map(|(i, c)| i + c.len_utf8())
         .last()
 }
 
 #[cfg(test)]
 #[test]
-fn test_non_space() {
+fn test_hor_space() {
     use ::ScanError as SE;
     use ::ScanErrorKind as SEK;
 
-    assert_match!(NonSpace::<&str>::scan_from(""), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
-    assert_match!(NonSpace::<&str>::scan_from(" abc "), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
-    assert_match!(NonSpace::<&str>::scan_from("abc "), Ok(("abc", 3)));
-    assert_match!(NonSpace::<&str>::scan_from("abc\t"), Ok(("abc", 3)));
-    assert_match!(NonSpace::<&str>::scan_from("abc\r"), Ok(("abc", 3)));
-    assert_match!(NonSpace::<&str>::scan_from("abc\n"), Ok(("abc", 3)));
-    assert_match!(NonSpace::<&str>::scan_from("abc\u{a0}"), Ok(("abc", 3)));
-    assert_match!(NonSpace::<&str>::scan_from("abc\u{2003}"), Ok(("abc", 3)));
-    assert_match!(NonSpace::<&str>::scan_from("abc\u{200B}"), Ok(("abc\u{200b}", 6)));
-    assert_match!(NonSpace::<&str>::scan_from("abc\u{3000}"), Ok(("abc", 3)));
+    assert_match!(HorSpace::<&str>::scan_from(""), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
+    assert_match!(HorSpace::<&str>::scan_from("a"), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
+    assert_match!(HorSpace::<&str>::scan_from("0"), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
+    assert_match!(HorSpace::<&str>::scan_from(" "), Ok((" ", 1)));
+    assert_match!(HorSpace::<&str>::scan_from("\t"), Ok(("\t", 1)));
+    assert_match!(HorSpace::<&str>::scan_from("\r"), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
+    assert_match!(HorSpace::<&str>::scan_from("\n"), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
+    assert_match!(HorSpace::<&str>::scan_from("\r\n"), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
+    assert_match!(HorSpace::<&str>::scan_from("  \t \n \t\t "), Ok(("  \t ", 4)));
 }
 
 /**
-Scans a single number into a string.
-
-Specifically, this will match a continuous run of decimal characters (*i.e.* /`\d+`/).
+Scans a single identifier into a string.
 
-Note that this *includes* non-ASCII decimal characters, meaning it will scan numbers such as "42", "１７０１", and "𐒩０꘠᧑".
+Specifically, this will match a single `XID_Start` character (or underscore) followed by zero or more `XID_Continue` characters.
 */
-pub struct Number<'a, Output=&'a str>(PhantomData<(&'a (), Output)>);
+pub struct Ident<'a, Output=&'a str>(PhantomData<(&'a (), Output)>);
 
 // FIXME: Error message omitted due to https://github.com/rust-lang/rust/issues/26448.
 #[cfg(str_into_output_extra_broken)]
-impl<'a> ScanFromStr<'a> for Number<'a, &'a str> {
+impl<'a> ScanFromStr<'a> for Ident<'a, &'a str> {
     type Output = &'a str;
     fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
         let s = s.as_str();
-        match match_number(s) {
+        match match_ident(s) {
             Some(b) => {
                 let word = &s[..b];
                 let tail = &s[b..];
                 Ok((word.into(), s.subslice_offset_stable(tail).unwrap()))
             },
-            // None => Err(ScanError::syntax("expected a number")),
-            None => Err(ScanError::syntax_no_message()),
+            None => {
+                // Err(ScanError::syntax("expected identifier"))
+                Err(ScanError::syntax_no_message())
+            },
         }
     }
 }
 
 // FIXME: Error message omitted due to https://github.com/rust-lang/rust/issues/26448.
 #[cfg(str_into_output_extra_broken)]
-impl<'a> ScanFromStr<'a> for Number<'a, String> {
+impl<'a> ScanFromStr<'a> for Ident<'a, String> {
     type Output = String;
     fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
         let s = s.as_str();
-        match match_number(s) {
+        match match_ident(s) {
             Some(b) => {
                 let word = &s[..b];
                 let tail = &s[b..];
                 Ok((word.into(), s.subslice_offset_stable(tail).unwrap()))
             },
-            // None => Err(ScanError::syntax("expected a number")),
-            None => Err(ScanError::syntax_no_message()),
+            None => {
+                // Err(ScanError::syntax("expected identifier"))
+                Err(ScanError::syntax_no_message())
+            },
         }
     }
 }
 
 // FIXME: Error message omitted due to https://github.com/rust-lang/rust/issues/26448.
+#[cfg(str_into_output_extra_broken)]
+impl<'a> ScanFromStr<'a> for Ident<'a, Cow<'a, str>> {
+    type Output = Cow<'a, str>;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        match match_ident(s) {
+            Some(b) => {
+                let word = &s[..b];
+                let tail = &s[b..];
+                Ok((word.into(), s.subslice_offset_stable(tail).unwrap()))
+            },
+            None => {
+                // Err(ScanError::syntax("expected identifier"))
+                Err(ScanError::syntax_no_message())
+            },
+        }
+    }
+}
+
 #[cfg(not(str_into_output_extra_broken))]
-impl<'a, Output> ScanFromStr<'a> for Number<'a, Output>
+// FIXME: Error message omitted due to https://github.com/rust-lang/rust/issues/26448.
+impl<'a, Output> ScanFromStr<'a> for Ident<'a, Output>
 where &'a str: Into<Output> {
     type Output = Output;
     fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
         let s = s.as_str();
-        match match_number(s) {
+        match match_ident(s) {
             Some(b) => {
                 let word = &s[..b];
                 let tail = &s[b..];
                 Ok((word.into(), s.subslice_offset_stable(tail).unwrap()))
             },
-            // None => Err(ScanError::syntax("expected a number")),
-            None => Err(ScanError::syntax_no_message()),
+            None => {
+                // Err(ScanError::syntax("expected identifier"))
+                Err(ScanError::syntax_no_message())
+            },
         }
     }
 }
 
-fn match_number(s: &str) -> Option<usize> {
-    use ::util::TableUtil;
-    use ::unicode::general_category::Nd_table as Nd;
+fn match_ident(s: &str) -> Option<usize> {
+    use ::util::TableUtil;
+    use ::unicode::derived_property::{XID_Continue_table, XID_Start_table};
+
+    let mut ics = s.char_indices();
+
+    let first_len = match ics.next() {
+        Some((_, '_')) => 1,
+        Some((_, c)) if XID_Start_table.span_table_contains(&c) => c.len_utf8(),
+        _ => return None,
+    };
+
+    let len = ics
+        .take_while(|&(_, c)| XID_Continue_table.span_table_contains(&c))
+        .map(|(i, c)| i + c.len_utf8())
+        .last()
+        .unwrap_or(first_len);
+
+    Some(len)
+}
+
+#[cfg(test)]
+#[test]
+fn test_ident() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+
+    assert_eq!(match_ident("a"), Some(1));
+
+    assert_match!(Ident::<&str>::scan_from(""), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
+    assert_match!(Ident::<&str>::scan_from("a"), Ok(("a", 1)));
+    assert_match!(Ident::<&str>::scan_from("two words "), Ok(("two", 3)));
+    assert_match!(Ident::<&str>::scan_from("two_words "), Ok(("two_words", 9)));
+    assert_match!(Ident::<&str>::scan_from("0123abc456 "), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
+    assert_match!(Ident::<&str>::scan_from("_0123abc456 "), Ok(("_0123abc456", 11)));
+    assert_match!(Ident::<&str>::scan_from("f(blah)"), Ok(("f", 1)));
+}
+
+/**
+Explicitly infer the type of a scanner.
+
+This is useful in cases where you want to only *partially* specify a scanner type, but the partial type cannot be inferred under normal circumstances.
+
+For example, tuples allow their element types to scan to be abstract scanners; *e.g.* `(Word<String>, Hex<i32>)` will scan to `(String, i32)`.  However, this interferes with inferring the scanner type when you *partially* specify a tuple type.  If you attempt to store the result of scanning `(_, _)` into a `(String, i32)`, Rust cannot determine whether the *scanner* type should be `(String, Hex<i32>)`, or `(Word<String>, i32)`, or something else entirely.
+
+This scanner, then, *requires* that the inner type scan to itself and *only* to itself.
+*/
+pub struct Inferred<T>(PhantomData<T>);
+
+impl<'a, T> ScanFromStr<'a> for Inferred<T>
+where T: ScanSelfFromStr<'a> {
+    type Output = T;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        T::scan_from(s)
+    }
+}
+
+/**
+Scans a run of integers separated by whitespace and/or commas into a `Vec<T>`.
+
+This is a specialised fast path for large, simple numeric inputs.  It behaves much like a repeated `let` binding, but makes a single pass over the input bytes rather than using the general repetition machinery, making it considerably faster on very large inputs.
+
+Each integer is an optional sign followed by ASCII decimal digits.  Separators may be any mix of ASCII whitespace (*including* line breaks) and commas.  Scanning stops at the first thing which is not an integer; any separator before it is not consumed.
+*/
+pub struct IntList<T>(PhantomData<T>);
+
+impl<'a, T> ScanFromStr<'a> for IntList<T>
+where T: FromStr<Err=ParseIntError> {
+    type Output = Vec<T>;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        let bs = s.as_bytes();
+        let mut vs = Vec::new();
+        let mut i = 0;
+        let mut end = 0;
+
+        loop {
+            let start = i;
+            if i < bs.len() && (bs[i] == b'-' || bs[i] == b'+') {
+                i += 1;
+            }
+            let digits = i;
+            while i < bs.len() && b'0' <= bs[i] && bs[i] <= b'9' {
+                i += 1;
+            }
+            if i == digits {
+                break;
+            }
+
+            match s[start..i].parse() {
+                Ok(v) => vs.push(v),
+                Err(err) => return Err(ScanError::int(err).add_offset(start)),
+            }
+            end = i;
+
+            while i < bs.len() && match bs[i] { b' ' | b'\t' | b'\n' | b'\r' | b',' => true, _ => false } {
+                i += 1;
+            }
+        }
+
+        if vs.is_empty() {
+            return Err(ScanError::syntax("expected integer"));
+        }
+
+        Ok((vs, end))
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_int_list() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+    let scan = IntList::<i32>::scan_from;
+
+    assert_match!(scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("x"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("-"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("1"), Ok((ref vs, 1)) if *vs == [1]);
+    assert_match!(scan("1 2 3"), Ok((ref vs, 5)) if *vs == [1, 2, 3]);
+    assert_match!(scan("1,2, -3\n+4 ,x"), Ok((ref vs, 10)) if *vs == [1, 2, -3, 4]);
+    assert_match!(scan("1 2 x"), Ok((ref vs, 3)) if *vs == [1, 2]);
+    assert_match!(scan("1 2.5"), Ok((ref vs, 3)) if *vs == [1, 2]);
+    assert_match!(scan("1 99999999999"), Err(SE { ref at, kind: SEK::Int(_), .. }) if at.offset() == 2);
+    assert_match!(IntList::<u8>::scan_from("1 -2"), Err(SE { kind: SEK::Int(_), .. }));
+}
+
+/**
+Scans a single alphabetic character.
+
+This uses the Unicode `Alphabetic` property, so it matches letters from any script, not just `a` through `z`.
+*/
+pub enum Letter {}
+
+impl<'a> ScanFromStr<'a> for Letter {
+    type Output = char;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        match s.as_str().chars().next() {
+            Some(c) if c.is_alphabetic() => Ok((c, c.len_utf8())),
+            _ => Err(ScanError::syntax("expected letter")),
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_letter() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+
+    assert_match!(Letter::scan_from(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(Letter::scan_from("1"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(Letter::scan_from("_"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(Letter::scan_from("e4"), Ok(('e', 1)));
+    assert_match!(Letter::scan_from("Ωmega"), Ok(('Ω', 2)));
+    assert_match!(Letter::scan_from("日本"), Ok(('日', 3)));
+}
+
+/**
+Scans a "plain" list of `T`s, without any enclosing brackets, outputting them as a `Vec`.
+
+Elements are separated according to `Sep`, which must implement [`ListSeparator`](trait.ListSeparator.html).  By default, elements are separated only by whitespace.  Scanning stops at the first point at which another element cannot be scanned; an empty list is allowed.  A separator *must* be followed by another element.
+
+```ignore
+let_scan!("foo bar baz"; (let words: ListOf<Word<String>>));
+let_scan!("1, 2, 3"; (let nums: ListOf<i32, CommaSep>));
+```
+*/
+pub struct ListOf<T, Sep=SpaceSep>(PhantomData<(T, Sep)>);
+
+impl<'a, T, Sep> ScanFromStr<'a> for ListOf<T, Sep>
+where T: ScanFromStr<'a>, Sep: ListSeparator {
+    type Output = Vec<T::Output>;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        use ::input::ScanCursor;
+        match Sep::separator() {
+            Some(sep) => scan!(s.to_cursor(); ([let vs: T](sep)*, ^..tail) => (vs, tail.offset())),
+            None => scan!(s.to_cursor(); ([let vs: T]*, ^..tail) => (vs, tail.offset())),
+        }
+    }
+}
+
+/**
+Specifies the separator used by the [`ListOf`](struct.ListOf.html) scanner.
+*/
+pub trait ListSeparator {
+    /**
+    The literal which separates elements, or `None` if elements are separated only by whitespace.
+    */
+    fn separator() -> Option<&'static str>;
+}
+
+/**
+Separates [`ListOf`](struct.ListOf.html) elements with whitespace.
+*/
+pub enum SpaceSep {}
+
+impl ListSeparator for SpaceSep {
+    fn separator() -> Option<&'static str> { None }
+}
+
+/**
+Separates [`ListOf`](struct.ListOf.html) elements with commas.
+*/
+pub enum CommaSep {}
+
+impl ListSeparator for CommaSep {
+    fn separator() -> Option<&'static str> { Some(",") }
+}
+
+#[cfg(test)]
+#[test]
+fn test_list_of() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+    use ::scanner::Word;
+
+    let scan = ListOf::<Word<&str>>::scan_from;
+    assert_match!(scan(""), Ok((ref v, 0)) if v.is_empty());
+    assert_match!(scan("foo bar  baz"), Ok((ref v, 12)) if *v == ["foo", "bar", "baz"]);
+    assert_match!(scan("foo bar, baz"), Ok((ref v, 7)) if *v == ["foo", "bar"]);
+
+    let scan = ListOf::<i32, CommaSep>::scan_from;
+    assert_match!(scan("1, 2 ,3 x"), Ok((ref v, 7)) if *v == [1, 2, 3]);
+    assert_match!(scan("1 2"), Ok((ref v, 1)) if *v == [1]);
+    assert_match!(scan("1, x"), Err(SE { kind: SEK::Syntax(_), .. }));
+}
+
+/**
+Scans everything up to the end of the current line, *or* the end of the input, whichever comes first.  The scanned result *does not* include the line terminator.
+
+Note that this is effectively equivalent to the `Everything` matcher when used with `readln!`.
+*/
+pub struct Line<'a, Output=&'a str>(PhantomData<(&'a (), Output)>);
+
+#[cfg(str_into_output_extra_broken)]
+impl<'a> ScanFromStr<'a> for Line<'a, &'a str> {
+    type Output = &'a str;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        let (a, b) = match_line(s);
+        Ok((s[..a].into(), b))
+    }
+}
+
+#[cfg(str_into_output_extra_broken)]
+impl<'a> ScanFromStr<'a> for Line<'a, String> {
+    type Output = String;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        let (a, b) = match_line(s);
+        Ok((s[..a].into(), b))
+    }
+}
+
+#[cfg(str_into_output_extra_broken)]
+impl<'a> ScanFromStr<'a> for Line<'a, Cow<'a, str>> {
+    type Output = Cow<'a, str>;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        let (a, b) = match_line(s);
+        Ok((s[..a].into(), b))
+    }
+}
+
+#[cfg(not(str_into_output_extra_broken))]
+impl<'a, Output> ScanFromStr<'a> for Line<'a, Output> where &'a str: Into<Output> {
+    type Output = Output;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        let (a, b) = match_line(s);
+        Ok((s[..a].into(), b))
+    }
+}
+
+fn match_line(s: &str) -> (usize, usize) {
+    let bs = s.as_bytes();
+    match ::fast::find_line_break(bs) {
+        None => (s.len(), s.len()),
+        Some(i) if bs[i] == b'\n' => (i, i + 1),
+        // Found an `\r`; it might be followed by an `\n`.
+        Some(i) if bs.get(i + 1) == Some(&b'\n') => (i, i + 2),
+        Some(i) => (i, i + 1),
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_line() {
+    assert_match!(Line::<&str>::scan_from(""), Ok(("", 0)));
+    assert_match!(Line::<&str>::scan_from("abc def"), Ok(("abc def", 7)));
+    assert_match!(Line::<&str>::scan_from("abc\ndef"), Ok(("abc", 4)));
+    assert_match!(Line::<&str>::scan_from("abc\r\ndef"), Ok(("abc", 5)));
+    assert_match!(Line::<&str>::scan_from("abc\rdef"), Ok(("abc", 4)));
+    assert_match!(Line::<Cow<str>>::scan_from("abc\ndef"), Ok((Cow::Borrowed("abc"), 4)));
+}
+
+/**
+Scans the name of a month, either in full or abbreviated, outputting its number (January is `1`).
+
+Names are matched case-insensitively, and must not be immediately followed by another letter.  The names are taken from `Names`, which defaults to [`English`](enum.English.html); see [`DateNames`](trait.DateNames.html) to support other languages.
+*/
+pub struct MonthName<Names=English>(PhantomData<Names>);
+
+impl<'a, Names> ScanFromStr<'a> for MonthName<Names> where Names: DateNames {
+    type Output = u8;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        match_name(s, Names::month_names())
+            .or_else(|| match_name(s, Names::month_abbrs()))
+            .map(|(i, n)| (i as u8 + 1, n))
+            .ok_or_else(|| ScanError::syntax("expected month name"))
+    }
+}
+
+/**
+Provides the names used by the [`MonthName`](struct.MonthName.html) and [`WeekdayName`](struct.WeekdayName.html) scanners.
+*/
+pub trait DateNames {
+    /**
+    Full month names, starting with January.  Must contain exactly twelve entries.
+    */
+    fn month_names() -> &'static [&'static str];
+
+    /**
+    Abbreviated month names, starting with January.  Must contain exactly twelve entries.
+    */
+    fn month_abbrs() -> &'static [&'static str];
+
+    /**
+    Full weekday names, starting with Monday.  Must contain exactly seven entries.
+    */
+    fn weekday_names() -> &'static [&'static str];
+
+    /**
+    Abbreviated weekday names, starting with Monday.  Must contain exactly seven entries.
+    */
+    fn weekday_abbrs() -> &'static [&'static str];
+}
+
+/**
+English month and weekday names.
+*/
+pub enum English {}
+
+impl DateNames for English {
+    fn month_names() -> &'static [&'static str] {
+        const NAMES: &'static [&'static str] = &[
+            "January", "February", "March", "April", "May", "June",
+            "July", "August", "September", "October", "November", "December",
+        ];
+        NAMES
+    }
+
+    fn month_abbrs() -> &'static [&'static str] {
+        const NAMES: &'static [&'static str] = &[
+            "Jan", "Feb", "Mar", "Apr", "May", "Jun",
+            "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+        ];
+        NAMES
+    }
+
+    fn weekday_names() -> &'static [&'static str] {
+        const NAMES: &'static [&'static str] = &[
+            "Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday",
+        ];
+        NAMES
+    }
+
+    fn weekday_abbrs() -> &'static [&'static str] {
+        const NAMES: &'static [&'static str] = &[
+            "Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun",
+        ];
+        NAMES
+    }
+}
+
+/**
+Matches one of `names` at the start of `s`, ignoring case, returning its index and length.
+*/
+fn match_name(s: &str, names: &[&str]) -> Option<(usize, usize)> {
+    for (i, name) in names.iter().enumerate() {
+        let mut s_chars = s.char_indices();
+        let mut matched = true;
+        for nc in name.chars() {
+            match s_chars.next() {
+                Some((_, sc)) if sc.to_lowercase().eq(nc.to_lowercase()) => (),
+                _ => { matched = false; break; }
+            }
+        }
+        if !matched {
+            continue;
+        }
+        let end = s_chars.next().map(|(j, c)| (j, c.is_alphabetic()));
+        match end {
+            Some((_, true)) => continue,
+            Some((j, false)) => return Some((i, j)),
+            None => return Some((i, s.len())),
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+#[test]
+fn test_month_name() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+
+    let scan = MonthName::<English>::scan_from;
+    assert_match!(scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("Jan"), Ok((1, 3)));
+    assert_match!(scan("january 5"), Ok((1, 7)));
+    assert_match!(scan("DEC"), Ok((12, 3)));
+    assert_match!(scan("May,"), Ok((5, 3)));
+    assert_match!(scan("Sept"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("Marching"), Err(SE { kind: SEK::Syntax(_), .. }));
+}
+
+/**
+Scans a single newline into a string.
+
+This *will not* match an empty sequence, and will not match more than one newline.
+*/
+pub struct Newline<'a, Output=&'a str>(PhantomData<(&'a (), Output)>);
+
+// FIXME: Error message omitted due to https://github.com/rust-lang/rust/issues/26448.
+#[cfg(str_into_output_extra_broken)]
+impl<'a> ScanFromStr<'a> for Newline<'a, &'a str> {
+    type Output = &'a str;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        match match_newline(s) {
+            Some(b) => {
+                let word = &s[..b];
+                let tail = &s[b..];
+                Ok((word.into(), s.subslice_offset_stable(tail).unwrap()))
+            },
+            // None => Err(ScanError::syntax("expected at least one non-space character")),
+            None => Err(ScanError::syntax_no_message())
+        }
+    }
+
+    fn wants_leading_junk_stripped() -> bool { false }
+}
+
+// FIXME: Error message omitted due to https://github.com/rust-lang/rust/issues/26448.
+#[cfg(str_into_output_extra_broken)]
+impl<'a> ScanFromStr<'a> for Newline<'a, String> {
+    type Output = String;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        match match_newline(s) {
+            Some(b) => {
+                let word = &s[..b];
+                let tail = &s[b..];
+                Ok((word.into(), s.subslice_offset_stable(tail).unwrap()))
+            },
+            // None => Err(ScanError::syntax("expected at least one non-space character")),
+            None => Err(ScanError::syntax_no_message())
+        }
+    }
+
+    fn wants_leading_junk_stripped() -> bool { false }
+}
+
+// FIXME: Error message omitted due to https://github.com/rust-lang/rust/issues/26448.
+#[cfg(str_into_output_extra_broken)]
+impl<'a> ScanFromStr<'a> for Newline<'a, Cow<'a, str>> {
+    type Output = Cow<'a, str>;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        match match_newline(s) {
+            Some(b) => {
+                let word = &s[..b];
+                let tail = &s[b..];
+                Ok((word.into(), s.subslice_offset_stable(tail).unwrap()))
+            },
+            // None => Err(ScanError::syntax("expected at least one non-space character")),
+            None => Err(ScanError::syntax_no_message())
+        }
+    }
+
+    fn wants_leading_junk_stripped() -> bool { false }
+}
+
+// FIXME: Error message omitted due to https://github.com/rust-lang/rust/issues/26448.
+#[cfg(not(str_into_output_extra_broken))]
+impl<'a, Output> ScanFromStr<'a> for Newline<'a, Output>
+where &'a str: Into<Output> {
+    type Output = Output;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        match match_newline(s) {
+            Some(b) => {
+                let word = &s[..b];
+                let tail = &s[b..];
+                Ok((word.into(), s.subslice_offset_stable(tail).unwrap()))
+            },
+            // None => Err(ScanError::syntax("expected at least one non-space character")),
+            None => Err(ScanError::syntax_no_message())
+        }
+    }
+
+    fn wants_leading_junk_stripped() -> bool { false }
+}
+
+fn match_newline(s: &str) -> Option<usize> {
+    // See: <http://www.unicode.org/reports/tr18/#RL1.6>.
+    println!("match_newline({:?})", s);
+    let mut cis = s.char_indices();
+
+    let r = match cis.next() {
+        Some((_, '\x0a')) => Some(1),
+        Some((_, '\x0b')) => Some(1),
+        Some((_, '\x0c')) => Some(1),
+        Some((_, '\x0d')) => match cis.next() {
+            Some((_, '\x0a')) => Some(2),
+            _ => Some(1)
+        },
+        Some((_, c @ '\u{85}')) => Some(c.len_utf8()),
+        Some((_, c @ '\u{2028}')) => Some(c.len_utf8()),
+        Some((_, c @ '\u{2029}')) => Some(c.len_utf8()),
+        _ => None
+    };
+
+    println!("-> {:?}", r);
+    r
+}
+
+#[cfg(test)]
+#[test]
+fn test_newline() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+
+    assert_match!(Newline::<&str>::scan_from(""), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
+    assert_match!(Newline::<&str>::scan_from("x"), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
+    assert_match!(Newline::<&str>::scan_from("\rx"), Ok(("\r", 1)));
+    assert_match!(Newline::<&str>::scan_from("\nx"), Ok(("\n", 1)));
+    assert_match!(Newline::<&str>::scan_from("\r\nx"), Ok(("\r\n", 2)));
+    assert_match!(Newline::<&str>::scan_from("\n\rx"), Ok(("\n", 1)));
+}
+
+/**
+Scans a sequence of non-space characters into a string.
+
+This *will not* match an empty sequence; there must be at least one non-space character for the scan to succeed.
+*/
+pub struct NonSpace<'a, Output=&'a str>(PhantomData<(&'a (), Output)>);
+
+// FIXME: Error message omitted due to https://github.com/rust-lang/rust/issues/26448.
+#[cfg(str_into_output_extra_broken)]
+impl<'a> ScanFromStr<'a> for NonSpace<'a, &'a str> {
+    type Output = &'a str;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        match match_non_space(s) {
+            Some(b) => {
+                let word = &s[..b];
+                let tail = &s[b..];
+                Ok((word.into(), s.subslice_offset_stable(tail).unwrap()))
+            },
+            // None => Err(ScanError::syntax("expected at least one non-space character")),
+            None => Err(ScanError::syntax_no_message())
+        }
+    }
+}
+
+// FIXME: Error message omitted due to https://github.com/rust-lang/rust/issues/26448.
+#[cfg(str_into_output_extra_broken)]
+impl<'a> ScanFromStr<'a> for NonSpace<'a, String> {
+    type Output = String;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        match match_non_space(s) {
+            Some(b) => {
+                let word = &s[..b];
+                let tail = &s[b..];
+                Ok((word.into(), s.subslice_offset_stable(tail).unwrap()))
+            },
+            // None => Err(ScanError::syntax("expected at least one non-space character")),
+            None => Err(ScanError::syntax_no_message())
+        }
+    }
+}
+
+// FIXME: Error message omitted due to https://github.com/rust-lang/rust/issues/26448.
+#[cfg(str_into_output_extra_broken)]
+impl<'a> ScanFromStr<'a> for NonSpace<'a, Cow<'a, str>> {
+    type Output = Cow<'a, str>;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        match match_non_space(s) {
+            Some(b) => {
+                let word = &s[..b];
+                let tail = &s[b..];
+                Ok((word.into(), s.subslice_offset_stable(tail).unwrap()))
+            },
+            // None => Err(ScanError::syntax("expected at least one non-space character")),
+            None => Err(ScanError::syntax_no_message())
+        }
+    }
+}
+
+// FIXME: Error message omitted due to https://github.com/rust-lang/rust/issues/26448.
+#[cfg(not(str_into_output_extra_broken))]
+impl<'a, Output> ScanFromStr<'a> for NonSpace<'a, Output>
+where &'a str: Into<Output> {
+    type Output = Output;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        match match_non_space(s) {
+            Some(b) => {
+                let word = &s[..b];
+                let tail = &s[b..];
+                Ok((word.into(), s.subslice_offset_stable(tail).unwrap()))
+            },
+            // None => Err(ScanError::syntax("expected at least one non-space character")),
+            None => Err(ScanError::syntax_no_message())
+        }
+    }
+}
+
+fn match_non_space(s: &str) -> Option<usize> {
+    use ::util::TableUtil;
+    use ::unicode::property::White_Space_table as WS;
+
+    let fast = ::fast::ascii_non_space_len(s);
+    let len = fast + s[fast..].char_indices()
+        .take_while(|&(_, c)| !WS.span_table_contains(&c))
+        .map(|(i, c)| i + c.len_utf8())
+        .last()
+        .unwrap_or(0);
+
+    if len == 0 { None } else { Some(len) }
+}
+
+#[cfg(test)]
+#[test]
+fn test_non_space() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+
+    assert_match!(NonSpace::<&str>::scan_from(""), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
+    assert_match!(NonSpace::<&str>::scan_from(" abc "), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
+    assert_match!(NonSpace::<&str>::scan_from("abc "), Ok(("abc", 3)));
+    assert_match!(NonSpace::<&str>::scan_from("abc\t"), Ok(("abc", 3)));
+    assert_match!(NonSpace::<&str>::scan_from("abc\r"), Ok(("abc", 3)));
+    assert_match!(NonSpace::<&str>::scan_from("abc\n"), Ok(("abc", 3)));
+    assert_match!(NonSpace::<&str>::scan_from("abc\u{a0}"), Ok(("abc", 3)));
+    assert_match!(NonSpace::<&str>::scan_from("abc\u{2003}"), Ok(("abc", 3)));
+    assert_match!(NonSpace::<&str>::scan_from("abc\u{200B}"), Ok(("abc\u{200b}", 6)));
+    assert_match!(NonSpace::<&str>::scan_from("abc\u{3000}"), Ok(("abc", 3)));
+}
+
+/**
+Scans a single number into a string.
+
+Specifically, this will match a continuous run of decimal characters (*i.e.* /`\d+`/).
+
+Note that this *includes* non-ASCII decimal characters, meaning it will scan numbers such as "42", "１７０１", and "𐒩０꘠᧑".
+*/
+pub struct Number<'a, Output=&'a str>(PhantomData<(&'a (), Output)>);
+
+// FIXME: Error message omitted due to https://github.com/rust-lang/rust/issues/26448.
+#[cfg(str_into_output_extra_broken)]
+impl<'a> ScanFromStr<'a> for Number<'a, &'a str> {
+    type Output = &'a str;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        match match_number(s) {
+            Some(b) => {
+                let word = &s[..b];
+                let tail = &s[b..];
+                Ok((word.into(), s.subslice_offset_stable(tail).unwrap()))
+            },
+            // None => Err(ScanError::syntax("expected a number")),
+            None => Err(ScanError::syntax_no_message()),
+        }
+    }
+}
+
+// FIXME: Error message omitted due to https://github.com/rust-lang/rust/issues/26448.
+#[cfg(str_into_output_extra_broken)]
+impl<'a> ScanFromStr<'a> for Number<'a, String> {
+    type Output = String;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        match match_number(s) {
+            Some(b) => {
+                let word = &s[..b];
+                let tail = &s[b..];
+                Ok((word.into(), s.subslice_offset_stable(tail).unwrap()))
+            },
+            // None => Err(ScanError::syntax("expected a number")),
+            None => Err(ScanError::syntax_no_message()),
+        }
+    }
+}
+
+// FIXME: Error message omitted due to https://github.com/rust-lang/rust/issues/26448.
+#[cfg(str_into_output_extra_broken)]
+impl<'a> ScanFromStr<'a> for Number<'a, Cow<'a, str>> {
+    type Output = Cow<'a, str>;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        match match_number(s) {
+            Some(b) => {
+                let word = &s[..b];
+                let tail = &s[b..];
+                Ok((word.into(), s.subslice_offset_stable(tail).unwrap()))
+            },
+            // None => Err(ScanError::syntax("expected a number")),
+            None => Err(ScanError::syntax_no_message()),
+        }
+    }
+}
+
+// FIXME: Error message omitted due to https://github.com/rust-lang/rust/issues/26448.
+#[cfg(not(str_into_output_extra_broken))]
+impl<'a, Output> ScanFromStr<'a> for Number<'a, Output>
+where &'a str: Into<Output> {
+    type Output = Output;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        match match_number(s) {
+            Some(b) => {
+                let word = &s[..b];
+                let tail = &s[b..];
+                Ok((word.into(), s.subslice_offset_stable(tail).unwrap()))
+            },
+            // None => Err(ScanError::syntax("expected a number")),
+            None => Err(ScanError::syntax_no_message()),
+        }
+    }
+}
+
+fn match_number(s: &str) -> Option<usize> {
+    use ::util::TableUtil;
+    use ::unicode::general_category::Nd_table as Nd;
+
+    s.char_indices()
+        .take_while(|&(_, c)| Nd.span_table_contains(&c))
+        .map(|(i, c)| i + c.len_utf8())
+        .last()
+}
+
+#[cfg(test)]
+#[test]
+fn test_number() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+
+    assert_match!(Number::<&str>::scan_from(""), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
+    assert_match!(Number::<&str>::scan_from("a"), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
+    assert_match!(Number::<&str>::scan_from("0"), Ok(("0", 1)));
+    assert_match!(Number::<&str>::scan_from("0x"), Ok(("0", 1)));
+    assert_match!(Number::<&str>::scan_from("x0"), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
+    assert_match!(Number::<&str>::scan_from("123 456 xyz"), Ok(("123", 3)));
+    assert_match!(Number::<&str>::scan_from("123 456 xyz"), Ok(("123", 3)));
+    assert_match!(Number::<&str>::scan_from("123４５６789 "), Ok(("123４５６789", 15)));
+    assert_match!(Number::<&str>::scan_from("𐒩０꘠᧑ "), Ok(("𐒩０꘠᧑", 13)));
+}
+
+/**
+Scans the given `Output` type from its octal representation.
+*/
+pub struct Octal<Output>(PhantomData<Output>);
+
+impl<'a, Output> ScanFromStr<'a> for Octal<Output>
+where Output: ScanFromOctal<'a> {
+    type Output = Output;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        Output::scan_from_octal(s)
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_octal() {
+    assert_match!(Octal::<i32>::scan_from("0 1 2 x"), Ok((0o0, 1)));
+    assert_match!(Octal::<i32>::scan_from("012x"), Ok((0o12, 3)));
+    assert_match!(Octal::<i32>::scan_from("0o012x"), Ok((0o0, 1)));
+    assert_match!(Octal::<i32>::scan_from("7558"), Ok((0o755, 3)));
+}
+
+/**
+Scans an English ordinal number, such as "1st", "22nd", or "113th", into an integer of type `Output`.
+
+The suffix must be correct for the number; *e.g.* "1th" and "12nd" are rejected.  The suffix may be written in either lower or upper case, but may not be immediately followed by a letter or digit.
+*/
+pub struct OrdinalNumber<Output=u32>(PhantomData<Output>);
+
+impl<'a, Output> ScanFromStr<'a> for OrdinalNumber<Output>
+where Output: ScanSelfFromStr<'a> {
+    type Output = Output;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s_str = s.as_str();
+        let digits = s_str.bytes().take_while(|b| b'0' <= *b && *b <= b'9').count();
+        if digits == 0 {
+            return Err(ScanError::syntax("expected ordinal number"));
+        }
+
+        let (v, n) = try!(Output::scan_from(s.from_subslice(&s_str[..digits])));
+        if n != digits {
+            return Err(ScanError::syntax("expected ordinal number").add_offset(n));
+        }
+
+        let ds = s_str[..digits].as_bytes();
+        let tens = if digits > 1 { ds[digits - 2] } else { b'0' };
+        let expected = match (tens, ds[digits - 1]) {
+            (b'1', _) => "th",
+            (_, b'1') => "st",
+            (_, b'2') => "nd",
+            (_, b'3') => "rd",
+            _ => "th",
+        };
+
+        let tail = &s_str[digits..];
+        if !(tail.starts_with(expected) || tail.starts_with(&*expected.to_uppercase())) {
+            return Err(ScanError::syntax("expected ordinal suffix").add_offset(digits));
+        }
+
+        if tail[2..].chars().next().map(|c| c.is_alphanumeric()).unwrap_or(false) {
+            return Err(ScanError::syntax("unexpected character after ordinal suffix").add_offset(digits + 2));
+        }
+
+        Ok((v, digits + 2))
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_ordinal_number() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+    let scan = OrdinalNumber::<u32>::scan_from;
+
+    assert_match!(scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("st"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("1"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("1st"), Ok((1, 3)));
+    assert_match!(scan("2nd place"), Ok((2, 3)));
+    assert_match!(scan("3RD"), Ok((3, 3)));
+    assert_match!(scan("4th,"), Ok((4, 3)));
+    assert_match!(scan("11th"), Ok((11, 4)));
+    assert_match!(scan("12th"), Ok((12, 4)));
+    assert_match!(scan("13th"), Ok((13, 4)));
+    assert_match!(scan("21st"), Ok((21, 4)));
+    assert_match!(scan("22nd"), Ok((22, 4)));
+    assert_match!(scan("113th"), Ok((113, 5)));
+    assert_match!(scan("1th"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("12nd"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("1sT"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("1stly"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("1日"), Err(SE { kind: SEK::Syntax(_), ref at, .. }) if at.offset() == 1);
+    assert_match!(scan("2né"), Err(SE { kind: SEK::Syntax(_), ref at, .. }) if at.offset() == 1);
+    assert_match!(scan("3rd日"), Err(SE { kind: SEK::Syntax(_), ref at, .. }) if at.offset() == 3);
+    assert_match!(OrdinalNumber::<u8>::scan_from("256th"), Err(SE { kind: SEK::Overflow("u8"), .. }));
+}
+
+/**
+Scans a list of filesystem paths, such as the value of the `PATH` environment variable, into a `Vec<PathBuf>`.
+
+Paths are separated and quoted according to `Style`, which must implement [`PathListStyle`](trait.PathListStyle.html).  By default, this is the style used by the current platform.  The list runs to the end of the current line, or the end of the input, whichever comes first.  Empty entries are skipped.  Neither quotes nor escapes extend past the end of the line; a quoted path which is still open there is an error.
+
+```ignore
+let_scan!("PATH=/usr/bin:/bin"; ("PATH=", let paths: PathList<UnixPaths>));
+let_scan!(r#"Path=C:\Windows;"C:\Odd;Dir""#; ("Path=", let paths: PathList<WindowsPaths>));
+```
+*/
+pub struct PathList<Style=NativePaths>(PhantomData<Style>);
+
+impl<'a, Style> ScanFromStr<'a> for PathList<Style> where Style: PathListStyle {
+    type Output = Vec<PathBuf>;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        let sep = Style::separator();
+        let quote = Style::quote();
+        let escape = Style::escape();
+
+        let mut paths = vec![];
+        let mut path = String::new();
+        let mut quote_at = None;
+        let mut end = s.len();
+        let mut cps = s.char_indices();
+
+        while let Some((i, cp)) = cps.next() {
+            if cp == '\r' || cp == '\n' {
+                end = i;
+                break;
+            } else if quote_at.is_some() {
+                if Some(cp) == quote {
+                    quote_at = None;
+                } else {
+                    path.push(cp);
+                }
+            } else if cp == sep {
+                if !path.is_empty() {
+                    paths.push(PathBuf::from(::std::mem::replace(&mut path, String::new())));
+                }
+            } else if Some(cp) == quote {
+                quote_at = Some(i);
+            } else if Some(cp) == escape {
+                match cps.next() {
+                    Some((_, cp)) if cp != '\r' && cp != '\n' => path.push(cp),
+                    _ => return Err(ScanError::syntax("expected character after escape").add_offset(i)),
+                }
+            } else {
+                path.push(cp);
+            }
+        }
+
+        if let Some(at) = quote_at {
+            return Err(ScanError::syntax("unterminated quoted path").add_offset(at));
+        }
+
+        if !path.is_empty() {
+            paths.push(PathBuf::from(path));
+        }
+
+        Ok((paths, end))
+    }
+}
+
+/**
+Specifies how paths are separated and quoted by the [`PathList`](struct.PathList.html) scanner.
+*/
+pub trait PathListStyle {
+    /**
+    The character which separates paths.
+    */
+    fn separator() -> char;
+
+    /**
+    The character which surrounds a path containing the separator, if any.
+    */
+    fn quote() -> Option<char>;
+
+    /**
+    The character which causes the following character to be taken literally, if any.
+    */
+    fn escape() -> Option<char>;
+}
+
+/**
+Unix-style path lists: paths are separated by `:`.  There is no quote or escape character, since neither is recognised in `PATH`; a path containing `:` cannot be represented.
+*/
+pub enum UnixPaths {}
+
+impl PathListStyle for UnixPaths {
+    fn separator() -> char { ':' }
+    fn quote() -> Option<char> { None }
+    fn escape() -> Option<char> { None }
+}
+
+/**
+Windows-style path lists: paths are separated by `;`, and may be surrounded by double quotes.  There is no escape character, since backslash is the path separator.
+*/
+pub enum WindowsPaths {}
+
+impl PathListStyle for WindowsPaths {
+    fn separator() -> char { ';' }
+    fn quote() -> Option<char> { Some('"') }
+    fn escape() -> Option<char> { None }
+}
+
+/**
+The path list style used by the current platform.
+*/
+#[cfg(windows)]
+pub type NativePaths = WindowsPaths;
+
+/**
+The path list style used by the current platform.
+*/
+#[cfg(not(windows))]
+pub type NativePaths = UnixPaths;
+
+#[cfg(test)]
+#[test]
+fn test_path_list() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+
+    fn paths(ps: &[PathBuf]) -> Vec<&str> {
+        ps.iter().map(|p| p.to_str().unwrap()).collect()
+    }
+
+    let scan = PathList::<UnixPaths>::scan_from;
+    assert_match!(scan(""), Ok((ref v, 0)) if v.is_empty());
+    assert_match!(scan("/usr/bin:/bin"), Ok((ref v, 13)) if paths(v) == ["/usr/bin", "/bin"]);
+    assert_match!(scan("/bin::/sbin:\nx"), Ok((ref v, 12)) if paths(v) == ["/bin", "/sbin"]);
+    assert_match!(scan("/my\\:dir:/my dir"), Ok((ref v, 16)) if paths(v) == ["/my\\", "dir", "/my dir"]);
+    assert_match!(scan("/bin\\"), Ok((ref v, 5)) if paths(v) == ["/bin\\"]);
+
+    let scan = PathList::<WindowsPaths>::scan_from;
+    assert_match!(scan("C:\\Windows;C:\\Program Files"), Ok((ref v, 27)) if paths(v) == ["C:\\Windows", "C:\\Program Files"]);
+    assert_match!(scan("\"C:\\Odd;Dir\";D:\\"), Ok((ref v, 16)) if paths(v) == ["C:\\Odd;Dir", "D:\\"]);
+    assert_match!(scan("C:\\a:b;"), Ok((ref v, 7)) if paths(v) == ["C:\\a:b"]);
+    assert_match!(scan("\"C:\\Odd"), Err(SE { ref at, kind: SEK::Syntax(_), .. }) if at.offset() == 0);
+    assert_match!(scan("C:\\;\"C:\\Odd\n;Dir\""), Err(SE { ref at, kind: SEK::Syntax(_), .. }) if at.offset() == 4);
+
+    // An escape can't take the line break.
+    enum Escaped {}
+    impl PathListStyle for Escaped {
+        fn separator() -> char { ':' }
+        fn quote() -> Option<char> { None }
+        fn escape() -> Option<char> { Some('\\') }
+    }
+    let scan = PathList::<Escaped>::scan_from;
+    assert_match!(scan("/my\\:dir:/bin"), Ok((ref v, 13)) if paths(v) == ["/my:dir", "/bin"]);
+    assert_match!(scan("/bin\\"), Err(SE { ref at, kind: SEK::Syntax(_), .. }) if at.offset() == 4);
+    assert_match!(scan("/bin\\\nx"), Err(SE { ref at, kind: SEK::Syntax(_), .. }) if at.offset() == 4);
+
+    assert_match!(
+        scan!("PATH=/usr/bin:/bin\nHOME=/root"; ("PATH=", let ps: PathList<UnixPaths>, "HOME=", ..home) => (ps, home)),
+        Ok((ref ps, "/root")) if paths(ps) == ["/usr/bin", "/bin"]
+    );
+}
+
+/**
+An abstract scanner that scans a `(K, V)` value using the syntax `K: V`.
+
+This scanner is designed to take advantage of three things:
+
+1. Maps (*i.e.* associative containers) typically print themselves with the syntax `{key_0: value_0, key_1: value_1, ...}`.
+
+2. Maps typically implement `Extend<(K, V)>`; that is, you can add new items by extending the map with a `(K, V)` tuple.
+
+3. Repeating bindings can be scanned into any container that implements `Default` and `Extend`.
+
+As such, this scanner allows one to parse a `Map` type like so:
+
+```ignore
+scan!(input; "{", [let kvs: KeyValuePair<K, V>],*: Map<_, _>, "}" => kvs)
+```
+
+`K` and `V` can be any scanners; the output is a tuple of their outputs.
+
+See also: [`key_value`](fn.key_value.html), for other separators, and [`MapOf`](struct.MapOf.html).
+*/
+pub struct KeyValuePair<K, V>(PhantomData<(K, V)>);
+
+impl<'a, K, V> ScanFromStr<'a> for KeyValuePair<K, V>
+where K: ScanFromStr<'a>, V: ScanFromStr<'a> {
+    type Output = (K::Output, V::Output);
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        scan!(s;
+            (let k: K, ":", let v: V, ..tail) => ((k, v), s.subslice_offset_stable(tail).unwrap())
+        )
+    }
+}
+
+/**
+Scans a map in `Debug` format (*i.e.* `{key_0: value_0, key_1: value_1, ...}`), outputting a `HashMap`.
+
+This differs from scanning a `HashMap` directly in that `K` and `V` are scanners, rather than the key and value types themselves.  This allows abstract scanners to be used for the keys and values.
+
+```ignore
+let_scan!(r#"{"a": [1, 2], "b": []}"#; (let m: MapOf<QuotedString, Vec<i32>>));
+let_scan!("{x: one, y: two}"; (let m: MapOf<Word<String>, Word<String>>));
+```
+*/
+#[cfg(feature="std")]
+pub struct MapOf<K, V>(PhantomData<(K, V)>);
+
+#[cfg(feature="std")]
+impl<'a, K, V> ScanFromStr<'a> for MapOf<K, V>
+where
+    K: ScanFromStr<'a>,
+    V: ScanFromStr<'a>,
+    K::Output: Eq + Hash,
+{
+    type Output = HashMap<K::Output, V::Output>;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        use ::input::ScanCursor;
+        scan!(s.to_cursor();
+            ("{", [ let kvs: KeyValuePair<K, V> ],*: HashMap<_, _>, "}", ^..tail) => (kvs, tail.offset())
+        )
+    }
+}
+
+/**
+Scans a set in `Debug` format (*i.e.* `{elem_0, elem_1, ...}`), outputting a `HashSet`.
+
+This differs from scanning a `HashSet` directly in that `T` is a scanner, rather than the element type itself.
+
+```ignore
+let_scan!(r#"{"a", "b"}"#; (let s: SetOf<QuotedString>));
+```
+*/
+#[cfg(feature="std")]
+pub struct SetOf<T>(PhantomData<T>);
+
+#[cfg(feature="std")]
+impl<'a, T> ScanFromStr<'a> for SetOf<T>
+where T: ScanFromStr<'a>, T::Output: Eq + Hash {
+    type Output = HashSet<T::Output>;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        use ::input::ScanCursor;
+        scan!(s.to_cursor();
+            ("{", [ let es: T ],*: HashSet<_>, "}", ^..tail) => (es, tail.offset())
+        )
+    }
+}
+
+#[cfg(feature="std")]
+#[cfg(test)]
+#[test]
+fn test_map_set_of() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+    use ::scanner::Word;
+
+    let scan = MapOf::<QuotedString, Vec<i32>>::scan_from;
+    assert_match!(scan("{}"), Ok((ref m, 2)) if m.is_empty());
+    assert_match!(
+        scan(r#"{"a": [1, 2], "b": []} x"#),
+        Ok((ref m, 22)) if m.len() == 2 && m["a"] == [1, 2] && m["b"].is_empty()
+    );
+    assert_match!(
+        scan(r#"{"a": [1, 2], b: []}"#),
+        Err(SE { kind: SEK::Syntax(_), ref at, .. }) if at.offset() == 14
+    );
+
+    let scan = MapOf::<Word<String>, MapOf<Word<&str>, i32>>::scan_from;
+    assert_match!(
+        scan("{x: {a: 1}, y: {}}"),
+        Ok((ref m, 18)) if m.len() == 2 && m["x"]["a"] == 1 && m["y"].is_empty()
+    );
+
+    let scan = SetOf::<Word<String>>::scan_from;
+    assert_match!(
+        scan("{a, b, a}"),
+        Ok((ref s, 9)) if s.len() == 2 && s.contains("a") && s.contains("b")
+    );
+    assert_match!(scan("[a]"), Err(SE { kind: SEK::LiteralMismatch, .. }));
+}
+
+/**
+Scans a single punctuation character.
+
+This matches any character in the Unicode `P` (punctuation) categories, as of Unicode 8.0.  Note that this does *not* include symbols such as `+`, `$` or `|`.
+*/
+pub enum PunctChar {}
+
+impl<'a> ScanFromStr<'a> for PunctChar {
+    type Output = char;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        use ::util::TableUtil;
+        use ::unicode::general_category::P_table as P;
+
+        match s.as_str().chars().next() {
+            Some(c) if P.span_table_contains(&c) => Ok((c, c.len_utf8())),
+            _ => Err(ScanError::syntax("expected punctuation")),
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_punct_char() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+
+    assert_match!(PunctChar::scan_from(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(PunctChar::scan_from("a"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(PunctChar::scan_from("+"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(PunctChar::scan_from(", x"), Ok((',', 1)));
+    assert_match!(PunctChar::scan_from("!?"), Ok(('!', 1)));
+    assert_match!(PunctChar::scan_from("«"), Ok(('«', 2)));
+    assert_match!(PunctChar::scan_from("。"), Ok(('。', 3)));
+    // Added after Unicode 8.0.
+    assert_match!(PunctChar::scan_from("\u{2e52}"), Err(SE { kind: SEK::Syntax(_), .. }));
+}
+
+/**
+Scans a Python boolean literal, `True` or `False`.
+
+**Note**: requires the `python-literals` feature.
+*/
+#[cfg(feature="python-literals")]
+pub enum PyBool {}
+
+#[cfg(feature="python-literals")]
+impl<'a> ScanFromStr<'a> for PyBool {
+    type Output = bool;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        match try!(Word::<&str>::scan_from(s).map_err(|_| ScanError::syntax("expected `True` or `False`"))) {
+            ("True", n) => Ok((true, n)),
+            ("False", n) => Ok((false, n)),
+            _ => Err(ScanError::syntax("expected `True` or `False`")),
+        }
+    }
+}
+
+/**
+Scans either the Python literal `None`, or a value using `T`.
+
+**Note**: requires the `python-literals` feature.
+*/
+#[cfg(feature="python-literals")]
+pub struct PyOption<T>(PhantomData<T>);
+
+#[cfg(feature="python-literals")]
+impl<'a, T> ScanFromStr<'a> for PyOption<T>
+where T: ScanFromStr<'a> {
+    type Output = Option<T::Output>;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        match Word::<&str>::scan_from(s.clone()) {
+            Ok(("None", n)) => Ok((None, n)),
+            _ => T::scan_from(s).map(|(v, n)| (Some(v), n)),
+        }
+    }
+}
+
+/**
+Scans a Python string literal, as produced by `repr`.
+
+Both single- and double-quoted strings are accepted, as are the escape sequences `repr` emits: `\\`, `\'`, `\"`, `\n`, `\r`, `\t`, `\xHH`, `\uHHHH` and `\UHHHHHHHH`.  Prefixes (such as `b` or `r`) and triple-quoted strings are not supported.
+
+The scanned string has all escape sequences expanded to their values, and the surrounding quotes removed.
+
+**Note**: requires the `python-literals` feature.
+*/
+#[cfg(feature="python-literals")]
+pub enum PyString {}
+
+#[cfg(feature="python-literals")]
+impl<'a> ScanFromStr<'a> for PyString {
+    type Output = String;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        let quote = match s.chars().next() {
+            Some(c @ '\'') | Some(c @ '"') => c,
+            _ => return Err(ScanError::syntax("expected quoted string")),
+        };
+
+        let mut out = String::new();
+        let mut cis = s.char_indices().skip(1);
+        loop {
+            let (i, c) = try!(cis.next().ok_or(ScanError::syntax("unterminated quoted string")));
+            if c == quote {
+                return Ok((out, i + 1));
+            }
+            if c != '\\' {
+                out.push(c);
+                continue;
+            }
+
+            let (_, e) = try!(cis.next().ok_or(ScanError::syntax("unterminated quoted string")));
+            let digits = match e {
+                '\\' | '\'' | '"' => { out.push(e); continue; },
+                'n' => { out.push('\n'); continue; },
+                'r' => { out.push('\r'); continue; },
+                't' => { out.push('\t'); continue; },
+                'x' => 2,
+                'u' => 4,
+                'U' => 8,
+                _ => return Err(ScanError::syntax("unknown escape sequence").add_offset(i)),
+            };
+
+            let hex: String = (&mut cis).take(digits).map(|(_, c)| c).collect();
+            let cp = if hex.len() == digits && hex.chars().all(|c| c.is_digit(16)) {
+                u32::from_str_radix(&hex, 16).ok().and_then(::std::char::from_u32)
+            } else {
+                None
+            };
+            match cp {
+                Some(cp) => out.push(cp),
+                None => return Err(ScanError::syntax("invalid escape sequence").add_offset(i)),
+            }
+        }
+    }
+}
+
+#[cfg(feature="python-literals")]
+#[cfg(test)]
+#[test]
+fn test_python_literals() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+
+    assert_match!(PyBool::scan_from("True"), Ok((true, 4)));
+    assert_match!(PyBool::scan_from("False,"), Ok((false, 5)));
+    assert_match!(PyBool::scan_from("true"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(PyBool::scan_from("Truest"), Err(SE { kind: SEK::Syntax(_), .. }));
+
+    assert_match!(PyOption::<i32>::scan_from("None"), Ok((None, 4)));
+    assert_match!(PyOption::<i32>::scan_from("-7"), Ok((Some(-7), 2)));
+    assert_match!(PyOption::<PyBool>::scan_from("True"), Ok((Some(true), 4)));
+    assert_match!(PyOption::<i32>::scan_from("Nonesuch"), Err(SE { kind: SEK::Syntax(_), .. }));
+
+    assert_match!(PyString::scan_from(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(PyString::scan_from("abc"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(PyString::scan_from("'abc"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(PyString::scan_from("'abc' x"), Ok((ref s, 5)) if s == "abc");
+    assert_match!(PyString::scan_from("\"it's\""), Ok((ref s, 6)) if s == "it's");
+    assert_match!(PyString::scan_from(r"'it\'s'"), Ok((ref s, 7)) if s == "it's");
+    assert_match!(PyString::scan_from(r"'a\tb\x00é\U0001f600'"), Ok((ref s, 22)) if s == "a\tb\0é😀");
+    assert_match!(PyString::scan_from(r"'\x4'"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(PyString::scan_from(r"'\q'"), Err(SE { kind: SEK::Syntax(_), .. }));
+}
+
+/**
+Scans a quoted string.
+
+Specifically, it scans the quoting format used by the `Debug` formatter for strings.
+
+The scanned string has all escape sequences expanded to their values, and the surrounding quotes removed.
+*/
+pub enum QuotedString {}
+
+impl<'a> ScanFromStr<'a> for QuotedString {
+    type Output = String;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        let syn = |s| ScanError::syntax(s);
+
+        match s.chars().next() {
+            None => return Err(syn("expected quoted string")),
+            Some('"') => (),
+            Some(_) => return Err(syn("expected `\"` for quoted string"))
+        }
+
+        // Copy whole runs of unescaped text at once, rather than pushing each code point.  This also means a string without escapes is allocated exactly once, at the right size.
+        let mut out = String::new();
+        let mut pos = 1;
+        loop {
+            let rest = &s[pos..];
+            let run = match rest.bytes().position(|b| b == b'"' || b == b'\\') {
+                None => return Err(syn("unterminated quoted string")),
+                Some(run) => run,
+            };
+            out.push_str(&rest[..run]);
+
+            let after = pos + run + 1;
+            if rest.as_bytes()[run] == b'"' {
+                return Ok((out, after));
+            }
+
+            match s[after..].split_escape_default() {
+                Err(err) => return Err(ScanError::other(err).add_offset(after)),
+                Ok((cp, tail)) => {
+                    out.push(cp);
+                    pos = s.len() - tail.len();
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_quoted_string() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+    use self::QuotedString as QS;
+
+    assert_match!(QS::scan_from(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(QS::scan_from("dummy xyz"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(QS::scan_from("'dummy' xyz"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(QS::scan_from("\"dummy\" xyz"),
+        Ok((ref s, 7)) if s == "dummy");
+    assert_match!(QS::scan_from("\"ab\\\"cd\" xyz"),
+        Ok((ref s, 8)) if s == "ab\"cd");
+    assert_match!(QS::scan_from("\"ab\\x41cd\" xyz"),
+        Ok((ref s, 10)) if s == "abAcd");
+    assert_match!(QS::scan_from("\"a\\'b\\u{5B57}c\\0d\" xyz"),
+        Ok((ref s, 18)) if s == "a'b字c\0d");
+}
+
+/**
+Scans a string which may or may not be quoted, outputting a `Cow<str>`.
+
+If the input begins with `"`, it is scanned as a [`QuotedString`](enum.QuotedString.html).  Otherwise, it is scanned using `Unquoted`, which defaults to [`Word`](struct.Word.html).
+
+The output borrows from the input wherever possible: an unquoted string, or a quoted string which contains no escape sequences, is never copied.  Only quoted strings whose escape sequences had to be expanded are owned.
+
+```ignore
+let_scan!(r#"name "Jane \"JD\" Doe""#; ("name", let n: CowString));
+let_scan!("comment whatever is left"; ("comment", let c: CowString<Line>));
+```
+*/
+pub struct CowString<'a, Unquoted=Word<'a, &'a str>>(PhantomData<(&'a (), Unquoted)>);
+
+impl<'a, Unquoted> ScanFromStr<'a> for CowString<'a, Unquoted>
+where Unquoted: ScanFromStr<'a, Output=&'a str> {
+    type Output = Cow<'a, str>;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s_str = s.as_str();
+        if !s_str.starts_with('"') {
+            return Unquoted::scan_from(s).map(|(v, n)| (Cow::Borrowed(v), n));
+        }
+
+        // Only fall back to unescaping (and allocating) if we have to.
+        for (i, b) in s_str.bytes().enumerate().skip(1) {
+            match b {
+                b'"' => return Ok((Cow::Borrowed(&s_str[1..i]), i + 1)),
+                b'\\' => break,
+                _ => (),
+            }
+        }
+
+        QuotedString::scan_from(s).map(|(v, n)| (Cow::Owned(v), n))
+    }
 
-    s.char_indices()
-        .take_while(|&(_, c)| Nd.span_table_contains(&c))
-        .map(|(i, c)| i + c.len_utf8())
-        .last()
+    fn wants_leading_junk_stripped() -> bool {
+        Unquoted::wants_leading_junk_stripped()
+    }
 }
 
 #[cfg(test)]
 #[test]
-fn test_number() {
+fn test_cow_string() {
     use ::ScanError as SE;
     use ::ScanErrorKind as SEK;
 
-    assert_match!(Number::<&str>::scan_from(""), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
-    assert_match!(Number::<&str>::scan_from("a"), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
-    assert_match!(Number::<&str>::scan_from("0"), Ok(("0", 1)));
-    assert_match!(Number::<&str>::scan_from("0x"), Ok(("0", 1)));
-    assert_match!(Number::<&str>::scan_from("x0"), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
-    assert_match!(Number::<&str>::scan_from("123 456 xyz"), Ok(("123", 3)));
-    assert_match!(Number::<&str>::scan_from("123 456 xyz"), Ok(("123", 3)));
-    assert_match!(Number::<&str>::scan_from("123４５６789 "), Ok(("123４５６789", 15)));
-    assert_match!(Number::<&str>::scan_from("𐒩０꘠᧑ "), Ok(("𐒩０꘠᧑", 13)));
+    let scan = <CowString>::scan_from;
+    assert_match!(scan(""), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
+    assert_match!(scan("hello world"), Ok((Cow::Borrowed("hello"), 5)));
+    assert_match!(scan("\"hello world\" x"), Ok((Cow::Borrowed("hello world"), 13)));
+    assert_match!(scan("\"\""), Ok((Cow::Borrowed(""), 2)));
+    assert_match!(
+        scan("\"say \\\"hi\\\"\" x"),
+        Ok((Cow::Owned(ref s), 12)) if s == "say \"hi\""
+    );
+    assert_match!(scan("\"unterminated"), Err(SE { kind: SEK::Syntax(_), .. }));
+
+    let scan = <CowString<Line>>::scan_from;
+    assert_match!(scan("hello world\nx"), Ok((Cow::Borrowed("hello world"), 12)));
 }
 
 /**
-Scans the given `Output` type from its octal representation.
+Scans a Roman numeral, such as "XIV" or "MCMXCIV", into an integer.
+
+Only numerals in the standard subtractive form between 1 and 3999 are accepted; *e.g.* "IIII" and "IC" are rejected.  Numerals may be written in either upper or lower case, but may not be immediately followed by a letter or digit.
 */
-pub struct Octal<Output>(PhantomData<Output>);
+pub enum RomanNumeral {}
 
-impl<'a, Output> ScanFromStr<'a> for Octal<Output>
-where Output: ScanFromOctal<'a> {
-    type Output = Output;
+impl<'a> ScanFromStr<'a> for RomanNumeral {
+    type Output = u32;
     fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
-        Output::scan_from_octal(s)
+        let s = s.as_str();
+        let (v, n) = match match_roman(s) {
+            Some(res) => res,
+            None => return Err(ScanError::syntax("expected Roman numeral")),
+        };
+
+        if s[n..].chars().next().map(|c| c.is_alphanumeric()).unwrap_or(false) {
+            return Err(ScanError::syntax("invalid Roman numeral").add_offset(n));
+        }
+
+        Ok((v, n))
+    }
+}
+
+fn match_roman(s: &str) -> Option<(u32, usize)> {
+    let bs = s.as_bytes();
+    let at = |i: usize| match bs.get(i) {
+        Some(&b) if b'a' <= b && b <= b'z' => b - b'a' + b'A',
+        Some(&b) => b,
+        None => 0,
+    };
+
+    let mut value = 0;
+    let mut i = 0;
+
+    while i < 3 && at(i) == b'M' {
+        value += 1000;
+        i += 1;
+    }
+
+    for &(one, five, ten, scale) in &[
+        (b'C', b'D', b'M', 100),
+        (b'X', b'L', b'C', 10),
+        (b'I', b'V', b'X', 1),
+    ] {
+        if at(i) == one && at(i + 1) == ten {
+            value += 9 * scale;
+            i += 2;
+        } else if at(i) == one && at(i + 1) == five {
+            value += 4 * scale;
+            i += 2;
+        } else {
+            if at(i) == five {
+                value += 5 * scale;
+                i += 1;
+            }
+            let mut ones = 0;
+            while ones < 3 && at(i) == one {
+                value += scale;
+                ones += 1;
+                i += 1;
+            }
+        }
     }
+
+    if i == 0 { None } else { Some((value, i)) }
 }
 
 #[cfg(test)]
 #[test]
-fn test_octal() {
-    assert_match!(Octal::<i32>::scan_from("0 1 2 x"), Ok((0o0, 1)));
-    assert_match!(Octal::<i32>::scan_from("012x"), Ok((0o12, 3)));
-    assert_match!(Octal::<i32>::scan_from("0o012x"), Ok((0o0, 1)));
-    assert_match!(Octal::<i32>::scan_from("7558"), Ok((0o755, 3)));
+fn test_roman_numeral() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+    let scan = RomanNumeral::scan_from;
+
+    assert_match!(scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("A"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("I"), Ok((1, 1)));
+    assert_match!(scan("iv"), Ok((4, 2)));
+    assert_match!(scan("IX."), Ok((9, 2)));
+    assert_match!(scan("XIV xyz"), Ok((14, 3)));
+    assert_match!(scan("XL"), Ok((40, 2)));
+    assert_match!(scan("MCMXCIV"), Ok((1994, 7)));
+    assert_match!(scan("MMMCMXCIX"), Ok((3999, 9)));
+    assert_match!(scan("IIII"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("IC"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("MMMM"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("MIXED"), Err(SE { kind: SEK::Syntax(_), .. }));
 }
 
 /**
-An abstract scanner that scans a `(K, V)` value using the syntax `K: V`.
-
-This scanner is designed to take advantage of three things:
-
-1. Maps (*i.e.* associative containers) typically print themselves with the syntax `{key_0: value_0, key_1: value_1, ...}`.
-
-2. Maps typically implement `Extend<(K, V)>`; that is, you can add new items by extending the map with a `(K, V)` tuple.
+Scans a single shell-style word into a string.
 
-3. Repeating bindings can be scanned into any container that implements `Default` and `Extend`.
+This follows the POSIX shell quoting rules, as used when splitting a command line into arguments:
 
-As such, this scanner allows one to parse a `Map` type like so:
+* Outside of quotes, a backslash causes the next character to be taken literally, and the word ends at the first whitespace character.
+* Text inside single quotes is taken literally.
+* Inside double quotes, a backslash only escapes `$`, `` ` ``, `"`, `\`, or a newline; otherwise, it is taken literally.
+* A backslash followed by a newline (outside of single quotes) is removed entirely.
 
-```ignore
-scan!(input; "{", [let kvs: KeyValuePair<K, V>],*: Map<_, _>, "}" => kvs)
-```
+Quoted and unquoted parts may be mixed within a single word (*e.g.* `--name="Jane Doe"`).  No other expansions (variables, globs, *etc.*) are performed.
 */
-pub struct KeyValuePair<K, V>(PhantomData<(K, V)>);
+pub enum ShellWord {}
 
-impl<'a, K, V> ScanFromStr<'a> for KeyValuePair<K, V>
-where K: ScanSelfFromStr<'a>, V: ScanSelfFromStr<'a> {
-    type Output = (K, V);
+impl<'a> ScanFromStr<'a> for ShellWord {
+    type Output = String;
     fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
         let s = s.as_str();
-        scan!(s;
-            (let k: K, ":", let v: V, ..tail) => ((k, v), s.subslice_offset_stable(tail).unwrap())
-        )
+        let syn = |msg, at| ScanError::syntax(msg).add_offset(at);
+
+        #[derive(Copy, Clone, PartialEq)]
+        enum Quote { None, Single, Double }
+
+        let mut word = String::new();
+        let mut quote = Quote::None;
+        let mut quote_at = 0;
+        let mut end = s.len();
+        let mut cps = s.char_indices();
+
+        while let Some((i, cp)) = cps.next() {
+            match (quote, cp) {
+                (Quote::None, cp) if cp.is_whitespace() => {
+                    end = i;
+                    break;
+                },
+                (Quote::None, '\'') => { quote = Quote::Single; quote_at = i; },
+                (Quote::None, '"') => { quote = Quote::Double; quote_at = i; },
+                (Quote::Single, '\'') | (Quote::Double, '"') => quote = Quote::None,
+                (Quote::Single, cp) => word.push(cp),
+                (Quote::None, '\\') | (Quote::Double, '\\') => {
+                    match cps.next() {
+                        None => return Err(syn("expected character after `\\`", i)),
+                        Some((_, '\n')) => (),
+                        Some((_, cp)) if quote == Quote::None => word.push(cp),
+                        Some((_, cp @ '$')) | Some((_, cp @ '`'))
+                        | Some((_, cp @ '"')) | Some((_, cp @ '\\')) => word.push(cp),
+                        Some((_, cp)) => { word.push('\\'); word.push(cp); },
+                    }
+                },
+                (_, cp) => word.push(cp),
+            }
+        }
+
+        if quote != Quote::None {
+            return Err(syn("unterminated quoted string", quote_at));
+        }
+
+        if end == 0 {
+            return Err(ScanError::syntax("expected shell word"));
+        }
+
+        Ok((word, end))
     }
 }
 
+#[cfg(test)]
+#[test]
+fn test_shell_word() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+    let scan = ShellWord::scan_from;
+
+    assert_match!(scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan(" abc"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("abc"), Ok((ref w, 3)) if w == "abc");
+    assert_match!(scan("abc def"), Ok((ref w, 3)) if w == "abc");
+    assert_match!(scan("''"), Ok((ref w, 2)) if w == "");
+    assert_match!(scan("'a b' c"), Ok((ref w, 5)) if w == "a b");
+    assert_match!(scan("\"a b\" c"), Ok((ref w, 5)) if w == "a b");
+    assert_match!(scan("a\\ b c"), Ok((ref w, 4)) if w == "a b");
+    assert_match!(scan("--name=\"Jane Doe\""), Ok((ref w, 17)) if w == "--name=Jane Doe");
+    assert_match!(scan("'it'\\''s'"), Ok((ref w, 9)) if w == "it's");
+    assert_match!(scan("'a\\b'"), Ok((ref w, 5)) if w == "a\\b");
+    assert_match!(scan("\"a\\\"\\$\\b\""), Ok((ref w, 9)) if w == "a\"$\\b");
+    assert_match!(scan("a\\\nb"), Ok((ref w, 4)) if w == "ab");
+    assert_match!(scan("'abc"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("\"abc"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("abc\\"), Err(SE { kind: SEK::Syntax(_), .. }));
+}
+
 /**
-Scans a quoted string.
+Scans a shell-style variable assignment, such as `FOO=bar`, into a `(name, value)` pair.
 
-Specifically, it scans the quoting format used by the `Debug` formatter for strings.
+This is the syntax used by `.env` files and simple shell scripts.  The assignment may be preceded by the `export` keyword.  The name must be made up of ASCII letters, digits, and underscores, and cannot start with a digit.  It must be followed immediately by `=`; the value follows the same quoting rules as [`ShellWord`](struct.ShellWord.html), except that it may be empty.
 
-The scanned string has all escape sequences expanded to their values, and the surrounding quotes removed.
+No expansions are performed on the value, and anything after the value (such as a trailing comment) is left unconsumed.
 */
-pub enum QuotedString {}
+pub enum EnvAssign {}
 
-impl<'a> ScanFromStr<'a> for QuotedString {
-    type Output = String;
+impl<'a> ScanFromStr<'a> for EnvAssign {
+    type Output = (String, String);
     fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
         let s = s.as_str();
-        let syn = |s| ScanError::syntax(s);
+        let bs = s.as_bytes();
 
-        let cur = StrCursor::new_at_start(s);
-        let (cp, cur) = try!(cur.next_cp().ok_or(syn("expected quoted string")));
-        match cp {
-            '"' => (),
-            _ => return Err(syn("expected `\"` for quoted string"))
+        let mut i = 0;
+        if s.starts_with("export") {
+            let spaces = bs[6..].iter().take_while(|&&b| b == b' ' || b == b'\t').count();
+            if spaces > 0 {
+                i = 6 + spaces;
+            }
         }
 
-        let mut s = String::new();
-        let mut cur = cur;
-        loop {
-            match cur.next_cp() {
-                None => return Err(syn("unterminated quoted string")),
-                Some(('\\', after)) => {
-                    match after.slice_after().split_escape_default() {
-                        Err(err) => return Err(ScanError::other(err).add_offset(after.byte_pos())),
-                        Ok((cp, tail)) => {
-                            // TODO: replace this
-                            unsafe { cur.unsafe_set_at(tail); }
-                            s.push(cp);
-                        },
-                    }
-                },
-                Some(('"', after)) => {
-                    cur = after;
-                    break;
-                },
-                Some((cp, after)) => {
-                    cur = after;
-                    s.push(cp);
-                },
-            }
+        let name_len = bs[i..].iter()
+            .enumerate()
+            .take_while(|&(j, &b)| match b {
+                b'_' | b'a'...b'z' | b'A'...b'Z' => true,
+                b'0'...b'9' => j > 0,
+                _ => false,
+            })
+            .count();
+        if name_len == 0 {
+            return Err(ScanError::syntax("expected variable name").add_offset(i));
         }
+        let name = s[i..i + name_len].to_owned();
+        i += name_len;
 
-        Ok((s, cur.byte_pos()))
+        if bs.get(i) != Some(&b'=') {
+            return Err(ScanError::syntax("expected `=` after variable name").add_offset(i));
+        }
+        i += 1;
+
+        match s[i..].chars().next() {
+            None => Ok(((name, String::new()), i)),
+            Some(cp) if cp.is_whitespace() => Ok(((name, String::new()), i)),
+            Some(_) => {
+                let (value, n) = try!(ShellWord::scan_from(&s[i..]).map_err(|e| e.add_offset(i)));
+                Ok(((name, value), i + n))
+            },
+        }
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_quoted_string() {
+fn test_env_assign() {
     use ::ScanError as SE;
     use ::ScanErrorKind as SEK;
-    use self::QuotedString as QS;
-
-    assert_match!(QS::scan_from(""), Err(SE { kind: SEK::Syntax(_), .. }));
-    assert_match!(QS::scan_from("dummy xyz"), Err(SE { kind: SEK::Syntax(_), .. }));
-    assert_match!(QS::scan_from("'dummy' xyz"), Err(SE { kind: SEK::Syntax(_), .. }));
-    assert_match!(QS::scan_from("\"dummy\" xyz"),
-        Ok((ref s, 7)) if s == "dummy");
-    assert_match!(QS::scan_from("\"ab\\\"cd\" xyz"),
-        Ok((ref s, 8)) if s == "ab\"cd");
-    assert_match!(QS::scan_from("\"ab\\x41cd\" xyz"),
-        Ok((ref s, 10)) if s == "abAcd");
-    assert_match!(QS::scan_from("\"a\\'b\\u{5B57}c\\0d\" xyz"),
-        Ok((ref s, 18)) if s == "a'b字c\0d");
+    let scan = EnvAssign::scan_from;
+
+    assert_match!(scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("=x"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("1X=x"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("FOO"), Err(SE { kind: SEK::Syntax(_), ref at, .. }) if at.offset() == 3);
+    assert_match!(scan("FOO =x"), Err(SE { kind: SEK::Syntax(_), ref at, .. }) if at.offset() == 3);
+    assert_match!(scan("FOO=bar"), Ok(((ref k, ref v), 7)) if k == "FOO" && v == "bar");
+    assert_match!(scan("_x2=1 # note"), Ok(((ref k, ref v), 5)) if k == "_x2" && v == "1");
+    assert_match!(scan("FOO="), Ok(((ref k, ref v), 4)) if k == "FOO" && v == "");
+    assert_match!(scan("FOO= bar"), Ok(((ref k, ref v), 4)) if k == "FOO" && v == "");
+    assert_match!(scan("export FOO=\"bar baz\""), Ok(((ref k, ref v), 20)) if k == "FOO" && v == "bar baz");
+    assert_match!(scan("export\tA='it'\\''s'"), Ok(((ref k, ref v), 18)) if k == "A" && v == "it's");
+    assert_match!(scan("exportFOO=1"), Ok(((ref k, ref v), 11)) if k == "exportFOO" && v == "1");
+    assert_match!(scan("export=1"), Ok(((ref k, ref v), 8)) if k == "export" && v == "1");
+    assert_match!(scan("PATH=/a\\ b:/c"), Ok(((ref k, ref v), 13)) if k == "PATH" && v == "/a b:/c");
+    assert_match!(scan("FOO=\"bar"), Err(SE { kind: SEK::Syntax(_), ref at, .. }) if at.offset() == 4);
 }
 
 /**
@@ -865,6 +3149,27 @@ impl<'a> ScanFromStr<'a> for Space<'a, String> {
     fn wants_leading_junk_stripped() -> bool { false }
 }
 
+// FIXME: Error message omitted due to https://github.com/rust-lang/rust/issues/26448.
+#[cfg(str_into_output_extra_broken)]
+impl<'a> ScanFromStr<'a> for Space<'a, Cow<'a, str>> {
+    type Output = Cow<'a, str>;
+
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        match match_space(s) {
+            Some(b) => {
+                let word = &s[..b];
+                let tail = &s[b..];
+                Ok((word.into(), s.subslice_offset_stable(tail).unwrap()))
+            },
+            // None => Err(ScanError::syntax("expected a space")),
+            None => Err(ScanError::syntax_no_message()),
+        }
+    }
+
+    fn wants_leading_junk_stripped() -> bool { false }
+}
+
 // FIXME: Error message omitted due to https://github.com/rust-lang/rust/issues/26448.
 #[cfg(not(str_into_output_extra_broken))]
 impl<'a, Output> ScanFromStr<'a> for Space<'a, Output>
@@ -915,6 +3220,210 @@ fn test_space() {
     assert_match!(Space::<&str>::scan_from("  \t \nx \t\t "), Ok(("  \t \n", 5)));
 }
 
+/**
+Scans a three-way setting keyword, such as `yes`, `no`, or `auto`, outputting `Some(true)`, `Some(false)`, or `None` respectively.
+
+Keywords are matched case-insensitively, and must not be immediately followed by another letter.  The keywords are taken from `Names`, which defaults to [`YesNoAuto`](enum.YesNoAuto.html); see [`TristateNames`](trait.TristateNames.html) for the other sets, or to define your own.
+
+```ignore
+let_scan!("color=always"; ("color", "=", let color: Tristate<AlwaysNeverAuto>));
+```
+*/
+pub struct Tristate<Names=YesNoAuto>(PhantomData<Names>);
+
+impl<'a, Names> ScanFromStr<'a> for Tristate<Names> where Names: TristateNames {
+    type Output = Option<bool>;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        match match_name(s.as_str(), Names::names()) {
+            Some((0, n)) => Ok((Some(true), n)),
+            Some((1, n)) => Ok((Some(false), n)),
+            Some((_, n)) => Ok((None, n)),
+            None => Err(ScanError::syntax("expected tri-state keyword")),
+        }
+    }
+}
+
+/**
+Provides the keywords used by the [`Tristate`](struct.Tristate.html) scanner.
+*/
+pub trait TristateNames {
+    /**
+    The keywords for "true", "false", and "unset", in that order.  Must contain exactly three entries.
+    */
+    fn names() -> &'static [&'static str];
+}
+
+/**
+Tri-state keywords `yes`, `no`, and `auto`.
+*/
+pub enum YesNoAuto {}
+
+impl TristateNames for YesNoAuto {
+    fn names() -> &'static [&'static str] {
+        const NAMES: &'static [&'static str] = &["yes", "no", "auto"];
+        NAMES
+    }
+}
+
+/**
+Tri-state keywords `always`, `never`, and `auto`.
+*/
+pub enum AlwaysNeverAuto {}
+
+impl TristateNames for AlwaysNeverAuto {
+    fn names() -> &'static [&'static str] {
+        const NAMES: &'static [&'static str] = &["always", "never", "auto"];
+        NAMES
+    }
+}
+
+/**
+Tri-state keywords `on`, `off`, and `default`.
+*/
+pub enum OnOffDefault {}
+
+impl TristateNames for OnOffDefault {
+    fn names() -> &'static [&'static str] {
+        const NAMES: &'static [&'static str] = &["on", "off", "default"];
+        NAMES
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_tristate() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+
+    let scan = <Tristate>::scan_from;
+    assert_match!(scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("yes"), Ok((Some(true), 3)));
+    assert_match!(scan("No,"), Ok((Some(false), 2)));
+    assert_match!(scan("AUTO x"), Ok((None, 4)));
+    assert_match!(scan("yesterday"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("on"), Err(SE { kind: SEK::Syntax(_), .. }));
+
+    let scan = Tristate::<AlwaysNeverAuto>::scan_from;
+    assert_match!(scan("always"), Ok((Some(true), 6)));
+    assert_match!(scan("never"), Ok((Some(false), 5)));
+    assert_match!(scan("auto"), Ok((None, 4)));
+    assert_match!(scan("yes"), Err(SE { kind: SEK::Syntax(_), .. }));
+
+    let scan = Tristate::<OnOffDefault>::scan_from;
+    assert_match!(scan("on"), Ok((Some(true), 2)));
+    assert_match!(scan("off"), Ok((Some(false), 3)));
+    assert_match!(scan("default"), Ok((None, 7)));
+    assert_match!(scan("once"), Err(SE { kind: SEK::Syntax(_), .. }));
+}
+
+/**
+Scans an integer written with any Unicode decimal digits into `Output`.
+
+The integer scanners only accept the ASCII digits `0` through `9`, whereas [`Number`](struct.Number.html) accepts any character in the Unicode `Nd` (decimal number) category.  This scanner accepts what `Number` does, with an optional leading `+` or `-`, folding each digit to its ASCII equivalent before parsing.  For example, "１７０１" scans as `1701`.
+
+Digits from different scripts may be mixed.  The folded integer is scanned by `Output`'s own scanner, so a value which does not fit is an `Overflow` error, and a sign is only accepted where `Output` accepts one.
+*/
+pub struct UnicodeInt<Output=i32>(PhantomData<Output>);
+
+impl<'a, Output> ScanFromStr<'a> for UnicodeInt<Output>
+where Output: for<'b> ScanFromStr<'b, Output=Output> {
+    type Output = Output;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        let mut folded = String::new();
+        let mut n = match s.chars().next() {
+            Some(c @ '+') | Some(c @ '-') => { folded.push(c); 1 },
+            _ => 0,
+        };
+
+        let digits_at = n;
+        while let Some((v, c)) = match_decimal_digit(&s[n..]) {
+            folded.push((b'0' + v) as char);
+            n += c.len_utf8();
+        }
+
+        if n == digits_at {
+            return Err(ScanError::syntax("expected decimal digits").add_offset(n));
+        }
+
+        // Errors from scanning the folded text are all at its start, which is also the start of `s`.
+        Output::scan_from(&folded[..]).map(|(v, _)| (v, n))
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_unicode_int() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+
+    let scan = <UnicodeInt>::scan_from;
+    assert_match!(scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("x"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("-"), Err(SE { ref at, kind: SEK::Syntax(_), .. }) if at.offset() == 1);
+    assert_match!(scan("42 x"), Ok((42, 2)));
+    assert_match!(scan("１７０１"), Ok((1701, 12)));
+    assert_match!(scan("-٣٢"), Ok((-32, 5)));
+    assert_match!(scan("+1２"), Ok((12, 5)));
+    assert_match!(scan("99999999999"), Err(SE { kind: SEK::Overflow("i32"), .. }));
+    assert_match!(scan("-٣٣٣٣٣٣٣٣٣٣٣"), Err(SE { kind: SEK::Overflow("i32"), .. }));
+
+    let scan = UnicodeInt::<u8>::scan_from;
+    assert_match!(scan("２５５"), Ok((255, 9)));
+    assert_match!(scan("２５６"), Err(SE { ref at, kind: SEK::Overflow("u8"), .. }) if at.offset() == 0);
+    assert_match!(scan("-1"), Err(SE { kind: SEK::Syntax(_), .. }));
+}
+
+/**
+Scans the name of a day of the week, either in full or abbreviated, outputting its ISO 8601 number (Monday is `1`, Sunday is `7`).
+
+Names are matched case-insensitively, and must not be immediately followed by another letter.  The names are taken from `Names`, which defaults to [`English`](enum.English.html); see [`DateNames`](trait.DateNames.html) to support other languages.
+*/
+pub struct WeekdayName<Names=English>(PhantomData<Names>);
+
+impl<'a, Names> ScanFromStr<'a> for WeekdayName<Names> where Names: DateNames {
+    type Output = u8;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        match_name(s, Names::weekday_names())
+            .or_else(|| match_name(s, Names::weekday_abbrs()))
+            .map(|(i, n)| (i as u8 + 1, n))
+            .ok_or_else(|| ScanError::syntax("expected weekday name"))
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_weekday_name() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+
+    let scan = WeekdayName::<English>::scan_from;
+    assert_match!(scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("Tue Jan  5"), Ok((2, 3)));
+    assert_match!(scan("sunday"), Ok((7, 6)));
+    assert_match!(scan("Monkey"), Err(SE { kind: SEK::Syntax(_), .. }));
+
+    enum Deutsch {}
+    impl DateNames for Deutsch {
+        fn month_names() -> &'static [&'static str] { English::month_names() }
+        fn month_abbrs() -> &'static [&'static str] { English::month_abbrs() }
+        fn weekday_names() -> &'static [&'static str] {
+            const NAMES: &'static [&'static str] = &[
+                "Montag", "Dienstag", "Mittwoch", "Donnerstag", "Freitag", "Samstag", "Sonntag",
+            ];
+            NAMES
+        }
+        fn weekday_abbrs() -> &'static [&'static str] {
+            const NAMES: &'static [&'static str] = &["Mo", "Di", "Mi", "Do", "Fr", "Sa", "So"];
+            NAMES
+        }
+    }
+
+    assert_match!(WeekdayName::<Deutsch>::scan_from("Mittwoch"), Ok((3, 8)));
+    assert_match!(WeekdayName::<Deutsch>::scan_from("So."), Ok((7, 2)));
+}
+
 /**
 Scans a single word into a string.
 
@@ -958,6 +3467,24 @@ impl<'a> ScanFromStr<'a> for Word<'a, String> {
     }
 }
 
+// FIXME: Error message omitted due to https://github.com/rust-lang/rust/issues/26448.
+#[cfg(str_into_output_extra_broken)]
+impl<'a> ScanFromStr<'a> for Word<'a, Cow<'a, str>> {
+    type Output = Cow<'a, str>;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        match match_word(s) {
+            Some(b) => {
+                let word = &s[..b];
+                let tail = &s[b..];
+                Ok((word.into(), s.subslice_offset_stable(tail).unwrap()))
+            },
+            // None => Err(ScanError::syntax("expected a word")),
+            None => Err(ScanError::syntax_no_message()),
+        }
+    }
+}
+
 // FIXME: Error message omitted due to https://github.com/rust-lang/rust/issues/26448.
 #[cfg(not(str_into_output_extra_broken))]
 impl<'a, Output> ScanFromStr<'a> for Word<'a, Output>
@@ -995,6 +3522,7 @@ fn test_word() {
 
     assert_match!(Word::<&str>::scan_from(""), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
     assert_match!(Word::<&str>::scan_from("a"), Ok(("a", 1)));
+    assert_match!(Word::<Cow<str>>::scan_from("ab c"), Ok((Cow::Borrowed("ab"), 2)));
     assert_match!(Word::<&str>::scan_from("0"), Ok(("0", 1)));
     assert_match!(Word::<&str>::scan_from("0x"), Ok(("0x", 2)));
     assert_match!(Word::<&str>::scan_from("x0"), Ok(("x0", 2)));
@@ -1051,6 +3579,25 @@ impl<'a> ScanFromStr<'a> for Wordish<'a, String> {
     }
 }
 
+// FIXME: Error message omitted due to https://github.com/rust-lang/rust/issues/26448.
+#[cfg(str_into_output_extra_broken)]
+impl<'a> ScanFromStr<'a> for Wordish<'a, Cow<'a, str>> {
+    type Output = Cow<'a, str>;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        // TODO: This should be modified to grab an entire *grapheme cluster* in the event it can't find a word or number.
+        match match_wordish(s) {
+            Some(b) => {
+                let word = &s[..b];
+                let tail = &s[b..];
+                Ok((word.into(), s.subslice_offset_stable(tail).unwrap()))
+            },
+            // None => Err(ScanError::syntax("expected a word, number or some other character")),
+            None => Err(ScanError::syntax_no_message()),
+        }
+    }
+}
+
 // FIXME: Error message omitted due to https://github.com/rust-lang/rust/issues/26448.
 #[cfg(not(str_into_output_extra_broken))]
 impl<'a, Output> ScanFromStr<'a> for Wordish<'a, Output>
@@ -34,6 +34,8 @@ For example, these two bindings are equivalent in terms of behaviour:
     let _ <| scan_a::<u32>()
 ```
 
+Going the other way, `Runtime<Type>` is a static scanner which takes a runtime scanner implementing `Default` as a type parameter, allowing it to be used in a binding's type position.  See also [`scanner_alias!`](../macro.scanner_alias!.html).
+
 ## Creating Runtime Scanners
 
 Runtime scanners are typically constructed using functions, rather than dealing with the implementing type itself.  For example, to get an instance of the `ExactWidth` runtime scanner, you would call either the `exact_width` or `exact_width_a` functions.
@@ -57,19 +59,60 @@ Functions ending in `_a` are a shorthand for the common case of wrapping a runti
 It is also where implementations for existing standard and external types are kept, though these do not appear in the documentation.
 */
 pub use self::misc::{
-    Everything, HorSpace, Newline, NonSpace, Space,
-    Ident, Line, Number, Word, Wordish,
-    Inferred, KeyValuePair, QuotedString,
-    Binary, Octal, Hex,
+    Everything, Fallible, HorSpace, Newline, NonSpace, Space,
+    GitRef, GitSha, Ident, Line, Number, FloatLiteral, UnicodeInt, Word, Wordish,
+    Inferred, IntList, KeyValuePair, QuotedString, CowString,
+    OrdinalNumber, RomanNumeral, ShellWord, EnvAssign,
+    PathList, PathListStyle, NativePaths, UnixPaths, WindowsPaths,
+    Binary, Bits, BitSet, Octal, Hex, HexDumpLine,
+    ByteSize, Color, DebugName, DebugTuple, DryRun,
+    DecimalDigit, Letter, PunctChar,
+    ListOf, ListSeparator, CommaSep, SpaceSep,
+    MonthName, WeekdayName, DateNames, English,
+    Tristate, TristateNames, YesNoAuto, AlwaysNeverAuto, OnOffDefault,
 };
 
 #[doc(inline)] pub use self::runtime::{
+    boxed, boxed_a,
+    collection_limits, collection_limits_a,
+    columns_by, columns_by_a, columns_by_str,
+    cut, cut_a,
+    datetime,
+    delimited,
     exact_width, exact_width_a,
+    fixed_point, FixedPoint, FixedPointRounding,
+    flag_present, FlagPresent,
+    float_with, FloatWith,
+    indented_block,
+    interned, interned_a, Interned, Interner,
+    into_buf, into_buf_a, IntoBuf,
+    key_value,
+    keyword_set, KeywordSet,
     max_width, max_width_a,
     min_width, min_width_a,
+    measure, Measure, MeasureUnit,
+    none_of, one_of,
+    padded, padded_a,
     scan_a,
+    Runtime,
+    soft, soft_a,
+    take_while_char,
+    until, until_a, until_any, until_char, until_fn, until_str,
+    with_raw, with_raw_a,
 };
 
+#[cfg(feature="english-numbers")]
+#[doc(inline)]
+pub use self::misc::EnglishNumber;
+
+#[cfg(feature="std")]
+#[doc(inline)]
+pub use self::misc::{MapOf, SetOf};
+
+#[cfg(feature="python-literals")]
+#[doc(inline)]
+pub use self::misc::{PyBool, PyOption, PyString};
+
 #[cfg(feature="regex")]
 #[doc(inline)]
 pub use self::runtime::{re, re_a, re_str};
@@ -83,6 +126,18 @@ pub use self::runtime::{until_pat, until_pat_a, until_pat_str};
 pub mod runtime;
 pub mod std;
 
+#[cfg(any(feature="http", feature="logformats", feature="rustdev"))]
+mod bytes;
+
+#[cfg(feature="http")]
+pub mod http;
+
+#[cfg(feature="logformats")]
+pub mod logformats;
+
+#[cfg(feature="rustdev")]
+pub mod rustdev;
+
 mod lang;
 mod misc;
 
@@ -118,6 +173,17 @@ pub trait ScanFromStr<'a>: Sized {
     */
     fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError>;
 
+    /**
+    Perform a scan on the given input, but only report how many bytes would have been consumed.
+
+    The default implementation simply discards the output of `scan_from`.  Implementations may override this if they can determine the length of a match more cheaply than by actually producing a value.
+
+    See: [`DryRun`](struct.DryRun.html).
+    */
+    fn peek_scan_from<I: ScanInput<'a>>(s: I) -> Result<usize, ScanError> {
+        Self::scan_from(s).map(|(_, n)| n)
+    }
+
     /**
     Indicates whether or not the scanner wants its input to have leading "junk", such as whitespace, stripped.
 
@@ -150,6 +216,8 @@ impl<'a, T> ScanSelfFromStr<'a> for T where T: ScanFromStr<'a, Output=T> {}
 This trait defines scanning a type from a binary representation.
 
 This should be implemented to match implementations of `std::fmt::Binary`.
+
+This is not implemented for `f32` or `f64`, which do not implement `Binary` either.  To round-trip the exact bit pattern of a float, format the result of `to_bits` in binary, scan it back with `Binary<u64>` (or `Binary<u32>`), and pass the result to `f64::from_bits` (or `f32::from_bits`).  Alternately, `Hex<f64>` can be used to scan the exact value of a float written in hexadecimal floating point notation.
 */
 pub trait ScanFromBinary<'a>: Sized {
     /**
@@ -178,6 +246,8 @@ pub trait ScanFromOctal<'a>: Sized {
 This trait defines scanning a type from a hexadecimal representation.
 
 This should be implemented to match implementations of `std::fmt::LowerHex` and `std::fmt::UpperHex`.
+
+For `f32` and `f64`, this scans the hexadecimal floating point notation from C99, as produced by `printf("%a", ...)`: for example, "0x1.8p+1" is `3.0`.  The `0x` prefix and the `p` exponent (a power of two, written in decimal) are both optional.  The result is correctly rounded, so any value printed exactly in this notation will round-trip.
 */
 pub trait ScanFromHex<'a>: Sized {
     /**
@@ -217,3 +287,15 @@ pub trait ScanStr<'a>: Sized {
     */
     fn wants_leading_junk_stripped(&self) -> bool;
 }
+
+impl<'a, 'b, S> ScanStr<'a> for &'b mut S where S: ScanStr<'a> {
+    type Output = S::Output;
+
+    fn scan<I: ScanInput<'a>>(&mut self, s: I) -> Result<(Self::Output, usize), ScanError> {
+        (**self).scan(s)
+    }
+
+    fn wants_leading_junk_stripped(&self) -> bool {
+        (**self).wants_leading_junk_stripped()
+    }
+}
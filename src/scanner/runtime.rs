@@ -11,77 +11,1727 @@ or distributed except according to those terms.
 Types and constructors for various runtime scanners.
 */
 use std::marker::PhantomData;
+use std::num::ParseFloatError;
+use std::ops::{Add, Mul, Range};
+use std::str::FromStr;
 use strcursor::StrCursor;
 use ::ScanError;
 use ::input::ScanInput;
-use ::scanner::{ScanFromStr, ScanStr};
+use ::scanner::{ScanFromStr, ScanSelfFromStr, ScanStr};
+use ::scanner::{English, MonthName, WeekdayName};
+use ::scanner::lang::match_float_point;
+
+#[cfg(all(feature="std", rc_from_str))] use std::collections::HashSet;
+#[cfg(all(feature="std", rc_from_str))] use std::hash::BuildHasher;
+#[cfg(all(feature="std", rc_from_str))] use std::rc::Rc;
+#[cfg(all(feature="std", rc_from_str))] use std::sync::Arc;
+#[cfg(feature="regex")] use regex::Regex;
+
+/**
+Boxes a runtime scanner, erasing its type.
+
+This allows scanners with different types, but the same `Output`, to be chosen between at runtime or stored together.
+
+Note that the boxed scanner is given its input as a plain string slice; any input-specific behaviour, such as case-insensitive literal matching, is lost.
+
+See: [`boxed_a`](fn.boxed_a.html).
+*/
+pub fn boxed<'a, Then>(then: Then) -> BoxedScanStr<'a, Then::Output>
+where Then: 'a + ScanStr<'a> {
+    BoxedScanStr(Box::new(then))
+}
+
+/**
+Boxes the static scanner `S` as a runtime scanner, erasing its type.
+
+See: [`boxed`](fn.boxed.html).
+*/
+pub fn boxed_a<'a, S>() -> BoxedScanStr<'a, S::Output>
+where S: 'a + ScanFromStr<'a> {
+    boxed(scan_a::<S>())
+}
+
+/**
+Runtime scanner with its concrete type erased.
+
+See: [`boxed`](fn.boxed.html).
+*/
+pub struct BoxedScanStr<'a, Output>(Box<ErasedScanStr<'a, Output> + 'a>);
+
+impl<'a, Output> ScanStr<'a> for BoxedScanStr<'a, Output> {
+    type Output = Output;
+
+    fn scan<I: ScanInput<'a>>(&mut self, s: I) -> Result<(Self::Output, usize), ScanError> {
+        self.0.erased_scan(s.as_str())
+    }
+
+    fn wants_leading_junk_stripped(&self) -> bool {
+        self.0.erased_wants_leading_junk_stripped()
+    }
+}
+
+/**
+Object-safe version of `ScanStr`, used to implement `BoxedScanStr`.
+*/
+trait ErasedScanStr<'a, Output> {
+    fn erased_scan(&mut self, s: &'a str) -> Result<(Output, usize), ScanError>;
+    fn erased_wants_leading_junk_stripped(&self) -> bool;
+}
+
+impl<'a, S> ErasedScanStr<'a, S::Output> for S where S: ScanStr<'a> {
+    fn erased_scan(&mut self, s: &'a str) -> Result<(S::Output, usize), ScanError> {
+        self.scan(s)
+    }
+
+    fn erased_wants_leading_junk_stripped(&self) -> bool {
+        self.wants_leading_junk_stripped()
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_boxed() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+
+    let mut scanners: Vec<BoxedScanStr<u32>> = vec![
+        boxed_a::<u32>(),
+        boxed(max_width_a::<u32>(2)),
+        boxed_a::<::scanner::Hex<u32>>(),
+    ];
+
+    assert_match!(scanners[0].scan("1234"), Ok((1234, 4)));
+    assert_match!(scanners[1].scan("1234"), Ok((12, 2)));
+    assert_match!(scanners[2].scan("1234"), Ok((0x1234, 4)));
+    assert_match!(scanners[0].scan("x"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert!(scanners[0].wants_leading_junk_stripped());
+
+    let results: Vec<_> = scanners.iter_mut()
+        .map(|mut scanner| scan!("10"; (let v <| scanner) => v))
+        .collect();
+    assert_match!(&results[..], &[Ok(10), Ok(10), Ok(0x10)]);
+}
+
+/**
+Creates a runtime scanner that limits the size of any collections scanned by `then`.
+
+The standard collection scanners (`Vec<T>`, `HashMap<K, V>`, *etc.*) will accept as many elements, nested as deeply, as the input contains.  When scanning untrusted input, this scanner can be used to bound them: each collection may contain at most `max_elems` elements, and collections may be nested at most `max_depth` deep (a `Vec<i32>` has a depth of one).  `None` means no limit.
+
+If either limit is exceeded, scanning fails with a `LimitExceeded` error.
+
+The limits apply to everything scanned by `then`, for the duration of the scan.
+
+See: [`collection_limits_a`](fn.collection_limits_a.html), [`Limited`](../input/struct.Limited.html).
+*/
+pub fn collection_limits<Then>(max_elems: Option<usize>, max_depth: Option<usize>, then: Then) -> CollectionLimits<Then> {
+    CollectionLimits {
+        max_elems: max_elems,
+        max_depth: max_depth,
+        then: then,
+    }
+}
+
+/**
+Creates a runtime scanner that limits the size of any collections scanned by the static scanner `S`.
+
+```ignore
+// Accept at most 100 elements per list, and no more than two levels of nesting.
+let _ <| collection_limits_a::<Vec<Vec<i32>>>(Some(100), Some(2))
+```
+
+See: [`collection_limits`](fn.collection_limits.html).
+*/
+pub fn collection_limits_a<S>(max_elems: Option<usize>, max_depth: Option<usize>) -> CollectionLimits<ScanA<S>> {
+    collection_limits(max_elems, max_depth, scan_a::<S>())
+}
+
+/**
+Runtime scanner that limits the size of scanned collections.
+
+See: [`collection_limits`](../fn.collection_limits.html).
+*/
+pub struct CollectionLimits<Then> {
+    max_elems: Option<usize>,
+    max_depth: Option<usize>,
+    then: Then,
+}
+
+impl<'a, Then> ScanStr<'a> for CollectionLimits<Then> where Then: ScanStr<'a> {
+    type Output = Then::Output;
+
+    fn scan<I: ScanInput<'a>>(&mut self, s: I) -> Result<(Self::Output, usize), ScanError> {
+        use ::internal::set_collection_limits;
+
+        /*
+        Restore the previous limits even if `then` panics.
+        */
+        struct Restore((Option<usize>, Option<usize>, usize));
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                set_collection_limits(self.0);
+            }
+        }
+
+        let _restore = Restore(set_collection_limits((self.max_elems, self.max_depth, 0)));
+        self.then.scan(s)
+    }
+
+    fn wants_leading_junk_stripped(&self) -> bool {
+        self.then.wants_leading_junk_stripped()
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_collection_limits() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+
+    let scan = || collection_limits_a::<Vec<Vec<i32>>>(Some(3), Some(2));
+    assert_match!(scan().scan("[]"), Ok((ref v, 2)) if v.is_empty());
+    assert_match!(scan().scan("[[1, 2, 3], []]"), Ok((ref v, 15)) if *v == [vec![1, 2, 3], vec![]]);
+    assert_match!(scan().scan("[[1, 2, 3, 4]]"), Err(SE { kind: SEK::LimitExceeded, .. }));
+    assert_match!(scan().scan("[[], [], [], []]"), Err(SE { kind: SEK::LimitExceeded, .. }));
+
+    let scan = || collection_limits_a::<Vec<Vec<i32>>>(None, Some(1));
+    assert_match!(scan().scan("[]"), Ok((ref v, 2)) if v.is_empty());
+    assert_match!(scan().scan("[[1]]"), Err(SE { kind: SEK::LimitExceeded, .. }));
+    assert_match!(scan().scan("[[]]"), Err(SE { kind: SEK::LimitExceeded, .. }));
+    assert_match!(collection_limits_a::<Vec<Vec<Vec<i32>>>>(None, Some(1)).scan("[[[1]]]"), Err(SE { kind: SEK::LimitExceeded, .. }));
+
+    let scan = || collection_limits_a::<::std::collections::BTreeMap<i32, Vec<i32>>>(Some(1), None);
+    assert_match!(scan().scan("{1: [2]}"), Ok((_, 8)));
+    assert_match!(scan().scan("{1: [2, 3]}"), Err(SE { kind: SEK::LimitExceeded, .. }));
+
+    // Limits only apply within the scanner.
+    assert_match!(collection_limits_a::<i32>(Some(0), Some(0)).scan("1"), Ok((1, 1)));
+    assert_match!(scan_a::<Vec<Vec<i32>>>().scan("[[1, 2, 3, 4]]"), Ok(_));
+
+    assert_match!(
+        scan!("[1, 2] [3, 4, 5]"; (let a <| collection_limits_a::<Vec<i32>>(Some(2), None), let b: Vec<i32>) => (a, b)),
+        Ok((ref a, ref b)) if *a == [1, 2] && *b == [3, 4, 5]
+    );
+}
+
+/**
+Creates a runtime scanner that extracts the next column of tabular text, passing it to another runtime scanner.
+
+Columns are separated by a tab, or by a run of at least `min_gap` spaces.  Shorter runs of spaces are considered part of the column, which allows for columns such as `Jan 14` or `/usr/bin/some command`.  A column also ends at a line break, or the end of the input.  Trailing spaces are not included in the column.
+
+This is intended for the output of tools such as `ps` and `ls -l`, which use spaces to align columns, but may also contain spaces within a column.
+
+The inner scanner must consume the entire column.
+
+## Panics
+
+Panics if `min_gap` is zero.
+
+See: [`columns_by_a`](fn.columns_by_a.html), [`columns_by_str`](fn.columns_by_str.html).
+*/
+pub fn columns_by<Then>(min_gap: usize, then: Then) -> ColumnsBy<Then> {
+    assert!(min_gap > 0, "column gap must be at least one space");
+    ColumnsBy(min_gap, then)
+}
+
+/**
+Creates a runtime scanner that extracts the next column of tabular text, passing it to the static scanner `S`.
+
+See: [`columns_by`](fn.columns_by.html).
+*/
+pub fn columns_by_a<S>(min_gap: usize) -> ColumnsBy<ScanA<S>> {
+    columns_by(min_gap, scan_a::<S>())
+}
+
+/**
+Creates a runtime scanner that yields the next column of tabular text as a string slice.
+
+See: [`columns_by`](fn.columns_by.html).
+*/
+pub fn columns_by_str<'a>(min_gap: usize) -> ColumnsBy<ScanA<::scanner::Everything<'a, &'a str>>> {
+    columns_by_a::<::scanner::Everything<'a, &'a str>>(min_gap)
+}
+
+/**
+Runtime scanner that extracts the next column of tabular text.
+
+See: [`columns_by`](fn.columns_by.html), [`columns_by_a`](fn.columns_by_a.html), [`columns_by_str`](fn.columns_by_str.html).
+*/
+pub struct ColumnsBy<Then>(usize, Then);
+
+impl<'a, Then> ScanStr<'a> for ColumnsBy<Then>
+where Then: ScanStr<'a> {
+    type Output = Then::Output;
+
+    fn scan<I: ScanInput<'a>>(&mut self, s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s_str = s.as_str();
+        let bs = s_str.as_bytes();
+
+        let mut end = 0;
+        let mut i = 0;
+        while i < bs.len() {
+            match bs[i] {
+                b'\t' | b'\r' | b'\n' => break,
+                b' ' => {
+                    let run = bs[i..].iter().take_while(|&&b| b == b' ').count();
+                    if run >= self.0 {
+                        break;
+                    }
+                    i += run;
+                },
+                _ => {
+                    i += 1;
+                    end = i;
+                },
+            }
+        }
+
+        if end == 0 {
+            return Err(ScanError::syntax("expected column"));
+        }
+
+        let sl = s.from_subslice(&s_str[..end]);
+
+        match self.1.scan(sl) {
+            Ok((_, n)) if n != end => Err(ScanError::syntax("value did not consume the whole column").add_offset(n)),
+            Err(err) => Err(err),
+            Ok((v, _)) => Ok((v, end))
+        }
+    }
+
+    fn wants_leading_junk_stripped(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_columns_by() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+    let scan = columns_by_str;
+
+    assert_match!(scan(2).scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan(2).scan("\tx"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan(2).scan("root"), Ok(("root", 4)));
+    assert_match!(scan(2).scan("Jan 14  x"), Ok(("Jan 14", 6)));
+    assert_match!(scan(2).scan("Jan 14 \tx"), Ok(("Jan 14", 6)));
+    assert_match!(scan(2).scan("a b c \nx"), Ok(("a b c", 5)));
+    assert_match!(scan(1).scan("Jan 14"), Ok(("Jan", 3)));
+    assert_match!(scan(3).scan("a  b   c"), Ok(("a  b", 4)));
+
+    assert_match!(columns_by_a::<i32>(2).scan("12  3"), Ok((12, 2)));
+    assert_match!(
+        columns_by_a::<i32>(2).scan("12 3  4"),
+        Err(SE { kind: SEK::Syntax(_), ref at, .. }) if at.offset() == 2
+    );
+
+    let inp = "root       1  0.0  /sbin/init splash\n";
+    let r = scan!(inp;
+        (let user <| columns_by_str(2), let pid: u32, let cpu <| columns_by_a::<f32>(2),
+            let cmd <| columns_by_str(2), ...) => (user, pid, cpu, cmd));
+    assert_match!(r, Ok(("root", 1, 0.0, "/sbin/init splash")));
+}
+
+/**
+Creates a runtime scanner that turns any failure of `then` into a hard failure.
+
+Ordinarily, when a rule in a `scan!` invocation fails, the next rule is tried.  If the failure comes from a `cut` scanner, scanning stops immediately with that error instead.  This is useful once enough of the input has been seen to know which rule *should* match: the error reported is then the one that actually went wrong, rather than whichever rule got furthest.
+
+The mark is kept as the error propagates outward, so a hard failure inside a repetition fails the whole repetition, rather than simply ending it.  Use [`soft`](fn.soft.html) to contain it.
+
+```ignore
+// Once we've seen "set", the rest of the line *must* be a key/value pair.
+scan!(line;
+    ("set", let k: Word, "=", let v <| cut_a::<i32>()) => Set(k, v),
+    (let k: Word) => Get(k),
+)
+```
+
+See: [`cut_a`](fn.cut_a.html), [`ScanError::cut`](../../struct.ScanError.html#method.cut).
+*/
+pub fn cut<Then>(then: Then) -> Cut<Then> {
+    Cut(then)
+}
+
+/**
+Creates a runtime scanner that turns any failure of the static scanner `S` into a hard failure.
+
+See: [`cut`](fn.cut.html).
+*/
+pub fn cut_a<S>() -> Cut<ScanA<S>> {
+    cut(scan_a::<S>())
+}
+
+/**
+Runtime scanner that turns failures into hard failures.
+
+See: [`cut`](fn.cut.html), [`cut_a`](fn.cut_a.html).
+*/
+pub struct Cut<Then>(Then);
+
+impl<'a, Then> ScanStr<'a> for Cut<Then>
+where Then: ScanStr<'a> {
+    type Output = Then::Output;
+
+    fn scan<I: ScanInput<'a>>(&mut self, s: I) -> Result<(Self::Output, usize), ScanError> {
+        self.0.scan(s).map_err(ScanError::cut)
+    }
+
+    fn wants_leading_junk_stripped(&self) -> bool {
+        self.0.wants_leading_junk_stripped()
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_cut() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+    use ::scanner::Word;
+
+    assert_match!(cut_a::<i32>().scan("42"), Ok((42, 2)));
+    assert_match!(cut_a::<i32>().scan("x"), Err(ref err) if err.is_fatal());
+
+    let scan_cmd = |s: &'static str| scan!(s;
+        ("set", let k: Word, "=", let v <| cut_a::<i32>()) => (k, Some(v)),
+        (let k: Word, ...) => (k, None),
+    );
+    assert_match!(scan_cmd("set x = 1"), Ok(("x", Some(1))));
+    assert_match!(scan_cmd("get x"), Ok(("get", None)));
+    assert_match!(scan_cmd("set x = y"), Err(SE { kind: SEK::Syntax(_), ref at, .. }) if at.offset() == 8);
+
+    let r = scan!("1, 2, x";
+        ([let ns <| cut_a::<i32>()],+, ...) => ns,
+        (..rest) => vec![rest.len() as i32],
+    );
+    assert_match!(r, Err(SE { kind: SEK::Syntax(_), ref at, .. }) if at.offset() == 6);
+}
+
+/**
+Creates a runtime scanner that scans a date and/or time according to a `strftime`-like format string.
+
+The output is a tuple of `(year, month, day, hour, minute, second)`.  Components which do not appear in the format default to zero, except for the month and day, which default to one.
+
+The following directives are supported:
+
+* `%Y` - year, as up to four digits.
+* `%y` - year within the century, as two digits.  `69` to `99` are taken to be in the 1900s, `00` to `68` in the 2000s.
+* `%m` - month, as one or two digits.
+* `%d` - day of the month, as one or two digits.
+* `%e` - like `%d`, except that it may be padded with a leading space.
+* `%H` - hour (24-hour clock), as one or two digits.
+* `%M` - minute, as one or two digits.
+* `%S` - second, as one or two digits.  `60` is allowed, for leap seconds.
+* `%b`, `%B`, `%h` - month name, full or abbreviated; see [`MonthName`](../struct.MonthName.html).
+* `%a`, `%A` - weekday name, full or abbreviated; see [`WeekdayName`](../struct.WeekdayName.html).  This is checked, but not included in the output.
+* `%F` - equivalent to `%Y-%m-%d`.
+* `%T` - equivalent to `%H:%M:%S`.
+* `%%` - a literal `%`.
+
+Whitespace in the format matches any amount (including none) of whitespace in the input.  All other characters must match exactly.
+
+## Panics
+
+Panics if the format contains an unsupported directive.
+*/
+pub fn datetime(fmt: &str) -> DateTime {
+    if let Err(msg) = DateTimeParts::new().scan_fmt(fmt, "", false) {
+        panic!("invalid date/time format {:?}: {}", fmt, msg);
+    }
+    DateTime(fmt)
+}
+
+/**
+Runtime scanner for dates and times.
+
+See: [`datetime`](../fn.datetime.html).
+*/
+pub struct DateTime<'f>(&'f str);
+
+impl<'a, 'f> ScanStr<'a> for DateTime<'f> {
+    type Output = (i32, u8, u8, u8, u8, u8);
+
+    fn scan<I: ScanInput<'a>>(&mut self, s: I) -> Result<(Self::Output, usize), ScanError> {
+        let mut parts = DateTimeParts::new();
+        match parts.scan_fmt(self.0, s.as_str(), true) {
+            Ok(n) => Ok((parts.into_tuple(), n)),
+            Err(msg) => Err(ScanError::syntax(msg).add_offset(parts.at)),
+        }
+    }
+
+    fn wants_leading_junk_stripped(&self) -> bool {
+        true
+    }
+}
+
+struct DateTimeParts {
+    year: i32,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minute: u8,
+    second: u8,
+    /// Input position, for error reporting.
+    at: usize,
+}
+
+impl DateTimeParts {
+    fn new() -> Self {
+        DateTimeParts { year: 0, month: 1, day: 1, hour: 0, minute: 0, second: 0, at: 0 }
+    }
+
+    fn into_tuple(self) -> (i32, u8, u8, u8, u8, u8) {
+        (self.year, self.month, self.day, self.hour, self.minute, self.second)
+    }
+
+    /**
+    Scans `s` according to `fmt`, returning the number of bytes consumed.  If `check` is false, this only validates the format.
+    */
+    fn scan_fmt(&mut self, fmt: &str, s: &str, check: bool) -> Result<usize, &'static str> {
+        let mut fmt_chars = fmt.chars();
+        while let Some(fc) = fmt_chars.next() {
+            if fc.is_whitespace() {
+                let rest = &s[self.at..];
+                self.at += rest.len() - rest.trim_left().len();
+                continue;
+            }
+
+            if fc != '%' {
+                if check {
+                    if !s[self.at..].starts_with(fc) {
+                        return Err("date/time does not match format");
+                    }
+                    self.at += fc.len_utf8();
+                }
+                continue;
+            }
+
+            let dir = match fmt_chars.next() {
+                Some(c) => c,
+                None => return Err("format ends with `%`"),
+            };
+
+            match dir {
+                'F' => { try!(self.scan_fmt("%Y-%m-%d", s, check)); continue; },
+                'T' => { try!(self.scan_fmt("%H:%M:%S", s, check)); continue; },
+                'Y' | 'y' | 'm' | 'd' | 'e' | 'H' | 'M' | 'S' | '%' => (),
+                'a' | 'A' | 'b' | 'B' | 'h' => (),
+                _ => return Err("unsupported directive"),
+            }
+
+            if !check {
+                continue;
+            }
+
+            if dir == '%' {
+                if !s[self.at..].starts_with('%') {
+                    return Err("date/time does not match format");
+                }
+                self.at += 1;
+                continue;
+            }
+
+            match dir {
+                'b' | 'B' | 'h' => {
+                    let (v, n) = try!(MonthName::<English>::scan_from(&s[self.at..])
+                        .map_err(|_| "expected month name"));
+                    self.month = v;
+                    self.at += n;
+                    continue;
+                },
+                'a' | 'A' => {
+                    let (_, n) = try!(WeekdayName::<English>::scan_from(&s[self.at..])
+                        .map_err(|_| "expected weekday name"));
+                    self.at += n;
+                    continue;
+                },
+                _ => (),
+            }
+
+            if dir == 'e' && s[self.at..].starts_with(' ') {
+                self.at += 1;
+            }
+
+            let (min_digits, max_digits) = match dir {
+                'Y' => (1, 4),
+                'y' => (2, 2),
+                _ => (1, 2),
+            };
+            let digits = s[self.at..].bytes()
+                .take(max_digits)
+                .take_while(|b| b'0' <= *b && *b <= b'9')
+                .count();
+            if digits < min_digits {
+                return Err("expected digits in date/time");
+            }
+            let v: u32 = s[self.at .. self.at + digits].parse().unwrap();
+
+            let ok = match dir {
+                'Y' => { self.year = v as i32; true },
+                'y' => { self.year = if v >= 69 { 1900 + v as i32 } else { 2000 + v as i32 }; true },
+                'm' => { self.month = v as u8; 1 <= v && v <= 12 },
+                'd' | 'e' => { self.day = v as u8; 1 <= v && v <= 31 },
+                'H' => { self.hour = v as u8; v <= 23 },
+                'M' => { self.minute = v as u8; v <= 59 },
+                'S' => { self.second = v as u8; v <= 60 },
+                _ => unreachable!(),
+            };
+            if !ok {
+                return Err("date/time component out of range");
+            }
+            self.at += digits;
+        }
+        Ok(self.at)
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_datetime() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+
+    let mut scan = datetime("%Y-%m-%d %H:%M:%S");
+    assert_match!(scan.scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan.scan("2016-01-05 14:01:02 x"), Ok(((2016, 1, 5, 14, 1, 2), 19)));
+    assert_match!(scan.scan("2016-1-5  4:01:02"), Ok(((2016, 1, 5, 4, 1, 2), 17)));
+    assert_match!(scan.scan("2016-13-05 14:01:02"), Err(SE { kind: SEK::Syntax(_), at, .. }) if at.offset() == 5);
+    assert_match!(scan.scan("2016/01/05 14:01:02"), Err(SE { kind: SEK::Syntax(_), at, .. }) if at.offset() == 4);
+
+    let mut scan = datetime("%FT%T");
+    assert_match!(scan.scan("2016-12-31T23:59:60"), Ok(((2016, 12, 31, 23, 59, 60), 19)));
+
+    let mut scan = datetime("[%d/%m/%y %%]");
+    assert_match!(scan.scan("[31/12/99 %]"), Ok(((1999, 12, 31, 0, 0, 0), 12)));
+    assert_match!(scan.scan("[01/01/16 %]"), Ok(((2016, 1, 1, 0, 0, 0), 12)));
+
+    let mut scan = datetime("%a %b %e %T %Y");
+    assert_match!(scan.scan("Tue Jan  5 14:01:02 2016"), Ok(((2016, 1, 5, 14, 1, 2), 24)));
+    assert_match!(scan.scan("Tue Jan 15 14:01:02 2016"), Ok(((2016, 1, 15, 14, 1, 2), 24)));
+    assert_match!(scan.scan("Tue Jam 15 14:01:02 2016"), Err(SE { kind: SEK::Syntax(_), at, .. }) if at.offset() == 4);
+
+    let mut scan = datetime("%H:%M");
+    assert_match!(scan.scan("9:30"), Ok(((0, 1, 1, 9, 30, 0), 4)));
+}
+
+#[cfg(test)]
+#[test]
+#[should_panic]
+fn test_datetime_bad_format() {
+    datetime("%Q");
+}
+
+/**
+Creates a runtime scanner that extracts the text between a pair of delimiters, such as `(` and `)`.
+
+The input must start with `open`.  Delimiters may be nested; the scanner continues until it finds the `close` which balances the initial `open`.  If `open` and `close` are the same, nesting is not possible, and the first `close` ends the text.
+
+If `esc` is given, any character following it is skipped, and so is never treated as a delimiter or quote.  Quote characters can be set with [`quotes`](struct.Delimited.html#method.quotes); delimiters inside quoted sections are ignored.
+
+The output is the text between the outer delimiters, exactly as it appears in the input.
+
+```ignore
+// Scans `{{ user.name | default("}}") }}` into `{ user.name | default("}}") }`.
+let _ <| delimited('{', '}', Some('\\')).quotes("\"'")
+```
+*/
+pub fn delimited(open: char, close: char, esc: Option<char>) -> Delimited<'static> {
+    Delimited {
+        open: open,
+        close: close,
+        esc: esc,
+        quotes: "",
+    }
+}
+
+/**
+Runtime scanner that extracts the text between a pair of delimiters.
+
+See: [`delimited`](../fn.delimited.html).
+*/
+pub struct Delimited<'q> {
+    open: char,
+    close: char,
+    esc: Option<char>,
+    quotes: &'q str,
+}
+
+impl<'q> Delimited<'q> {
+    /**
+    Treat each character in `quotes` as a quote.  Delimiters between a quote and the next matching quote are ignored.
+    */
+    pub fn quotes<'r>(self, quotes: &'r str) -> Delimited<'r> {
+        Delimited {
+            open: self.open,
+            close: self.close,
+            esc: self.esc,
+            quotes: quotes,
+        }
+    }
+}
+
+impl<'a, 'q> ScanStr<'a> for Delimited<'q> {
+    type Output = &'a str;
+
+    fn scan<I: ScanInput<'a>>(&mut self, s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        if !s.starts_with(self.open) {
+            return Err(ScanError::syntax("expected opening delimiter"));
+        }
+
+        let start = self.open.len_utf8();
+        let mut depth = 1;
+        let mut quote = None;
+        let mut cis = s[start..].char_indices().map(|(i, c)| (i + start, c));
+        while let Some((i, c)) = cis.next() {
+            if Some(c) == self.esc {
+                cis.next();
+            } else if let Some(q) = quote {
+                if c == q {
+                    quote = None;
+                }
+            } else if self.quotes.contains(c) {
+                quote = Some(c);
+            } else if c == self.close {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok((&s[start..i], i + c.len_utf8()));
+                }
+            } else if c == self.open {
+                depth += 1;
+            }
+        }
+
+        Err(ScanError::syntax("expected closing delimiter").add_offset(s.len()))
+    }
+
+    fn wants_leading_junk_stripped(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_delimited() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+
+    let parens = || delimited('(', ')', None);
+    assert_match!(parens().scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(parens().scan("x(a)"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(parens().scan("()"), Ok(("", 2)));
+    assert_match!(parens().scan("(a (b) c) d"), Ok(("a (b) c", 9)));
+    assert_match!(parens().scan("(a (b c)"), Err(SE { ref at, kind: SEK::Syntax(_), .. }) if at.offset() == 8);
+    assert_match!(parens().scan("(a \")\" b)"), Ok(("a \"", 5)));
+    assert_match!(parens().quotes("\"'").scan("(a \")\" ')' b) c"), Ok(("a \")\" ')' b", 13)));
+    assert_match!(parens().quotes("\"").scan("(a \")"), Err(SE { kind: SEK::Syntax(_), .. }));
+
+    let esc = || delimited('{', '}', Some('\\')).quotes("\"");
+    assert_match!(esc().scan(r"{a \} b}"), Ok((r"a \} b", 8)));
+    assert_match!(esc().scan(r#"{"\"}" }"#), Ok((r#""\"}" "#, 8)));
+
+    let pipes = || delimited('|', '|', None);
+    assert_match!(pipes().scan("|a|b|"), Ok(("a", 3)));
+    assert_match!(delimited('«', '»', None).scan("«x «y»»"), Ok(("x «y»", 11)));
+}
+
+/**
+Creates a runtime scanner that forces *exactly* `width` bytes to be consumed.
+
+This is done in two steps: first, it truncates the input provided to the inner scanner to exactly `width` bytes.  Secondly, it verifies that the inner scanner consumed all of the truncated input.
+
+See: [`exact_width_a`](fn.exact_width_a.html).
+*/
+pub fn exact_width<Then>(width: usize, then: Then) -> ExactWidth<Then> {
+    ExactWidth(width, then)
+}
+
+/**
+Creates a runtime scanner that forces *exactly* `width` bytes to be consumed by the static scanner `S`.
+
+See: [`exact_width`](fn.exact_width.html).
+*/
+pub fn exact_width_a<S>(width: usize) -> ExactWidth<ScanA<S>> {
+    exact_width(width, scan_a::<S>())
+}
+
+/**
+Runtime scanner that forces *exactly* `width` bytes to be consumed.
+
+See: [`exact_width`](fn.exact_width.html), [`exact_width_a`](fn.exact_width_a.html).
+*/
+pub struct ExactWidth<Then>(usize, Then);
+
+impl<'a, Then> ScanStr<'a> for ExactWidth<Then>
+where Then: ScanStr<'a> {
+    type Output = Then::Output;
+
+    fn scan<I: ScanInput<'a>>(&mut self, s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s_str = s.as_str();
+        if s_str.len() < self.0 {
+            return Err(ScanError::syntax("input not long enough"));
+        }
+
+        let sl = s.from_subslice(&s_str[..self.0]);
+
+        match self.1.scan(sl) {
+            Ok((_, n)) if n != self.0 => Err(ScanError::syntax("value did not consume enough characters")),
+            Err(err) => Err(err),
+            Ok((v, _)) => Ok((v, self.0))
+        }
+    }
+
+    fn wants_leading_junk_stripped(&self) -> bool {
+        self.1.wants_leading_junk_stripped()
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_exact_width() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+    use ::scanner::Word;
+    let scan = exact_width_a::<Word>;
+
+    assert_match!(scan(2).scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan(2).scan("a"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan(2).scan("a b"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan(2).scan("ab"), Ok(("ab", 2)));
+    assert_match!(scan(2).scan("abc"), Ok(("ab", 2)));
+}
+
+/**
+Creates a runtime scanner that scans a decimal number as a fixed point integer with `scale` decimal places.
+
+The result is the number multiplied by 10<sup>`scale`</sup>, as an `i64`.  For example, with a `scale` of 2, "12.34" is scanned as `1234`, and "-5" as `-500`.  The number consists of an optional sign, and digits with an optional decimal point; there must be at least one digit, and exponents are not supported.  No floating point arithmetic is involved, so this is suitable for things like monetary amounts.
+
+By default, a number with more than `scale` significant decimal places is rejected.  This can be changed using [`FixedPoint::rounding`](struct.FixedPoint.html#method.rounding).
+
+```ignore
+// Scan a price in cents, rounding fractions of a cent to even.
+let _ <| fixed_point(2).rounding(FixedPointRounding::HalfEven)
+```
+*/
+pub fn fixed_point(scale: u32) -> FixedPoint {
+    FixedPoint {
+        scale: scale,
+        rounding: FixedPointRounding::Exact,
+    }
+}
+
+/**
+Runtime scanner that scans decimal numbers as fixed point integers.
+
+See: [`fixed_point`](fn.fixed_point.html).
+*/
+pub struct FixedPoint {
+    scale: u32,
+    rounding: FixedPointRounding,
+}
+
+impl FixedPoint {
+    /**
+    Sets how numbers with more decimal places than the scale are handled.
+    */
+    pub fn rounding(self, rounding: FixedPointRounding) -> Self {
+        FixedPoint { rounding: rounding, ..self }
+    }
+}
+
+impl<'a> ScanStr<'a> for FixedPoint {
+    type Output = i64;
+
+    fn scan<I: ScanInput<'a>>(&mut self, s: I) -> Result<(Self::Output, usize), ScanError> {
+        let bs = s.as_str().as_bytes();
+        let is_digit = |i: usize| bs.get(i).map(|&b| b'0' <= b && b <= b'9').unwrap_or(false);
+        let overflow = || ScanError::overflow("i64");
+
+        let mut i = 0;
+        let neg = match bs.first() {
+            Some(&b'-') => { i += 1; true },
+            Some(&b'+') => { i += 1; false },
+            _ => false,
+        };
+
+        let mut mag: u64 = 0;
+        let mut any_digits = false;
+        while is_digit(i) {
+            mag = try!(mag.checked_mul(10).and_then(|m| m.checked_add((bs[i] - b'0') as u64)).ok_or_else(&overflow));
+            any_digits = true;
+            i += 1;
+        }
+
+        let mut places = 0;
+        let mut first_extra = 0;
+        let mut extra_at = None;
+        let mut sticky = false;
+        if bs.get(i) == Some(&b'.') && is_digit(i + 1) {
+            i += 1;
+            while is_digit(i) {
+                let d = bs[i] - b'0';
+                if places < self.scale {
+                    mag = try!(mag.checked_mul(10).and_then(|m| m.checked_add(d as u64)).ok_or_else(&overflow));
+                    places += 1;
+                } else if extra_at.is_none() {
+                    first_extra = d;
+                    extra_at = Some(i);
+                } else if d != 0 {
+                    sticky = true;
+                }
+                i += 1;
+            }
+            any_digits = true;
+        }
+
+        if !any_digits {
+            return Err(ScanError::syntax("expected decimal number"));
+        }
+
+        // Zero stays zero however large the scale is; anything else overflows within twenty places.
+        if mag != 0 {
+            for _ in places..self.scale {
+                mag = try!(mag.checked_mul(10).ok_or_else(&overflow));
+            }
+        }
+
+        let round_up = match self.rounding {
+            FixedPointRounding::Exact => {
+                if let Some(at) = extra_at {
+                    if first_extra != 0 || sticky {
+                        return Err(ScanError::syntax("too many decimal places").add_offset(at));
+                    }
+                }
+                false
+            },
+            FixedPointRounding::Truncate => false,
+            FixedPointRounding::HalfUp => first_extra >= 5,
+            FixedPointRounding::HalfEven => first_extra > 5 || (first_extra == 5 && (sticky || mag % 2 == 1)),
+        };
+        if round_up {
+            mag = try!(mag.checked_add(1).ok_or_else(&overflow));
+        }
+
+        const MIN_MAG: u64 = 1 << 63;
+        let v = match (neg, mag) {
+            (true, MIN_MAG) => ::std::i64::MIN,
+            (true, m) if m < MIN_MAG => -(m as i64),
+            (false, m) if m < MIN_MAG => m as i64,
+            _ => return Err(overflow()),
+        };
+        Ok((v, i))
+    }
+
+    fn wants_leading_junk_stripped(&self) -> bool {
+        true
+    }
+}
+
+/**
+How a [`FixedPoint`](struct.FixedPoint.html) scanner handles numbers with more decimal places than its scale.
+*/
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum FixedPointRounding {
+    /// Fail to scan the number, unless all of the extra decimal places are zero.
+    Exact,
+    /// Discard the extra decimal places, rounding towards zero.
+    Truncate,
+    /// Round to the nearest value, with ties rounded away from zero.
+    HalfUp,
+    /// Round to the nearest value, with ties rounded to the nearest even value.
+    HalfEven,
+}
+
+#[cfg(test)]
+#[test]
+fn test_fixed_point() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+    let scan = fixed_point;
+
+    assert_match!(scan(2).scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan(2).scan("x"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan(2).scan("."), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan(2).scan("-x"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan(2).scan("12.34"), Ok((1234, 5)));
+    assert_match!(scan(2).scan("12"), Ok((1200, 2)));
+    assert_match!(scan(2).scan("+12.3 x"), Ok((1230, 5)));
+    assert_match!(scan(2).scan("-0.05"), Ok((-5, 5)));
+    assert_match!(scan(2).scan(".5"), Ok((50, 2)));
+    assert_match!(scan(2).scan("1."), Ok((100, 1)));
+    assert_match!(scan(2).scan("1e3"), Ok((100, 1)));
+    assert_match!(scan(2).scan("12.340"), Ok((1234, 6)));
+    assert_match!(scan(2).scan("12.345"), Err(SE { kind: SEK::Syntax(_), ref at, .. }) if at.offset() == 5);
+    assert_match!(scan(0).scan("42"), Ok((42, 2)));
+
+    let scan = |r| fixed_point(2).rounding(r);
+    assert_match!(scan(FixedPointRounding::Truncate).scan("12.349"), Ok((1234, 6)));
+    assert_match!(scan(FixedPointRounding::Truncate).scan("-12.349"), Ok((-1234, 7)));
+    assert_match!(scan(FixedPointRounding::HalfUp).scan("12.345"), Ok((1235, 6)));
+    assert_match!(scan(FixedPointRounding::HalfUp).scan("-12.345"), Ok((-1235, 7)));
+    assert_match!(scan(FixedPointRounding::HalfUp).scan("12.3449"), Ok((1234, 7)));
+    assert_match!(scan(FixedPointRounding::HalfEven).scan("12.345"), Ok((1234, 6)));
+    assert_match!(scan(FixedPointRounding::HalfEven).scan("12.355"), Ok((1236, 6)));
+    assert_match!(scan(FixedPointRounding::HalfEven).scan("12.3451"), Ok((1235, 7)));
+    assert_match!(scan(FixedPointRounding::HalfEven).scan("-12.345"), Ok((-1234, 7)));
+    assert_match!(fixed_point(0).rounding(FixedPointRounding::HalfUp).scan("42.5"), Ok((43, 4)));
+
+    assert_match!(fixed_point(2).scan("92233720368547758.07"), Ok((::std::i64::MAX, 20)));
+    assert_match!(fixed_point(2).scan("-92233720368547758.08"), Ok((::std::i64::MIN, 21)));
+    assert_match!(fixed_point(2).scan("92233720368547758.08"), Err(SE { kind: SEK::Overflow("i64"), .. }));
+    assert_match!(fixed_point(20).scan("1"), Err(SE { kind: SEK::Overflow("i64"), .. }));
+    assert_match!(fixed_point(::std::u32::MAX).scan("0"), Ok((0, 1)));
+    assert_match!(fixed_point(::std::u32::MAX).scan("-0.00"), Ok((0, 5)));
+    assert_match!(fixed_point(::std::u32::MAX).scan("1"), Err(SE { kind: SEK::Overflow("i64"), .. }));
+    assert_match!(
+        scan(FixedPointRounding::HalfUp).scan("92233720368547758.075"),
+        Err(SE { kind: SEK::Overflow("i64"), .. }));
+
+    assert_match!(
+        scan!("total: 234.50 USD"; ("total:", let a <| fixed_point(2), "USD") => a),
+        Ok(23450));
+}
+
+/**
+Creates a runtime scanner that scans a floating point number which uses `decimal_point` in place of `.`, such as "3,14".
+
+Aside from the decimal point, this accepts the same syntax as the `f32` and `f64` scanners.  `.` is *not* accepted as a decimal point, unless it is `decimal_point`.  This does not attempt to handle digit grouping or any other locale-specific formatting.
+
+```ignore
+let _ <| float_with::<f64>(',')
+```
+*/
+pub fn float_with<Output>(decimal_point: char) -> FloatWith<Output> {
+    FloatWith(decimal_point, PhantomData)
+}
+
+/**
+Runtime scanner that scans a floating point number with a custom decimal point.
+
+See: [`float_with`](fn.float_with.html).
+*/
+pub struct FloatWith<Output>(char, PhantomData<Output>);
+
+impl<'a, Output> ScanStr<'a> for FloatWith<Output>
+where Output: FromStr<Err=ParseFloatError> {
+    type Output = Output;
+
+    fn scan<I: ScanInput<'a>>(&mut self, s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        let n = match match_float_point(s, false, self.0) {
+            Some((_, n)) => n,
+            None => return Err(ScanError::syntax("expected floating point number")),
+        };
+
+        let lit = &s[..n];
+        let v = if self.0 != '.' && lit.contains(self.0) {
+            lit.replace(self.0, ".").parse()
+        } else {
+            lit.parse()
+        };
+
+        v.map(|v| (v, n)).map_err(ScanError::float)
+    }
+
+    fn wants_leading_junk_stripped(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_float_with() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+    let scan = float_with::<f64>;
+
+    assert_match!(scan(',').scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan(',').scan(",5"), Ok((0.5, 2)));
+    assert_match!(scan(',').scan("3,25"), Ok((3.25, 4)));
+    assert_match!(scan(',').scan("-1,5e3"), Ok((-1500.0, 6)));
+    assert_match!(scan(',').scan("3.25"), Ok((3.0, 1)));
+    assert_match!(scan(',').scan("1, 2"), Ok((1.0, 1)));
+    assert_match!(scan(',').scan("1,"), Ok((1.0, 1)));
+    assert_match!(scan(',').scan("inf"), Ok((v, 3)) if v.is_infinite());
+    assert_match!(scan('.').scan("1."), Ok((1.0, 2)));
+    assert_match!(scan('·').scan("1·5"), Ok((1.5, 4)));
+    assert_match!(float_with::<f32>(',').scan("2,5"), Ok((2.5, 3)));
+
+    assert_match!(
+        scan!("1,5; 2,25"; (let a <| float_with::<f64>(','), ";", let b <| float_with::<f64>(',')) => (a, b)),
+        Ok((1.5, 2.25))
+    );
+}
+
+/**
+Creates a runtime scanner that checks for the whole word `name`, outputting whether or not it was present.
+
+If the word is present, it is consumed.  If it is absent, *nothing* is consumed and the scanner outputs `false`, so this scanner never fails.  The word must not be immediately followed by another word character; `verbose` will not match the start of `verbosely`.
+
+This allows optional flags to be placed in a pattern without needing a separate rule for every combination of present and absent flags.
+
+## Panics
+
+Panics if `name` is empty, since an empty flag would always be "present".
+*/
+pub fn flag_present(name: &str) -> FlagPresent {
+    assert!(!name.is_empty(), "flag name must not be empty");
+    FlagPresent(name)
+}
+
+/**
+Runtime scanner that checks for the presence of a whole word.
+
+See: [`flag_present`](fn.flag_present.html).
+*/
+pub struct FlagPresent<'s>(&'s str);
+
+impl<'a, 's> ScanStr<'a> for FlagPresent<'s> {
+    type Output = bool;
+
+    fn scan<I: ScanInput<'a>>(&mut self, s: I) -> Result<(Self::Output, usize), ScanError> {
+        use ::util::TableUtil;
+        use ::unicode::regex::PERLW as W;
+
+        let s = s.as_str();
+        if !s.starts_with(self.0) {
+            return Ok((false, 0));
+        }
+
+        match s[self.0.len()..].chars().next() {
+            Some(c) if W.span_table_contains(&c) => Ok((false, 0)),
+            _ => Ok((true, self.0.len()))
+        }
+    }
+
+    fn wants_leading_junk_stripped(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_flag_present() {
+    assert_match!(flag_present("verbose").scan(""), Ok((false, 0)));
+    assert_match!(flag_present("verbose").scan("quiet"), Ok((false, 0)));
+    assert_match!(flag_present("verbose").scan("verbosely"), Ok((false, 0)));
+    assert_match!(flag_present("verbose").scan("verbose"), Ok((true, 7)));
+    assert_match!(flag_present("verbose").scan("verbose 1"), Ok((true, 7)));
+    assert_match!(flag_present("-v").scan("-v-q"), Ok((true, 2)));
+
+    let rule = |s: &str| scan!(s;
+        (let v <| flag_present("verbose"), let n: i32, let q <| flag_present("quiet")) => (v, n, q)
+    );
+    assert_match!(rule("1"), Ok((false, 1, false)));
+    assert_match!(rule("verbose 2"), Ok((true, 2, false)));
+    assert_match!(rule("3 quiet"), Ok((false, 3, true)));
+    assert_match!(rule("verbose 4 quiet"), Ok((true, 4, true)));
+    assert_match!(rule("loud 5"), Err(_));
+}
+
+#[cfg(test)]
+#[test]
+#[should_panic]
+fn test_flag_present_empty() {
+    flag_present("");
+}
+
+/**
+Creates a runtime scanner that captures a block of lines indented by at least `min_indent` spaces.
+
+If the input starts with the end of a line (*i.e.* optional spaces or tabs, then a line break), that line break is skipped, so that the block starts on the *following* line.  This allows the scanner to be used directly after a term like `"key:"`.
+
+The block continues for as long as lines are indented by at least `min_indent` spaces.  Blank lines are included if they are followed by another line of the block.  The block must contain at least one line.  Everything up to and including the line break of the last line in the block is consumed.
+
+The output has the common indentation of the block removed from every line, and lines are joined with `\n`, without a trailing line break.
+
+```ignore
+// Scans `"doc:\n    first\n      second\nnext"` into `"first\n  second"`.
+scan!(s; ("doc:", let doc <| indented_block(2), ..tail) => doc)
+```
+*/
+pub fn indented_block(min_indent: usize) -> IndentedBlock {
+    IndentedBlock(min_indent)
+}
+
+/**
+Runtime scanner that captures a block of indented lines.
+
+See: [`indented_block`](fn.indented_block.html).
+*/
+pub struct IndentedBlock(usize);
+
+impl<'a> ScanStr<'a> for IndentedBlock {
+    type Output = String;
+
+    fn scan<I: ScanInput<'a>>(&mut self, s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+
+        // Skip the remainder of the current line, if there's nothing else on it.
+        let start = {
+            let sp = s.len() - s.trim_left_matches(|c| c == ' ' || c == '\t').len();
+            let rest = &s[sp..];
+            if rest.starts_with("\r\n") {
+                sp + 2
+            } else if rest.starts_with("\n") {
+                sp + 1
+            } else {
+                0
+            }
+        };
+
+        let mut lines = vec![];
+        let mut end = start;
+        let mut pending_blank = vec![];
+        let mut pos = start;
+        while pos < s.len() {
+            let (line, next) = match s[pos..].find('\n') {
+                Some(i) => (&s[pos..pos + i], pos + i + 1),
+                None => (&s[pos..], s.len()),
+            };
+            let line = line.trim_right_matches('\r');
+
+            if line.trim().is_empty() {
+                pending_blank.push("");
+            } else if indent_of(line) >= self.0 {
+                lines.extend(pending_blank.drain(..));
+                lines.push(line);
+                end = next;
+            } else {
+                break;
+            }
+            pos = next;
+        }
+
+        if lines.is_empty() {
+            return Err(ScanError::syntax("expected indented block").add_offset(start));
+        }
+
+        let common = lines.iter()
+            .filter(|l| !l.is_empty())
+            .map(|l| indent_of(l))
+            .min()
+            .unwrap_or(0);
+
+        let mut out = String::new();
+        for (i, line) in lines.into_iter().enumerate() {
+            if i > 0 {
+                out.push('\n');
+            }
+            if !line.is_empty() {
+                out.push_str(&line[common..]);
+            }
+        }
+
+        Ok((out, end))
+    }
+
+    fn wants_leading_junk_stripped(&self) -> bool {
+        false
+    }
+}
+
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_left_matches(' ').len()
+}
+
+#[cfg(test)]
+#[test]
+fn test_indented_block() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+    use ::scanner::Word;
+
+    assert_match!(indented_block(2).scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(indented_block(2).scan("a\n  b"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(indented_block(2).scan("\nb"), Err(SE { ref at, kind: SEK::Syntax(_), .. }) if at.offset() == 1);
+    assert_match!(indented_block(2).scan("  a"), Ok((ref v, 3)) if v == "a");
+    assert_match!(indented_block(2).scan("  a\n  b\nc"), Ok((ref v, 8)) if v == "a\nb");
+    assert_match!(indented_block(2).scan(" \n    a\n      b\n\n    c\n\nd"), Ok((ref v, 23)) if v == "a\n  b\n\nc");
+    assert_match!(indented_block(2).scan("\r\n  a\r\n  b\r\n"), Ok((ref v, 12)) if v == "a\nb");
+    assert_match!(indented_block(4).scan("\n    a\n  b"), Ok((ref v, 7)) if v == "a");
+
+    assert_match!(
+        scan!("doc:\n    first\n      second\nnext"; ("doc:", let doc <| indented_block(2), let n: Word) => (doc, n)),
+        Ok((ref doc, "next")) if doc == "first\n  second"
+    );
+}
+
+/**
+Creates a runtime scanner that interns the strings scanned by `then` in `pool`.
+
+When the same strings appear many times in the input (such as the fields of a large table), this avoids allocating a new string for every occurrence: each distinct string is allocated once, and every later occurrence shares it.  The output of `then` can be any string type, though `&str` (as output by scanners like [`Word`](../struct.Word.html)) avoids allocating at all when the string has already been seen.
+
+The pool can be anything which implements [`Interner`](trait.Interner.html), such as a `HashSet<Rc<str>>`.  It is borrowed, rather than owned, by the scanner, so it can be shared between scans.
+
+```ignore
+let mut pool = HashSet::new();
+let r = scan!(input; ([let users <| interned_a::<Word, _>(&mut pool)]*) => users);
+```
+
+See: [`interned_a`](fn.interned_a.html).
+*/
+pub fn interned<'p, P: ?Sized, Then>(pool: &'p mut P, then: Then) -> Interned<'p, P, Then> {
+    Interned(pool, then)
+}
+
+/**
+Creates a runtime scanner that interns the strings scanned by the static scanner `S` in `pool`.
+
+See: [`interned`](fn.interned.html).
+*/
+pub fn interned_a<'p, S, P: ?Sized>(pool: &'p mut P) -> Interned<'p, P, ScanA<S>> {
+    interned(pool, scan_a::<S>())
+}
+
+/**
+Runtime scanner that interns scanned strings.
+
+See: [`interned`](fn.interned.html), [`interned_a`](fn.interned_a.html).
+*/
+pub struct Interned<'p, P: 'p + ?Sized, Then>(&'p mut P, Then);
+
+impl<'a, 'p, P: ?Sized, Then> ScanStr<'a> for Interned<'p, P, Then>
+where
+    P: Interner,
+    Then: ScanStr<'a>,
+    Then::Output: AsRef<str>,
+{
+    type Output = P::Output;
+
+    fn scan<I: ScanInput<'a>>(&mut self, s: I) -> Result<(Self::Output, usize), ScanError> {
+        let (v, n) = try!(self.1.scan(s));
+        Ok((self.0.intern(v.as_ref()), n))
+    }
+
+    fn wants_leading_junk_stripped(&self) -> bool {
+        self.1.wants_leading_junk_stripped()
+    }
+}
+
+/**
+This trait defines a pool of interned strings, for use with the [`interned`](fn.interned.html) runtime scanner.
+
+It is implemented for `HashSet<Rc<str>>` and `HashSet<Arc<str>>` on Rust 1.21 and higher, when the `std` feature is enabled.
+*/
+pub trait Interner {
+    /**
+    The type of a shared, interned string.
+    */
+    type Output;
+
+    /**
+    Returns the interned copy of `s`, adding it to the pool if it is not already present.
+    */
+    fn intern(&mut self, s: &str) -> Self::Output;
+}
+
+#[cfg(all(feature="std", rc_from_str))]
+impl<H> Interner for HashSet<Rc<str>, H>
+where H: BuildHasher {
+    type Output = Rc<str>;
+
+    fn intern(&mut self, s: &str) -> Self::Output {
+        if let Some(v) = self.get(s) {
+            return v.clone();
+        }
+        let v: Rc<str> = Rc::from(s);
+        self.insert(v.clone());
+        v
+    }
+}
+
+#[cfg(all(feature="std", rc_from_str))]
+impl<H> Interner for HashSet<Arc<str>, H>
+where H: BuildHasher {
+    type Output = Arc<str>;
+
+    fn intern(&mut self, s: &str) -> Self::Output {
+        if let Some(v) = self.get(s) {
+            return v.clone();
+        }
+        let v: Arc<str> = Arc::from(s);
+        self.insert(v.clone());
+        v
+    }
+}
+
+#[cfg(all(feature="std", rc_from_str))]
+#[cfg(test)]
+#[test]
+fn test_interned() {
+    use std::collections::HashSet;
+    use std::rc::Rc;
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+    use ::scanner::Word;
 
-#[cfg(feature="regex")] use regex::Regex;
+    let mut pool: HashSet<Rc<str>> = HashSet::new();
+
+    assert_match!(interned_a::<Word, _>(&mut pool).scan(""), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
+    assert_match!(interned_a::<Word, _>(&mut pool).scan("abc def"), Ok((ref v, 3)) if &**v == "abc");
+    assert_match!(interned_a::<Word<String>, _>(&mut pool).scan("abc"), Ok((ref v, 3)) if &**v == "abc");
+    assert_eq!(pool.len(), 1);
+
+    let r = scan!("alice bob alice alice bob";
+        ([let users <| interned_a::<Word, _>(&mut pool)]*) => users);
+    let users = r.unwrap();
+    assert_eq!(users.len(), 5);
+    assert_eq!(pool.len(), 3);
+    assert!(Rc::ptr_eq(&users[0], &users[2]) && Rc::ptr_eq(&users[0], &users[3]));
+    assert!(Rc::ptr_eq(&users[1], &users[4]));
+    assert_eq!(&*users[1], "bob");
+}
 
 /**
-Creates a runtime scanner that forces *exactly* `width` bytes to be consumed.
+Creates a runtime scanner that appends the output of `then` to `buf`, rather than returning it.
 
-This is done in two steps: first, it truncates the input provided to the inner scanner to exactly `width` bytes.  Secondly, it verifies that the inner scanner consumed all of the truncated input.
+The output is the range of `buf` the text was written to.  This allows many values to be scanned into a single, reused buffer without allocating a new string for each; combined with a scanner which outputs `&str` (such as [`Word`](../struct.Word.html)), no allocation is needed at all once the buffer is large enough.  Nothing is appended if `then` fails.
 
-See: [`exact_width_a`](fn.exact_width_a.html).
+However, text is *not* removed again if a later part of the rule fails, or if the scan backtracks to try a different rule.  If the buffer must only contain text from a successful scan, record its length beforehand and truncate it back on failure.
+
+```ignore
+let mut buf = String::new();
+let r = scan!(input; ([let names <| into_buf_a::<Word>(&mut buf)],+) => names);
+// `r` is a `Vec<Range<usize>>` of the positions of each name within `buf`.
+```
+
+```ignore
+let mark = buf.len();
+let r = scan!(input; (let name <| into_buf_a::<Word>(&mut buf), "=", let v: i32) => (name, v));
+if r.is_err() {
+    buf.truncate(mark);
+}
+```
+
+See: [`into_buf_a`](fn.into_buf_a.html).
 */
-pub fn exact_width<Then>(width: usize, then: Then) -> ExactWidth<Then> {
-    ExactWidth(width, then)
+pub fn into_buf<Then>(buf: &mut String, then: Then) -> IntoBuf<Then> {
+    IntoBuf(buf, then)
 }
 
 /**
-Creates a runtime scanner that forces *exactly* `width` bytes to be consumed by the static scanner `S`.
+Creates a runtime scanner that appends the output of the static scanner `S` to `buf`, rather than returning it.
 
-See: [`exact_width`](fn.exact_width.html).
+See: [`into_buf`](fn.into_buf.html).
 */
-pub fn exact_width_a<S>(width: usize) -> ExactWidth<ScanA<S>> {
-    exact_width(width, scan_a::<S>())
+pub fn into_buf_a<S>(buf: &mut String) -> IntoBuf<ScanA<S>> {
+    into_buf(buf, scan_a::<S>())
 }
 
 /**
-Runtime scanner that forces *exactly* `width` bytes to be consumed.
+Runtime scanner that appends scanned text to an existing buffer.
 
-See: [`exact_width`](fn.exact_width.html), [`exact_width_a`](fn.exact_width_a.html).
+See: [`into_buf`](fn.into_buf.html), [`into_buf_a`](fn.into_buf_a.html).
 */
-pub struct ExactWidth<Then>(usize, Then);
+pub struct IntoBuf<'b, Then>(&'b mut String, Then);
 
-impl<'a, Then> ScanStr<'a> for ExactWidth<Then>
-where Then: ScanStr<'a> {
-    type Output = Then::Output;
+impl<'a, 'b, Then> ScanStr<'a> for IntoBuf<'b, Then>
+where
+    Then: ScanStr<'a>,
+    Then::Output: AsRef<str>,
+{
+    type Output = Range<usize>;
+
+    fn scan<I: ScanInput<'a>>(&mut self, s: I) -> Result<(Self::Output, usize), ScanError> {
+        let (v, n) = try!(self.1.scan(s));
+        let start = self.0.len();
+        self.0.push_str(v.as_ref());
+        Ok((start..self.0.len(), n))
+    }
+
+    fn wants_leading_junk_stripped(&self) -> bool {
+        self.1.wants_leading_junk_stripped()
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_into_buf() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+    use ::scanner::{QuotedString, Word};
+
+    let mut buf = String::from("x");
+
+    assert_match!(into_buf_a::<Word>(&mut buf).scan(""), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
+    assert_eq!(buf, "x");
+    assert_match!(into_buf_a::<Word>(&mut buf).scan("abc def"), Ok((ref r, 3)) if *r == (1..4));
+    assert_match!(into_buf_a::<QuotedString>(&mut buf).scan(r#""\"q\"" z"#), Ok((ref r, 7)) if *r == (4..7));
+    assert_eq!(buf, "xabc\"q\"");
+
+    buf.clear();
+    let r = scan!("alpha, beta, gamma"; ([let names <| into_buf_a::<Word>(&mut buf)],+) => names);
+    let names = r.unwrap();
+    assert_eq!(buf, "alphabetagamma");
+    assert_eq!(names.iter().map(|r| &buf[r.clone()]).collect::<Vec<_>>(), ["alpha", "beta", "gamma"]);
+
+    // Text from a rule which fails later on is left in the buffer.
+    let mark = buf.len();
+    let r = scan!("delta = x"; (let _ <| into_buf_a::<Word>(&mut buf), "=", let v: i32) => v);
+    assert_match!(r, Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_eq!(&buf[mark..], "delta");
+    buf.truncate(mark);
+    assert_eq!(buf, "alphabetagamma");
+}
+
+/**
+Creates a runtime scanner that scans a key and a value, scanned using the static scanners `K` and `V`, separated by the literal `sep`.
+
+This is a generalisation of [`KeyValuePair`](../struct.KeyValuePair.html), which always uses `:` as the separator.  The separator is matched like any other literal pattern term, and may be any string, such as `"="`, `"=>"`, or `"is"`.
+
+See: [`KeyValue::ignore_case`](struct.KeyValue.html#method.ignore_case), [`KeyValue::trim_value`](struct.KeyValue.html#method.trim_value).
+*/
+pub fn key_value<K, V>(sep: &str) -> KeyValue<K, V> {
+    KeyValue {
+        sep: sep,
+        ignore_case: false,
+        trim_value: false,
+        _marker: PhantomData,
+    }
+}
+
+/**
+Runtime scanner that scans a key and value separated by a literal.
+
+See: [`key_value`](../fn.key_value.html).
+*/
+pub struct KeyValue<'s, K, V> {
+    sep: &'s str,
+    ignore_case: bool,
+    trim_value: bool,
+    _marker: PhantomData<(K, V)>,
+}
+
+impl<'s, K, V> KeyValue<'s, K, V> {
+    /**
+    Match the separator case-insensitively, irrespective of how the input itself compares literals.
+    */
+    pub fn ignore_case(self) -> Self {
+        KeyValue { ignore_case: true, ..self }
+    }
+
+    /**
+    Restrict the value to the rest of the current line, with trailing whitespace removed.
+
+    This allows values which contain spaces, such as `name = Major Tom  `, to be scanned with a scanner like `Everything`.
+    */
+    pub fn trim_value(self) -> Self {
+        KeyValue { trim_value: true, ..self }
+    }
+}
+
+impl<'a, 's, K, V> ScanStr<'a> for KeyValue<'s, K, V>
+where K: ScanFromStr<'a>, V: ScanFromStr<'a> {
+    type Output = (K::Output, V::Output);
 
     fn scan<I: ScanInput<'a>>(&mut self, s: I) -> Result<(Self::Output, usize), ScanError> {
+        use ::input::{IgnoreCase, ScanCursor};
+        use ::internal::try_scan_static;
+
         let s_str = s.as_str();
-        if s_str.len() < self.0 {
-            return Err(ScanError::syntax("input not long enough"));
+        let (k, k_end) = try!(K::scan_from(s.clone()));
+
+        let sep_s = s.from_subslice(&s_str[k_end..]);
+        let sep_len = if self.ignore_case {
+            sep_s.match_literal_with::<IgnoreCase>(self.sep)
+        } else {
+            sep_s.to_cursor().try_match_literal(self.sep)
+                .map(|cur| cur.offset())
+                .map_err(|(err, _)| err)
+        };
+        let v_start = k_end + try!(sep_len.map_err(|err| err.add_offset(k_end)));
+
+        let v_cur = s.from_subslice(&s_str[v_start..]).to_cursor();
+        let v = if self.trim_value {
+            let scan_v = |v_s: <I::ScanCursor as ScanCursor<'a>>::ScanInput| {
+                let v_str = v_s.as_str();
+                let end = ::fast::find_line_break(v_str.as_bytes()).unwrap_or(v_str.len());
+                V::scan_from(v_s.from_subslice(v_str[..end].trim_right()))
+            };
+            if V::wants_leading_junk_stripped() {
+                v_cur.try_scan(scan_v)
+            } else {
+                v_cur.try_scan_raw(scan_v)
+            }
+        } else {
+            try_scan_static::<_, V>(v_cur)
+        };
+
+        match v {
+            Ok((v, cur)) => Ok(((k, v), v_start + cur.offset())),
+            Err((err, _)) => Err(err.add_offset(v_start)),
         }
+    }
 
-        let sl = s.from_subslice(&s_str[..self.0]);
+    fn wants_leading_junk_stripped(&self) -> bool {
+        K::wants_leading_junk_stripped()
+    }
+}
 
-        match self.1.scan(sl) {
-            Ok((_, n)) if n != self.0 => Err(ScanError::syntax("value did not consume enough characters")),
-            Err(err) => Err(err),
-            Ok((v, _)) => Ok((v, self.0))
+#[cfg(test)]
+#[test]
+fn test_key_value() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+    use ::scanner::{Everything, Word};
+
+    let scan = key_value::<Word, i32>;
+    assert_match!(scan("=").scan(""), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
+    assert_match!(scan("=").scan("x"), Err(SE { kind: SEK::LiteralMismatch, .. }));
+    assert_match!(scan("=").scan("x = 1"), Ok((("x", 1), 5)));
+    assert_match!(scan("=").scan("x=1, y=2"), Ok((("x", 1), 3)));
+    assert_match!(scan("=").scan("x : 1"), Err(SE { ref at, kind: SEK::LiteralMismatch, .. }) if at.offset() == 2);
+    assert_match!(scan("=").scan("x = y"), Err(SE { ref at, kind: SEK::Syntax(_), .. }) if at.offset() == 4);
+    assert_match!(scan("=>").scan("x => 1"), Ok((("x", 1), 6)));
+    assert_match!(scan("is").scan("x is 1"), Ok((("x", 1), 6)));
+    assert_match!(scan("is").scan("x IS 1"), Err(SE { kind: SEK::LiteralMismatch, .. }));
+    assert_match!(scan("is").ignore_case().scan("x IS 1"), Ok((("x", 1), 6)));
+
+    // The input's space handling still applies when ignoring case.
+    {
+        use ::input::{ExactSpace, IgnoreCase, StrCursor, Wordish};
+        let inp = StrCursor::<IgnoreCase, ExactSpace, Wordish>::new("x IS 1");
+        assert_match!(scan("is").ignore_case().scan(inp), Err(SE { ref at, kind: SEK::LiteralMismatch, .. }) if at.offset() == 1);
+        assert_match!(scan(" is ").ignore_case().scan(inp), Ok((("x", 1), 6)));
+    }
+
+    let scan = || key_value::<Word, Everything>("=").trim_value();
+    assert_match!(scan().scan("name = Major Tom  "), Ok((("name", "Major Tom"), 16)));
+    assert_match!(scan().scan("name=Major Tom\nx = y"), Ok((("name", "Major Tom"), 14)));
+    assert_match!(key_value::<Word, Everything>("=").scan("name = Major Tom  "), Ok((("name", "Major Tom  "), 18)));
+}
+
+/**
+Creates a runtime scanner that matches one of a fixed set of `keywords`, outputting the index of the keyword that matched.
+
+Where more than one keyword matches, the longest one wins; this means `["<", "<="]` will match all of `<=`, rather than stopping after `<`.  A keyword ending in a word character will not match the start of a longer word; `GET` will not match the start of `GETS`.
+
+Empty keywords are ignored, since they would match anywhere without consuming anything; they still count towards the indices of the keywords which follow them.
+
+This is useful for things like protocol verbs, where the set of keywords may only be known at runtime, or where a separate rule for each keyword would be unwieldy.
+
+See: [`KeywordSet::ignore_case`](struct.KeywordSet.html#method.ignore_case).
+*/
+pub fn keyword_set<'s>(keywords: &'s [&'s str]) -> KeywordSet<'s> {
+    KeywordSet {
+        keywords: keywords,
+        ignore_case: false,
+    }
+}
+
+/**
+Runtime scanner that matches one of a set of keywords.
+
+See: [`keyword_set`](../fn.keyword_set.html).
+*/
+pub struct KeywordSet<'s> {
+    keywords: &'s [&'s str],
+    ignore_case: bool,
+}
+
+impl<'s> KeywordSet<'s> {
+    /**
+    Match keywords case-insensitively.
+    */
+    pub fn ignore_case(self) -> Self {
+        KeywordSet { ignore_case: true, ..self }
+    }
+}
+
+impl<'a, 's> ScanStr<'a> for KeywordSet<'s> {
+    type Output = usize;
+
+    fn scan<I: ScanInput<'a>>(&mut self, s: I) -> Result<(Self::Output, usize), ScanError> {
+        use ::util::TableUtil;
+        use ::unicode::regex::PERLW as W;
+
+        let s = s.as_str();
+        let mut best: Option<(usize, usize, usize)> = None;
+
+        for (i, kw) in self.keywords.iter().enumerate() {
+            if kw.is_empty() {
+                continue;
+            }
+
+            let end = match match_keyword(s, kw, self.ignore_case) {
+                Some(end) => end,
+                None => continue,
+            };
+
+            let splits_word = match (kw.chars().next_back(), s[end..].chars().next()) {
+                (Some(a), Some(b)) => W.span_table_contains(&a) && W.span_table_contains(&b),
+                _ => false
+            };
+            if splits_word {
+                continue;
+            }
+
+            match best {
+                Some((_, best_len, _)) if best_len >= kw.len() => (),
+                _ => best = Some((i, kw.len(), end)),
+            }
+        }
+
+        match best {
+            Some((i, _, end)) => Ok((i, end)),
+            None => Err(ScanError::syntax("expected a keyword")),
         }
     }
 
     fn wants_leading_junk_stripped(&self) -> bool {
-        self.1.wants_leading_junk_stripped()
+        true
+    }
+}
+
+/**
+Matches `kw` at the start of `s`, returning the number of bytes of `s` it covers.
+*/
+fn match_keyword(s: &str, kw: &str, ignore_case: bool) -> Option<usize> {
+    if !ignore_case {
+        return if s.starts_with(kw) { Some(kw.len()) } else { None };
+    }
+
+    let mut s_chars = s.char_indices();
+    for kc in kw.chars() {
+        match s_chars.next() {
+            Some((_, sc)) if sc.to_lowercase().eq(kc.to_lowercase()) => (),
+            _ => return None,
+        }
     }
+    Some(s_chars.next().map(|(j, _)| j).unwrap_or(s.len()))
 }
 
 #[cfg(test)]
 #[test]
-fn test_exact_width() {
+fn test_keyword_set() {
     use ::ScanError as SE;
     use ::ScanErrorKind as SEK;
-    use ::scanner::Word;
-    let scan = exact_width_a::<Word>;
 
-    assert_match!(scan(2).scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
-    assert_match!(scan(2).scan("a"), Err(SE { kind: SEK::Syntax(_), .. }));
-    assert_match!(scan(2).scan("a b"), Err(SE { kind: SEK::Syntax(_), .. }));
-    assert_match!(scan(2).scan("ab"), Ok(("ab", 2)));
-    assert_match!(scan(2).scan("abc"), Ok(("ab", 2)));
+    const VERBS: &'static [&'static str] = &["GET", "PUT", "POST", "PUTS"];
+    assert_match!(keyword_set(VERBS).scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(keyword_set(VERBS).scan("HEAD /"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(keyword_set(VERBS).scan("GET /"), Ok((0, 3)));
+    assert_match!(keyword_set(VERBS).scan("POST"), Ok((2, 4)));
+    assert_match!(keyword_set(VERBS).scan("PUTS /"), Ok((3, 4)));
+    assert_match!(keyword_set(VERBS).scan("GETS /"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(keyword_set(VERBS).scan("get /"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(keyword_set(VERBS).ignore_case().scan("get /"), Ok((0, 3)));
+    assert_match!(keyword_set(VERBS).ignore_case().scan("Post"), Ok((2, 4)));
+    assert_match!(keyword_set(&[]).scan("GET"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(keyword_set(&[""]).scan("GET"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(keyword_set(&["", "GET"]).scan("GET"), Ok((1, 3)));
+
+    const OPS: &'static [&'static str] = &["<", "<=", "="];
+    assert_match!(keyword_set(OPS).scan("<= 2"), Ok((1, 2)));
+    assert_match!(keyword_set(OPS).scan("<2"), Ok((0, 1)));
+    assert_match!(keyword_set(OPS).scan("=="), Ok((2, 1)));
+
+    assert_match!(
+        scan!("PUT /index.html"; (let verb <| keyword_set(VERBS), let path: ::scanner::NonSpace) => (VERBS[verb], path)),
+        Ok(("PUT", "/index.html"))
+    );
 }
 
 /**
@@ -156,54 +1806,384 @@ pub fn min_width<Then>(width: usize, then: Then) -> MinWidth<Then> {
 }
 
 /**
-Creates a runtime scanner that forces *at least* `width` bytes to be consumed by the static scanner `S`.
+Creates a runtime scanner that forces *at least* `width` bytes to be consumed by the static scanner `S`.
+
+See: [`min_width`](fn.min_width.html).
+*/
+pub fn min_width_a<S>(width: usize) -> MinWidth<ScanA<S>> {
+    min_width(width, scan_a::<S>())
+}
+
+/**
+Runtime scanner that forces *at least* `width` bytes to be consumed.
+
+See: [`min_width`](fn.min_width.html), [`min_width_a`](fn.min_width_a.html).
+*/
+pub struct MinWidth<Then>(usize, Then);
+
+impl<'a, Then> ScanStr<'a> for MinWidth<Then>
+where Then: ScanStr<'a> {
+    type Output = Then::Output;
+
+    fn scan<I: ScanInput<'a>>(&mut self, s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s_str = s.as_str();
+        if s_str.len() < self.0 {
+            return Err(ScanError::syntax("expected more bytes to scan"));
+        }
+        match self.1.scan(s) {
+            Ok((_, n)) if n < self.0 => Err(ScanError::syntax("scanned value too short")),
+            other => other
+        }
+    }
+
+    fn wants_leading_junk_stripped(&self) -> bool {
+        self.1.wants_leading_junk_stripped()
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_min_width() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+    use ::scanner::Word;
+    let scan = min_width_a::<Word>;
+
+    assert_match!(scan(2).scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan(2).scan("a"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan(2).scan("a b"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan(2).scan("ab"), Ok(("ab", 2)));
+    assert_match!(scan(2).scan("abc"), Ok(("abc", 3)));
+}
+
+/**
+Creates a runtime scanner that matches exactly one character which *does not* appear in `chars`, outputting it.
+
+See: [`one_of`](fn.one_of.html).
+*/
+pub fn none_of(chars: &str) -> NoneOf {
+    NoneOf(chars)
+}
+
+/**
+Runtime scanner that matches a single character not in a given set.
+
+See: [`none_of`](../fn.none_of.html).
+*/
+pub struct NoneOf<'s>(&'s str);
+
+impl<'a, 's> ScanStr<'a> for NoneOf<'s> {
+    type Output = char;
+
+    fn scan<I: ScanInput<'a>>(&mut self, s: I) -> Result<(Self::Output, usize), ScanError> {
+        match s.as_str().chars().next() {
+            Some(c) if !self.0.contains(c) => Ok((c, c.len_utf8())),
+            _ => Err(ScanError::syntax("expected a character not in the excluded set")),
+        }
+    }
+
+    fn wants_leading_junk_stripped(&self) -> bool {
+        true
+    }
+}
+
+/**
+Creates a runtime scanner that matches exactly one character which appears in `chars`, outputting it.
+
+See: [`none_of`](fn.none_of.html).
+*/
+pub fn one_of(chars: &str) -> OneOf {
+    OneOf(chars)
+}
+
+/**
+Runtime scanner that matches a single character from a given set.
+
+See: [`one_of`](../fn.one_of.html).
+*/
+pub struct OneOf<'s>(&'s str);
+
+impl<'a, 's> ScanStr<'a> for OneOf<'s> {
+    type Output = char;
+
+    fn scan<I: ScanInput<'a>>(&mut self, s: I) -> Result<(Self::Output, usize), ScanError> {
+        match s.as_str().chars().next() {
+            Some(c) if self.0.contains(c) => Ok((c, c.len_utf8())),
+            _ => Err(ScanError::syntax("expected a character from the allowed set")),
+        }
+    }
+
+    fn wants_leading_junk_stripped(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_one_of() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+
+    assert_match!(one_of("+-").scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(one_of("+-").scan("*"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(one_of("+-").scan("-1"), Ok(('-', 1)));
+    assert_match!(one_of("αβγ").scan("γδ"), Ok(('γ', 2)));
+    assert_match!(one_of("").scan("a"), Err(SE { kind: SEK::Syntax(_), .. }));
+
+    assert_match!(none_of(",;").scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(none_of(",;").scan(";"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(none_of(",;").scan("ab"), Ok(('a', 1)));
+    assert_match!(none_of("").scan("日本"), Ok(('日', 3)));
+
+    assert_match!(
+        scan!("x = 1"; (let v <| one_of("xyz"), let op <| one_of("=<>"), let _: i32) => (v, op)),
+        Ok(('x', '='))
+    );
+}
+
+/**
+Creates a runtime scanner that scans a value from a fixed-width field padded with spaces.
+
+The field is `width` bytes long.  Any leading spaces within the field are skipped (if the inner scanner wants leading junk stripped), the value is scanned, then any trailing spaces up to the end of the field are consumed.  This makes it suitable for both left- and right-aligned columns.
+
+The inner scanner only sees the field itself, so fields may touch one another, as in right-aligned numeric columns.  It is an error for anything other than spaces to follow the value within the field.  If the input ends before the field does, the field is treated as though it had been padded out.
+
+See: [`padded_a`](fn.padded_a.html).
+*/
+pub fn padded<Then>(width: usize, then: Then) -> Padded<Then> {
+    Padded(width, then)
+}
+
+/**
+Creates a runtime scanner that scans a value using the static scanner `S` from a fixed-width field padded with spaces.
+
+See: [`padded`](fn.padded.html).
+*/
+pub fn padded_a<S>(width: usize) -> Padded<ScanA<S>> {
+    padded(width, scan_a::<S>())
+}
+
+/**
+Runtime scanner that scans a value from a fixed-width field padded with spaces.
+
+See: [`padded`](fn.padded.html), [`padded_a`](fn.padded_a.html).
+*/
+pub struct Padded<Then>(usize, Then);
+
+impl<'a, Then> ScanStr<'a> for Padded<Then>
+where Then: ScanStr<'a> {
+    type Output = Then::Output;
+
+    fn scan<I: ScanInput<'a>>(&mut self, s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s_str = s.as_str();
+        let field_end = StrCursor::new_at_left_of_byte_pos(s_str, ::std::cmp::min(s_str.len(), self.0))
+            .byte_pos();
+
+        let lead = if self.1.wants_leading_junk_stripped() {
+            s_str[..field_end].bytes().take_while(|&b| b == b' ').count()
+        } else {
+            0
+        };
+
+        let (v, n) = match self.1.scan(s.from_subslice(&s_str[lead..field_end])) {
+            Ok(res) => res,
+            Err(err) => return Err(err.add_offset(lead)),
+        };
+
+        let end = lead + n;
+
+        if let Some(off) = s_str[end..field_end].bytes().position(|b| b != b' ') {
+            return Err(ScanError::syntax("expected padding after value").add_offset(end + off));
+        }
+
+        Ok((v, field_end))
+    }
+
+    fn wants_leading_junk_stripped(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_padded() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+    use ::scanner::Word;
+    let scan = padded_a::<Word>;
+
+    assert_match!(scan(4).scan(""), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
+    assert_match!(scan(4).scan("    "), Err(SE { kind: SEK::SyntaxNoMessage, .. }));
+    assert_match!(scan(4).scan("ab"), Ok(("ab", 2)));
+    assert_match!(scan(4).scan("ab  cd"), Ok(("ab", 4)));
+    assert_match!(scan(4).scan("  ab"), Ok(("ab", 4)));
+    assert_match!(scan(4).scan("  abcd"), Ok(("ab", 4)));
+    assert_match!(scan(4).scan("abcd  "), Ok(("abcd", 4)));
+    assert_match!(scan(4).scan("abcde"), Ok(("abcd", 4)));
+    assert_match!(scan(4).scan("ab c"), Err(SE { kind: SEK::Syntax(_), ref at, .. }) if at.offset() == 3);
+    assert_match!(scan(4).scan("  ab    cd"), Ok(("ab", 4)));
+
+    let scan = padded_a::<u32>;
+    assert_match!(scan(5).scan("   42    7"), Ok((42, 5)));
+    assert_match!(scan(5).scan("    7   42"), Ok((7, 5)));
+    assert_match!(scan(5).scan("123456"), Ok((12345, 5)));
+    assert_match!(scan(5).scan("   x7"), Err(SE { kind: SEK::Syntax(_), ref at, .. }) if at.offset() == 3);
+
+    // Right-aligned columns which touch.
+    assert_match!(
+        scan!("  1234"; (let a <| padded_a::<u32>(3), let b <| padded_a::<u32>(3)) => (a, b)),
+        Ok((1, 234))
+    );
+    assert_match!(
+        scan!(" 12345  6"; ([let ns <| padded_a::<u32>(3)]{3}) => ns),
+        Ok(ref ns) if *ns == [12, 345, 6]
+    );
+}
+
+/**
+Creates a runtime scanner that scans a measurement with a unit suffix, normalising it to a base unit.
 
-See: [`min_width`](fn.min_width.html).
+`units` is a table of units, each of which is either a `(suffix, factor)` pair or a `(suffix, factor, offset)` triple; see [`MeasureUnit`](trait.MeasureUnit.html).  The scanner first scans a value of type `T`, then looks for the longest suffix in the table which follows it (optionally separated by spaces or tabs).  The result is the value multiplied by that unit's factor, plus its offset, if it has one.  A suffix must not be directly followed by a letter or digit.
+
+If the table contains an entry with an empty suffix, it is used when no other suffix matches, making the unit optional.  Otherwise, a missing unit is an error.
+
+Suffixes are always matched exactly, irrespective of the input's case sensitivity.
+
+```ignore
+    // Scan a distance in metres.
+    let dist <| measure(&[("km", 1000.0), ("m", 1.0), ("cm", 0.01)][..])
+
+    // Scan a temperature in degrees Celsius.
+    let temp <| measure(&[("°C", 1.0, 0.0), ("°F", 5.0/9.0, -160.0/9.0), ("K", 1.0, -273.15)][..])
+```
 */
-pub fn min_width_a<S>(width: usize) -> MinWidth<ScanA<S>> {
-    min_width(width, scan_a::<S>())
+pub fn measure<T, U, E>(units: U) -> Measure<T, U, E> where U: AsRef<[E]> {
+    Measure(units, PhantomData)
 }
 
 /**
-Runtime scanner that forces *at least* `width` bytes to be consumed.
+Runtime scanner that scans a measurement with a unit suffix.
 
-See: [`min_width`](fn.min_width.html), [`min_width_a`](fn.min_width_a.html).
+See: [`measure`](fn.measure.html).
 */
-pub struct MinWidth<Then>(usize, Then);
+pub struct Measure<T, U, E>(U, PhantomData<(T, E)>);
 
-impl<'a, Then> ScanStr<'a> for MinWidth<Then>
-where Then: ScanStr<'a> {
-    type Output = Then::Output;
+impl<'a, T, U, E> ScanStr<'a> for Measure<T, U, E>
+where
+    T: ScanSelfFromStr<'a>,
+    U: AsRef<[E]>,
+    E: MeasureUnit<T>,
+{
+    type Output = T;
 
     fn scan<I: ScanInput<'a>>(&mut self, s: I) -> Result<(Self::Output, usize), ScanError> {
         let s_str = s.as_str();
-        if s_str.len() < self.0 {
-            return Err(ScanError::syntax("expected more bytes to scan"));
+        let (v, n) = try!(T::scan_from(s.clone()));
+
+        let rest = &s_str[n..];
+        let ws = rest.len() - rest.trim_left_matches(|c| c == ' ' || c == '\t').len();
+        let rest = &rest[ws..];
+
+        let mut best: Option<(usize, &E)> = None;
+        for unit in self.0.as_ref() {
+            let suffix = unit.suffix();
+            if suffix.is_empty() || !rest.starts_with(suffix) {
+                continue;
+            }
+            if best.map(|(len, _)| len >= suffix.len()).unwrap_or(false) {
+                continue;
+            }
+            if rest[suffix.len()..].chars().next().map(|c| c.is_alphanumeric()).unwrap_or(false) {
+                continue;
+            }
+            best = Some((suffix.len(), unit));
         }
-        match self.1.scan(s) {
-            Ok((_, n)) if n < self.0 => Err(ScanError::syntax("scanned value too short")),
-            other => other
+
+        match best {
+            Some((len, unit)) => Ok((unit.to_base(v), n + ws + len)),
+            None => match self.0.as_ref().iter().find(|unit| unit.suffix().is_empty()) {
+                Some(unit) => Ok((unit.to_base(v), n)),
+                None => Err(ScanError::syntax("expected unit").add_offset(n + ws)),
+            }
         }
     }
 
     fn wants_leading_junk_stripped(&self) -> bool {
-        self.1.wants_leading_junk_stripped()
+        <T as ScanFromStr<'a>>::wants_leading_junk_stripped()
+    }
+}
+
+/**
+This trait defines an entry in the unit table of a [`measure`](fn.measure.html) scanner.
+
+It is implemented for `(suffix, factor)` pairs, which convert a value by multiplying it by `factor`, and `(suffix, factor, offset)` triples, which multiply it by `factor` then add `offset`.  The latter allows for units with different zero points, such as degrees Fahrenheit and Celsius.
+*/
+pub trait MeasureUnit<T> {
+    /**
+    The suffix which identifies this unit.
+    */
+    fn suffix(&self) -> &str;
+
+    /**
+    Converts a value in this unit to the base unit.
+    */
+    fn to_base(&self, v: T) -> T;
+}
+
+impl<'u, T> MeasureUnit<T> for (&'u str, T)
+where T: Mul<Output=T> + Copy {
+    fn suffix(&self) -> &str {
+        self.0
+    }
+
+    fn to_base(&self, v: T) -> T {
+        v * self.1
+    }
+}
+
+impl<'u, T> MeasureUnit<T> for (&'u str, T, T)
+where T: Mul<Output=T> + Add<Output=T> + Copy {
+    fn suffix(&self) -> &str {
+        self.0
+    }
+
+    fn to_base(&self, v: T) -> T {
+        v * self.1 + self.2
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_min_width() {
+fn test_measure() {
     use ::ScanError as SE;
     use ::ScanErrorKind as SEK;
-    use ::scanner::Word;
-    let scan = min_width_a::<Word>;
 
-    assert_match!(scan(2).scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
-    assert_match!(scan(2).scan("a"), Err(SE { kind: SEK::Syntax(_), .. }));
-    assert_match!(scan(2).scan("a b"), Err(SE { kind: SEK::Syntax(_), .. }));
-    assert_match!(scan(2).scan("ab"), Ok(("ab", 2)));
-    assert_match!(scan(2).scan("abc"), Ok(("abc", 3)));
+    let units = [("km", 1000.0), ("m", 1.0), ("mm", 0.001), ("mi", 1609.344)];
+    let scan = || measure(&units[..]);
+
+    assert_match!(scan().scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan().scan("5"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan().scan("5 parsecs"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan().scan("5ms"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan().scan("5m"), Ok((5.0, 2)));
+    assert_match!(scan().scan("1.5km away"), Ok((1500.0, 5)));
+    assert_match!(scan().scan("250 mm"), Ok((0.25, 6)));
+    assert_match!(scan().scan("2 mi."), Ok((3218.688, 4)));
+
+    let units = vec![("", 1), ("k", 1000)];
+    let mut scan = measure(units);
+    assert_match!(scan.scan("12"), Ok((12, 2)));
+    assert_match!(scan.scan("12 k"), Ok((12000, 4)));
+    assert_match!(scan.scan("12 x"), Ok((12, 2)));
+
+    let units = [("°C", 1.0, 0.0), ("°F", 5.0 / 9.0, -160.0 / 9.0), ("K", 1.0, -273.15)];
+    let scan = || measure(&units[..]);
+    let near = |a: f64, b: f64| (a - b).abs() < 1e-9;
+    assert_match!(scan().scan("21.5°C"), Ok((21.5, 7)));
+    assert_match!(scan().scan("212 °F"), Ok((v, 7)) if near(v, 100.0));
+    assert_match!(scan().scan("-40°F"), Ok((v, 6)) if near(v, -40.0));
+    assert_match!(scan().scan("0 K"), Ok((v, 3)) if near(v, -273.15));
+    assert_match!(scan().scan("0 Kelvin"), Err(SE { kind: SEK::Syntax(_), .. }));
 }
 
 /**
@@ -326,6 +2306,12 @@ See: [`scan_a`](../fn.scan_a.html).
 */
 pub struct ScanA<S>(PhantomData<S>);
 
+impl<S> Default for ScanA<S> {
+    fn default() -> Self {
+        ScanA(PhantomData)
+    }
+}
+
 impl<'a, S> ScanStr<'a> for ScanA<S>
 where S: ScanFromStr<'a> {
     type Output = S::Output;
@@ -339,6 +2325,337 @@ where S: ScanFromStr<'a> {
     }
 }
 
+/**
+Static scanner that delegates to a default-constructed runtime scanner.
+
+This is the inverse of [`ScanA`](struct.ScanA.html), and allows a runtime scanner which implements `Default` to be used in a binding's type position (*e.g.* `let v: Runtime<MyScanner>`), rather than with `<|`.
+
+See: [`scanner_alias!`](../../macro.scanner_alias!.html).
+*/
+pub struct Runtime<S>(PhantomData<S>);
+
+impl<'a, S> ScanFromStr<'a> for Runtime<S>
+where S: ScanStr<'a> + Default {
+    type Output = S::Output;
+
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        S::default().scan(s)
+    }
+
+    fn wants_leading_junk_stripped() -> bool {
+        S::default().wants_leading_junk_stripped()
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_runtime() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+    let scan = <Runtime<ScanA<u16>>>::scan_from;
+
+    assert_match!(scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("8080"), Ok((8080, 4)));
+    assert_match!(scan("70000"), Err(SE { kind: SEK::Overflow("u16"), .. }));
+}
+
+/**
+Creates a runtime scanner that turns any failure of `then` into a soft failure.
+
+This undoes the effect of [`cut`](fn.cut.html), allowing a hard failure inside `then` to be treated as an ordinary failure by the surrounding pattern, so that later rules can still be tried.
+
+Errors of kind `LimitExceeded` always abort scanning, and cannot be made soft.
+
+See: [`soft_a`](fn.soft_a.html), [`ScanError::soft`](../../struct.ScanError.html#method.soft).
+*/
+pub fn soft<Then>(then: Then) -> Soft<Then> {
+    Soft(then)
+}
+
+/**
+Creates a runtime scanner that turns any failure of the static scanner `S` into a soft failure.
+
+See: [`soft`](fn.soft.html).
+*/
+pub fn soft_a<S>() -> Soft<ScanA<S>> {
+    soft(scan_a::<S>())
+}
+
+/**
+Runtime scanner that turns failures into soft failures.
+
+See: [`soft`](fn.soft.html), [`soft_a`](fn.soft_a.html).
+*/
+pub struct Soft<Then>(Then);
+
+impl<'a, Then> ScanStr<'a> for Soft<Then>
+where Then: ScanStr<'a> {
+    type Output = Then::Output;
+
+    fn scan<I: ScanInput<'a>>(&mut self, s: I) -> Result<(Self::Output, usize), ScanError> {
+        self.0.scan(s).map_err(ScanError::soft)
+    }
+
+    fn wants_leading_junk_stripped(&self) -> bool {
+        self.0.wants_leading_junk_stripped()
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_soft() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+    use ::scanner::Word;
+
+    assert_match!(soft(cut_a::<i32>()).scan("42"), Ok((42, 2)));
+    assert_match!(soft(cut_a::<i32>()).scan("x"), Err(ref err) if !err.is_fatal());
+
+    let scan_cmd = |s: &'static str| scan!(s;
+        ("set", let k: Word, "=", let v <| soft(cut_a::<i32>())) => (k, Some(v)),
+        (let k: Word, ...) => (k, None),
+    );
+    assert_match!(scan_cmd("set x = 1"), Ok(("x", Some(1))));
+    assert_match!(scan_cmd("set x = y"), Ok(("set", None)));
+
+    assert_match!(
+        collection_limits(Some(1), None, soft_a::<Vec<i32>>()).scan("[1, 2]"),
+        Err(SE { kind: SEK::LimitExceeded, .. }));
+}
+
+/**
+Creates a runtime scanner that extracts the longest non-empty run of characters for which `pred` returns `true`.
+
+Any `FnMut(char) -> bool` can be used, including the predicates on `char` itself, such as `char::is_uppercase` or `char::is_numeric`.  This is a stable alternative to [`until_pat`](fn.until_pat.html) for character-based tests.
+
+```ignore
+// Scan a run of upper-case letters.
+let _ <| take_while_char(char::is_uppercase)
+```
+*/
+pub fn take_while_char<F>(pred: F) -> TakeWhileChar<F>
+where F: FnMut(char) -> bool {
+    TakeWhileChar(pred)
+}
+
+/**
+Runtime scanner that extracts a run of characters satisfying a predicate.
+
+See: [`take_while_char`](fn.take_while_char.html).
+*/
+pub struct TakeWhileChar<F>(F);
+
+impl<'a, F> ScanStr<'a> for TakeWhileChar<F>
+where F: FnMut(char) -> bool {
+    type Output = &'a str;
+
+    fn scan<I: ScanInput<'a>>(&mut self, s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        let pred = &mut self.0;
+        let end = s.char_indices()
+            .find(|&(_, c)| !pred(c))
+            .map(|(i, _)| i)
+            .unwrap_or(s.len());
+
+        if end == 0 {
+            Err(ScanError::syntax("expected at least one matching character"))
+        } else {
+            Ok((&s[..end], end))
+        }
+    }
+
+    fn wants_leading_junk_stripped(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_take_while_char() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+
+    assert_match!(take_while_char(char::is_uppercase).scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(take_while_char(char::is_uppercase).scan("abc"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(take_while_char(char::is_uppercase).scan("ABc"), Ok(("AB", 2)));
+    assert_match!(take_while_char(char::is_uppercase).scan("ÀÉ"), Ok(("ÀÉ", 4)));
+    assert_match!(take_while_char(|c| c == 'x' || c == 'y').scan("xyxz"), Ok(("xyx", 3)));
+
+    let mut n = 0;
+    assert_match!(take_while_char(|_| { n += 1; n <= 2 }).scan("abcd"), Ok(("ab", 2)));
+
+    assert_match!(
+        scan!("HTTP 200"; (let proto <| take_while_char(char::is_alphabetic), let code: u16) => (proto, code)),
+        Ok(("HTTP", 200))
+    );
+}
+
+/**
+Creates a runtime scanner that will extract a slice of the input up to, but *not* including, the first match of `pat`, and passes it to `then`.
+
+This is the stable counterpart to [`until_pat`](fn.until_pat.html).  `pat` can be anything which implements [`UntilPattern`](runtime/trait.UntilPattern.html); see also the `until_str`, `until_char`, `until_any` and `until_fn` shorthands.
+
+Note that this scanner *does not* respect the case sensitivity of the input.
+
+See: [`until_a`](fn.until_a.html).
+*/
+pub fn until<Then, P>(pat: P, then: Then) -> Until<Then, P> {
+    Until(pat, then)
+}
+
+/**
+Creates a runtime scanner that will extract a slice of the input up to, but *not* including, the first match of `pat`, and passes it to the static scanner `S`.
+
+See: [`until`](fn.until.html).
+*/
+pub fn until_a<S, P>(pat: P) -> Until<ScanA<S>, P> {
+    until(pat, scan_a::<S>())
+}
+
+/**
+Creates a runtime scanner that will extract a slice of the input up to, but *not* including, the first occurrence of the string `pat`.
+
+See: [`until`](fn.until.html).
+*/
+pub fn until_str<'a, 'p>(pat: &'p str) -> Until<ScanA<::scanner::Everything<'a, &'a str>>, &'p str> {
+    until_a::<::scanner::Everything<&str>, _>(pat)
+}
+
+/**
+Creates a runtime scanner that will extract a slice of the input up to, but *not* including, the first occurrence of the character `pat`.
+
+See: [`until`](fn.until.html).
+*/
+pub fn until_char<'a>(pat: char) -> Until<ScanA<::scanner::Everything<'a, &'a str>>, char> {
+    until_a::<::scanner::Everything<&str>, _>(pat)
+}
+
+/**
+Creates a runtime scanner that will extract a slice of the input up to, but *not* including, the first occurrence of any of the characters in `pat`.
+
+See: [`until`](fn.until.html).
+*/
+pub fn until_any<'a, 'p>(pat: &'p [char]) -> Until<ScanA<::scanner::Everything<'a, &'a str>>, &'p [char]> {
+    until_a::<::scanner::Everything<&str>, _>(pat)
+}
+
+/**
+Creates a runtime scanner that will extract a slice of the input up to, but *not* including, the first character for which `pred` returns `true`.
+
+See: [`until`](fn.until.html).
+*/
+pub fn until_fn<'a, F>(pred: F) -> Until<ScanA<::scanner::Everything<'a, &'a str>>, CharFn<F>>
+where F: FnMut(char) -> bool {
+    until_a::<::scanner::Everything<&str>, _>(CharFn(pred))
+}
+
+/**
+Runtime scanner that slices the input up to the first match of a pattern.
+
+See: [`until`](../fn.until.html).
+*/
+pub struct Until<Then, P>(P, Then);
+
+impl<'a, Then, P> ScanStr<'a> for Until<Then, P>
+where Then: ScanStr<'a>, P: UntilPattern {
+    type Output = Then::Output;
+
+    fn scan<I: ScanInput<'a>>(&mut self, s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s_str = s.as_str();
+        let off = match self.0.find_in(s_str) {
+            Some(off) => off,
+            None => return Err(ScanError::syntax("no match for pattern")),
+        };
+
+        let sl = &s_str[..off];
+        let sl = s.from_subslice(sl);
+
+        self.1.scan(sl)
+    }
+
+    fn wants_leading_junk_stripped(&self) -> bool {
+        self.1.wants_leading_junk_stripped()
+    }
+}
+
+/**
+A pattern which can be searched for by the [`until`](../fn.until.html) scanner.
+
+This is a stable stand-in for `std::str::pattern::Pattern`.
+*/
+pub trait UntilPattern {
+    /**
+    Returns the byte offset of the first match of this pattern in `s`, if there is one.
+    */
+    fn find_in(&mut self, s: &str) -> Option<usize>;
+}
+
+impl<'p> UntilPattern for &'p str {
+    fn find_in(&mut self, s: &str) -> Option<usize> {
+        s.find(*self)
+    }
+}
+
+impl UntilPattern for char {
+    fn find_in(&mut self, s: &str) -> Option<usize> {
+        s.find(*self)
+    }
+}
+
+impl<'p> UntilPattern for &'p [char] {
+    fn find_in(&mut self, s: &str) -> Option<usize> {
+        let chars = *self;
+        s.find(|c| chars.contains(&c))
+    }
+}
+
+/**
+Adapts a `FnMut(char) -> bool` predicate into an [`UntilPattern`](trait.UntilPattern.html).
+
+See: [`until_fn`](../fn.until_fn.html).
+*/
+pub struct CharFn<F>(pub F);
+
+impl<F> UntilPattern for CharFn<F> where F: FnMut(char) -> bool {
+    fn find_in(&mut self, s: &str) -> Option<usize> {
+        let pred = &mut self.0;
+        s.char_indices().find(|&(_, c)| pred(c)).map(|(i, _)| i)
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_until_stable() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+
+    assert_match!(until_str("x").scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(until_str("x").scan("ab"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(until_str("x").scan("x"), Ok(("", 0)));
+    assert_match!(until_str("x").scan("abx"), Ok(("ab", 2)));
+    assert_match!(until_str("-->").scan("a -- b --> c"), Ok(("a -- b ", 7)));
+
+    assert_match!(until_char('x').scan("ab"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(until_char('x').scan("x"), Ok(("", 0)));
+    assert_match!(until_char('é').scan("caféx"), Ok(("caf", 3)));
+
+    assert_match!(until_any(&['x', 'y']).scan("ab"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(until_any(&['x', 'y']).scan("abyx"), Ok(("ab", 2)));
+    assert_match!(until_any(&[]).scan("ab"), Err(SE { kind: SEK::Syntax(_), .. }));
+
+    assert_match!(until_fn(char::is_whitespace).scan("ab"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(until_fn(char::is_whitespace).scan("ab\tc"), Ok(("ab", 2)));
+    assert_match!(until_fn(|c| c == ';').scan("日本;"), Ok(("日本", 6)));
+
+    assert_match!(until_a::<i32, _>(';').scan("42;"), Ok((42, 2)));
+    assert_match!(until(";", scan_a::<i32>()).scan("x;"), Err(SE { kind: SEK::Syntax(_), .. }));
+
+    assert_match!(
+        scan!("key = some value; next"; (let k: ::scanner::Word, "=", let v <| until_char(';'), ";", ..tail) => (k, v, tail)),
+        Ok(("key", "some value", " next"))
+    );
+}
+
 /**
 Creates a runtime scanner that will extract a slice of the input up to, but *not* including, a specified string pattern.
 
@@ -478,3 +2795,57 @@ fn test_until() {
     assert_match!(until_pat_str(&['x'][..]).scan("ax"), Ok(("a", 1)));
     assert_match!(until_pat_str(&['x'][..]).scan("abx"), Ok(("ab", 2)));
 }
+
+/**
+Creates a runtime scanner that yields both the output of `then`, and the exact slice of input it consumed.
+
+This is useful for echoing input back verbatim, or for caching values by their source text.
+
+See: [`with_raw_a`](fn.with_raw_a.html).
+*/
+pub fn with_raw<Then>(then: Then) -> WithRaw<Then> {
+    WithRaw(then)
+}
+
+/**
+Creates a runtime scanner that yields both the output of the static scanner `S`, and the exact slice of input it consumed.
+
+See: [`with_raw`](fn.with_raw.html).
+*/
+pub fn with_raw_a<S>() -> WithRaw<ScanA<S>> {
+    with_raw(scan_a::<S>())
+}
+
+/**
+Runtime scanner that yields both a scanned value and the input it was scanned from.
+
+See: [`with_raw`](fn.with_raw.html), [`with_raw_a`](fn.with_raw_a.html).
+*/
+pub struct WithRaw<Then>(Then);
+
+impl<'a, Then> ScanStr<'a> for WithRaw<Then>
+where Then: ScanStr<'a> {
+    type Output = (Then::Output, &'a str);
+
+    fn scan<I: ScanInput<'a>>(&mut self, s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s_str = s.as_str();
+        self.0.scan(s).map(|(v, n)| ((v, &s_str[..n]), n))
+    }
+
+    fn wants_leading_junk_stripped(&self) -> bool {
+        self.0.wants_leading_junk_stripped()
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_with_raw() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+    let scan = with_raw_a::<f64>;
+
+    assert_match!(scan().scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan().scan("1.50"), Ok(((1.5, "1.50"), 4)));
+    assert_match!(scan().scan("1e3 x"), Ok(((1000.0, "1e3"), 3)));
+    assert_match!(with_raw(max_width_a::<u32>(2)).scan("1234"), Ok(((12, "12"), 2)));
+}
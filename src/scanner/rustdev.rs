@@ -0,0 +1,343 @@
+/*
+Copyright ⓒ 2016 Daniel Keep.
+
+Licensed under the MIT license (see LICENSE or <http://opensource.org
+/licenses/MIT>) or the Apache License, Version 2.0 (see LICENSE of
+<http://www.apache.org/licenses/LICENSE-2.0>), at your option. All
+files in the project carrying such notice may not be copied, modified,
+or distributed except according to those terms.
+*/
+/*!
+Scanners for the output of the Rust toolchain.
+
+Each scanner is a "static self scanner": it scans into an instance of itself, with one public field per part of the line.
+
+**Note**: requires the `rustdev` feature.
+
+## Examples
+
+```rust
+# #[macro_use] extern crate scan_rules;
+# use scan_rules::scanner::rustdev::{RustcVersion, TestResult};
+# fn main() {
+let_scan!("rustc 1.6.0 (c30b771ad 2016-01-19)"; (let v: RustcVersion));
+assert_eq!((v.major, v.minor, v.patch), (1, 6, 0));
+assert_eq!(v.commit_date, Some((2016, 1, 19)));
+
+let out = "test result: FAILED. 10 passed; 2 failed; 1 ignored; 0 measured; 0 filtered out";
+let_scan!(out; (let r: TestResult));
+assert!(!r.ok);
+assert_eq!((r.passed, r.failed), (10, 2));
+# }
+```
+*/
+use std::time::Duration;
+use ::ScanError;
+use ::input::ScanInput;
+use ::scanner::bytes::skip_space;
+use ::scanner::{ScanFromStr, ScanStr};
+use ::scanner::runtime::{datetime, fixed_point, FixedPointRounding};
+
+/**
+Scans the version line output by `rustc --version`, such as `rustc 1.6.0 (c30b771ad 2016-01-19)` or `rustc 1.10.0-nightly (2016-04-10)`.
+
+The commit hash and date are both optional, as is the parenthesised section as a whole.  Scanning stops after the closing parenthesis, or after the version number if there isn't one.
+*/
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RustcVersion<'a> {
+    /// The major version number.
+    pub major: u32,
+    /// The minor version number.
+    pub minor: u32,
+    /// The patch version number.
+    pub patch: u32,
+    /// The pre-release identifier, such as `nightly` or `beta.2`.
+    pub pre: Option<&'a str>,
+    /// The abbreviated hash of the commit the compiler was built from.
+    pub commit_hash: Option<&'a str>,
+    /// The `(year, month, day)` of the commit the compiler was built from.
+    pub commit_date: Option<(i32, u8, u8)>,
+}
+
+impl<'a> ScanFromStr<'a> for RustcVersion<'a> {
+    type Output = Self;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        let bs = s.as_bytes();
+
+        if !s.starts_with("rustc ") {
+            return Err(ScanError::syntax("expected `rustc`"));
+        }
+        let i = skip_space(bs, 6);
+
+        let (major, i) = try!(number(bs, i, "expected major version"));
+        let i = try!(expect_byte(bs, i, b'.'));
+        let (minor, i) = try!(number(bs, i, "expected minor version"));
+        let i = try!(expect_byte(bs, i, b'.'));
+        let (patch, mut i) = try!(number(bs, i, "expected patch version"));
+
+        let mut pre = None;
+        if bs.get(i) == Some(&b'-') {
+            let len = bs[i + 1..].iter()
+                .take_while(|&&b| match b {
+                    b'a'...b'z' | b'A'...b'Z' | b'0'...b'9' | b'.' | b'-' => true,
+                    _ => false,
+                })
+                .count();
+            if len == 0 {
+                return Err(ScanError::syntax("expected pre-release identifier").add_offset(i + 1));
+            }
+            pre = Some(&s[i + 1..i + 1 + len]);
+            i += 1 + len;
+        }
+
+        let mut version = RustcVersion {
+            major: major,
+            minor: minor,
+            patch: patch,
+            pre: pre,
+            commit_hash: None,
+            commit_date: None,
+        };
+
+        let j = skip_space(bs, i);
+        if j == i || bs.get(j) != Some(&b'(') {
+            return Ok((version, i));
+        }
+        let mut j = j + 1;
+
+        let hash_len = bs[j..].iter().take_while(|&&b| match b {
+            b'0'...b'9' | b'a'...b'f' => true,
+            _ => false,
+        }).count();
+        if hash_len > 0 && bs.get(j + hash_len) != Some(&b'-') {
+            version.commit_hash = Some(&s[j..j + hash_len]);
+            j = skip_space(bs, j + hash_len);
+        }
+
+        if bs.get(j) != Some(&b')') {
+            let ((y, m, d, _, _, _), n) = try!(datetime("%Y-%m-%d").scan(&s[j..]).map_err(|e| e.add_offset(j)));
+            version.commit_date = Some((y, m, d));
+            j += n;
+        }
+
+        let j = try!(expect_byte(bs, j, b')'));
+        Ok((version, j))
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_rustc_version() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+
+    let scan = RustcVersion::scan_from;
+
+    assert_match!(scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(scan("cargo 1.6.0"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(
+        scan("rustc 1.6.0 (c30b771ad 2016-01-19)\n"),
+        Ok((RustcVersion {
+            major: 1, minor: 6, patch: 0,
+            pre: None,
+            commit_hash: Some("c30b771ad"),
+            commit_date: Some((2016, 1, 19)),
+        }, 34)));
+    assert_match!(
+        scan("rustc 1.10.0-nightly (2016-04-10)"),
+        Ok((RustcVersion {
+            major: 1, minor: 10, patch: 0,
+            pre: Some("nightly"),
+            commit_hash: None,
+            commit_date: Some((2016, 4, 10)),
+        }, 33)));
+    assert_match!(
+        scan("rustc 1.0.0-beta.2 (bfb41de47)"),
+        Ok((RustcVersion { pre: Some("beta.2"), commit_hash: Some("bfb41de47"), commit_date: None, .. }, 30)));
+    assert_match!(
+        scan("rustc 1.7.0-dev x"),
+        Ok((RustcVersion { minor: 7, pre: Some("dev"), commit_hash: None, commit_date: None, .. }, 15)));
+    assert_match!(scan("rustc 1.6"), Err(SE { kind: SEK::Syntax(_), ref at, .. }) if at.offset() == 9);
+    assert_match!(scan("rustc 1.6.0 (c30b771ad 2016-01-19"), Err(SE { kind: SEK::Syntax(_), ref at, .. }) if at.offset() == 33);
+    assert_match!(scan("rustc 1.6.0 (c30b771ad 2016-13-19)"), Err(SE { kind: SEK::Syntax(_), .. }));
+}
+
+/**
+Scans the summary line output by the test harness after running a test suite, such as `test result: ok. 31 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.03s`.
+
+The counts may appear in any order.  Counts which are missing (such as `filtered out`, which older versions of the harness do not output) are zero.  Scanning stops at the end of the line.
+*/
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TestResult {
+    /// Whether the test suite passed (`ok`) or not (`FAILED`).
+    pub ok: bool,
+    /// The number of tests which passed.
+    pub passed: u32,
+    /// The number of tests which failed.
+    pub failed: u32,
+    /// The number of tests which were ignored.
+    pub ignored: u32,
+    /// The number of benchmarks which were run.
+    pub measured: u32,
+    /// The number of tests which were excluded by a filter.
+    pub filtered_out: u32,
+    /// How long the test suite took to run.
+    pub finished_in: Option<Duration>,
+}
+
+impl<'a> ScanFromStr<'a> for TestResult {
+    type Output = Self;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        let bs = s.as_bytes();
+
+        if !s.starts_with("test result: ") {
+            return Err(ScanError::syntax("expected `test result:`"));
+        }
+        let i = skip_space(bs, 13);
+
+        let (ok, i) = if s[i..].starts_with("ok.") {
+            (true, i + 3)
+        } else if s[i..].starts_with("FAILED.") {
+            (false, i + 7)
+        } else {
+            return Err(ScanError::syntax("expected `ok` or `FAILED`").add_offset(i));
+        };
+
+        let mut result = TestResult {
+            ok: ok,
+            passed: 0,
+            failed: 0,
+            ignored: 0,
+            measured: 0,
+            filtered_out: 0,
+            finished_in: None,
+        };
+
+        let mut i = i;
+        loop {
+            i = skip_space(bs, i);
+            let rest = &s[i..];
+
+            if rest.starts_with("finished in ") {
+                let at = i + 12;
+                let (nanos, n) = try!(fixed_point(9).rounding(FixedPointRounding::Truncate).scan(&s[at..]).map_err(|e| e.add_offset(at)));
+                if nanos < 0 || bs.get(at + n) != Some(&b's') {
+                    return Err(ScanError::syntax("expected duration in seconds").add_offset(at));
+                }
+                let nanos = nanos as u64;
+                result.finished_in = Some(Duration::new(nanos / 1_000_000_000, (nanos % 1_000_000_000) as u32));
+                i = at + n + 1;
+            } else {
+                let (count, j) = try!(number(bs, i, "expected test count"));
+                let j = skip_space(bs, j);
+                let rest = &s[j..];
+                let (field, label_len) = if rest.starts_with("passed") {
+                    (&mut result.passed, 6)
+                } else if rest.starts_with("failed") {
+                    (&mut result.failed, 6)
+                } else if rest.starts_with("ignored") {
+                    (&mut result.ignored, 7)
+                } else if rest.starts_with("measured") {
+                    (&mut result.measured, 8)
+                } else if rest.starts_with("filtered out") {
+                    (&mut result.filtered_out, 12)
+                } else {
+                    return Err(ScanError::syntax("expected test count label").add_offset(j));
+                };
+                *field = count;
+                i = j + label_len;
+            }
+
+            match bs.get(i) {
+                Some(&b';') => i += 1,
+                None | Some(&b'\r') | Some(&b'\n') => break,
+                Some(&b) if b == b' ' || b == b'\t' => {
+                    // Allow trailing whitespace.
+                    let j = skip_space(bs, i);
+                    match bs.get(j) {
+                        None | Some(&b'\r') | Some(&b'\n') => break,
+                        _ => return Err(ScanError::syntax("expected `;`").add_offset(i)),
+                    }
+                },
+                Some(_) => return Err(ScanError::syntax("expected `;`").add_offset(i)),
+            }
+        }
+
+        Ok((result, i))
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_test_result() {
+    use std::time::Duration;
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+
+    let scan = TestResult::scan_from;
+
+    assert_match!(scan(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(
+        scan("test result: ok. 31 passed; 0 failed; 0 ignored; 0 measured; 2 filtered out; finished in 0.03s\n\nx"),
+        Ok((TestResult {
+            ok: true,
+            passed: 31,
+            failed: 0,
+            ignored: 0,
+            measured: 0,
+            filtered_out: 2,
+            finished_in: Some(d),
+        }, 94)) if d == Duration::new(0, 30_000_000));
+    assert_match!(
+        scan("test result: FAILED. 5 passed; 1 failed; 2 ignored; 3 measured"),
+        Ok((TestResult {
+            ok: false,
+            passed: 5,
+            failed: 1,
+            ignored: 2,
+            measured: 3,
+            filtered_out: 0,
+            finished_in: None,
+        }, 62)));
+    assert_match!(
+        scan("test result: ok. 1 passed; finished in 12.5s  \r\n"),
+        Ok((TestResult { passed: 1, finished_in: Some(d), .. }, 44)) if d == Duration::new(12, 500_000_000));
+    assert_match!(scan("test result: meh. 1 passed"), Err(SE { kind: SEK::Syntax(_), ref at, .. }) if at.offset() == 13);
+    assert_match!(scan("test result: ok. 1 exploded"), Err(SE { kind: SEK::Syntax(_), ref at, .. }) if at.offset() == 19);
+    assert_match!(scan("test result: ok. 1 passed, 0 failed"), Err(SE { kind: SEK::Syntax(_), ref at, .. }) if at.offset() == 25);
+    assert_match!(scan("test result: ok. 1 passed; finished in 1m"), Err(SE { kind: SEK::Syntax(_), .. }));
+}
+
+/**
+Fails unless the byte at `i` is `b`, returning the position after it.
+*/
+fn expect_byte(bs: &[u8], i: usize, b: u8) -> Result<usize, ScanError> {
+    match bs.get(i) {
+        Some(&c) if c == b => Ok(i + 1),
+        _ => Err(ScanError::syntax(match b {
+            b'.' => "expected `.`",
+            b')' => "expected `)`",
+            _ => "unexpected character",
+        }).add_offset(i)),
+    }
+}
+
+/**
+Scans a decimal number starting at `i`.
+*/
+fn number(bs: &[u8], i: usize, msg: &'static str) -> Result<(u32, usize), ScanError> {
+    let n = bs[i..].iter().take_while(|&&b| b'0' <= b && b <= b'9').count();
+    if n == 0 {
+        return Err(ScanError::syntax(msg).add_offset(i));
+    }
+    let mut v: u32 = 0;
+    for &b in &bs[i..i + n] {
+        v = match v.checked_mul(10).and_then(|v| v.checked_add((b - b'0') as u32)) {
+            Some(v) => v,
+            None => return Err(ScanError::overflow("u32").add_offset(i)),
+        };
+    }
+    Ok((v, i + n))
+}
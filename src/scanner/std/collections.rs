@@ -12,42 +12,44 @@ Scanner implementations for `std::collections::*`.
 */
 use std::collections::{
     BTreeMap, BTreeSet, BinaryHeap,
-    HashMap, HashSet,
     LinkedList,
     VecDeque,
 };
-use std::hash::Hash;
+#[cfg(feature="std")] use std::collections::{HashMap, HashSet};
+#[cfg(feature="std")] use std::hash::Hash;
 use ::scanner::KeyValuePair;
 
-scanner! { impl<'a, K, V> ScanFromStr for BTreeMap<K, V> => BTreeMap, where {K: Ord} {
+scanner! { impl<'a, K, V> ScanFromStr for BTreeMap<K, V> => BTreeMap, collection where {K: Ord} {
     ("{", [ let es: KeyValuePair<K, V> ],*: BTreeMap<K, V>, "}", ..tail) => (es, tail)
 }}
 
-scanner! { impl<'a, T> ScanFromStr for BTreeSet<T> => BTreeSet, where {T: Ord} {
+scanner! { impl<'a, T> ScanFromStr for BTreeSet<T> => BTreeSet, collection where {T: Ord} {
     ("{", [ let es: T ],*: BTreeSet<_>, "}", ..tail) => (es, tail)
 }}
 
-scanner! { impl<'a, T> ScanFromStr for BinaryHeap<T> => BinaryHeap, where {T: Ord} {
+scanner! { impl<'a, T> ScanFromStr for BinaryHeap<T> => BinaryHeap, collection where {T: Ord} {
     ("[", [ let es: T ],*: BinaryHeap<_>, "]", ..tail) => (es, tail)
 }}
 
-scanner! { impl<'a, K, V> ScanFromStr for HashMap<K, V> => HashMap, where {K: Hash + Eq} {
+#[cfg(feature="std")]
+scanner! { impl<'a, K, V> ScanFromStr for HashMap<K, V> => HashMap, collection where {K: Hash + Eq} {
     ("{", [ let es: KeyValuePair<K, V> ],*: HashMap<K, V>, "}", ..tail) => (es, tail)
 }}
 
-scanner! { impl<'a, T> ScanFromStr for HashSet<T> => HashSet, where {T: Hash + Eq} {
+#[cfg(feature="std")]
+scanner! { impl<'a, T> ScanFromStr for HashSet<T> => HashSet, collection where {T: Hash + Eq} {
     ("{", [ let es: T ],*: HashSet<_>, "}", ..tail) => (es, tail)
 }}
 
-scanner! { impl<'a, T> ScanFromStr for LinkedList<T> => LinkedList {
+scanner! { impl<'a, T> ScanFromStr for LinkedList<T> => LinkedList, collection where {} {
     ("[", [ let es: T ],*: LinkedList<_>, "]", ..tail) => (es, tail)
 }}
 
-scanner! { impl<'a, T> ScanFromStr for Vec<T> => Vec {
+scanner! { impl<'a, T> ScanFromStr for Vec<T> => Vec, collection where {} {
     ("[", [ let es: T ],*, "]", ..tail) => (es, tail)
 }}
 
-scanner! { impl<'a, T> ScanFromStr for VecDeque<T> => VecDeque {
+scanner! { impl<'a, T> ScanFromStr for VecDeque<T> => VecDeque, collection where {} {
     ("[", [ let es: T ],*: VecDeque<_>, "]", ..tail) => (es, tail)
 }}
 
@@ -132,6 +134,7 @@ fn test_binaryheap() {
     check!(<bool> "[true, false]", Ok([false, true], 13));
 }
 
+#[cfg(feature="std")]
 #[cfg(test)]
 #[test]
 fn test_hashmap() {
@@ -159,6 +162,7 @@ fn test_hashmap() {
     check!(<i32, bool> "{0: true, 1: false}", Ok([(0, true), (1, false)], 19));
 }
 
+#[cfg(feature="std")]
 #[cfg(test)]
 #[test]
 fn test_hashset() {
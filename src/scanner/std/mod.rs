@@ -9,16 +9,22 @@ or distributed except according to those terms.
 */
 /*!
 Scanner implementations for standard library (and other "official" crates) types.
+
+Note that `std::time::Instant` cannot be scanned, and attempting to do so results in an error that `Instant` does not implement `ScanFromStr`.  An `Instant` is a reading of a monotonic clock with an arbitrary, per-process origin, so a scanned value would be meaningless.  Scan a `SystemTime`, or a `Duration` with [`Iso8601Duration`](struct.Iso8601Duration.html), instead.
 */
 pub use self::time::Iso8601Duration;
 
 mod collections;
-mod net;
+#[cfg(feature="std")] mod net;
 mod time;
 
+use std::cmp::Ordering;
+#[cfg(feature="std")] use std::io::ErrorKind;
+use std::num::FpCategory;
 use std::ops::{Range, RangeFrom, RangeFull, RangeTo};
+use std::sync::atomic::Ordering as AtomicOrdering;
 use ::ScanError;
-use ::input::ScanInput;
+use ::input::{ScanCursor, ScanInput};
 use ::scanner::ScanFromStr;
 use ::util::StrUtil;
 
@@ -31,12 +37,18 @@ macro_rules! impl_tuple {
             type Output = (<$head as ::scanner::ScanFromStr<'a>>::Output, $(<$tail as ::scanner::ScanFromStr<'a>>::Output,)*);
             fn scan_from<I: $crate::input::ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ::ScanError> {
                 #![allow(non_snake_case)]
-                use ::util::StrUtil;
-                let s = s.as_str();
-                scan!(s;
-                    ("(", let $head: $head, $(",", let $tail: $tail,)* [","]?, ")", ..tail)
-                    => (($head, $($tail,)*), s.subslice_offset_stable(tail).unwrap())
-                )
+                use ::input::ScanCursor;
+                use super::{match_literal, scan_element, TUPLE_ELEMENT_CONTEXT};
+                let mut idx = 0;
+                let cur = try!(match_literal(s.to_cursor(), "("));
+                let ($head, cur) = try!(scan_element::<_, $head>(cur, &mut idx, &TUPLE_ELEMENT_CONTEXT));
+                $(
+                    let cur = try!(match_literal(cur, ","));
+                    let ($tail, cur) = try!(scan_element::<_, $tail>(cur, &mut idx, &TUPLE_ELEMENT_CONTEXT));
+                )*
+                let cur = match_literal(cur.clone(), ",").unwrap_or(cur);
+                let cur = try!(match_literal(cur, ")"));
+                Ok((($head, $($tail,)*), cur.offset()))
             }
         }
 
@@ -54,6 +66,122 @@ mod impl_tuples {
     impl_tuple! { T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13 T14 T15 T16 }
 }
 
+const TUPLE_ELEMENT_CONTEXT: [&'static str; 17] = [
+    "while scanning tuple element 0",
+    "while scanning tuple element 1",
+    "while scanning tuple element 2",
+    "while scanning tuple element 3",
+    "while scanning tuple element 4",
+    "while scanning tuple element 5",
+    "while scanning tuple element 6",
+    "while scanning tuple element 7",
+    "while scanning tuple element 8",
+    "while scanning tuple element 9",
+    "while scanning tuple element 10",
+    "while scanning tuple element 11",
+    "while scanning tuple element 12",
+    "while scanning tuple element 13",
+    "while scanning tuple element 14",
+    "while scanning tuple element 15",
+    "while scanning tuple element 16",
+];
+
+const ARRAY_ELEMENT_CONTEXT: [&'static str; 32] = [
+    "while scanning array element 0",
+    "while scanning array element 1",
+    "while scanning array element 2",
+    "while scanning array element 3",
+    "while scanning array element 4",
+    "while scanning array element 5",
+    "while scanning array element 6",
+    "while scanning array element 7",
+    "while scanning array element 8",
+    "while scanning array element 9",
+    "while scanning array element 10",
+    "while scanning array element 11",
+    "while scanning array element 12",
+    "while scanning array element 13",
+    "while scanning array element 14",
+    "while scanning array element 15",
+    "while scanning array element 16",
+    "while scanning array element 17",
+    "while scanning array element 18",
+    "while scanning array element 19",
+    "while scanning array element 20",
+    "while scanning array element 21",
+    "while scanning array element 22",
+    "while scanning array element 23",
+    "while scanning array element 24",
+    "while scanning array element 25",
+    "while scanning array element 26",
+    "while scanning array element 27",
+    "while scanning array element 28",
+    "while scanning array element 29",
+    "while scanning array element 30",
+    "while scanning array element 31",
+];
+
+fn match_literal<'a, C>(cur: C, lit: &str) -> Result<C, ScanError>
+where C: ScanCursor<'a> {
+    cur.try_match_literal(lit).map_err(|(err, _)| err)
+}
+
+/**
+Scans the next element of a tuple or array, attributing any error to the element's position using the matching entry in `contexts`.
+*/
+fn scan_element<'a, C, T>(cur: C, idx: &mut usize, contexts: &[&'static str]) -> Result<(T::Output, C), ScanError>
+where C: ScanCursor<'a>, T: ScanFromStr<'a> {
+    let ctx = contexts[*idx];
+    *idx += 1;
+    ::internal::try_scan_static::<C, T>(cur).map_err(|(err, _)| err.context(ctx))
+}
+
+#[cfg(test)]
+#[test]
+fn test_tuples() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+
+    assert_match!(<(i32,)>::scan_from("(1,)"), Ok(((1,), 4)));
+    assert_match!(<(i32,)>::scan_from("(1)"), Ok(((1,), 3)));
+    assert_match!(<(i32, bool)>::scan_from("(1, true)"), Ok(((1, true), 9)));
+    assert_match!(<(i32, bool)>::scan_from("(1,true) x"), Ok(((1, true), 8)));
+    assert_match!(<(i32, bool)>::scan_from("( 1 , true , )"), Ok(((1, true), 14)));
+    assert_match!(<(i32, bool)>::scan_from("(1, true x)"), Err(SE { kind: SEK::LiteralMismatch, .. }));
+    assert_match!(<(i32, bool)>::scan_from("(1 true)"), Err(SE { kind: SEK::LiteralMismatch, .. }));
+    assert_match!(
+        <(i32, bool, u8)>::scan_from("(1, true, 256)"),
+        Err(ref err @ SE { kind: SEK::Overflow(_), .. })
+            if err.at.offset() == 10 && err.context_chain() == ["while scanning tuple element 2"]
+    );
+    assert_match!(
+        <(i32, (bool, u8))>::scan_from("(1, (yes, 2))"),
+        Err(ref err) if err.context_chain() == ["while scanning tuple element 0", "while scanning tuple element 1"]
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn test_arrays() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+
+    assert_match!(<[i32; 1]>::scan_from("[1]"), Ok(([1], 3)));
+    assert_match!(<[i32; 3]>::scan_from("[1,2,3] x"), Ok(([1, 2, 3], 7)));
+    assert_match!(<[i32; 3]>::scan_from("[1, 2, 3, ]"), Ok(([1, 2, 3], 11)));
+    assert_match!(<[i32; 3]>::scan_from("[1, 2]"), Err(SE { kind: SEK::LiteralMismatch, .. }));
+    assert_match!(<[i32; 3]>::scan_from("[1, 2, 3, 4]"), Err(SE { kind: SEK::LiteralMismatch, .. }));
+    assert_match!(
+        <[u8; 8]>::scan_from("[0, 1, 2, 3, 4, 500, 6, 7]"),
+        Err(ref err @ SE { kind: SEK::Overflow(_), .. })
+            if err.at.offset() == 16 && err.context_chain() == ["while scanning array element 5"]
+    );
+    assert_match!(
+        <[(i32, bool); 2]>::scan_from("[(1, true), (2, 3)]"),
+        Err(ref err) if err.context_chain() == ["while scanning tuple element 1", "while scanning array element 1"]
+    );
+}
+
 impl<'a> ScanFromStr<'a> for () {
     type Output = Self;
     fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
@@ -74,12 +202,18 @@ macro_rules! impl_array {
             impl<'a, T> ::scanner::ScanFromStr<'a> for [T; $len] where T: ::scanner::ScanFromStr<'a> {
                 type Output = [T::Output; $len];
                 fn scan_from<I: $crate::input::ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ::ScanError> {
-                    use ::util::StrUtil;
-                    let s = s.as_str();
-                    scan!(s;
-                        ("[", let $e0: T, $(",", let $es: T,)* [","]?, "]", ..tail)
-                        => ([$e0, $($es,)*], s.subslice_offset_stable(tail).unwrap())
-                    )
+                    use ::input::ScanCursor;
+                    use super::{match_literal, scan_element, ARRAY_ELEMENT_CONTEXT};
+                    let mut idx = 0;
+                    let cur = try!(match_literal(s.to_cursor(), "["));
+                    let ($e0, cur) = try!(scan_element::<_, T>(cur, &mut idx, &ARRAY_ELEMENT_CONTEXT));
+                    $(
+                        let cur = try!(match_literal(cur, ","));
+                        let ($es, cur) = try!(scan_element::<_, T>(cur, &mut idx, &ARRAY_ELEMENT_CONTEXT));
+                    )*
+                    let cur = match_literal(cur.clone(), ",").unwrap_or(cur);
+                    let cur = try!(match_literal(cur, "]"));
+                    Ok(([$e0, $($es,)*], cur.offset()))
                 }
             }
         }
@@ -153,6 +287,35 @@ scanner! { impl<'a, T> ScanFromStr for RangeTo<T> => RangeTo {
     ("..", let b: T, ..tail) => (..b, tail)
 }}
 
+#[cfg(collections_bound)]
+impl<'a, T> ScanFromStr<'a> for ::std::collections::Bound<T> where T: ScanFromStr<'a> {
+    type Output = ::std::collections::Bound<T::Output>;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        use std::collections::Bound;
+        scan!( s.to_cursor();
+            ("Included", "(", let v: T, ")", ..tail) => (Bound::Included(v), tail),
+            ("Excluded", "(", let v: T, ")", ..tail) => (Bound::Excluded(v), tail),
+            ("Unbounded", ..tail) => (Bound::Unbounded, tail),
+        ).map(|(v, t)| (v, s.as_str().subslice_offset_stable(t).unwrap()))
+    }
+}
+
+#[cfg(collections_bound)]
+#[cfg(test)]
+#[test]
+fn test_bound() {
+    use std::collections::Bound;
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+    let scan = <Bound<i32>>::scan_from;
+
+    assert_match!(scan(""), Err(SE { kind: SEK::LiteralMismatch, .. }));
+    assert_match!(scan("Included(1)"), Ok((Bound::Included(1), 11)));
+    assert_match!(scan("Excluded( -2 ) x"), Ok((Bound::Excluded(-2), 14)));
+    assert_match!(scan("Unbounded, x"), Ok((Bound::Unbounded, 9)));
+    assert_match!(scan("Included"), Err(_));
+}
+
 impl<'a> ScanFromStr<'a> for RangeFull {
     type Output = Self;
 
@@ -163,3 +326,44 @@ impl<'a> ScanFromStr<'a> for RangeFull {
         }
     }
 }
+
+unit_enum_scanner! { impl<'a> for Ordering { Less, Equal, Greater }, err desc "expected ordering" }
+
+unit_enum_scanner! {
+    impl<'a> for AtomicOrdering { Relaxed, Release, Acquire, AcqRel, SeqCst },
+    err desc "expected atomic ordering"
+}
+
+unit_enum_scanner! {
+    impl<'a> for FpCategory { Nan, Infinite, Zero, Subnormal, Normal },
+    err desc "expected floating point category"
+}
+
+#[cfg(feature="std")]
+unit_enum_scanner! {
+    impl<'a> for ErrorKind {
+        NotFound, PermissionDenied, ConnectionRefused, ConnectionReset,
+        ConnectionAborted, NotConnected, AddrInUse, AddrNotAvailable,
+        BrokenPipe, AlreadyExists, WouldBlock, InvalidInput, InvalidData,
+        TimedOut, WriteZero, Interrupted, Other, UnexpectedEof,
+    },
+    err desc "expected I/O error kind"
+}
+
+#[cfg(test)]
+#[test]
+fn test_unit_enums() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+
+    assert_match!(<Ordering>::scan_from(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(<Ordering>::scan_from("Lesser"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(<Ordering>::scan_from("Less"), Ok((Ordering::Less, 4)));
+    assert_match!(<Ordering>::scan_from("Greater, x"), Ok((Ordering::Greater, 7)));
+    assert_match!(<AtomicOrdering>::scan_from("AcqRel"), Ok((AtomicOrdering::AcqRel, 6)));
+    assert_match!(<AtomicOrdering>::scan_from("Acquire"), Ok((AtomicOrdering::Acquire, 7)));
+    assert_match!(<FpCategory>::scan_from("Subnormal"), Ok((FpCategory::Subnormal, 9)));
+
+    #[cfg(feature="std")]
+    assert_match!(<ErrorKind>::scan_from("UnexpectedEof)"), Ok((ErrorKind::UnexpectedEof, 13)));
+}
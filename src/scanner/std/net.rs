@@ -10,7 +10,7 @@ or distributed except according to those terms.
 /*!
 Scanner implementations for `std::net::*`.
 */
-use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::net::{Ipv4Addr, Ipv6Addr, Shutdown, SocketAddr};
 use itertools::Itertools;
 #[cfg(test)] use ::scanner::ScanFromStr;
 
@@ -18,6 +18,8 @@ parse_scanner! { impl<'a> for Ipv4Addr, matcher match_ipv4, matcher err "expecte
 parse_scanner! { impl<'a> for Ipv6Addr, matcher match_ipv6, matcher err "expected IPv6 address", err map ScanError::other }
 parse_scanner! { impl<'a> for SocketAddr, matcher match_sock_addr, matcher err "expected socket address", err map ScanError::other }
 
+unit_enum_scanner! { impl<'a> for Shutdown { Read, Write, Both }, err desc "expected shutdown mode" }
+
 fn match_ipv4(s: &str) -> Option<((usize, usize), usize)> {
     let ibs = &mut s.bytes().enumerate();
     try_opt!(eat_dec_digs(ibs));
@@ -212,6 +214,18 @@ fn eat_dec_digs<I: Clone + Iterator<Item=(usize, u8)>>(ibs: &mut I) -> Option<((
         .map(|n| ((0, n), n))
 }
 
+#[cfg(test)]
+#[test]
+fn test_scan_shutdown() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+
+    assert_match!(<Shutdown>::scan_from(""), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(<Shutdown>::scan_from("Readable"), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert_match!(<Shutdown>::scan_from("Read"), Ok((Shutdown::Read, 4)));
+    assert_match!(<Shutdown>::scan_from("Both x"), Ok((Shutdown::Both, 4)));
+}
+
 #[cfg(test)]
 #[test]
 fn test_scan_ipv4addr() {
@@ -10,10 +10,10 @@ or distributed except according to those terms.
 /*!
 Scanner implementations for `std::time` types.
 */
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use strcursor::StrCursor;
 use ::ScanError;
-use ::input::ScanInput;
+use ::input::{ScanCursor, ScanInput};
 use ::scanner::ScanFromStr;
 use ::util::MsgErr;
 
@@ -58,6 +58,115 @@ impl<'a> ScanFromStr<'a> for Iso8601Duration {
     }
 }
 
+/**
+Scans a `SystemTime` from its `Debug` output.
+
+`SystemTime` has no stable `Debug` representation; it just dumps the platform's underlying value.  The shapes produced by current and past standard libraries are accepted:
+
+* Unix: `SystemTime { tv_sec: 1, tv_nsec: 2 }`.
+* Older Unix: `SystemTime { t: Timespec { tv_sec: 1, tv_nsec: 2 } }`.
+* Windows: `SystemTime { intervals: 3 }`, in units of 100 ns since 1601-01-01.
+
+`Instant` is deliberately *not* scannable: it is a reading of a monotonic clock with an arbitrary, per-process origin, so a scanned value would be meaningless.  Attempting to scan one fails to compile, as `Instant` does not implement `ScanFromStr`.
+
+The range of `SystemTime` is platform-specific; a time which the platform cannot represent is an `Overflow` error.
+*/
+impl<'a> ScanFromStr<'a> for SystemTime {
+    type Output = Self;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        scan!( s.to_cursor();
+            ("SystemTime", "{", "tv_sec", ":", let secs: i64, ",", "tv_nsec", ":", let nanos: u32, "}", ^..tail)
+                => (try!(unix_system_time(secs, nanos)), tail.offset()),
+            ("SystemTime", "{", "t", ":", "Timespec", "{", "tv_sec", ":", let secs: i64, ",", "tv_nsec", ":", let nanos: u32, "}", "}", ^..tail)
+                => (try!(unix_system_time(secs, nanos)), tail.offset()),
+            ("SystemTime", "{", "intervals", ":", let intervals: i64, "}", ^..tail)
+                => (try!(windows_system_time(intervals)), tail.offset()),
+        )
+    }
+}
+
+fn unix_system_time(secs: i64, nanos: u32) -> Result<SystemTime, ScanError> {
+    if nanos >= NANOS_IN_SEC {
+        return Err(ScanError::syntax("`tv_nsec` must be less than one second"));
+    }
+
+    let t = if secs >= 0 {
+        UNIX_EPOCH.checked_add(Duration::new(secs as u64, nanos))
+    } else {
+        /*
+        `tv_nsec` always counts *forward* from `tv_sec`, even when `tv_sec` is negative.  `-secs` would overflow on `i64::MIN`, so the magnitude is taken with a wrapping negation of the `u64` bit pattern instead.
+        */
+        let neg_secs = (secs as u64).wrapping_neg();
+        UNIX_EPOCH.checked_sub(Duration::new(neg_secs, 0))
+            .and_then(|t| t.checked_add(Duration::new(0, nanos)))
+    };
+    t.ok_or_else(|| ScanError::overflow("SystemTime"))
+}
+
+fn windows_system_time(intervals: i64) -> Result<SystemTime, ScanError> {
+    const INTERVALS_IN_SEC: i64 = 10_000_000;
+    const SECS_1601_TO_1970: i64 = 11_644_473_600;
+
+    let secs = intervals / INTERVALS_IN_SEC - SECS_1601_TO_1970;
+    let rem = intervals % INTERVALS_IN_SEC;
+    // Neither of these can overflow: `secs` is at most `i64::MAX / INTERVALS_IN_SEC` in magnitude, plus the epoch shift.
+    let (secs, rem) = if rem < 0 { (secs - 1, rem + INTERVALS_IN_SEC) } else { (secs, rem) };
+    unix_system_time(secs, (rem * 100) as u32)
+}
+
+#[cfg(test)]
+#[test]
+fn test_system_time() {
+    use ::ScanError as SE;
+    use ::ScanErrorKind as SEK;
+
+    let scan = <SystemTime>::scan_from;
+
+    assert_match!(
+        scan("SystemTime { tv_sec: 1460000000, tv_nsec: 250 }, x"),
+        Ok((t, 47)) if t == UNIX_EPOCH + Duration::new(1460000000, 250)
+    );
+    assert_match!(
+        scan("SystemTime { tv_sec: -2, tv_nsec: 500000000 }"),
+        Ok((t, 45)) if t == UNIX_EPOCH - Duration::new(1, 500_000_000)
+    );
+    assert_match!(
+        scan("SystemTime { t: Timespec { tv_sec: 7, tv_nsec: 0 } }"),
+        Ok((t, 52)) if t == UNIX_EPOCH + Duration::new(7, 0)
+    );
+    assert_match!(
+        scan("SystemTime { intervals: 116444736000000015 }"),
+        Ok((t, 44)) if t == UNIX_EPOCH + Duration::new(0, 1500)
+    );
+    assert_match!(
+        scan("SystemTime { intervals: 116444735999999999 }"),
+        Ok((t, 44)) if t == UNIX_EPOCH - Duration::new(0, 100)
+    );
+
+    assert_match!(
+        scan("SystemTime { tv_sec: 1, tv_nsec: 1000000000 }"),
+        Err(SE { kind: SEK::Syntax(_), .. })
+    );
+    assert_match!(scan("SystemTime { tv_sec: 1 }"), Err(_));
+    assert_match!(scan("Instant { tv_sec: 1, tv_nsec: 2 }"), Err(_));
+
+    // Extreme values are either representable or an overflow, never a panic.
+    for inp in &[
+        "SystemTime { tv_sec: 9223372036854775807, tv_nsec: 999999999 }",
+        "SystemTime { tv_sec: -9223372036854775808, tv_nsec: 1 }",
+        "SystemTime { intervals: 9223372036854775807 }",
+        "SystemTime { intervals: -9223372036854775808 }",
+    ] {
+        match scan(inp) {
+            Ok(_) | Err(SE { kind: SEK::Overflow("SystemTime"), .. }) => (),
+            Err(err) => panic!("{:?}: {:?}", inp, err),
+        }
+    }
+
+    let now = SystemTime::now();
+    assert_match!(scan(&format!("{:?}", now)), Ok((t, _)) if t == now);
+}
+
 const SECS_IN_SEC: u64 = 1;
 const SECS_IN_MIN: u64 = 60;
 const SECS_IN_HOUR: u64 = 60 * SECS_IN_MIN;
@@ -0,0 +1,120 @@
+/*
+Copyright ⓒ 2016 Daniel Keep.
+
+Licensed under the MIT license (see LICENSE or <http://opensource.org
+/licenses/MIT>) or the Apache License, Version 2.0 (see LICENSE of
+<http://www.apache.org/licenses/LICENSE-2.0>), at your option. All
+files in the project carrying such notice may not be copied, modified,
+or distributed except according to those terms.
+*/
+/*!
+Helpers for scanning line-oriented tables, such as the files in `/proc` and `/sys`.
+*/
+use ::ScanError;
+
+/**
+Scans a line-oriented table, one record per line.
+
+Each line is scanned independently by a user-supplied function, which is typically a closure wrapping `scan!`.  If any line fails to scan, scanning stops, and the 1-based number of the failing line is returned along with the error.  Since the error's offset is relative to the start of that line, together they give the line and column of the failure.
+
+```ignore
+let meminfo = ProcTable::new(&text)
+    .skip_blank()
+    .scan(|line| scan!(line;
+        (let name: Ident, ":", let kb: u64, "kB") => (name, kb),
+        (let name: Ident, ":", let n: u64) => (name, n),
+    ));
+
+match meminfo {
+    Ok(rows) => println!("{} rows", rows.len()),
+    Err((line, err)) => println!("line {}, column {}: {}", line, err.at.offset() + 1, err),
+}
+```
+*/
+#[derive(Clone, Debug)]
+pub struct ProcTable<'a> {
+    input: &'a str,
+    header_lines: usize,
+    skip_blank: bool,
+}
+
+impl<'a> ProcTable<'a> {
+    /**
+    Construct a table over `input`, scanning every line.
+    */
+    pub fn new(input: &'a str) -> Self {
+        ProcTable {
+            input: input,
+            header_lines: 0,
+            skip_blank: false,
+        }
+    }
+
+    /**
+    Skip the first `lines` lines of input, such as a row of column headings.
+
+    Skipped lines still count towards the line numbers reported in errors.
+    */
+    pub fn skip_header(self, lines: usize) -> Self {
+        ProcTable { header_lines: lines, ..self }
+    }
+
+    /**
+    Skip lines which are empty, or contain only whitespace.
+    */
+    pub fn skip_blank(self) -> Self {
+        ProcTable { skip_blank: true, ..self }
+    }
+
+    /**
+    Scan each line using `f`, collecting the results.
+
+    On failure, returns the 1-based line number of the line which failed to scan, and the error.
+    */
+    pub fn scan<T, F>(&self, mut f: F) -> Result<Vec<T>, (usize, ScanError)>
+    where F: FnMut(&'a str) -> Result<T, ScanError> {
+        let mut rows = vec![];
+        for (i, line) in self.input.lines().enumerate() {
+            if i < self.header_lines
+                || (self.skip_blank && line.trim().is_empty())
+            {
+                continue;
+            }
+
+            match f(line) {
+                Ok(row) => rows.push(row),
+                Err(err) => return Err((i + 1, err)),
+            }
+        }
+        Ok(rows)
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_proc_table() {
+    use ::scanner::Ident;
+
+    let inp = "Inter-|   Receive\n face |bytes\n  eth0: 12\n\n    lo: 3\r\n";
+
+    let table = ProcTable::new(inp).skip_header(2).skip_blank();
+    let rows = table.scan(|line| scan!(line; (let name: Ident, ":", let n: u64) => (name, n)));
+    assert_eq!(rows.ok(), Some(vec![("eth0", 12), ("lo", 3)]));
+
+    let table = ProcTable::new(inp).skip_header(2);
+    let rows = table.scan(|line| scan!(line; (let name: Ident, ":", let n: u64) => (name, n)));
+    match rows {
+        Err((4, ref err)) => assert_eq!(err.at.offset(), 0),
+        other => panic!("unexpected: {:?}", other),
+    }
+
+    let table = ProcTable::new(inp).skip_blank();
+    let rows = table.scan(|line| scan!(line; (let name: Ident, ":", let n: u64) => (name, n)));
+    match rows {
+        Err((1, ref err)) => assert_eq!(err.at.offset(), 5),
+        other => panic!("unexpected: {:?}", other),
+    }
+
+    let rows = ProcTable::new("").scan(|line| scan!(line; (let n: u64) => n));
+    assert_eq!(rows.ok(), Some(vec![]));
+}
@@ -8,7 +8,20 @@ files in the project carrying such notice may not be copied, modified,
 or distributed except according to those terms.
 */
 /*!
-Internal utilities.
+Utilities for implementing scanners.
+
+The `StrUtil` trait and `EscapeError` type are a stable part of the public API, and are intended for use by crates defining their own scanners.
+
+## Examples
+
+```rust
+use scan_rules::util::StrUtil;
+
+let s = "\\u{2764} and more";
+let (cp, tail) = s[1..].split_escape_default().unwrap();
+assert_eq!(cp, '❤');
+assert_eq!(s.subslice_offset_stable(tail), Some(8));
+```
 */
 use std::error::Error;
 use std::fmt::{self, Display};
@@ -19,6 +32,7 @@ String error message.
 
 This exists because `Error` is not implemented for `&str` in Rust < 1.6.
 */
+#[doc(hidden)]
 #[derive(Copy, Clone)]
 pub struct MsgErr(pub &'static str);
 
@@ -45,7 +59,9 @@ Various string utility methods.
 */
 pub trait StrUtil {
     /**
-    Returns the byte offset of an inner slice relative to an enclosing outer slice.
+    Returns the byte offset of an inner slice relative to an enclosing outer slice, or `None` if `inner` does not lie within `self`.
+
+    This is typically used to turn the unconsumed tail of a scan back into the number of bytes consumed.
 
     Named `*_stable` to avoid conflicting with the deprecated method in < 1.4.0.
     */
@@ -53,6 +69,8 @@ pub trait StrUtil {
 
     /**
     Extracts an escape sequence (sans leading backslash) from the start of this string, returning the unescaped code point, and the unconsumed input.
+
+    This supports the same escapes as Rust character and string literals, and is what `QuotedString` uses.
     */
     fn split_escape_default(&self) -> Result<(char, &Self), EscapeError>;
 }
@@ -108,6 +126,7 @@ impl StrUtil for str {
 /**
 Extension trait for Unicode tables.
 */
+#[doc(hidden)]
 pub trait TableUtil<T: Ord> {
     /**
     Determines whether or not the given character is in the table.
@@ -0,0 +1,38 @@
+/*
+Copyright ⓒ 2016 Daniel Keep.
+
+Licensed under the MIT license (see LICENSE or <http://opensource.org
+/licenses/MIT>) or the Apache License, Version 2.0 (see LICENSE of
+<http://www.apache.org/licenses/LICENSE-2.0>), at your option. All
+files in the project carrying such notice may not be copied, modified,
+or distributed except according to those terms.
+*/
+#[macro_use] extern crate scan_rules;
+#[macro_use] mod util;
+
+use scan_rules::ScanError as SE;
+use scan_rules::ScanErrorKind as SEK;
+use scan_rules::scanner::{Ident, Word};
+
+#[test]
+fn test_backreference() {
+    let inp = "<b> bold </b>";
+    assert_match!(
+        scan!(inp; ("<", let tag: Ident, ">", let body: Word, "</", =tag, ">") => (tag, body)),
+        Ok(("b", "bold")));
+
+    let inp = "<b> bold </i>";
+    assert_match!(
+        scan!(inp; ("<", let tag: Ident, ">", let body: Word, "</", =tag, ">") => (tag, body)),
+        Err(SE { ref at, kind: SEK::LiteralMismatch, .. }) if at.offset() == 11);
+
+    assert_match!(scan!("3 x 3"; (let n: i32, "x", =n) => n), Ok(3));
+    assert_match!(scan!("3 x 4"; (let n: i32, "x", =n) => n), Err(SE { kind: SEK::LiteralMismatch, .. }));
+
+    assert_match!(
+        scan!("a=a, b=b"; ([ let k: Word, "=", =k ],+) => k),
+        Ok(ref ks) if *ks == ["a", "b"]);
+    assert_match!(
+        scan!("a=a, b=c"; ([ let k: Word, "=", =k ],+) => k),
+        Err(SE { kind: SEK::LiteralMismatch, .. }));
+}
@@ -0,0 +1,45 @@
+/*
+Copyright ⓒ 2016 Daniel Keep.
+
+Licensed under the MIT license (see LICENSE or <http://opensource.org
+/licenses/MIT>) or the Apache License, Version 2.0 (see LICENSE of
+<http://www.apache.org/licenses/LICENSE-2.0>), at your option. All
+files in the project carrying such notice may not be copied, modified,
+or distributed except according to those terms.
+*/
+#[macro_use] extern crate scan_rules;
+#[macro_use] mod util;
+
+use scan_rules::scanner::Word;
+
+#[test]
+fn test_consumed() {
+    assert_match!(
+        scan!("hello world"; (let n0 = #consumed, let _: Word, let n1 = #consumed, let _: Word, let n2 = #consumed) => (n0, n1, n2)),
+        Ok((0, 5, 11))
+    );
+
+    assert_match!(
+        scan!("x 1 2 3";
+            ("y", let n = #consumed, ..rest) => (n, rest),
+            ("x", [let _: i32, let n = #consumed]+) => (0, "")
+        ),
+        Ok((0, ""))
+    );
+
+    assert_match!(
+        scan!("1 22 333"; ([let _: i32, let ends = #consumed]+) => ends),
+        Ok(ref ends) if *ends == vec![1, 4, 8]
+    );
+
+    // Counts from the start of the rule, not the start of the input.
+    let (_, cur) = scan_partial!("skip this, then count"; ("skip", "this", ",") => ()).unwrap();
+    assert_match!(
+        scan!(cur; ("then", let n = #consumed, let _: Word) => n),
+        Ok(5)
+    );
+
+    let_scan!("abc def"; (let first: Word, let len = #consumed, let _: Word));
+    assert_eq!(first, "abc");
+    assert_eq!(len, 3);
+}
@@ -0,0 +1,78 @@
+/*
+Copyright ⓒ 2016 Daniel Keep.
+
+Licensed under the MIT license (see LICENSE or <http://opensource.org
+/licenses/MIT>) or the Apache License, Version 2.0 (see LICENSE of
+<http://www.apache.org/licenses/LICENSE-2.0>), at your option. All
+files in the project carrying such notice may not be copied, modified,
+or distributed except according to those terms.
+*/
+#[macro_use] extern crate scan_rules;
+#[macro_use] mod util;
+
+use scan_rules::ScanError as SE;
+use scan_rules::ScanErrorKind as SEK;
+
+#[derive(Debug, PartialEq)]
+struct Point { x: i32, y: i32 }
+
+#[derive(Debug, PartialEq)]
+struct Server { name: String, port: u16, tls: Option<bool> }
+
+#[derive(Debug, PartialEq)]
+struct Wrapper { point: Point }
+
+scanner_for_debug_struct! {
+    Point { x: i32, y: i32 }
+    any order Server { name: String, port: u16, tls: Option<bool>, }
+    Wrapper { point: Point }
+}
+
+#[test]
+fn test_debug_struct() {
+    let p = Point { x: 1, y: -2 };
+    let inp = format!("{:?}", p);
+    assert_match!(scan!(&inp; (let p: Point) => p), Ok(Point { x: 1, y: -2 }));
+    let inp = format!("{:#?}", p);
+    assert_match!(scan!(&inp; (let p: Point) => p), Ok(Point { x: 1, y: -2 }));
+    assert_match!(scan!("Point { x: 1, y: 2, }"; (let p: Point) => p), Ok(Point { x: 1, y: 2 }));
+    assert_match!(scan!("Point { y: 2, x: 1 }"; (let p: Point) => p), Err(SE { kind: SEK::LiteralMismatch, .. }));
+    assert_match!(scan!("Point { x: 1 }"; (let p: Point) => p), Err(SE { kind: SEK::LiteralMismatch, .. }));
+    assert_match!(scan!("Point { x: 1 y: 2 }"; (let p: Point) => p), Err(SE { kind: SEK::LiteralMismatch, .. }));
+    assert_match!(scan!("Pt { x: 1, y: 2 }"; (let p: Point) => p), Err(SE { kind: SEK::LiteralMismatch, .. }));
+
+    let w = Wrapper { point: Point { x: 3, y: 4 } };
+    let inp = format!("{:?}", w);
+    assert_match!(scan!(&inp; (let w: Wrapper) => w), Ok(Wrapper { point: Point { x: 3, y: 4 } }));
+
+    assert_match!(
+        scan!("[Point { x: 1, y: 2 }, Point { x: 3, y: 4 }]"; (let ps: Vec<Point>) => ps),
+        Ok(ref ps) if *ps == [Point { x: 1, y: 2 }, Point { x: 3, y: 4 }]
+    );
+}
+
+#[test]
+fn test_debug_struct_any_order() {
+    let s = Server { name: "web".into(), port: 80, tls: Some(true) };
+    let inp = format!("{:?}", s);
+    assert_match!(scan!(&inp; (let s: Server) => s), Ok(ref v) if *v == s);
+    let s = Server { name: "db".into(), port: 5432, tls: None };
+    let inp = format!("{:#?}", s);
+    assert_match!(scan!(&inp; (let s: Server) => s), Ok(ref v) if *v == s);
+
+    assert_match!(
+        scan!(r#"Server { tls: None, port: 8080, name: "api", }"#; (let s: Server) => s),
+        Ok(Server { ref name, port: 8080, tls: None }) if name == "api"
+    );
+    assert_match!(
+        scan!(r#"Server { port: 1, name: "a" }"#; (let s: Server) => s),
+        Err(SE { ref at, kind: SEK::Syntax(_), .. }) if at.offset() == 29
+    );
+    assert_match!(
+        scan!(r#"Server { port: 1, port: 2, name: "a", tls: None }"#; (let s: Server) => s),
+        Err(SE { ref at, kind: SEK::Syntax(_), .. }) if at.offset() == 17
+    );
+    assert_match!(scan!("Server {}"; (let s: Server) => s), Err(_));
+    assert_match!(scan!(r#"Server { port: 1 name: "a", tls: None }"#; (let s: Server) => s), Err(SE { kind: SEK::LiteralMismatch, .. }));
+    assert_match!(scan!(r#"Server { port: 1, , name: "a", tls: None }"#; (let s: Server) => s), Err(_));
+}
@@ -0,0 +1,60 @@
+/*
+Copyright ⓒ 2016 Daniel Keep.
+
+Licensed under the MIT license (see LICENSE or <http://opensource.org
+/licenses/MIT>) or the Apache License, Version 2.0 (see LICENSE of
+<http://www.apache.org/licenses/LICENSE-2.0>), at your option. All
+files in the project carrying such notice may not be copied, modified,
+or distributed except according to those terms.
+*/
+#[macro_use] extern crate scan_rules;
+#[macro_use] mod util;
+
+use scan_rules::ScanError as SE;
+use scan_rules::ScanErrorKind as SEK;
+use scan_rules::scanner::Word;
+
+define_scan_rule! {
+    Date -> (u16, u8, u8) {
+        (let y: u16, "-", let m: u8, "-", let d: u8) => (y, m, d),
+    }
+
+    Time -> (u8, u8) {
+        (let h: u8, ":", let m: u8) => (h, m),
+        (let h: u8, "h") => (h, 0),
+    }
+
+    Assign<'a> -> (&'a str, i32) {
+        (let k: Word, "=", let v: i32) => (k, v),
+    }
+}
+
+#[test]
+fn test_define_scan_rule() {
+    assert_match!(
+        scan!("2016-01-01 to 2016-12-31"; (let a: Date, "to", let b: Date) => (a, b)),
+        Ok(((2016, 1, 1), (2016, 12, 31)))
+    );
+    assert_match!(
+        scan!("2016-01-01 to 2016-12-31"; (@Date, "to", @Date) => ()),
+        Ok(())
+    );
+    assert_match!(
+        scan!("2016-01-01 to 2016-12"; (@Date, "to", @Date) => ()),
+        Err(SE { kind: SEK::LiteralMismatch, .. })
+    );
+
+    assert_match!(scan!("at 9h"; ("at", let t: Time) => t), Ok((9, 0)));
+    assert_match!(scan!("at 13:45"; ("at", let t: Time) => t), Ok((13, 45)));
+
+    assert_match!(
+        scan!("x = 1, y = -2"; ([let kv: Assign],+) => kv),
+        Ok(ref kvs) if *kvs == vec![("x", 1), ("y", -2)]
+    );
+}
+
+#[test]
+fn test_define_scan_rule_let_scan() {
+    let_scan!("2016-02-29, 23:59"; (let d: Date, ",", @Time));
+    assert_eq!(d, (2016, 2, 29));
+}
@@ -0,0 +1,58 @@
+/*
+Copyright ⓒ 2016 Daniel Keep.
+
+Licensed under the MIT license (see LICENSE or <http://opensource.org
+/licenses/MIT>) or the Apache License, Version 2.0 (see LICENSE of
+<http://www.apache.org/licenses/LICENSE-2.0>), at your option. All
+files in the project carrying such notice may not be copied, modified,
+or distributed except according to those terms.
+*/
+#[macro_use] extern crate scan_rules;
+#[macro_use] mod util;
+
+use scan_rules::ScanError as SE;
+use scan_rules::ScanErrorKind as SEK;
+use scan_rules::input::{ScanCursor, ScanInput};
+
+/// A hand-written parser for a run of `#` characters.
+fn parse_hashes(s: &str) -> Result<(usize, usize), SE> {
+    match s.bytes().take_while(|&b| b == b'#').count() {
+        0 => Err(SE::syntax("expected `#`")),
+        n => Ok((n, n)),
+    }
+}
+
+#[test]
+fn test_do_term() {
+    assert_match!(
+        scan!("### Title"; (
+            let level = do |cur| cur.try_scan(|s| parse_hashes(s.as_str())).map_err(|(e, _)| e),
+            ..title
+        ) => (level, title)),
+        Ok((3, " Title"))
+    );
+    assert_match!(
+        scan!("Title"; (
+            let level = do |cur| cur.try_scan(|s| parse_hashes(s.as_str())).map_err(|(e, _)| e),
+            ..title
+        ) => (level, title)),
+        Err(SE { kind: SEK::Syntax(_), .. })
+    );
+
+    // Skip exactly one character, whatever it is.
+    assert_match!(
+        scan!("1x2"; (
+            let a: i32,
+            do |cur| cur.try_scan_raw(|s| Ok(((), s.as_str().chars().next().map_or(0, |c| c.len_utf8())))).map(|(_, c)| c).map_err(|(e, _)| e),
+            let b: i32
+        ) => (a, b)),
+        Ok((1, 2))
+    );
+
+    assert_match!(
+        scan!("#, ##, ###"; (
+            [let ls = do |cur| cur.try_scan(|s| parse_hashes(s.as_str())).map_err(|(e, _)| e)],+
+        ) => ls),
+        Ok(ref ls) if *ls == [1, 2, 3]
+    );
+}
@@ -0,0 +1,25 @@
+/*
+Copyright ⓒ 2016 Daniel Keep.
+
+Licensed under the MIT license (see LICENSE or <http://opensource.org
+/licenses/MIT>) or the Apache License, Version 2.0 (see LICENSE of
+<http://www.apache.org/licenses/LICENSE-2.0>), at your option. All
+files in the project carrying such notice may not be copied, modified,
+or distributed except according to those terms.
+*/
+#[macro_use] extern crate scan_rules;
+#[macro_use] mod util;
+
+use scan_rules::scanner::{Fallible, Word};
+
+#[test]
+fn test_fallible() {
+    let inp = "alice 32 oops bob";
+    assert_match!(
+        scan!(inp;
+            (let a: Word, let b: Fallible<u32>, let c: Fallible<u32>, let d: Word, ..tail)
+            => (a, b, c, d, tail)
+        ),
+        Ok(("alice", Ok(32), Err(_), "oops", " bob"))
+    );
+}
@@ -0,0 +1,67 @@
+/*
+Copyright ⓒ 2016 Daniel Keep.
+
+Licensed under the MIT license (see LICENSE or <http://opensource.org
+/licenses/MIT>) or the Apache License, Version 2.0 (see LICENSE of
+<http://www.apache.org/licenses/LICENSE-2.0>), at your option. All
+files in the project carrying such notice may not be copied, modified,
+or distributed except according to those terms.
+*/
+#[macro_use] extern crate scan_rules;
+#[macro_use] mod util;
+
+use scan_rules::FromScan;
+use scan_rules::ScanError as SE;
+use scan_rules::ScanErrorKind as SEK;
+
+#[derive(Debug, PartialEq)]
+struct Vector { x: f64, y: f64, z: f64 }
+
+impl FromScan<(f64, f64, f64)> for Vector {
+    fn from_scan((x, y, z): (f64, f64, f64)) -> Self {
+        Vector { x: x, y: y, z: z }
+    }
+}
+
+impl FromScan<(f64,)> for Vector {
+    fn from_scan((s,): (f64,)) -> Self {
+        Vector { x: s, y: s, z: s }
+    }
+}
+
+impl FromScan<()> for Vector {
+    fn from_scan(_: ()) -> Self {
+        Vector { x: 0.0, y: 0.0, z: 0.0 }
+    }
+}
+
+#[test]
+fn test_from_scan() {
+    let parse = |s: &str| scan!(s;
+        ("<", let x: f64, ",", let y: f64, ",", let z: f64, ">") => @into Vector,
+        ("splat", let s: f64) => @into Vector,
+        ("zero") => @into Vector,
+    );
+
+    assert_match!(parse("<1, 2, 3>"), Ok(Vector { x: 1.0, y: 2.0, z: 3.0 }));
+    assert_match!(parse("splat 4"), Ok(Vector { x: 4.0, y: 4.0, z: 4.0 }));
+    assert_match!(parse("zero"), Ok(Vector { x: 0.0, y: 0.0, z: 0.0 }));
+    assert_match!(parse("one"), Err(SE { kind: SEK::LiteralMismatch, .. }));
+
+    type Pair = (char, u8);
+    let pair = scan!("a 1"; (let k: char, let v: u8) => @into Pair);
+    assert_match!(pair, Ok(('a', 1)));
+
+    let mixed = scan!("2";
+        ("splat", let s: f64) => @into Vector,
+        (let n: f64) => Vector { x: n, y: 0.0, z: 0.0 }
+    );
+    assert_match!(mixed, Ok(Vector { x: 2.0, y: 0.0, z: 0.0 }));
+
+    // A plain `into` is just an expression.
+    fn into(v: (f64, f64)) -> Vector {
+        Vector { x: v.0, y: v.1, z: 0.0 }
+    }
+    let call = scan!("5 6"; (let x: f64, let y: f64) => into((x, y)));
+    assert_match!(call, Ok(Vector { x: 5.0, y: 6.0, z: 0.0 }));
+}
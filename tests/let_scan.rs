@@ -17,3 +17,37 @@ fn test_let_scan() {
     assert_eq!(cost, 10);
     assert_eq!(product, "うまい棒");
 }
+
+#[test]
+fn test_let_scan_borrowed_from_owned() {
+    let input = String::from("10¥, うまい棒");
+    let_scan!(input; (let cost: u32, "¥,", let product: Word));
+    assert_eq!(cost, 10);
+    assert_eq!(product, "うまい棒");
+    assert!(input.ends_with(product));
+}
+
+#[test]
+fn test_let_scan_alternatives() {
+    let scan_item = |input: &'static str| {
+        let_scan!(input;
+            (let cost: u32, "¥,", let product: Word),
+            ("cost=", let cost: u32, "product=", let product: Word),
+        );
+        (cost, product)
+    };
+    assert_eq!(scan_item("10¥, うまい棒"), (10, "うまい棒"));
+    assert_eq!(scan_item("cost=25 product=ガリガリ君"), (25, "ガリガリ君"));
+
+    let input = "3 x 4";
+    let_scan!(input; (let w: u32, "x", let h: u32), (let w: u32, "by", let h: u32));
+    assert_eq!((w, h), (3, 4));
+}
+
+#[test]
+#[should_panic]
+fn test_let_scan_alternatives_no_match() {
+    let input = "3 by four";
+    let_scan!(input; (let w: u32, "x", let h: u32), (let w: u32, "by", let h: u32));
+    let _ = (w, h);
+}
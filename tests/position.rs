@@ -0,0 +1,72 @@
+/*
+Copyright ⓒ 2016 Daniel Keep.
+
+Licensed under the MIT license (see LICENSE or <http://opensource.org
+/licenses/MIT>) or the Apache License, Version 2.0 (see LICENSE of
+<http://www.apache.org/licenses/LICENSE-2.0>), at your option. All
+files in the project carrying such notice may not be copied, modified,
+or distributed except according to those terms.
+*/
+#[macro_use] extern crate scan_rules;
+#[macro_use] mod util;
+
+use scan_rules::ScanError as SE;
+use scan_rules::ScanErrorKind as SEK;
+use scan_rules::input::{LineTrackingCursor, Raw};
+use scan_rules::scanner::{Line, Word};
+
+#[test]
+fn test_offset() {
+    assert_match!(scan!("abc   def"; (let a: Word, @offset(6), let b: Word) => (a, b)), Ok(("abc", "def")));
+    assert_match!(
+        scan!("abc  def"; (let a: Word, @offset(6), let b: Word) => (a, b)),
+        Err(SE { ref at, kind: SEK::Syntax(_), .. }) if at.offset() == 5);
+    assert_match!(scan!("abc"; (@offset(0), let a: Word, @offset(3)) => a), Ok("abc"));
+
+    // With raw input, whitespace is not skipped.
+    assert_match!(scan!(Raw::new("ab cd"); (let a: Word, @offset(2), " ", let b: Word) => (a, b)), Ok(("ab", "cd")));
+    assert_match!(scan!(Raw::new("ab cd"); (let a: Word, @offset(3), let b: Word) => (a, b)), Err(_));
+
+    // Alternatives are tried as usual.
+    let scan_field = |s: &'static str| scan!(s;
+        (let k: Word, @offset(8), let v: Word) => (k, Some(v)),
+        (let k: Word) => (k, None),
+    );
+    assert_match!(scan_field("name    value"), Ok(("name", Some("value"))));
+    assert_match!(scan_field("name"), Ok(("name", None)));
+}
+
+#[test]
+fn test_column() {
+    let inp = "01 alpha\n02   beta\n03    gamma";
+
+    let cur = LineTrackingCursor::new("02   beta");
+    assert_match!(
+        scan!(cur; (let n: u32, @column(4), let name: Word) => (n, name)),
+        Err(SE { ref at, kind: SEK::Syntax(_), .. }) if at.offset() == 5);
+
+    let cur = LineTrackingCursor::new(inp);
+    assert_match!(
+        scan!(cur; ([let n: u32, @column(4), let name: Word]+, ..rest) => (n, name, rest)),
+        Ok((ref ns, ref names, "\n02   beta\n03    gamma")) if *ns == [1] && *names == ["alpha"]);
+
+    let inp = "01 alpha\n02 beta";
+    let cur = LineTrackingCursor::new(inp);
+    assert_match!(
+        scan!(cur; ([@column(1), let n: u32, @column(4), let name: Line]+) => (n, name)),
+        Ok((ref ns, ref names)) if *ns == [1, 2] && *names == ["alpha", "beta"]);
+}
+
+#[test]
+fn test_column_untracked() {
+    use scan_rules::ScanError as SE;
+    use scan_rules::ScanErrorKind as SEK;
+
+    assert_match!(
+        scan!("a b";
+            (let _: Word, @column(3), let _: Word) => 0,
+            (let _: Word, let _: Word) => 1,
+        ),
+        Err(SE { kind: SEK::Syntax(_), ref at, .. }) if at.offset() == 2
+    );
+}
@@ -0,0 +1,31 @@
+/*
+Copyright ⓒ 2016 Daniel Keep.
+
+Licensed under the MIT license (see LICENSE or <http://opensource.org
+/licenses/MIT>) or the Apache License, Version 2.0 (see LICENSE of
+<http://www.apache.org/licenses/LICENSE-2.0>), at your option. All
+files in the project carrying such notice may not be copied, modified,
+or distributed except according to those terms.
+*/
+#[macro_use] extern crate scan_rules;
+#[macro_use] mod util;
+
+use scan_rules::ScanError as SE;
+use scan_rules::ScanErrorKind as SEK;
+
+#[test]
+fn test_prefix() {
+    assert_match!(scan!("GET /index.html HTTP/1.1"; ("GET", ...) => ()), Ok(()));
+    assert_match!(scan!("GET"; ("GET", ...) => ()), Ok(()));
+    assert_match!(scan!("POST /"; ("GET", ...) => ()),
+        Err(SE { kind: SEK::LiteralMismatch, .. }));
+    assert_match!(scan!("404 Not Found"; (let code: u16, ...) => code), Ok(404));
+    assert_match!(scan!("anything"; (...) => ()), Ok(()));
+}
+
+#[test]
+fn test_prefix_let_scan() {
+    let input = "42 and more";
+    let_scan!(input; (let n: u32, ...));
+    assert_eq!(n, 42);
+}
@@ -0,0 +1,38 @@
+/*
+Copyright ⓒ 2016 Daniel Keep.
+
+Licensed under the MIT license (see LICENSE or <http://opensource.org
+/licenses/MIT>) or the Apache License, Version 2.0 (see LICENSE of
+<http://www.apache.org/licenses/LICENSE-2.0>), at your option. All
+files in the project carrying such notice may not be copied, modified,
+or distributed except according to those terms.
+*/
+#[macro_use] extern crate scan_rules;
+#[macro_use] mod util;
+
+use scan_rules::ScanError as SE;
+use scan_rules::ScanErrorKind as SEK;
+use scan_rules::input::{IgnoreCase, StrCursor};
+use scan_rules::scanner::Word;
+
+#[test]
+fn test_raw_literal() {
+    assert_match!(scan!("a  b"; (let a: Word, raw "  ", let b: Word) => (a, b)), Ok(("a", "b")));
+    assert_match!(scan!("a b"; (let a: Word, raw "  ", let b: Word) => (a, b)),
+        Err(SE { ref at, kind: SEK::LiteralMismatch, .. }) if at.offset() == 1);
+    assert_match!(scan!("a   b"; (let a: Word, raw "  ", let b: Word) => (a, b)), Ok(("a", "b")));
+    assert_match!(scan!("a\tb"; (let a: Word, raw "\t", let b: Word) => (a, b)), Ok(("a", "b")));
+    assert_match!(scan!("x := 1"; (let _: Word, raw " := ", let v: i32) => v), Ok(1));
+    assert_match!(scan!("x : = 1"; (let _: Word, raw " := ", let v: i32) => v), Err(SE { kind: SEK::LiteralMismatch, .. }));
+
+    let sep = "--";
+    assert_match!(scan!("1--2"; (let a: i32, raw sep, let b: i32) => (a, b)), Ok((1, 2)));
+
+    let cur = StrCursor::<IgnoreCase>::new("END end");
+    assert_match!(scan!(cur; ("end", "END") => ()), Ok(()));
+    assert_match!(scan!(cur; (raw "end", ...) => ()), Err(SE { kind: SEK::LiteralMismatch, .. }));
+    assert_match!(scan!(cur; (raw "END", raw " ", "END") => ()), Ok(()));
+
+    assert_match!(scan!("(1 2)(3)"; ([ raw "(", [ let ns: i32 ]+, raw ")" ]+) => ns),
+        Ok(ref ns) if *ns == [vec![1, 2], vec![3]]);
+}
@@ -31,6 +31,21 @@ fn test_repeating() {
         Err(SE { ref at, kind: SEK::Syntax(_), .. }) if at.offset() == 1
     );
 
+    assert_match!(
+        scan!("[300]"; ("[", [ let ns: u8 ]+, "]") => ns),
+        Err(SE { ref at, kind: SEK::Overflow("u8"), .. }) if at.offset() == 1
+    );
+
+    assert_match!(
+        scan!("x"; ([ let ns: i32 ]+) => ns),
+        Err(SE { ref at, kind: SEK::Syntax("expected integer"), .. }) if at.offset() == 0
+    );
+
+    assert_match!(
+        scan!(""; ([ let ns: i32 ]+) => ns),
+        Err(SE { ref at, kind: SEK::Missing, .. }) if at.offset() == 0
+    );
+
     assert_match!(
         scan!("[0]"; ("[", [ let ns: i32 ]?, "]") => ns),
         Ok(ref ns) if *ns == vec![0]
@@ -88,7 +103,12 @@ fn test_repeating() {
 
     assert_match!(
         scan!("0"; ([ let ns: i32 ]{2}, ..tail) => (ns, tail)),
-        Err(SE { ref at, kind: SEK::Syntax(_), .. }) if at.offset() == 1
+        Err(SE { ref at, kind: SEK::Missing, .. }) if at.offset() == 1
+    );
+
+    assert_match!(
+        scan!("1, 2"; ([ let ns: i32 ],{3}) => ns),
+        Err(SE { ref at, kind: SEK::Missing, .. }) if at.offset() == 4
     );
 
     assert_match!(
@@ -103,7 +123,7 @@ fn test_repeating() {
 
     assert_match!(
         scan!("0"; ([ let ns: i32 ]{2,}, ..tail) => (ns, tail)),
-        Err(SE { ref at, kind: SEK::Syntax(_), .. }) if at.offset() == 1
+        Err(SE { ref at, kind: SEK::Missing, .. }) if at.offset() == 1
     );
 
     assert_match!(
@@ -118,7 +138,7 @@ fn test_repeating() {
 
     assert_match!(
         scan!("0"; ([ let ns: i32 ]{2, 3}, ..tail) => (ns, tail)),
-        Err(SE { ref at, kind: SEK::Syntax(_), .. }) if at.offset() == 1
+        Err(SE { ref at, kind: SEK::Missing, .. }) if at.offset() == 1
     );
 
     assert_match!(
@@ -226,3 +246,149 @@ fn test_repeating() {
         Ok((ref ns, ref sep, "")) if *ns == vec![0, 1, 2, 3] && *sep == vec!["and", "and", "and"]
     );
 }
+
+#[test]
+fn test_repeating_binding() {
+    use std::collections::HashMap;
+
+    assert_match!(
+        scan!("a=1, b=2, c=3";
+            ([ let k: Word<String>, "=", let v: i32 ](",")* binding kvs = (k, v): HashMap<_, _>) => kvs),
+        Ok(ref kvs) if kvs.len() == 3 && kvs["a"] == 1 && kvs["b"] == 2 && kvs["c"] == 3
+    );
+
+    assert_match!(
+        scan!("a=1 b=2"; ([ let k: Word, "=", let v: i32 ]+ binding kvs = (v, k)) => kvs),
+        Ok(ref kvs) if *kvs == vec![(1, "a"), (2, "b")]
+    );
+
+    assert_match!(
+        scan!("1 and 2 or 3"; ([ let n: i32 ]( let sep: Word ){2,} binding ns = n, ..tail) => (ns, sep, tail)),
+        Ok((ref ns, ref sep, "")) if *ns == vec![1, 2, 3] && *sep == vec!["and", "or"]
+    );
+
+    assert_match!(
+        scan!("[x=1]"; ("[", [ let k: Word, "=", let v: i32 ],+ binding kvs = (k, v): HashMap<_, _>, "]") => kvs),
+        Ok(ref kvs) if kvs.len() == 1 && kvs["x"] == 1
+    );
+
+    assert_match!(
+        scan!("a=1, b"; ([ let k: Word, "=", let v: i32 ],* binding kvs = (k, v), ..tail) => (kvs, tail)),
+        Err(SE { ref at, kind: SEK::LiteralMismatch, .. }) if at.offset() == 6
+    );
+}
+
+#[test]
+fn test_repeating_reserve() {
+    use std::collections::BTreeSet;
+
+    assert_match!(
+        scan!("1, 2, 3, 4, 5, 6, 7, 8, 9"; ([ let ns: i32 ],+) => ns),
+        Ok(ref ns) if ns.len() == 9 && ns.capacity() < 16
+    );
+
+    assert_match!(
+        scan!("1, 2, 3, 4, 5, 6, 7, 8, 9"; ([ let ns: i32 ],{,4}, ",", ..tail) => (ns, tail)),
+        Ok((ref ns, _)) if ns.len() == 4 && ns.capacity() < 8
+    );
+
+    assert_match!(
+        scan!("3, 1, 2"; ([ let ns: i32 ],+: BTreeSet<_>) => ns),
+        Ok(ref ns) if ns.iter().cloned().collect::<Vec<_>>() == vec![1, 2, 3]
+    );
+}
+
+#[test]
+fn test_repeating_terminated() {
+    use std::collections::BTreeSet;
+    use scan_rules::ScanError as SE;
+    use scan_rules::ScanErrorKind as SEK;
+
+    assert_match!(
+        scan!("a;b;c;"; ([ let ws: Word ](";")*t) => ws),
+        Ok(ref ws) if *ws == ["a", "b", "c"]
+    );
+
+    assert_match!(
+        scan!("a;b;c"; ([ let ws: Word ](";")*t) => ws),
+        Ok(ref ws) if *ws == ["a", "b", "c"]
+    );
+
+    assert_match!(
+        scan!(""; ([ let ws: Word ](";")*t) => ws),
+        Ok(ref ws) if ws.is_empty()
+    );
+
+    assert_match!(
+        scan!(";"; ([ let ws: Word ](";")*t) => ws),
+        Err(SE { kind: SEK::ExpectedEnd, .. })
+    );
+
+    assert_match!(
+        scan!("a;b;c;"; ([ let ws: Word ](";")*) => ws),
+        Err(SE { ref at, .. }) if at.offset() == 6
+    );
+
+    assert_match!(
+        scan!("1, 2, 3, 4"; ([ let ns: i32 ],{2}t, ..tail) => (ns, tail)),
+        Ok((ref ns, " 3, 4")) if *ns == [1, 2]
+    );
+
+    assert_match!(
+        scan!("3, 1, 2,"; ([ let ns: i32 ],+t: BTreeSet<_>) => ns),
+        Ok(ref ns) if ns.iter().cloned().collect::<Vec<_>>() == vec![1, 2, 3]
+    );
+
+    assert_match!(
+        scan!("x=1; y=2;"; ([ let k: Word, "=", let v: i32 ](";")+t binding kvs = (k, v)) => kvs),
+        Ok(ref kvs) if *kvs == [("x", 1), ("y", 2)]
+    );
+
+    assert_match!(
+        scan!("(1; 2;) (3)"; ([ "(", [ let ns: i32 ](";")*t, ")" ]+) => ns),
+        Ok(ref ns) if *ns == [vec![1, 2], vec![3]]
+    );
+
+    assert_match!(
+        scan!("a; b; c;"; ([ let ws: Word, ";" ]*t) => ws),
+        Ok(ref ws) if *ws == ["a", "b", "c"]
+    );
+
+    assert_match!(
+        scan!("1 2 3 4"; ([ let ns: i32 ]{2}t as n, ..tail) => (ns, n, tail)),
+        Ok((ref ns, 2, " 3 4")) if *ns == [1, 2]
+    );
+
+    assert_match!(
+        scan!("x=1 y=2"; ([ let k: Word, "=", let v: i32 ]+t binding kvs = (k, v)) => kvs),
+        Ok(ref kvs) if *kvs == [("x", 1), ("y", 2)]
+    );
+}
+
+#[test]
+fn test_repeating_count() {
+    use std::collections::BTreeSet;
+
+    assert_match!(scan!("very very good"; ([ "very" ]* as n, "good") => n), Ok(2));
+    assert_match!(scan!("good"; ([ "very" ]* as n, "good") => n), Ok(0));
+
+    assert_match!(
+        scan!("1, 2, 3"; ([ let _: i32 ],+ as n) => n),
+        Ok(3)
+    );
+
+    assert_match!(
+        scan!("1, 2, 3,"; ([ let ns: i32 ],+t as n: BTreeSet<_>) => (ns.len(), n)),
+        Ok((3, 3))
+    );
+
+    assert_match!(
+        scan!("x=1; y=2"; ([ let k: Word, "=", let v: i32 ](";")+ binding kvs = (k, v) as n) => (kvs, n)),
+        Ok((ref kvs, 2)) if *kvs == [("x", 1), ("y", 2)]
+    );
+
+    assert_match!(
+        scan!("(a b) () (c)"; ([ "(", [ let _: Word ]* as n, ")" ]+) => n),
+        Ok(ref ns) if *ns == [2, 0, 1]
+    );
+}
@@ -0,0 +1,75 @@
+/*
+Copyright ⓒ 2016 Daniel Keep.
+
+Licensed under the MIT license (see LICENSE or <http://opensource.org
+/licenses/MIT>) or the Apache License, Version 2.0 (see LICENSE of
+<http://www.apache.org/licenses/LICENSE-2.0>), at your option. All
+files in the project carrying such notice may not be copied, modified,
+or distributed except according to those terms.
+*/
+#[macro_use] extern crate scan_rules;
+
+use scan_rules::ScanError;
+use scan_rules::input::ScanInput;
+use scan_rules::scanner::{ScanFromStr, Binary, QuotedString};
+
+#[derive(Debug, PartialEq)]
+struct Point { x: i32, y: i32 }
+
+#[derive(Debug, PartialEq)]
+struct Server { name: String, port: u16, tls: Option<bool> }
+
+scanner_for_debug_struct! {
+    Point { x: i32, y: i32 }
+    any order Server { name: String, port: u16, tls: Option<bool> }
+}
+
+/// Scans its `Debug` output, but forgets the sign.
+#[derive(Debug, PartialEq)]
+struct Lossy(i32);
+
+impl<'a> ScanFromStr<'a> for Lossy {
+    type Output = Self;
+    fn scan_from<I: ScanInput<'a>>(s: I) -> Result<(Self::Output, usize), ScanError> {
+        let s = s.as_str();
+        scan!(s; ("Lossy", "(", let v: i32, ")", ..tail) => (Lossy(v.abs()), s.len() - tail.len()))
+    }
+}
+
+#[test]
+fn test_roundtrip() {
+    assert_roundtrip!(0u8);
+    assert_roundtrip!(-2147483648i32);
+    assert_roundtrip!(18446744073709551615u64);
+    assert_roundtrip!(true);
+    assert_roundtrip!(0.1f64);
+    assert_roundtrip!(-1.5e300f64);
+    assert_roundtrip!(1.0f32 / 3.0);
+    assert_roundtrip!(String::from("\"quoted\" \\ and ünïcödé\n"));
+    assert_roundtrip!(Some(7u16));
+    assert_roundtrip!(None::<u16>);
+    assert_roundtrip!(vec![vec![1, 2], vec![], vec![3]]);
+    assert_roundtrip!((1, -2.5, String::from("three")));
+    assert_roundtrip!(Point { x: 1, y: -2 });
+    assert_roundtrip!(Server { name: "web".into(), port: 443, tls: Some(true) });
+    assert_roundtrip!(Lossy(3));
+}
+
+#[test]
+fn test_roundtrip_with_scanner() {
+    assert_roundtrip!(String::from("x"), QuotedString);
+    assert_roundtrip!((255u8, String::from("y")), (u8, QuotedString));
+}
+
+#[test]
+#[should_panic(expected = "does not round-trip")]
+fn test_roundtrip_mismatch() {
+    assert_roundtrip!(Lossy(-3));
+}
+
+#[test]
+#[should_panic(expected = "could not scan the formatted value")]
+fn test_roundtrip_scan_failure() {
+    // `Binary` expects binary, but `Debug` writes decimal.
+    assert_roundtrip!(29u32, Binary<u32>);
+}
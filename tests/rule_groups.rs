@@ -0,0 +1,56 @@
+/*
+Copyright ⓒ 2016 Daniel Keep.
+
+Licensed under the MIT license (see LICENSE or <http://opensource.org
+/licenses/MIT>) or the Apache License, Version 2.0 (see LICENSE of
+<http://www.apache.org/licenses/LICENSE-2.0>), at your option. All
+files in the project carrying such notice may not be copied, modified,
+or distributed except according to those terms.
+*/
+#[macro_use] extern crate scan_rules;
+#[macro_use] mod util;
+
+use scan_rules::ScanError as SE;
+use scan_rules::ScanErrorKind as SEK;
+use scan_rules::scanner::Word;
+
+#[test]
+fn test_rule_groups() {
+    assert_match!(parse("set volume 11"), Ok(Cmd::Volume(11)));
+    assert_match!(parse("set name Bob"), Ok(Cmd::Name("Bob")));
+    assert_match!(parse("set mute on"), Ok(Cmd::Mute(true)));
+    assert_match!(parse("set mute off"), Ok(Cmd::Mute(false)));
+    assert_match!(parse("get volume"), Ok(Cmd::Get("volume")));
+    assert_match!(parse("quit"), Ok(Cmd::Quit));
+
+    assert_match!(parse("set volume 300"),
+        Err(SE { ref at, kind: SEK::Overflow("u8"), .. }) if at.offset() == 11);
+    assert_match!(parse("set colour red"),
+        Err(SE { ref at, kind: SEK::LiteralMismatch, .. }) if at.offset() == 4);
+    assert_match!(parse("set mute maybe"),
+        Err(SE { ref at, kind: SEK::LiteralMismatch, .. }) if at.offset() == 9);
+}
+
+#[derive(Debug)]
+enum Cmd<'a> {
+    Volume(u8),
+    Name(&'a str),
+    Mute(bool),
+    Get(&'a str),
+    Quit,
+}
+
+fn parse(s: &str) -> Result<Cmd, SE> {
+    scan! { s;
+        ("set", {
+            ("volume", let v: u8) => Cmd::Volume(v),
+            ("name", let n: Word) => Cmd::Name(n),
+            ("mute", {
+                ("on") => Cmd::Mute(true),
+                ("off") => Cmd::Mute(false),
+            }),
+        }),
+        ("get", let k: Word) => Cmd::Get(k),
+        ("quit") => Cmd::Quit,
+    }
+}
@@ -40,3 +40,34 @@ fn test_runtime_scanner_re() {
         Ok(("01234567", "9"))
     );
 }
+
+#[cfg(feature="regex")]
+#[test]
+fn test_regex_term() {
+    use scan_rules::ScanError as SE;
+    use scan_rules::ScanErrorKind as SEK;
+
+    assert_match!(
+        scan!("id: 2016-03 -> 42"; (@re(r"[a-z]+:"), @re(r"\d{4}-\d{2}"), "->", let n: u32) => n),
+        Ok(42)
+    );
+    assert_match!(
+        scan!("x 2016-03"; (@re(r"\d{4}"), ..tail) => tail),
+        Err(_)
+    );
+    assert_match!(
+        scan!("2016-03 rest"; ([@re(r"\d+"), "-"]+, ..tail) => tail),
+        Ok("03 rest")
+    );
+    assert_match!(
+        scan!("2016"; (@re(r"\d{4"), ..tail) => tail),
+        Err(SE { kind: SEK::Syntax(_), .. })
+    );
+
+    // A user variable named `re` is still an ordinary literal.
+    let re = "id:";
+    assert_match!(
+        scan!("id: 7"; (re, let n: u32) => n),
+        Ok(7)
+    );
+}
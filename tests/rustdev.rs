@@ -0,0 +1,58 @@
+/*
+Copyright ⓒ 2016 Daniel Keep.
+
+Licensed under the MIT license (see LICENSE or <http://opensource.org
+/licenses/MIT>) or the Apache License, Version 2.0 (see LICENSE of
+<http://www.apache.org/licenses/LICENSE-2.0>), at your option. All
+files in the project carrying such notice may not be copied, modified,
+or distributed except according to those terms.
+*/
+#[macro_use] extern crate scan_rules;
+#[macro_use] mod util;
+
+#[cfg(feature="rustdev")]
+#[test]
+fn test_rustc_version() {
+    use scan_rules::scanner::rustdev::RustcVersion;
+
+    let scan_version = |s: &'static str| scan!(s;
+        (let v: RustcVersion) => (v.major, v.minor, v.patch, v.pre, v.commit_hash),
+    );
+
+    assert_match!(scan_version("rustc 1.6.0 (c30b771ad 2016-01-19)\n"), Ok((1, 6, 0, None, Some("c30b771ad"))));
+    assert_match!(scan_version("rustc 1.12.0-nightly (7ad125c4e 2016-07-11)\n"), Ok((1, 12, 0, Some("nightly"), Some("7ad125c4e"))));
+    assert_match!(scan_version("rustc 1.6.0 (c30b771ad 2016-01-19) extra"), Err(_));
+}
+
+#[cfg(feature="rustdev")]
+#[test]
+fn test_test_result() {
+    use scan_rules::scanner::rustdev::TestResult;
+
+    let output = "\
+running 3 tests
+test test_a ... ok
+test test_b ... FAILED
+test test_c ... ignored
+
+failures:
+    test_b
+
+test result: FAILED. 1 passed; 1 failed; 1 ignored; 0 measured; 4 filtered out
+
+error: test failed
+";
+
+    let results: Vec<_> = output.lines()
+        .filter_map(|line| scan!(line;
+            (let r: TestResult) => Some(r),
+            (...) => None,
+        ).unwrap())
+        .collect();
+
+    assert_eq!(results.len(), 1);
+    let r = &results[0];
+    assert!(!r.ok);
+    assert_eq!((r.passed, r.failed, r.ignored, r.measured, r.filtered_out), (1, 1, 1, 0, 4));
+    assert_eq!(r.finished_in, None);
+}
@@ -0,0 +1,46 @@
+/*
+Copyright ⓒ 2016 Daniel Keep.
+
+Licensed under the MIT license (see LICENSE or <http://opensource.org
+/licenses/MIT>) or the Apache License, Version 2.0 (see LICENSE of
+<http://www.apache.org/licenses/LICENSE-2.0>), at your option. All
+files in the project carrying such notice may not be copied, modified,
+or distributed except according to those terms.
+*/
+#[macro_use] extern crate scan_rules;
+#[macro_use] mod util;
+
+use scan_rules::ScanError as SE;
+use scan_rules::ScanErrorKind as SEK;
+use scan_rules::scanner::Word;
+
+#[derive(Debug, PartialEq)]
+enum Command<'a> {
+    Go(i32, i32),
+    Say(&'a str),
+    Take(Vec<&'a str>),
+    Quit,
+}
+
+fn parse(input: &str) -> Result<Command, SE> {
+    scan_keyword! { input;
+        "go" => (let x: i32, ",", let y: i32) => Command::Go(x, y),
+        "say" => (..msg) => Command::Say(msg),
+        "take" => ([let items: Word]+) => Command::Take(items),
+        "quit" => () => Command::Quit,
+    }
+}
+
+#[test]
+fn test_scan_keyword() {
+    assert_match!(parse("go 1, -2"), Ok(Command::Go(1, -2)));
+    assert_match!(parse("  say hello there"), Ok(Command::Say(" hello there")));
+    assert_match!(parse("take lamp sword"), Ok(Command::Take(ref v)) if *v == ["lamp", "sword"]);
+    assert_match!(parse("quit"), Ok(Command::Quit));
+
+    assert_match!(parse(""), Err(SE { kind: SEK::LiteralMismatch, .. }));
+    assert_match!(parse("  jump"), Err(SE { ref at, kind: SEK::LiteralMismatch, .. }) if at.offset() == 2);
+    assert_match!(parse("quitter"), Err(SE { kind: SEK::LiteralMismatch, .. }));
+    assert_match!(parse("quit now"), Err(SE { kind: SEK::ExpectedEnd, .. }));
+    assert_match!(parse("go 1 2"), Err(SE { ref at, kind: SEK::LiteralMismatch, .. }) if at.offset() == 5);
+}
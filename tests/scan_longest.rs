@@ -0,0 +1,45 @@
+/*
+Copyright ⓒ 2016 Daniel Keep.
+
+Licensed under the MIT license (see LICENSE or <http://opensource.org
+/licenses/MIT>) or the Apache License, Version 2.0 (see LICENSE of
+<http://www.apache.org/licenses/LICENSE-2.0>), at your option. All
+files in the project carrying such notice may not be copied, modified,
+or distributed except according to those terms.
+*/
+#[macro_use] extern crate scan_rules;
+#[macro_use] mod util;
+
+use scan_rules::ScanError as SE;
+use scan_rules::ScanErrorKind as SEK;
+use scan_rules::input::{Limited, ScanCursor};
+use scan_rules::scanner::Word;
+
+#[test]
+fn test_scan_longest() {
+    let lex = |s: &'static str| scan_longest!(s;
+        ("=") => "assign",
+        ("==") => "eq",
+        ("if") => "if",
+        (let _: Word) => "ident",
+        (let _: i32) => "int",
+    ).map(|(tok, cur)| (tok, cur.remaining()));
+
+    assert_match!(lex("== x"), Ok(("eq", " x")));
+    assert_match!(lex("= x"), Ok(("assign", " x")));
+    assert_match!(lex("if x"), Ok(("if", " x")));
+    assert_match!(lex("iffy x"), Ok(("ident", " x")));
+    assert_match!(lex("12 x"), Ok(("ident", " x")));
+    assert_match!(lex("-12 x"), Ok(("int", " x")));
+    assert_match!(lex("+"), Err(SE { kind: SEK::LiteralMismatch, .. }));
+
+    let (v, cur) = scan_longest!("1 2 3 x"; ([let ns: i32]*) => ns).unwrap();
+    assert_eq!(v, [1, 2, 3]);
+    assert_eq!(cur.remaining(), " x");
+
+    let limited = Limited::new("1 2 3").max_repeats(2);
+    assert_match!(
+        scan_longest!(limited; (let n: i32) => vec![n], ([let ns: i32]*) => ns),
+        Err(SE { kind: SEK::LimitExceeded, .. })
+    );
+}
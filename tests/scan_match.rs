@@ -0,0 +1,51 @@
+/*
+Copyright ⓒ 2016 Daniel Keep.
+
+Licensed under the MIT license (see LICENSE or <http://opensource.org
+/licenses/MIT>) or the Apache License, Version 2.0 (see LICENSE of
+<http://www.apache.org/licenses/LICENSE-2.0>), at your option. All
+files in the project carrying such notice may not be copied, modified,
+or distributed except according to those terms.
+*/
+#[macro_use] extern crate scan_rules;
+
+#[derive(Debug, PartialEq)]
+enum Cmd<'a> {
+    Add(i32, i32),
+    Neg(i32),
+    Unknown(&'a str),
+}
+
+fn parse_cmd(input: &str) -> Cmd {
+    scan_match! { input;
+        ("add", let a: i32, let b: i32) => Cmd::Add(a, b),
+        ("neg", let a: i32) => Cmd::Neg(a),
+        (..other) => Cmd::Unknown(other),
+    }
+}
+
+#[test]
+fn test_scan_match() {
+    assert_eq!(parse_cmd("add 1 2"), Cmd::Add(1, 2));
+    assert_eq!(parse_cmd("neg 3"), Cmd::Neg(3));
+    assert_eq!(parse_cmd("add 1"), Cmd::Unknown("add 1"));
+    assert_eq!(parse_cmd("  mul 2 3"), Cmd::Unknown("  mul 2 3"));
+    assert_eq!(parse_cmd(""), Cmd::Unknown(""));
+}
+
+#[test]
+fn test_scan_match_ignored_rest() {
+    let f = |s: &str| scan_match! { s;
+        (let n: u8) => Some(n),
+        (.._) => None
+    };
+    assert_eq!(f("42"), Some(42));
+    assert_eq!(f("420"), None);
+}
+
+#[test]
+fn test_scan_match_only_catch_all() {
+    let s = "anything";
+    let r = scan_match!(s; (..rest) => rest.len());
+    assert_eq!(r, 8);
+}
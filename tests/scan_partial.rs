@@ -0,0 +1,36 @@
+/*
+Copyright ⓒ 2016 Daniel Keep.
+
+Licensed under the MIT license (see LICENSE or <http://opensource.org
+/licenses/MIT>) or the Apache License, Version 2.0 (see LICENSE of
+<http://www.apache.org/licenses/LICENSE-2.0>), at your option. All
+files in the project carrying such notice may not be copied, modified,
+or distributed except according to those terms.
+*/
+#[macro_use] extern crate scan_rules;
+#[macro_use] mod util;
+
+use scan_rules::ScanError as SE;
+use scan_rules::ScanErrorKind as SEK;
+use scan_rules::input::ScanCursor;
+use scan_rules::scanner::Word;
+
+#[test]
+fn test_scan_partial() {
+    let inp = "add 1 2; neg 3";
+
+    let (v, cur) = scan_partial!(inp;
+        ("add", let a: i32, let b: i32) => a + b,
+        ("neg", let a: i32) => -a,
+    ).unwrap();
+    assert_eq!(v, 3);
+    assert_eq!(cur.remaining(), "; neg 3");
+    assert_eq!(cur.offset(), 7);
+
+    let (v, cur) = scan_partial!(cur; (";", let w: Word) => w).unwrap();
+    assert_eq!(v, "neg");
+    assert_eq!(cur.remaining(), " 3");
+
+    assert_match!(scan_partial!(cur.clone(); ("x") => ()), Err(SE { kind: SEK::LiteralMismatch, .. }));
+    assert_match!(scan!(cur; (let n: i32) => n), Ok(3));
+}
@@ -0,0 +1,47 @@
+/*
+Copyright ⓒ 2016 Daniel Keep.
+
+Licensed under the MIT license (see LICENSE or <http://opensource.org
+/licenses/MIT>) or the Apache License, Version 2.0 (see LICENSE of
+<http://www.apache.org/licenses/LICENSE-2.0>), at your option. All
+files in the project carrying such notice may not be copied, modified,
+or distributed except according to those terms.
+*/
+#![cfg(feature="std")]
+
+#[macro_use] extern crate scan_rules;
+#[macro_use] mod util;
+
+use scan_rules::ScanError as SE;
+use scan_rules::ScanErrorKind as SEK;
+use scan_rules::input::TokenReader;
+use scan_rules::scanner::Word;
+
+#[test]
+fn test_scan_tokens() {
+    let mut tokens = TokenReader::new("2\n\n  10\n20 word\nend".as_bytes());
+
+    assert_match!(scan_tokens!(tokens; (let n: usize) => n), Ok(2));
+    assert_match!(scan_tokens!(tokens; (let a: i32, let b: i32) => (a, b)), Ok((10, 20)));
+    assert_match!(scan_tokens!(tokens; (let w: Word<String>) => w), Ok(ref w) if w == "word");
+    assert_match!(scan_tokens!(tokens; ("end") => ()), Ok(()));
+    assert_match!(scan_tokens!(tokens; (let n: i32) => n), Err(SE { kind: SEK::Syntax(_), .. }));
+    assert!(tokens.is_eof());
+}
+
+#[test]
+fn test_scan_tokens_no_overread() {
+    let mut tokens = TokenReader::new("1 x\n2 3\n".as_bytes());
+
+    assert_match!(
+        scan_tokens!(tokens; (let a: i32, let b: i32) => (a, b)),
+        Err(SE { kind: SEK::Syntax(_), .. })
+    );
+    assert_eq!(tokens.buffer(), "1 x\n");
+
+    assert_match!(
+        scan_tokens!(tokens; (let a: i32, let b: i32) => (a, b), (let a: i32, "x") => (a, 0)),
+        Ok((1, 0))
+    );
+    assert_match!(scan_tokens!(tokens; (let a: i32, let b: i32) => (a, b)), Ok((2, 3)));
+}
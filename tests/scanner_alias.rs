@@ -0,0 +1,64 @@
+/*
+Copyright ⓒ 2016 Daniel Keep.
+
+Licensed under the MIT license (see LICENSE or <http://opensource.org
+/licenses/MIT>) or the Apache License, Version 2.0 (see LICENSE of
+<http://www.apache.org/licenses/LICENSE-2.0>), at your option. All
+files in the project carrying such notice may not be copied, modified,
+or distributed except according to those terms.
+*/
+#[macro_use] extern crate scan_rules;
+#[macro_use] mod util;
+
+use scan_rules::ScanError as SE;
+use scan_rules::ScanErrorKind as SEK;
+use scan_rules::input::ScanInput;
+use scan_rules::scanner::{Hex, ScanStr, Word};
+use scan_rules::scanner::runtime::{MaxWidth, ScanA, max_width_a};
+
+/// Scans up to three digits.
+struct ThreeDigits(MaxWidth<ScanA<u32>>);
+
+impl Default for ThreeDigits {
+    fn default() -> Self {
+        ThreeDigits(max_width_a::<u32>(3))
+    }
+}
+
+impl<'a> ScanStr<'a> for ThreeDigits {
+    type Output = u32;
+
+    fn scan<I: ScanInput<'a>>(&mut self, s: I) -> Result<(Self::Output, usize), SE> {
+        self.0.scan(s)
+    }
+
+    fn wants_leading_junk_stripped(&self) -> bool {
+        self.0.wants_leading_junk_stripped()
+    }
+}
+
+scanner_alias! {
+    Port = Hex<u16>;
+    pub Key<'a> = Word<'a, &'a str>;
+    Area = <| ThreeDigits;
+}
+
+#[test]
+fn test_scanner_alias() {
+    assert_match!(
+        scan!("port 1f90"; ("port", let p: Port) => p),
+        Ok(8080)
+    );
+    assert_match!(
+        scan!("name = x"; (let k: Key, "=", let v: Key) => (k, v)),
+        Ok(("name", "x"))
+    );
+    assert_match!(
+        scan!("555123"; (let a: Area, let b: u32) => (a, b)),
+        Ok((555, 123))
+    );
+    assert_match!(
+        scan!("port xyz"; ("port", let p: Port) => p),
+        Err(SE { kind: SEK::Syntax(_), .. })
+    );
+}
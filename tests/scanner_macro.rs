@@ -0,0 +1,53 @@
+/*
+Copyright ⓒ 2016 Daniel Keep.
+
+Licensed under the MIT license (see LICENSE or <http://opensource.org
+/licenses/MIT>) or the Apache License, Version 2.0 (see LICENSE of
+<http://www.apache.org/licenses/LICENSE-2.0>), at your option. All
+files in the project carrying such notice may not be copied, modified,
+or distributed except according to those terms.
+*/
+#[macro_use] extern crate scan_rules;
+#[macro_use] mod util;
+
+use std::marker::PhantomData;
+use scan_rules::ScanError as SE;
+use scan_rules::ScanErrorKind as SEK;
+use scan_rules::scanner::{ScanFromStr, Word};
+
+/// Scans `key: value`, borrowing the key from the input.
+struct Field<V>(PhantomData<V>);
+
+scanner! { impl<'a, V> ScanFromStr for Field<V>, type Output = (&'a str, V::Output); where {V: ScanFromStr<'a>} {
+    (let k: Word, ":", let v: V, ..tail) => ((k, v), tail)
+}}
+
+/// Scans a bracketed list of fields.
+struct Fields<V>(PhantomData<V>);
+
+scanner! { impl<'a, V> ScanFromStr for Fields<V>, type Output = Vec<(&'a str, V::Output)>; where {V: ScanFromStr<'a>} {
+    ("{", [let fs: Field<V>],*, "}", ..tail) => (fs, tail)
+}}
+
+#[test]
+fn test_scanner_macro() {
+    let input = String::from("a: 1, b: 2");
+    let fields = scan!(&input; ([let fs: Field<i32>],+) => fs);
+    assert_match!(fields, Ok(ref fs) if *fs == [("a", 1), ("b", 2)]);
+
+    let keys = {
+        let input = &input[..];
+        scan!(input; (let f: Field<Word>, ..rest) => (f.0, rest))
+    };
+    assert_match!(keys, Ok(("a", ", b: 2")));
+
+    assert_match!(
+        scan!("{x: 7, y: 8} rest"; (let fs: Fields<u8>, ..rest) => (fs, rest)),
+        Ok((ref fs, " rest")) if *fs == [("x", 7), ("y", 8)]
+    );
+
+    assert_match!(
+        scan!("x = 1"; (let _: Field<i32>) => ()),
+        Err(SE { kind: SEK::LiteralMismatch, ref at, .. }) if at.offset() == 2
+    );
+}
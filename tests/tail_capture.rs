@@ -0,0 +1,37 @@
+/*
+Copyright ⓒ 2016 Daniel Keep.
+
+Licensed under the MIT license (see LICENSE or <http://opensource.org
+/licenses/MIT>) or the Apache License, Version 2.0 (see LICENSE of
+<http://www.apache.org/licenses/LICENSE-2.0>), at your option. All
+files in the project carrying such notice may not be copied, modified,
+or distributed except according to those terms.
+*/
+#[macro_use] extern crate scan_rules;
+#[macro_use] mod util;
+
+#[test]
+fn test_tail_capture() {
+    let inp = "cmd  say hello  \n";
+    assert_match!(scan!(inp; ("cmd", ..rest) => rest), Ok("  say hello  \n"));
+    assert_match!(scan!(inp; ("cmd", ..rest trimmed) => rest), Ok("say hello"));
+    assert_match!(scan!(inp; ("cmd", ..rest at pos) => (pos, rest)), Ok((3, "  say hello  \n")));
+    assert_match!(scan!(inp; ("cmd", ..rest trimmed at pos) => (pos, rest)), Ok((5, "say hello")));
+    assert_match!(scan!(inp; ("cmd", ..rest trimmed at pos) => &inp[pos..pos + rest.len()] == rest), Ok(true));
+
+    assert_match!(scan!("cmd   "; ("cmd", ..rest trimmed at pos) => (pos, rest)), Ok((6, "")));
+    assert_match!(scan!("cmd"; ("cmd", ..rest trimmed at pos) => (pos, rest)), Ok((3, "")));
+    assert_match!(scan!("  x"; (..rest trimmed at pos) => (pos, rest)), Ok((2, "x")));
+
+    assert_match!(
+        scan!("a = 1"; ("a", "=", ..v trimmed) => v, (..line at pos) => line),
+        Ok("1")
+    );
+}
+
+#[test]
+fn test_tail_capture_let_scan() {
+    let inp = "42 things  ";
+    let_scan!(inp; (let n: u32, ..rest trimmed));
+    assert_eq!(n, 42);
+}